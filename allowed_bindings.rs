@@ -83,9 +83,11 @@ bind! {
     zend_ce_serializable,
     zend_ce_countable,
     zend_ce_stringable,
+    zend_ce_fiber,
     zend_class_entry,
     zend_declare_class_constant,
     zend_declare_property,
+    zend_declare_typed_property,
     zend_do_implement_interface,
     zend_empty_array,
     zend_read_property,
@@ -103,6 +105,10 @@ bind! {
     zend_hash_index_find,
     zend_hash_index_update,
     zend_hash_next_index_insert,
+    zend_hash_real_init_packed,
+    zend_hash_extend,
+    zend_hash_sort_ex,
+    zend_sort,
     zend_hash_str_del,
     zend_hash_str_find,
     zend_hash_str_update,
@@ -120,7 +126,39 @@ bind! {
     zend_fcall_info_cache,
     _zend_fcall_info_cache,
     zend_is_identical,
+    zend_compare,
+    numeric_compare_function,
+    string_compare_function,
+    string_case_compare_function,
+    add_function,
+    sub_function,
+    mul_function,
+    div_function,
+    mod_function,
+    pow_function,
+    concat_function,
+    bitwise_and_function,
+    bitwise_or_function,
+    bitwise_xor_function,
+    bitwise_not_function,
+    shift_left_function,
+    shift_right_function,
+    rsrc_dtor_func_t,
+    zend_register_list_destructors_ex,
+    zend_register_resource,
+    zend_register_persistent_resource,
+    zend_list_delete,
+    zend_list_close,
+    _zend_hash_init,
+    zend_hash_destroy,
+    zend_weakrefs_hash_add,
+    zend_weakrefs_hash_del,
+    zend_string_hash_func,
     zend_is_iterable,
+    zend_iterator_init,
+    zend_create_internal_iterator_zval,
+    zend_fiber,
+    zend_fiber_switch_blocked,
     zend_known_strings,
     zend_long,
     zend_lookup_class_ex,
@@ -170,7 +208,11 @@ bind! {
     GC_FLAGS_MASK,
     GC_FLAGS_SHIFT,
     GC_IMMUTABLE,
+    GC_NOT_COLLECTABLE,
+    GC_PERSISTENT,
     HT_MIN_SIZE,
+    HASH_FLAG_PACKED,
+    HASH_FLAG_UNINITIALIZED,
     IS_ARRAY,
     IS_ARRAY_EX,
     IS_CALLABLE,
@@ -234,9 +276,13 @@ bind! {
     ZEND_ACC_PRELOADED,
     ZEND_ACC_PRIVATE,
     ZEND_ACC_PROMOTED,
+    ZEND_ACC_PROTECTED_SET,
+    ZEND_ACC_PRIVATE_SET,
+    ZEND_ACC_PUBLIC_SET,
     ZEND_ACC_PROPERTY_TYPES_RESOLVED,
     ZEND_ACC_PROTECTED,
     ZEND_ACC_PUBLIC,
+    ZEND_ACC_READONLY,
     ZEND_ACC_READONLY_CLASS,
     ZEND_ACC_RESOLVED_INTERFACES,
     ZEND_ACC_RESOLVED_PARENT,
@@ -251,6 +297,7 @@ bind! {
     ZEND_ACC_USES_THIS,
     ZEND_ACC_USE_GUARDS,
     ZEND_ACC_VARIADIC,
+    ZEND_ACC_VIRTUAL,
     ZEND_DEBUG,
     ZEND_HAS_STATIC_IN_METHODS,
     ZEND_ISEMPTY,
@@ -267,17 +314,50 @@ bind! {
     ts_rsrc_id,
     _ZEND_TYPE_NAME_BIT,
     _ZEND_TYPE_LITERAL_NAME_BIT,
+    _ZEND_TYPE_LIST_BIT,
+    _ZEND_TYPE_UNION_BIT,
+    _ZEND_TYPE_INTERSECTION_BIT,
+    zend_type_list,
+    zend_attribute,
+    ZEND_ATTRIBUTE_TARGET_CLASS,
+    ZEND_ATTRIBUTE_TARGET_FUNCTION,
+    ZEND_ATTRIBUTE_TARGET_METHOD,
+    ZEND_ATTRIBUTE_TARGET_PROPERTY,
+    ZEND_ATTRIBUTE_TARGET_CLASS_CONST,
+    ZEND_ATTRIBUTE_TARGET_PARAMETER,
+    ZEND_ATTRIBUTE_TARGET_CONST,
+    ZEND_ATTRIBUTE_TARGET_ALL,
+    ZEND_ATTRIBUTE_IS_REPEATABLE,
     ZEND_INTERNAL_FUNCTION,
     ZEND_USER_FUNCTION,
     ZEND_EVAL_CODE,
+    ZEND_INTERNAL_CLASS,
+    ZEND_ADD,
+    ZEND_SUB,
+    ZEND_MUL,
+    ZEND_DIV,
+    ZEND_MOD,
+    ZEND_CONCAT,
+    ZEND_POW,
     zval_ptr_dtor,
     zend_refcounted_h,
     zend_is_true,
     zend_object_std_dtor,
     zend_std_read_property,
     zend_std_write_property,
+    zend_verify_property_type,
+    zend_readonly_property_modification_error,
     zend_std_get_properties,
     zend_std_has_property,
+    zend_std_read_dimension,
+    zend_std_write_dimension,
+    zend_std_has_dimension,
+    zend_std_unset_dimension,
+    BP_VAR_R,
+    BP_VAR_W,
+    BP_VAR_RW,
+    BP_VAR_IS,
+    BP_VAR_UNSET,
     zend_objects_new,
     zend_object_make_lazy,
     zend_lazy_object_init,
@@ -283,6 +283,11 @@ fn main() -> Result<()> {
         .into_iter()
         .filter(|p| p.file_stem() != Some(std::ffi::OsStr::new("closure")))
         .collect();
+    #[cfg(not(feature = "serde"))]
+    let test_md: Vec<_> = test_md
+        .into_iter()
+        .filter(|p| p.file_stem() != Some(std::ffi::OsStr::new("serde")))
+        .collect();
     skeptic::generate_doc_tests(&test_md);
 
     Ok(())
@@ -1,11 +1,16 @@
-use darling::util::Flag;
+use darling::util::{Flag, Override};
 use darling::{FromAttributes, FromMeta, ToTokens};
 use proc_macro2::TokenStream;
 use quote::{TokenStreamExt, quote};
+use syn::spanned::Spanned;
 use syn::{Attribute, Expr, Fields, ItemStruct};
 
 use crate::helpers::get_docs;
-use crate::parsing::{PhpNameContext, PhpRename, RenameRule, ident_to_php_name, validate_php_name};
+use crate::operator::{Operators, generate_operators_impl};
+use crate::parsing::{
+    PhpNameContext, PhpRename, RenameRule, Visibility, deprecation_tokens, ident_to_php_name,
+    validate_php_name,
+};
 use crate::prelude::*;
 
 #[derive(FromAttributes, Debug, Default)]
@@ -14,6 +19,11 @@ pub struct StructAttributes {
     /// The name of the PHP class. Defaults to the same name as the struct.
     #[darling(flatten)]
     rename: PhpRename,
+    /// Whether the class is deprecated, with an optional message. Classes
+    /// cannot be deprecated in PHP, so this is only rendered in the stubs.
+    deprecated: Option<Override<String>>,
+    /// The version since which the class is deprecated.
+    since: Option<String>,
     /// A modifier function which should accept one argument, a `ClassBuilder`,
     /// and return the same object. Allows the user to modify the class before
     /// it is built.
@@ -27,9 +37,64 @@ pub struct StructAttributes {
     extends: Option<ClassEntryAttribute>,
     #[darling(multiple)]
     implements: Vec<ClassEntryAttribute>,
+    /// Whether the objects of the class are traversed by `foreach` with the
+    /// `Iterator` implementation of the struct.
+    iterator: Flag,
+    /// Whether the objects of the class are accessed as arrays with the
+    /// `PhpArrayAccess` implementation of the struct.
+    array_access: Flag,
+    /// The operators applied to the objects of the class with the `std::ops`
+    /// implementations of the struct.
+    operators: Option<Operators>,
+    /// An expression of `AttributeFlags` giving the items an attribute class
+    /// can be applied to. Only valid on `#[php_attribute]` classes.
+    target: Option<syn::Expr>,
+    /// The instantiations of a generic struct, each registered as a separate
+    /// class.
+    instantiate: Option<Instantiations>,
     attrs: Vec<Attribute>,
 }
 
+/// Instantiations of a generic struct, given as pairs of a concrete type and
+/// the name of the PHP class.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[php(instantiate(Cache<i64> = "IntCache", Cache<String> = "StringCache"))]
+/// ```
+#[derive(Debug)]
+pub struct Instantiations(Vec<Instantiation>);
+
+#[derive(Debug)]
+struct Instantiation {
+    ty: syn::TypePath,
+    name: syn::LitStr,
+}
+
+impl syn::parse::Parse for Instantiation {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        let name = input.parse()?;
+        Ok(Self { ty, name })
+    }
+}
+
+impl FromMeta for Instantiations {
+    fn from_meta(item: &syn::Meta) -> darling::Result<Self> {
+        match item {
+            syn::Meta::List(list) => {
+                let instantiations = list.parse_args_with(
+                    syn::punctuated::Punctuated::<Instantiation, syn::Token![,]>::parse_terminated,
+                )?;
+                Ok(Self(instantiations.into_iter().collect()))
+            }
+            _ => Err(darling::Error::unsupported_format("expected list format")),
+        }
+    }
+}
+
 /// Represents a class entry reference, either explicit (with `ce` and `stub`)
 /// or a simple type reference to a Rust type implementing `RegisteredClass`.
 ///
@@ -110,14 +175,25 @@ impl ToTokens for ClassEntryAttribute {
     }
 }
 
-pub fn parser(mut input: ItemStruct) -> Result<TokenStream> {
+/// Parses a `#[php_class]` struct, or a `#[php_attribute]` struct when
+/// `is_attribute` is set.
+pub fn parser(mut input: ItemStruct, is_attribute: bool) -> Result<TokenStream> {
     let attr = StructAttributes::from_attributes(&input.attrs)?;
+    let attribute_flags = match (&attr.target, is_attribute) {
+        (Some(target), true) => Some(target.to_token_stream()),
+        (None, true) => Some(quote! { ::ext_php_rs::flags::AttributeFlags::All }),
+        (Some(target), false) => {
+            bail!(target => "`target` is only valid on `#[php_attribute]` classes.")
+        }
+        (None, false) => None,
+    };
     let ident = &input.ident;
     let name = attr
         .rename
         .rename(ident_to_php_name(ident), RenameRule::Pascal);
     validate_php_name(&name, PhpNameContext::Class, ident.span())?;
     let docs = get_docs(&attr.attrs)?;
+    let deprecation = deprecation_tokens(attr.deprecated.as_ref(), attr.since.as_ref(), ident)?;
 
     // Check if the struct derives Default - this is needed for exception classes
     // that extend \Exception to work correctly with zend_throw_exception_ex
@@ -145,33 +221,156 @@ pub fn parser(mut input: ItemStruct) -> Result<TokenStream> {
 
     input.attrs.retain(|attr| !attr.path().is_ident("php"));
 
+    let classes = instantiations(&input, attr.instantiate.as_ref(), name)?;
     let fields = match &mut input.fields {
         Fields::Named(fields) => parse_fields(fields.named.iter_mut())?,
         _ => vec![],
     };
 
-    let class_impl = generate_registered_class_impl(
-        ident,
-        &name,
-        attr.modifier.as_ref(),
-        attr.extends.as_ref(),
-        &attr.implements,
-        &fields,
-        attr.flags.as_ref(),
-        attr.readonly.is_present(),
-        &docs,
-        has_derive_default,
-        has_derive_clone,
-    );
+    let class_impls = classes
+        .iter()
+        .map(|(ty, name, templates)| {
+            let class_impl = generate_registered_class_impl(
+                ty,
+                name,
+                attr.modifier.as_ref(),
+                attr.extends.as_ref(),
+                &attr.implements,
+                &fields,
+                templates,
+                attr.flags.as_ref(),
+                attr.readonly.is_present(),
+                &docs,
+                deprecation.as_ref(),
+                attribute_flags.as_ref(),
+                ObjectHandlers {
+                    iterator: attr.iterator.is_present(),
+                    array_access: attr.array_access.is_present(),
+                    operators: attr.operators.as_ref(),
+                },
+                has_derive_default,
+                has_derive_clone,
+            );
+
+            let operators_impl = attr
+                .operators
+                .as_ref()
+                .map(|operators| generate_operators_impl(ty, operators));
+
+            quote! {
+                #class_impl
+                #operators_impl
+
+                ::ext_php_rs::class_derives!(#ty);
+            }
+        })
+        .collect::<Vec<_>>();
 
     Ok(quote! {
         #input
-        #class_impl
-
-        ::ext_php_rs::class_derives!(#ident);
+        #(#class_impls)*
     })
 }
 
+/// The generic parameters of a struct, along with the types they are
+/// instantiated with.
+type Templates = Vec<(syn::Ident, syn::Type)>;
+
+/// Returns the classes registered for a struct, as their type, PHP name and
+/// the types their generic parameters are instantiated with. Generic structs
+/// are registered once per instantiation given with
+/// `#[php(instantiate(...))]`.
+///
+/// # Parameters
+///
+/// * `input` - The struct.
+/// * `instantiate` - The instantiations of the struct, if it is generic.
+/// * `name` - The PHP name of the struct, if it is not generic.
+fn instantiations(
+    input: &ItemStruct,
+    instantiate: Option<&Instantiations>,
+    name: String,
+) -> Result<Vec<(syn::Type, String, Templates)>> {
+    let ident = &input.ident;
+    let Some(instantiate) = instantiate else {
+        if !input.generics.params.is_empty() {
+            bail!(input.generics => "Generic classes must be instantiated with `#[php(instantiate(...))]`, e.g. `#[php(instantiate({}<i64> = \"Int{}\"))]`.", ident, ident);
+        }
+        return Ok(vec![(syn::parse_quote!(#ident), name, vec![])]);
+    };
+
+    let mut params = vec![];
+    for param in &input.generics.params {
+        match param {
+            syn::GenericParam::Type(param) => params.push(&param.ident),
+            _ => bail!(param => "Only type parameters are supported on `#[php_class]` structs."),
+        }
+    }
+    if params.is_empty() {
+        bail!(ident => "`instantiate` is only valid on generic structs.");
+    }
+
+    let mut classes = vec![];
+    for Instantiation { ty, name } in &instantiate.0 {
+        let segment = ty
+            .path
+            .segments
+            .last()
+            .filter(|segment| ty.qself.is_none() && segment.ident == *ident);
+        let args = match segment.map(|segment| &segment.arguments) {
+            Some(syn::PathArguments::AngleBracketed(args)) => args
+                .args
+                .iter()
+                .map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Ok(ty.clone()),
+                    _ => bail!(arg => "Expected a type."),
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => bail!(ty => "Expected an instantiation of `{}`, e.g. `{}<i64>`.", ident, ident),
+        };
+        if args.len() != params.len() {
+            bail!(ty => "Expected {} type arguments.", params.len());
+        }
+
+        let name = name.value();
+        validate_php_name(&name, PhpNameContext::Class, ty.span())?;
+        let templates = params
+            .iter()
+            .map(|param| (*param).clone())
+            .zip(args)
+            .collect();
+        classes.push((syn::Type::Path(ty.clone()), name, templates));
+    }
+    Ok(classes)
+}
+
+/// Replaces the generic parameters of a struct by the types they are
+/// instantiated with, in the type of a field.
+///
+/// # Parameters
+///
+/// * `ty` - The type of the field.
+/// * `templates` - The generic parameters, along with their types.
+fn instantiate_type(ty: TokenStream, templates: &[(syn::Ident, syn::Type)]) -> TokenStream {
+    ty.into_iter()
+        .map(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => templates
+                .iter()
+                .find(|(param, _)| *param == ident)
+                .map_or_else(|| ident.into_token_stream(), |(_, ty)| ty.to_token_stream()),
+            proc_macro2::TokenTree::Group(group) => {
+                let mut instantiated = proc_macro2::Group::new(
+                    group.delimiter(),
+                    instantiate_type(group.stream(), templates),
+                );
+                instantiated.set_span(group.span());
+                instantiated.into_token_stream()
+            }
+            token => token.into_token_stream(),
+        })
+        .collect()
+}
+
 #[derive(FromAttributes, Debug, Default)]
 #[darling(attributes(php), forward_attrs(doc), default)]
 struct PropAttributes {
@@ -181,6 +380,11 @@ struct PropAttributes {
     #[darling(flatten)]
     rename: PhpRename,
     flags: Option<Expr>,
+    /// The visibility of the property when it is modified, if it differs from
+    /// its visibility (PHP 8.4+).
+    set_vis: Option<Visibility>,
+    /// Whether the property is readonly (PHP 8.1+).
+    readonly: Flag,
     default: Option<Expr>,
     attrs: Vec<Attribute>,
 }
@@ -201,6 +405,12 @@ fn parse_fields<'a>(fields: impl Iterator<Item = &'a mut syn::Field>) -> Result<
                 .rename
                 .rename(ident_to_php_name(ident), RenameRule::Camel);
             validate_php_name(&name, PhpNameContext::Property, ident.span())?;
+            if attr.static_.is_present() && attr.set_vis.is_some() {
+                bail!(ident => "Static properties cannot have asymmetric visibility.");
+            }
+            if attr.static_.is_present() && attr.readonly.is_present() {
+                bail!(ident => "Static properties cannot be readonly.");
+            }
 
             result.push(Property {
                 ident,
@@ -230,18 +440,107 @@ impl Property<'_> {
     }
 }
 
+/// Adds the flag of the set visibility of a property to its flags. Asymmetric
+/// visibility is only available from PHP 8.4, so a compile error is emitted
+/// on earlier versions.
+fn set_visibility_flags(flags: TokenStream, set_vis: Option<Visibility>) -> TokenStream {
+    let Some(set_vis) = set_vis else {
+        return flags;
+    };
+    let set_flag = match set_vis {
+        Visibility::Public => quote! { PublicSet },
+        Visibility::Protected => quote! { ProtectedSet },
+        Visibility::Private => quote! { PrivateSet },
+    };
+    versioned_property_flag(
+        &flags,
+        &set_flag,
+        &quote! { php84 },
+        "Asymmetric property visibility requires PHP 8.4 or later",
+    )
+}
+
+/// Adds the `Readonly` flag to the flags of a readonly property. Readonly
+/// properties are only available from PHP 8.1, so a compile error is emitted
+/// on earlier versions.
+fn readonly_flags(flags: TokenStream, readonly: bool) -> TokenStream {
+    if !readonly {
+        return flags;
+    }
+    versioned_property_flag(
+        &flags,
+        &quote! { Readonly },
+        &quote! { php81 },
+        "Readonly properties require PHP 8.1 or later",
+    )
+}
+
+/// Adds a property flag which is only available from the PHP version enabling
+/// `cfg`, emitting `error` on earlier versions.
+fn versioned_property_flag(
+    flags: &TokenStream,
+    flag: &TokenStream,
+    cfg: &TokenStream,
+    error: &str,
+) -> TokenStream {
+    quote! {
+        {
+            #[cfg(not(#cfg))]
+            compile_error!(#error);
+
+            #[cfg(#cfg)]
+            {
+                ::ext_php_rs::flags::PropertyFlags::from_bits_retain(
+                    (#flags).bits() | ::ext_php_rs::flags::PropertyFlags::#flag.bits()
+                )
+            }
+            #[cfg(not(#cfg))]
+            { #flags }
+        }
+    }
+}
+
+/// Generates the setter of a field property, converting the assigned zval
+/// into the type of the field.
+fn field_property_setter(
+    ident: &syn::Type,
+    field_ident: &syn::Ident,
+    field_ty: &TokenStream,
+    setter_name: &syn::Ident,
+) -> TokenStream {
+    quote! {
+        fn #setter_name(
+            this: &mut #ident,
+            __zv: &::ext_php_rs::types::Zval,
+        ) -> ::ext_php_rs::exception::PhpResult {
+            use ::ext_php_rs::convert::FromZval as _;
+            let val = <#field_ty as ::ext_php_rs::convert::FromZval>::from_zval(__zv)
+                .ok_or_else(|| {
+                    let ty = __zv.get_type();
+                    format!("Failed to set property: could not convert from {ty:?}")
+                })?;
+            this.#field_ident = val;
+            Ok(())
+        }
+    }
+}
+
 /// Generates an implementation of `RegisteredClass` for struct `ident`.
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 fn generate_registered_class_impl(
-    ident: &syn::Ident,
+    ident: &syn::Type,
     class_name: &str,
     modifier: Option<&syn::Ident>,
     extends: Option<&ClassEntryAttribute>,
     implements: &[ClassEntryAttribute],
     fields: &[Property],
+    templates: &[(syn::Ident, syn::Type)],
     flags: Option<&syn::Expr>,
     readonly: bool,
     docs: &[String],
+    deprecation: Option<&TokenStream>,
+    attribute_flags: Option<&TokenStream>,
+    handlers: ObjectHandlers<'_>,
     has_derive_default: bool,
     has_derive_clone: bool,
 ) -> TokenStream {
@@ -261,7 +560,7 @@ fn generate_registered_class_impl(
         .map(|(i, prop)| {
             let name = &prop.name;
             let field_ident = prop.ident;
-            let field_ty = prop.ty;
+            let field_ty = instantiate_type(prop.ty.to_token_stream(), templates);
             let flags = prop
                 .attr
                 .flags
@@ -271,6 +570,16 @@ fn generate_registered_class_impl(
             let docs = &prop.docs;
             let getter_name = syn::Ident::new(&format!("__prop_get_{i}"), field_ident.span());
             let setter_name = syn::Ident::new(&format!("__prop_set_{i}"), field_ident.span());
+            // Properties of readonly classes are implicitly readonly.
+            let readonly = readonly || prop.attr.readonly.is_present();
+            // Readonly properties are never modified once the object is
+            // constructed, so they have no setter.
+            let setter = (!readonly)
+                .then(|| field_property_setter(ident, field_ident, &field_ty, &setter_name));
+            let setter_ref = setter.as_ref().map_or_else(
+                || quote! { ::std::option::Option::None },
+                |_| quote! { ::std::option::Option::Some(#setter_name) },
+            );
 
             let fns = quote! {
                 fn #getter_name(
@@ -282,19 +591,7 @@ fn generate_registered_class_impl(
                         .map_err(|e| format!("Failed to get property value: {e:?}"))?;
                     Ok(())
                 }
-                fn #setter_name(
-                    this: &mut #ident,
-                    __zv: &::ext_php_rs::types::Zval,
-                ) -> ::ext_php_rs::exception::PhpResult {
-                    use ::ext_php_rs::convert::FromZval as _;
-                    let val = <#field_ty as ::ext_php_rs::convert::FromZval>::from_zval(__zv)
-                        .ok_or_else(|| {
-                            let ty = __zv.get_type();
-                            format!("Failed to set property: could not convert from {ty:?}")
-                        })?;
-                    this.#field_ident = val;
-                    Ok(())
-                }
+                #setter
             };
 
             // Determine visibility from the token stream. The flags expression
@@ -309,17 +606,31 @@ fn generate_registered_class_impl(
                 name.clone()
             };
 
+            let flags = set_visibility_flags(flags, prop.attr.set_vis);
+            let flags = readonly_flags(flags, readonly);
+            let default = prop.attr.default.as_ref().map_or_else(
+                || quote! { ::std::option::Option::None },
+                |expr| {
+                    quote! {
+                        ::std::option::Option::Some(
+                            &#expr as &'static (dyn ::ext_php_rs::convert::IntoZvalDyn + Sync)
+                        )
+                    }
+                },
+            );
+
             let descriptor = quote! {
                 ::ext_php_rs::internal::property::PropertyDescriptor {
                     name: #name,
                     mangled_name: #mangled_name,
                     get: ::std::option::Option::Some(#getter_name),
-                    set: ::std::option::Option::Some(#setter_name),
+                    set: #setter_ref,
                     flags: #flags,
                     docs: &[#(#docs,)*],
                     ty: <#field_ty as ::ext_php_rs::convert::IntoZval>::TYPE,
                     nullable: <#field_ty as ::ext_php_rs::convert::IntoZval>::NULLABLE,
-                    readonly: false,
+                    readonly: #readonly,
+                    default: #default,
                 }
             };
 
@@ -405,6 +716,22 @@ fn generate_registered_class_impl(
         #(#docs,)*
     };
 
+    let deprecation = deprecation.map(|deprecation| {
+        quote! {
+            const DEPRECATION: ::std::option::Option<::ext_php_rs::deprecation::Deprecation> =
+                ::std::option::Option::Some(#deprecation);
+        }
+    });
+
+    let attribute_flags = attribute_flags.map(|flags| {
+        quote! {
+            #[inline]
+            fn attribute_flags() -> ::std::option::Option<::ext_php_rs::flags::AttributeFlags> {
+                ::std::option::Option::Some(#flags)
+            }
+        }
+    });
+
     let extends = if let Some(extends) = extends {
         quote! {
             Some(#extends)
@@ -417,6 +744,22 @@ fn generate_registered_class_impl(
         quote! { #imp }
     });
 
+    let templates = (!templates.is_empty()).then(|| {
+        let templates = templates.iter().map(|(param, ty)| {
+            let param = param.to_string();
+            quote! {
+                (#param, ::ext_php_rs::internal::class::TemplateTypeCollector::<#ty>::default().template_type())
+            }
+        });
+        quote! {
+            #[inline]
+            fn templates() -> ::std::vec::Vec<(&'static str, ::ext_php_rs::flags::DataType)> {
+                use ::ext_php_rs::internal::class::TemplateType as _;
+                vec![#(#templates),*]
+            }
+        }
+    });
+
     let default_init_impl = generate_default_init_impl(ident, has_derive_default);
     let clone_obj_impl = generate_clone_obj_impl(ident, has_derive_clone);
 
@@ -436,6 +779,7 @@ fn generate_registered_class_impl(
             const DOC_COMMENTS: &'static [&'static str] = &[
                 #docs
             ];
+            #deprecation
 
             #[inline]
             fn get_metadata() -> &'static ::ext_php_rs::class::ClassMetadata<Self> {
@@ -501,6 +845,12 @@ fn generate_registered_class_impl(
                 ::ext_php_rs::internal::class::PhpClassImplCollector::<Self>::default().get_interface_methods()
             }
 
+            #templates
+
+            #attribute_flags
+
+            #handlers
+
             #default_init_impl
 
             #clone_obj_impl
@@ -508,8 +858,58 @@ fn generate_registered_class_impl(
     }
 }
 
+/// The object handlers a class opts into, which are implemented by traits of
+/// the struct.
+#[derive(Debug, Clone, Copy)]
+struct ObjectHandlers<'a> {
+    /// `get_iterator`, implemented with `Iterator`.
+    iterator: bool,
+    /// The dimension handlers, implemented with `PhpArrayAccess`.
+    array_access: bool,
+    /// `do_operation`, implemented with the `std::ops` traits, and
+    /// `cast_object` if the objects are converted into strings with `Display`.
+    operators: Option<&'a Operators>,
+}
+
+impl ToTokens for ObjectHandlers<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if self.iterator {
+            tokens.append_all(quote! {
+                #[inline]
+                fn get_iterator() -> ::std::option::Option<::ext_php_rs::iterator::GetIterator> {
+                    ::std::option::Option::Some(::ext_php_rs::iterator::get_iterator::<Self>())
+                }
+            });
+        }
+        if self.array_access {
+            tokens.append_all(quote! {
+                #[inline]
+                fn array_access() -> ::std::option::Option<::ext_php_rs::array_access::ArrayAccessHandlers> {
+                    ::std::option::Option::Some(::ext_php_rs::array_access::array_access::<Self>())
+                }
+            });
+        }
+        if self.operators.is_some() {
+            tokens.append_all(quote! {
+                #[inline]
+                fn do_operation() -> ::std::option::Option<::ext_php_rs::operator::DoOperation> {
+                    ::std::option::Option::Some(::ext_php_rs::operator::do_operation::<Self>())
+                }
+            });
+        }
+        if self.operators.is_some_and(Operators::converts_to_string) {
+            tokens.append_all(quote! {
+                #[inline]
+                fn cast_object() -> ::std::option::Option<::ext_php_rs::operator::CastObject> {
+                    ::std::option::Option::Some(::ext_php_rs::operator::cast_object::<Self>())
+                }
+            });
+        }
+    }
+}
+
 /// Generates the `clone_obj` method implementation for the trait.
-fn generate_clone_obj_impl(_ident: &syn::Ident, has_derive_clone: bool) -> TokenStream {
+fn generate_clone_obj_impl(_ident: &syn::Type, has_derive_clone: bool) -> TokenStream {
     if has_derive_clone {
         quote! {
             #[inline]
@@ -524,7 +924,7 @@ fn generate_clone_obj_impl(_ident: &syn::Ident, has_derive_clone: bool) -> Token
 }
 
 /// Generates the `default_init` method implementation for the trait.
-fn generate_default_init_impl(ident: &syn::Ident, has_derive_default: bool) -> TokenStream {
+fn generate_default_init_impl(ident: &syn::Type, has_derive_default: bool) -> TokenStream {
     if has_derive_default {
         quote! {
             #[inline]
@@ -1,20 +1,26 @@
 use darling::FromAttributes;
+use darling::util::Override;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::ItemConst;
 
 use crate::helpers::get_docs;
-use crate::parsing::{PhpNameContext, PhpRename, RenameRule, ident_to_php_name, validate_php_name};
+use crate::parsing::{
+    PhpNameContext, PhpRename, RenameRule, deprecation_tokens, ident_to_php_name, validate_php_name,
+};
 use crate::prelude::*;
 
 const INTERNAL_CONST_DOC_PREFIX: &str = "_internal_const_docs_";
 const INTERNAL_CONST_NAME_PREFIX: &str = "_internal_const_name_";
+const INTERNAL_CONST_VALUE_PREFIX: &str = "_internal_const_value_";
 
 #[derive(FromAttributes, Default, Debug)]
 #[darling(default, attributes(php), forward_attrs(doc))]
 pub(crate) struct PhpConstAttribute {
     #[darling(flatten)]
     pub(crate) rename: PhpRename,
+    pub(crate) deprecated: Option<Override<String>>,
+    pub(crate) since: Option<String>,
     // TODO: Implement const Visibility
     // pub(crate) vis: Option<Visibility>,
     pub(crate) attrs: Vec<syn::Attribute>,
@@ -33,12 +39,34 @@ pub fn parser(mut item: ItemConst) -> Result<TokenStream> {
     let docs_ident = format_ident!("{INTERNAL_CONST_DOC_PREFIX}{}", item.ident);
     item.attrs.retain(|attr| !attr.path().is_ident("php"));
 
+    // The value registered with PHP, which is wrapped when it is deprecated.
+    let value_ident = format_ident!("{INTERNAL_CONST_VALUE_PREFIX}{}", item.ident);
+    let ident = &item.ident;
+    let ty = &item.ty;
+    let value = if let Some(deprecation) =
+        deprecation_tokens(attr.deprecated.as_ref(), attr.since.as_ref(), &item.ident)?
+    {
+        quote! {
+            const #value_ident: ::ext_php_rs::deprecation::Deprecated<#ty> =
+                ::ext_php_rs::deprecation::Deprecated {
+                    value: #ident,
+                    deprecation: #deprecation,
+                };
+        }
+    } else {
+        quote! {
+            const #value_ident: #ty = #ident;
+        }
+    };
+
     Ok(quote! {
         #item
         #[allow(non_upper_case_globals)]
         const #docs_ident: &[&str] = &[#(#docs),*];
         #[allow(non_upper_case_globals)]
         const #name_ident: &str = #name;
+        #[allow(non_upper_case_globals)]
+        #value
     })
 }
 
@@ -47,9 +75,10 @@ pub fn wrap(input: &syn::Path) -> Result<TokenStream> {
         bail!(input => "Pass a PHP const into `wrap_constant!()`.");
     };
     let doc_const = format_ident!("{INTERNAL_CONST_DOC_PREFIX}{const_name}");
+    let value_const = format_ident!("{INTERNAL_CONST_VALUE_PREFIX}{const_name}");
     let const_name = format_ident!("{INTERNAL_CONST_NAME_PREFIX}{const_name}");
 
     Ok(quote! {
-        (#const_name, #input, #doc_const)
+        (#const_name, #value_const, #doc_const)
     })
 }
@@ -1,6 +1,9 @@
 use std::convert::TryFrom;
 
-use darling::{FromAttributes, util::Flag};
+use darling::{
+    FromAttributes,
+    util::{Flag, Override},
+};
 use itertools::Itertools;
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
@@ -9,7 +12,8 @@ use syn::{Fields, Ident, ItemEnum, Lit};
 use crate::{
     helpers::get_docs,
     parsing::{
-        PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
+        PhpNameContext, PhpRename, RenameRule, Visibility, deprecation_tokens, ident_to_php_name,
+        validate_php_name,
     },
     prelude::*,
 };
@@ -32,6 +36,8 @@ struct PhpEnumAttribute {
 struct PhpEnumVariantAttribute {
     #[darling(flatten)]
     rename: PhpRename,
+    deprecated: Option<Override<String>>,
+    since: Option<String>,
     #[darling(rename = "value")]
     discriminant: Option<Lit>,
     attrs: Vec<syn::Attribute>,
@@ -89,12 +95,19 @@ pub fn parser(mut input: ItemEnum) -> Result<TokenStream> {
         );
         validate_php_name(&case_name, PhpNameContext::EnumCase, variant.ident.span())?;
 
+        let deprecation = deprecation_tokens(
+            variant_attr.deprecated.as_ref(),
+            variant_attr.since.as_ref(),
+            &variant.ident,
+        )?;
+
         cases.push(EnumCase {
             ident: variant.ident.clone(),
             name: case_name,
             attrs: variant_attr,
             discriminant,
             docs,
+            deprecation,
         });
 
         if !cases
@@ -327,6 +340,7 @@ impl<'a> Enum<'a> {
 
 impl ToTokens for Enum<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
         let class = self.registered_class();
         let enum_impl = self.registered_enum();
         let impl_try_from = self.impl_try_from();
@@ -337,6 +351,8 @@ impl ToTokens for Enum<'_> {
             #enum_impl
             #impl_try_from
             #impl_into
+
+            ::ext_php_rs::enum_derives!(#ident);
         });
     }
 }
@@ -349,6 +365,7 @@ struct EnumCase {
     attrs: PhpEnumVariantAttribute,
     discriminant: Option<Discriminant>,
     docs: Vec<String>,
+    deprecation: Option<TokenStream>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -400,12 +417,14 @@ impl ToTokens for EnumCase {
             .as_ref()
             .map_or_else(|| quote! { None }, |v| quote! { Some(#v) });
         let docs = &self.docs;
+        let deprecation = self.deprecation.option_tokens();
 
         tokens.extend(quote! {
             ::ext_php_rs::enum_::EnumCase {
                 name: #ident,
                 discriminant: #discriminant,
                 docs: &[#(#docs,)*],
+                deprecation: #deprecation,
             }
         });
     }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use darling::util::Override;
 use darling::{FromAttributes, ToTokens};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
@@ -8,14 +9,15 @@ use syn::{Expr, FnArg, GenericArgument, ItemFn, PatType, PathArguments, Type, Ty
 
 use crate::helpers::get_docs;
 use crate::parsing::{
-    PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
+    PhpNameContext, PhpRename, RenameRule, Visibility, deprecation_tokens, ident_to_php_name,
+    validate_php_name,
 };
 use crate::prelude::*;
 use crate::syn_ext::DropLifetimes;
 
 /// Checks if the return type is a reference to Self (`&Self` or `&mut Self`).
 /// This is used to detect methods that return `$this` in PHP.
-fn returns_self_ref(output: Option<&Type>) -> bool {
+pub fn returns_self_ref(output: Option<&Type>) -> bool {
     let Some(ty) = output else {
         return false;
     };
@@ -31,7 +33,7 @@ fn returns_self_ref(output: Option<&Type>) -> bool {
 
 /// Checks if the return type is `Self` (not a reference).
 /// This is used to detect methods that return a new instance of the same class.
-fn returns_self(output: Option<&Type>) -> bool {
+pub fn returns_self(output: Option<&Type>) -> bool {
     let Some(ty) = output else {
         return false;
     };
@@ -44,6 +46,58 @@ fn returns_self(output: Option<&Type>) -> bool {
     false
 }
 
+/// Checks if the return type is `impl Iterator<...>`. The iterator is returned
+/// to PHP as a `RustIterator` object.
+fn returns_iterator(output: Option<&Type>) -> bool {
+    let Some(Type::ImplTrait(ty)) = output else {
+        return false;
+    };
+    ty.bounds.iter().any(|bound| {
+        matches!(bound, syn::TypeParamBound::Trait(bound)
+            if bound.path.segments.last().is_some_and(|segment| segment.ident == "Iterator"))
+    })
+}
+
+/// Wraps the result of a function returning `impl Iterator<...>` into a
+/// `RustIterator`.
+fn wrap_iterator(output: Option<&Type>, result: &Ident) -> TokenStream {
+    if returns_iterator(output) {
+        quote! {
+            let #result = ::ext_php_rs::iterator::RustIterator::new(#result);
+        }
+    } else {
+        quote! {}
+    }
+}
+
+/// Runs the future returned by an `async` function to completion. The handler
+/// returns early if the fiber running the function is interrupted, leaving the
+/// exception pending.
+fn wrap_async(is_async: bool, result: &Ident) -> TokenStream {
+    if is_async {
+        quote! {
+            let Ok(#result) = ::ext_php_rs::executor::block_on(#result) else {
+                return;
+            };
+        }
+    } else {
+        quote! {}
+    }
+}
+
+/// Splits the generic parameters of a generic `impl` block into the parameters,
+/// turbofish and where clause of the functions nested in its methods, which
+/// cannot use the parameters of the `impl` block.
+fn split_generics(generics: &syn::Generics) -> (TokenStream, TokenStream, TokenStream) {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+    (
+        quote! { #impl_generics },
+        quote! { #turbofish },
+        quote! { #where_clause },
+    )
+}
+
 pub fn wrap(input: &syn::Path) -> Result<TokenStream> {
     let Some(func_name) = input.get_ident() else {
         bail!(input => "Pass a PHP function name into `wrap_function!()`.");
@@ -60,6 +114,8 @@ pub fn wrap(input: &syn::Path) -> Result<TokenStream> {
 struct PhpFunctionAttribute {
     #[darling(flatten)]
     rename: PhpRename,
+    deprecated: Option<Override<String>>,
+    since: Option<String>,
     defaults: HashMap<Ident, Expr>,
     optional: Option<Ident>,
     vis: Option<Visibility>,
@@ -81,7 +137,12 @@ pub fn parser(mut input: ItemFn) -> Result<TokenStream> {
         .rename
         .rename(ident_to_php_name(&input.sig.ident), RenameRule::Snake);
     validate_php_name(&func_name, PhpNameContext::Function, input.sig.ident.span())?;
-    let func = Function::new(&input.sig, func_name, args, php_attr.optional, docs);
+    let mut func = Function::new(&input.sig, func_name, args, php_attr.optional, docs);
+    func.deprecation = deprecation_tokens(
+        php_attr.deprecated.as_ref(),
+        php_attr.since.as_ref(),
+        &input.sig.ident,
+    )?;
     let function_impl = func.php_function_impl();
 
     Ok(quote! {
@@ -100,10 +161,14 @@ pub struct Function<'a> {
     pub args: Args<'a>,
     /// Function outputs.
     pub output: Option<&'a Type>,
+    /// Whether the function is `async`.
+    pub is_async: bool,
     /// The first optional argument of the function.
     pub optional: Option<Ident>,
     /// Doc comments for the function.
     pub docs: Vec<String>,
+    /// Deprecation of the function, if it is deprecated.
+    pub deprecation: Option<TokenStream>,
 }
 
 #[derive(Debug)]
@@ -112,6 +177,9 @@ pub enum CallType<'a> {
     Method {
         class: &'a syn::Path,
         receiver: MethodReceiver,
+        /// Generic parameters of the `impl` block, along with a bound on the
+        /// class being registered.
+        generics: &'a syn::Generics,
     },
 }
 
@@ -122,8 +190,45 @@ pub enum MethodReceiver {
     Static,
     /// Class method, takes `&self` or `&mut self`.
     Class,
+    /// `async` class method, takes `self` and is called on a clone of the
+    /// object.
+    Cloned,
     /// Class method, takes `&mut ZendClassObject<Self>`.
     ZendClassObject,
+    /// Trait method, takes the `&mut ZendObject` of the class using the
+    /// trait.
+    ZendObject,
+}
+
+impl MethodReceiver {
+    /// Returns the expression returning the argument parser along with the
+    /// object the method is called on, for methods taking the object.
+    fn parser_with_this(&self, class: &syn::Path) -> Option<TokenStream> {
+        match self {
+            Self::Static => None,
+            Self::Class | Self::Cloned | Self::ZendClassObject => {
+                Some(quote! { ex.parser_method::<#class>() })
+            }
+            Self::ZendObject => Some(quote! { ex.parser_object() }),
+        }
+    }
+}
+
+/// Returns the statement binding `this` to the object returned by `value`, an
+/// `Option`, which throws an exception and returns from the handler if the
+/// method is not called on an object.
+fn bind_this(this: &Ident, value: &TokenStream) -> TokenStream {
+    quote! {
+        let #this = match #value {
+            Some(this) => this,
+            None => {
+                ::ext_php_rs::exception::PhpException::default("Failed to retrieve reference to `$this`".into())
+                    .throw()
+                    .unwrap();
+                return;
+            }
+        };
+    }
 }
 
 impl<'a> Function<'a> {
@@ -150,8 +255,10 @@ impl<'a> Function<'a> {
                 syn::ReturnType::Default => None,
                 syn::ReturnType::Type(_, ty) => Some(&**ty),
             },
+            is_async: sig.asyncness.is_some(),
             optional,
             docs,
+            deprecation: None,
         }
     }
 
@@ -175,14 +282,8 @@ impl<'a> Function<'a> {
             .collect::<Vec<_>>();
 
         let returns = self.build_returns(None);
-        let docs = if self.docs.is_empty() {
-            quote! {}
-        } else {
-            let docs = &self.docs;
-            quote! {
-                .docs(&[#(#docs),*])
-            }
-        };
+        let docs = self.build_docs();
+        let deprecated = self.build_deprecated();
 
         quote! {
             ::ext_php_rs::builders::FunctionBuilder::new_abstract(#name)
@@ -191,9 +292,28 @@ impl<'a> Function<'a> {
             #(.arg(#not_required_args))*
             #returns
             #docs
+            #deprecated
+        }
+    }
+
+    fn build_docs(&self) -> TokenStream {
+        if self.docs.is_empty() {
+            quote! {}
+        } else {
+            let docs = &self.docs;
+            quote! {
+                .docs(&[#(#docs),*])
+            }
         }
     }
 
+    fn build_deprecated(&self) -> TokenStream {
+        self.deprecation
+            .as_ref()
+            .map(|deprecation| quote! { .deprecated(#deprecation) })
+            .unwrap_or_default()
+    }
+
     /// Generates the function builder for the function.
     pub fn function_builder(&self, call_type: &CallType) -> TokenStream {
         let name = &self.name;
@@ -219,29 +339,11 @@ impl<'a> Function<'a> {
 
         let returns = self.build_returns(Some(call_type));
         let result = self.build_result(call_type, required, not_required);
-        let docs = if self.docs.is_empty() {
-            quote! {}
-        } else {
-            let docs = &self.docs;
-            quote! {
-                .docs(&[#(#docs),*])
-            }
-        };
+        let docs = self.build_docs();
+        let deprecated = self.build_deprecated();
 
-        // Static methods cannot return &Self or &mut Self
-        if returns_self_ref(self.output)
-            && let CallType::Method {
-                receiver: MethodReceiver::Static,
-                ..
-            } = call_type
-            && let Some(output) = self.output
-        {
-            return quote_spanned! { output.span() =>
-                compile_error!(
-                    "Static methods cannot return `&Self` or `&mut Self`. \
-                     Only instance methods can use fluent interface pattern returning `$this`."
-                )
-            };
+        if let Some(error) = self.returns_self_ref_error(call_type) {
+            return error;
         }
 
         // Check if this method returns &Self or &mut Self
@@ -255,22 +357,24 @@ impl<'a> Function<'a> {
                 }
             );
 
+        let (impl_generics, turbofish, where_clause) = match call_type {
+            CallType::Function => (quote! {}, quote! {}, quote! {}),
+            CallType::Method { generics, .. } => split_generics(generics),
+        };
         let handler_body = if self.is_fast_path_eligible(call_type) {
             self.build_fast_handler_body(call_type)
         } else if returns_this {
+            // The method returns &Self or &mut Self, use `this` directly
+            let set_retval = self.build_set_retval(&format_ident!("this"));
             quote! {
                 use ::ext_php_rs::convert::IntoZval;
 
                 #(#arg_declarations)*
                 #result
-
-                // The method returns &Self or &mut Self, use `this` directly
-                if let Err(e) = this.set_zval(retval, false) {
-                    let e: ::ext_php_rs::exception::PhpException = e.into();
-                    e.throw().expect("Failed to throw PHP exception.");
-                }
+                #set_retval
             }
         } else {
+            let set_retval = self.build_set_retval(&format_ident!("result"));
             quote! {
                 use ::ext_php_rs::convert::IntoZval;
 
@@ -278,11 +382,7 @@ impl<'a> Function<'a> {
                 let result = {
                     #result
                 };
-
-                if let Err(e) = result.set_zval(retval, false) {
-                    let e: ::ext_php_rs::exception::PhpException = e.into();
-                    e.throw().expect("Failed to throw PHP exception.");
-                }
+                #set_retval
             }
         };
 
@@ -290,10 +390,10 @@ impl<'a> Function<'a> {
             ::ext_php_rs::builders::FunctionBuilder::new(#name, {
                 ::ext_php_rs::zend_fastcall! {
                     #[allow(clippy::used_underscore_binding)]
-                    extern fn handler(
+                    extern fn handler #impl_generics (
                         ex: &mut ::ext_php_rs::zend::ExecuteData,
                         retval: &mut ::ext_php_rs::types::Zval,
-                    ) {
+                    ) #where_clause {
                         use ::ext_php_rs::zend::try_catch;
                         use ::std::panic::AssertUnwindSafe;
 
@@ -310,13 +410,14 @@ impl<'a> Function<'a> {
                         }
                     }
                 }
-                handler
+                handler #turbofish
             })
             #(.arg(#required_args))*
             .not_required()
             #(.arg(#not_required_args))*
             #returns
             #docs
+            #deprecated
         }
     }
 
@@ -338,6 +439,11 @@ impl<'a> Function<'a> {
         let mut output = output;
         output.drop_lifetimes();
 
+        // Iterators are returned as `RustIterator` objects
+        if returns_iterator(self.output) {
+            output = syn::parse_quote!(::ext_php_rs::iterator::RustIterator);
+        }
+
         // If returning &Self or &mut Self from a method, use the class type
         // for return type information since we return `this` (ZendClassObject)
         if returns_self_ref(self.output)
@@ -372,6 +478,52 @@ impl<'a> Function<'a> {
                 false,
                 <#output as ::ext_php_rs::convert::IntoZval>::NULLABLE,
             )
+            .returns_doc_type(<#output as ::ext_php_rs::convert::IntoZval>::doc_type())
+            .returns_type_decl(<#output as ::ext_php_rs::convert::IntoZval>::type_decl())
+        }
+    }
+
+    /// Returns a compile error if the function returns `&Self` or `&mut Self`
+    /// but cannot return `$this`.
+    fn returns_self_ref_error(&self, call_type: &CallType) -> Option<TokenStream> {
+        let output = self.output.filter(|_| returns_self_ref(self.output))?;
+
+        // Static methods cannot return &Self or &mut Self
+        if let CallType::Method {
+            receiver: MethodReceiver::Static,
+            ..
+        } = call_type
+        {
+            return Some(quote_spanned! { output.span() =>
+                compile_error!(
+                    "Static methods cannot return `&Self` or `&mut Self`. \
+                     Only instance methods can use fluent interface pattern returning `$this`."
+                )
+            });
+        }
+
+        // Returning `$this` is not supported by `async` methods
+        self.is_async.then(|| {
+            quote_spanned! { output.span() =>
+                compile_error!("`async` methods cannot return `&Self` or `&mut Self`.")
+            }
+        })
+    }
+
+    /// Returns the statements setting the return value of the handler to the
+    /// value bound to `result`. The future returned by an `async` function is
+    /// run to completion, and iterators are wrapped in a `RustIterator`.
+    fn build_set_retval(&self, result: &syn::Ident) -> TokenStream {
+        let wrap_async = wrap_async(self.is_async, result);
+        let wrap_iterator = wrap_iterator(self.output, result);
+        quote! {
+            #wrap_async
+            #wrap_iterator
+
+            if let Err(e) = #result.set_zval(retval, false) {
+                let e: ::ext_php_rs::exception::PhpException = e.into();
+                e.throw().expect("Failed to throw PHP exception.");
+            }
         }
     }
 
@@ -398,14 +550,13 @@ impl<'a> Function<'a> {
             }
         });
 
-        let arg_accessors = self.args.typed.iter().map(|arg| {
-            arg.accessor(|e| {
-                quote! {
-                    #e.throw().expect("Failed to throw PHP exception.");
-                    return;
-                }
-            })
-        });
+        let bail = quote! { return; };
+        let arg_accessors = self
+            .args
+            .typed
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| arg.accessor(u32::try_from(i + 1).expect("too many args"), &bail));
 
         // Check if this method returns &Self or &mut Self
         let returns_this = returns_self_ref(self.output);
@@ -424,23 +575,19 @@ impl<'a> Function<'a> {
 
                 #ident(#({#arg_accessors}),*)
             },
-            CallType::Method { class, receiver } => {
-                let this = match receiver {
-                    MethodReceiver::Static => quote! {
+            CallType::Method {
+                class, receiver, ..
+            } => {
+                let this = if let Some(parser) = receiver.parser_with_this(class) {
+                    let this = bind_this(&format_ident!("this"), &quote! { this });
+                    quote! {
+                        let (parse, this) = #parser;
+                        #this
+                    }
+                } else {
+                    quote! {
                         let parse = ex.parser();
-                    },
-                    MethodReceiver::ZendClassObject | MethodReceiver::Class => quote! {
-                        let (parse, this) = ex.parser_method::<#class>();
-                        let this = match this {
-                            Some(this) => this,
-                            None => {
-                                ::ext_php_rs::exception::PhpException::default("Failed to retrieve reference to `$this`".into())
-                                    .throw()
-                                    .unwrap();
-                                return;
-                            }
-                        };
-                    },
+                    }
                 };
 
                 // When returning &Self or &mut Self, discard the return value
@@ -455,6 +602,9 @@ impl<'a> Function<'a> {
                     (MethodReceiver::Class, false) => {
                         quote! { this.#ident(#({#arg_accessors}),*) }
                     }
+                    (MethodReceiver::Cloned, _) => {
+                        quote! { ::std::clone::Clone::clone(&**this).#ident(#({#arg_accessors}),*) }
+                    }
                     (MethodReceiver::ZendClassObject, true) => {
                         // Explicit scope helps with mutable borrow lifetime when
                         // the method returns `&mut Self`
@@ -464,7 +614,7 @@ impl<'a> Function<'a> {
                             }
                         }
                     }
-                    (MethodReceiver::ZendClassObject, false) => {
+                    (MethodReceiver::ZendClassObject, false) | (MethodReceiver::ZendObject, _) => {
                         quote! { #class::#ident(this, #({#arg_accessors}),*) }
                     }
                 };
@@ -497,7 +647,9 @@ impl<'a> Function<'a> {
                 | CallType::Method {
                     receiver: MethodReceiver::Static
                         | MethodReceiver::Class
-                        | MethodReceiver::ZendClassObject,
+                        | MethodReceiver::Cloned
+                        | MethodReceiver::ZendClassObject
+                        | MethodReceiver::ZendObject,
                     ..
                 }
         );
@@ -542,9 +694,40 @@ impl<'a> Function<'a> {
             syn::parse_quote! { Option<#ty> }
         };
 
+        let arg_num = u32::try_from(i + 1).expect("too many args");
+        let nullable = arg.nullable;
+        let php_type = quote! { <#convert_ty as ::ext_php_rs::convert::FromZvalMut>::TYPE };
+        let throw_invalid = |zval: TokenStream| {
+            quote! {
+                let expected = <#convert_ty as ::ext_php_rs::convert::FromZvalMut>::type_decl()
+                    .unwrap_or_else(|| #php_type.into());
+                let expected = if #nullable { expected.nullable() } else { expected };
+                ::ext_php_rs::args::throw_invalid_arg(#zval, &expected, #arg_num);
+                return;
+            }
+        };
+        let throw_given = throw_invalid(quote! { Some(&*#zval_ident) });
+
+        // Scalar arguments are coerced as the engine does for internal functions,
+        // unless the caller declares `strict_types=1`.
+        let coerce = (!arg.as_ref).then(|| {
+            quote! {
+                if !::ext_php_rs::args::coerce_arg(
+                    #zval_ident,
+                    #php_type,
+                    #nullable,
+                    #arg_num,
+                    || ex.arg_uses_strict_types(),
+                ) {
+                    #throw_given
+                }
+            }
+        });
+
         let read_zval = quote! {
             let #zval_ident = unsafe { ex.zend_call_arg(#i) };
             let Some(#zval_ident) = #zval_ident else { return; };
+            #coerce
         };
 
         let from_zval = quote! {
@@ -554,35 +737,22 @@ impl<'a> Function<'a> {
         };
 
         let convert = if arg.nullable {
-            from_zval.clone()
-        } else {
             quote! {
                 match #from_zval {
-                    Some(val) => val,
+                    Some(val) => Some(val),
                     None => {
-                        ::ext_php_rs::exception::PhpException::default(
-                            concat!("Invalid value given for argument `", stringify!(#name), "`.").into()
-                        ).throw().expect("Failed to throw PHP exception.");
-                        return;
+                        if !#zval_ident.dereference().is_null() { #throw_given }
+                        None
                     }
                 }
             }
-        };
-
-        let throw_invalid = quote! {
-            ::ext_php_rs::exception::PhpException::default(
-                concat!("Invalid value given for argument `", stringify!(#name), "`.").into()
-            ).throw().expect("Failed to throw PHP exception.");
-            return;
-        };
-
-        let throw_null = quote! {
-            ::ext_php_rs::exception::PhpException::new(
-                concat!("Argument `$", stringify!(#name), "` must not be null").into(),
-                0,
-                ::ext_php_rs::zend::ce::type_error(),
-            ).throw().expect("Failed to throw PHP exception.");
-            return;
+        } else {
+            quote! {
+                match #from_zval {
+                    Some(val) => val,
+                    None => { #throw_given }
+                }
+            }
         };
 
         // Required arg — always present
@@ -597,7 +767,7 @@ impl<'a> Function<'a> {
         let fallback = match (&arg.default, arg.nullable) {
             (Some(expr), _) => quote! { #expr },
             (None, true) => quote! { None },
-            (None, false) => throw_invalid.clone(),
+            (None, false) => throw_invalid(quote! { None }),
         };
 
         // Non-nullable with default: explicit null must throw TypeError
@@ -605,7 +775,7 @@ impl<'a> Function<'a> {
             return quote! {
                 let #name: #binding_ty = if __num_args > #i {
                     #read_zval
-                    if #zval_ident.is_null() { #throw_null }
+                    if #zval_ident.is_null() { #throw_given }
                     #convert
                 } else {
                     #fallback
@@ -669,13 +839,6 @@ impl<'a> Function<'a> {
 
         let arg_names: Vec<_> = self.args.typed.iter().map(|arg| arg.name).collect();
 
-        let this_error = quote! {
-            ::ext_php_rs::exception::PhpException::default(
-                "Failed to retrieve reference to `$this`".into()
-            ).throw().unwrap();
-            return;
-        };
-
         let returns_this = returns_self_ref(self.output);
 
         let (this_binding, call) = match call_type {
@@ -686,69 +849,54 @@ impl<'a> Function<'a> {
                 ..
             } => (quote! {}, quote! { #class::#ident(#(#arg_names),*) }),
             CallType::Method {
-                class,
-                receiver: MethodReceiver::Class,
-                ..
-            } => (
-                quote! {
-                    let __this = match ex.get_object::<#class>() {
-                        Some(v) => v,
-                        None => { #this_error }
-                    };
-                },
-                if returns_this {
-                    quote! { let _ = __this.#ident(#(#arg_names),*); }
-                } else {
-                    quote! { __this.#ident(#(#arg_names),*) }
-                },
-            ),
-            CallType::Method {
-                class,
-                receiver: MethodReceiver::ZendClassObject,
-                ..
-            } => (
-                quote! {
-                    let __this = match ex.get_object::<#class>() {
-                        Some(v) => v,
-                        None => { #this_error }
-                    };
-                },
-                if returns_this {
-                    quote! { { let _ = #class::#ident(__this, #(#arg_names),*); } }
+                class, receiver, ..
+            } => {
+                let this = format_ident!("__this");
+                let getter = if matches!(receiver, MethodReceiver::ZendObject) {
+                    quote! { ex.get_self() }
                 } else {
-                    quote! { #class::#ident(__this, #(#arg_names),*) }
-                },
-            ),
+                    quote! { ex.get_object::<#class>() }
+                };
+                let call = match (receiver, returns_this) {
+                    (MethodReceiver::Class, true) => {
+                        quote! { let _ = __this.#ident(#(#arg_names),*); }
+                    }
+                    (MethodReceiver::Class, false) => {
+                        quote! { __this.#ident(#(#arg_names),*) }
+                    }
+                    (MethodReceiver::Cloned, _) => {
+                        quote! { ::std::clone::Clone::clone(&**__this).#ident(#(#arg_names),*) }
+                    }
+                    (MethodReceiver::ZendClassObject, true) => {
+                        quote! { { let _ = #class::#ident(__this, #(#arg_names),*); } }
+                    }
+                    _ => quote! { #class::#ident(__this, #(#arg_names),*) },
+                };
+                (bind_this(&this, &getter), call)
+            }
         };
 
-        if returns_this {
+        let call = if returns_this {
+            let set_retval = self.build_set_retval(&format_ident!("__this"));
             quote! {
-                use ::ext_php_rs::convert::{FromZvalMut, IntoZval};
-
-                #count_check
-                #(#arg_bindings)*
-                #this_binding
                 #call
-
-                if let Err(e) = __this.set_zval(retval, false) {
-                    let e: ::ext_php_rs::exception::PhpException = e.into();
-                    e.throw().expect("Failed to throw PHP exception.");
-                }
+                #set_retval
             }
         } else {
+            let set_retval = self.build_set_retval(&format_ident!("__result"));
             quote! {
-                use ::ext_php_rs::convert::{FromZvalMut, IntoZval};
-
-                #count_check
-                #(#arg_bindings)*
-                #this_binding
                 let __result = { #call };
-
-                if let Err(e) = __result.set_zval(retval, false) {
-                    let e: ::ext_php_rs::exception::PhpException = e.into();
-                    e.throw().expect("Failed to throw PHP exception.");
-                }
+                #set_retval
             }
+        };
+
+        quote! {
+            use ::ext_php_rs::convert::{FromZvalMut, IntoZval};
+
+            #count_check
+            #(#arg_bindings)*
+            #this_binding
+            #call
         }
     }
 
@@ -779,9 +927,11 @@ impl<'a> Function<'a> {
     pub fn constructor_meta(
         &self,
         class: &syn::Path,
+        generics: &syn::Generics,
         visibility: Option<&Visibility>,
     ) -> TokenStream {
         let ident = self.ident;
+        let (impl_generics, turbofish, where_clause) = split_generics(generics);
         let (required, not_required) = self.args.split_args(self.optional.as_ref());
         let required_args = required
             .iter()
@@ -812,23 +962,26 @@ impl<'a> Function<'a> {
                 None
             }
         });
-        let arg_accessors = self.args.typed.iter().map(|arg| {
-            arg.accessor(
-                |e| quote! { return ::ext_php_rs::class::ConstructorResult::Exception(#e); },
-            )
-        });
+        let bail = quote! { return ::ext_php_rs::class::ConstructorResult::ArgError; };
+        let arg_accessors = self
+            .args
+            .typed
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| arg.accessor(u32::try_from(i + 1).expect("too many args"), &bail));
         let variadic = self.args.typed.iter().any(|arg| arg.variadic).then(|| {
             quote! {
                 .variadic()
             }
         });
         let docs = &self.docs;
+        let deprecated = self.build_deprecated();
         let flags = visibility.option_tokens();
 
         quote! {
             ::ext_php_rs::class::ConstructorMeta {
                 constructor: {
-                    fn inner(ex: &mut ::ext_php_rs::zend::ExecuteData) -> ::ext_php_rs::class::ConstructorResult<#class> {
+                    fn inner #impl_generics (ex: &mut ::ext_php_rs::zend::ExecuteData) -> ::ext_php_rs::class::ConstructorResult<#class> #where_clause {
                         use ::ext_php_rs::zend::try_catch;
                         use ::std::panic::AssertUnwindSafe;
 
@@ -858,18 +1011,19 @@ impl<'a> Function<'a> {
                             }
                         }
                     }
-                    inner
+                    inner #turbofish
                 },
                 build_fn: {
-                    fn inner(func: ::ext_php_rs::builders::FunctionBuilder) -> ::ext_php_rs::builders::FunctionBuilder {
+                    fn inner #impl_generics (func: ::ext_php_rs::builders::FunctionBuilder) -> ::ext_php_rs::builders::FunctionBuilder #where_clause {
                         func
                             .docs(&[#(#docs),*])
                             #(.arg(#required_args))*
                             .not_required()
                             #(.arg(#not_required_args))*
                             #variadic
+                            #deprecated
                     }
-                    inner
+                    inner #turbofish
                 },
                 flags: #flags
             }
@@ -880,6 +1034,8 @@ impl<'a> Function<'a> {
 #[derive(Debug)]
 pub struct ReceiverArg {
     pub _mutable: bool,
+    /// Whether the receiver is `self`, taken by value.
+    pub by_value: bool,
     pub span: Span,
 }
 
@@ -911,13 +1067,12 @@ impl<'a> Args<'a> {
         for arg in args {
             match arg {
                 FnArg::Receiver(receiver) => {
-                    if receiver.reference.is_none() {
-                        bail!(receiver => "PHP objects are heap-allocated and cannot be passed by value. Try using `&self` or `&mut self`.");
-                    } else if result.receiver.is_some() {
+                    if result.receiver.is_some() {
                         bail!(receiver => "Too many receivers specified.")
                     }
                     result.receiver.replace(ReceiverArg {
                         _mutable: receiver.mutability.is_some(),
+                        by_value: receiver.reference.is_none(),
                         span: receiver.span(),
                     });
                 }
@@ -1095,6 +1250,8 @@ impl TypedArg<'_> {
         let variadic = self.variadic.then(|| quote! { .is_variadic() });
         quote! {
             ::ext_php_rs::args::Arg::new(#name, <#ty as ::ext_php_rs::convert::FromZvalMut>::TYPE)
+                .doc_type(<#ty as ::ext_php_rs::convert::FromZvalMut>::doc_type())
+                .type_decl(<#ty as ::ext_php_rs::convert::FromZvalMut>::type_decl())
                 #null
                 #default
                 #as_ref
@@ -1103,79 +1260,47 @@ impl TypedArg<'_> {
     }
 
     /// Get the accessor used to access the value of the argument.
-    fn accessor(&self, bail_fn: impl Fn(TokenStream) -> TokenStream) -> TokenStream {
+    ///
+    /// The error for an invalid argument is thrown by `Arg::try_val`, after
+    /// which the tokens in `bail` are run.
+    fn accessor(&self, arg_num: u32, bail: &TokenStream) -> TokenStream {
         let name = self.name;
-        if let Some(default) = &self.default {
-            if self.nullable {
-                // For nullable types with defaults, null is acceptable
-                quote! {
-                    #name.val().unwrap_or(#default.into())
-                }
-            } else {
-                // For non-nullable types with defaults:
-                // - If argument was omitted: use default
-                // - If null was explicitly passed: throw TypeError
-                // - If a value was passed: try to convert it
-                let bail_null = bail_fn(quote! {
-                    ::ext_php_rs::exception::PhpException::new(
-                        concat!("Argument `$", stringify!(#name), "` must not be null").into(),
-                        0,
-                        ::ext_php_rs::zend::ce::type_error(),
-                    )
-                });
-                let bail_invalid = bail_fn(quote! {
-                    ::ext_php_rs::exception::PhpException::default(
-                        concat!("Invalid value given for argument `", stringify!(#name), "`.").into()
-                    )
-                });
-                quote! {
-                    match #name.zval() {
-                        Some(zval) if zval.is_null() => {
-                            // Null was explicitly passed to a non-nullable parameter
-                            #bail_null
-                        }
-                        Some(_) => {
-                            // A value was passed, try to convert it
-                            match #name.val() {
-                                Some(val) => val,
-                                None => {
-                                    #bail_invalid
-                                }
-                            }
-                        }
-                        None => {
-                            // Argument was omitted, use default
-                            #default.into()
-                        }
-                    }
-                }
-            }
-        } else if self.variadic {
+        if self.variadic {
             let variadic_name = format_ident!("__variadic_{}", name);
-            quote! {
+            return quote! {
                 #variadic_name.as_slice()
-            }
-        } else if self.nullable {
-            // Originally I thought we could just use the below case for `null` options, as
-            // `val()` will return `Option<Option<T>>`, however, this isn't the case when
-            // the argument isn't given, as the underlying zval is null.
+            };
+        }
+
+        let try_val = quote! { #name.try_val(#arg_num) };
+        let val = if self.nullable {
             quote! {
-                #name.val()
+                match #try_val {
+                    Ok(val) => val,
+                    Err(_) => { #bail }
+                }
             }
         } else {
-            let bail = bail_fn(quote! {
-                ::ext_php_rs::exception::PhpException::default(
-                    concat!("Invalid value given for argument `", stringify!(#name), "`.").into()
-                )
-            });
             quote! {
-                match #name.val() {
-                    Some(val) => val,
-                    None => {
-                        #bail;
-                    }
+                match #try_val {
+                    Ok(Some(val)) => val,
+                    _ => { #bail }
+                }
+            }
+        };
+
+        if let Some(default) = &self.default {
+            // The default is used if the argument was omitted. `null` given for
+            // a non-nullable parameter is rejected by `try_val`.
+            quote! {
+                if #name.zval().is_none() {
+                    #default.into()
+                } else {
+                    #val
                 }
             }
+        } else {
+            val
         }
     }
 }
@@ -1307,8 +1432,9 @@ pub fn type_is_nullable(ty: &Type) -> Result<bool> {
             .iter()
             .next_back()
             .is_some_and(|seg| seg.ident == "Option"),
-        Type::Reference(_) => false, /* Reference cannot be nullable unless */
-        // wrapped in `Option` (in that case it'd be a Path).
+        // References cannot be nullable unless wrapped in `Option` (in that
+        // case it'd be a Path).
+        Type::Reference(_) | Type::Tuple(_) | Type::Array(_) => false,
         _ => bail!(ty => "Unsupported argument type."),
     })
 }
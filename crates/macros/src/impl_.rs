@@ -1,5 +1,5 @@
 use darling::FromAttributes;
-use darling::util::Flag;
+use darling::util::{Flag, Override};
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::collections::{HashMap, HashSet};
@@ -9,13 +9,14 @@ use crate::constant::PhpConstAttribute;
 use crate::function::{Args, CallType, Function, MethodReceiver};
 use crate::helpers::get_docs;
 use crate::parsing::{
-    PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
+    PhpNameContext, PhpRename, RenameRule, Visibility, deprecation_tokens, ident_to_php_name,
+    validate_php_name,
 };
 use crate::prelude::*;
 
 /// Method types.
 #[derive(Debug)]
-enum MethodTy {
+pub enum MethodTy {
     /// Regular PHP method.
     Normal,
     /// Constructor method.
@@ -40,15 +41,39 @@ pub struct PhpImpl {
 pub fn parser(mut input: ItemImpl) -> Result<TokenStream> {
     let args = PhpImpl::from_attributes(&input.attrs)?;
     input.attrs.retain(|attr| !attr.path().is_ident("php"));
-    let path = match &*input.self_ty {
-        syn::Type::Path(ty) => &ty.path,
+    let mut path = match &*input.self_ty {
+        syn::Type::Path(ty) => ty.path.clone(),
         _ => {
             bail!(input.self_ty => "The `#[php_impl]` attribute is only valid for struct implementations.")
         }
     };
 
+    // The path is used in expressions, e.g. `Cache::<T>::new()`.
+    for segment in &mut path.segments {
+        if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+            args.colon2_token = Some(syn::Token![::](segment.ident.span()));
+        }
+    }
+
+    // Generic `impl` blocks are implemented for each instantiation of the
+    // class, with the generic parameters threaded through the functions
+    // generated for their methods.
+    let mut generics = input.generics.clone();
+    if !generics.params.is_empty() {
+        if let Some(param) = generics.lifetimes().next() {
+            bail!(param => "Lifetime parameters are not supported on `#[php_impl]` blocks.");
+        }
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(
+                #path: ::ext_php_rs::class::RegisteredClass + ::ext_php_rs::convert::IntoZval
+            ));
+    }
+
     let mut parsed = ParsedImpl::new(
-        path,
+        &path,
+        &generics,
         args.change_method_case.unwrap_or(RenameRule::Camel),
         args.change_constant_case
             .unwrap_or(RenameRule::ScreamingSnake),
@@ -64,30 +89,32 @@ pub fn parser(mut input: ItemImpl) -> Result<TokenStream> {
 
 /// Arguments applied to methods.
 #[derive(Debug)]
-struct MethodArgs {
+pub struct MethodArgs {
     /// Method name. Only applies to PHP (not the Rust method name).
-    name: String,
+    pub name: String,
     /// The first optional argument of the function signature.
-    optional: Option<Ident>,
+    pub optional: Option<Ident>,
     /// Default values for optional arguments.
-    defaults: HashMap<Ident, Expr>,
+    pub defaults: HashMap<Ident, Expr>,
     /// Visibility of the method (public, protected, private).
-    vis: Visibility,
+    pub vis: Visibility,
     /// Method type.
-    ty: MethodTy,
+    pub ty: MethodTy,
     /// Whether this is a final method.
-    is_final: bool,
+    pub is_final: bool,
 }
 
 #[derive(FromAttributes, Default, Debug)]
 #[darling(default, attributes(php), forward_attrs(doc))]
 pub struct PhpFunctionImplAttribute {
     #[darling(flatten)]
-    rename: PhpRename,
+    pub rename: PhpRename,
+    pub deprecated: Option<Override<String>>,
+    pub since: Option<String>,
     defaults: HashMap<Ident, Expr>,
     optional: Option<Ident>,
     vis: Option<Visibility>,
-    attrs: Vec<syn::Attribute>,
+    pub attrs: Vec<syn::Attribute>,
     getter: Flag,
     setter: Flag,
     constructor: Flag,
@@ -99,7 +126,7 @@ pub struct PhpFunctionImplAttribute {
 
 impl MethodArgs {
     #[allow(clippy::similar_names)]
-    fn new(name: String, attr: PhpFunctionImplAttribute) -> Result<Self> {
+    pub fn new(name: String, attr: PhpFunctionImplAttribute) -> Result<Self> {
         let is_constructor = name == "__construct" || attr.constructor.is_present();
         let is_getter = attr.getter.is_present();
         let is_setter = attr.setter.is_present();
@@ -182,6 +209,7 @@ struct PropertyMethod<'a> {
 #[derive(Debug)]
 struct ParsedImpl<'a> {
     path: &'a syn::Path,
+    generics: &'a syn::Generics,
     change_method_case: RenameRule,
     change_constant_case: RenameRule,
     functions: Vec<FnBuilder>,
@@ -228,6 +256,8 @@ pub struct Constant<'a> {
     pub ident: &'a syn::Ident,
     /// Documentation for the constant.
     pub docs: Vec<String>,
+    /// Deprecation of the constant, if it is deprecated.
+    pub deprecation: Option<TokenStream>,
 }
 
 impl<'a> ParsedImpl<'a> {
@@ -236,11 +266,18 @@ impl<'a> ParsedImpl<'a> {
     /// # Parameters
     ///
     /// * `path` - Path of the type the `impl` block is for.
+    /// * `generics` - Generic parameters of the `impl` block.
     /// * `rename_methods` - Rule to rename methods with.
     /// * `rename_constants` - Rule to rename constants with.
-    fn new(path: &'a syn::Path, rename_methods: RenameRule, rename_constants: RenameRule) -> Self {
+    fn new(
+        path: &'a syn::Path,
+        generics: &'a syn::Generics,
+        rename_methods: RenameRule,
+        rename_constants: RenameRule,
+    ) -> Self {
         Self {
             path,
+            generics,
             change_method_case: rename_methods,
             change_constant_case: rename_constants,
             functions: Vec::default(),
@@ -289,94 +326,151 @@ impl<'a> ParsedImpl<'a> {
         for items in items {
             match items {
                 syn::ImplItem::Const(c) => {
+                    if !self.generics.params.is_empty() {
+                        bail!(c => "Constants are not supported in generic `#[php_impl]` blocks.");
+                    }
                     let attr = PhpConstAttribute::from_attributes(&c.attrs)?;
                     let name = attr
                         .rename
                         .rename(ident_to_php_name(&c.ident), self.change_constant_case);
                     validate_php_name(&name, PhpNameContext::Constant, c.ident.span())?;
                     let docs = get_docs(&attr.attrs)?;
+                    let deprecation = deprecation_tokens(
+                        attr.deprecated.as_ref(),
+                        attr.since.as_ref(),
+                        &c.ident,
+                    )?;
                     c.attrs.retain(|attr| !attr.path().is_ident("php"));
 
                     self.constants.push(Constant {
                         name,
                         ident: &c.ident,
                         docs,
+                        deprecation,
                     });
                 }
-                syn::ImplItem::Fn(method) => {
-                    let attr = PhpFunctionImplAttribute::from_attributes(&method.attrs)?;
-                    let name = attr.rename.rename_method(
-                        ident_to_php_name(&method.sig.ident),
-                        self.change_method_case,
-                    );
-                    validate_php_name(&name, PhpNameContext::Method, method.sig.ident.span())?;
-                    let docs = get_docs(&attr.attrs)?;
-                    method.attrs.retain(|attr| !attr.path().is_ident("php"));
+                syn::ImplItem::Fn(method) => self.parse_method(method)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a method of the impl block, adding it to the functions,
+    /// constructor or properties of `self`.
+    fn parse_method(&mut self, method: &'a mut syn::ImplItemFn) -> Result<()> {
+        let attr = PhpFunctionImplAttribute::from_attributes(&method.attrs)?;
+        let name = attr.rename.rename_method(
+            ident_to_php_name(&method.sig.ident),
+            self.change_method_case,
+        );
+        validate_php_name(&name, PhpNameContext::Method, method.sig.ident.span())?;
+        let docs = get_docs(&attr.attrs)?;
+        let deprecation = deprecation_tokens(
+            attr.deprecated.as_ref(),
+            attr.since.as_ref(),
+            &method.sig.ident,
+        )?;
+        method.attrs.retain(|attr| !attr.path().is_ident("php"));
+
+        let opts = MethodArgs::new(name, attr)?;
+
+        if method.sig.asyncness.is_some()
+            && matches!(
+                opts.ty,
+                MethodTy::Constructor | MethodTy::Getter | MethodTy::Setter
+            )
+        {
+            bail!(method.sig.asyncness => "Constructors, getters and setters cannot be `async`.");
+        }
 
-                    let opts = MethodArgs::new(name, attr)?;
+        // The object could be used by other fibers while the call is suspended,
+        // so `async` methods are called on a clone of the object instead.
+        let borrows_object = match method.sig.inputs.first() {
+            Some(syn::FnArg::Receiver(receiver)) => receiver.reference.is_some(),
+            Some(syn::FnArg::Typed(arg)) => {
+                matches!(&*arg.pat, syn::Pat::Ident(pat) if pat.ident == "self_")
+            }
+            None => false,
+        };
+        if method.sig.asyncness.is_some() && borrows_object {
+            bail!(method.sig.inputs => "`async` methods cannot borrow the object, which other fibers can use while the call is suspended. Take `self` to call the method on a clone of the object.");
+        }
+        if let Some(receiver) = method.sig.receiver()
+            && method.sig.asyncness.is_none()
+            && receiver.reference.is_none()
+        {
+            bail!(receiver => "PHP objects are heap-allocated and cannot be passed by value. Try using `&self` or `&mut self`.");
+        }
 
-                    // Handle getter/setter methods
-                    if matches!(opts.ty, MethodTy::Getter | MethodTy::Setter) {
-                        self.parse_property_method(method, &opts, docs);
-                        continue;
-                    }
+        // Handle getter/setter methods
+        if matches!(opts.ty, MethodTy::Getter | MethodTy::Setter) {
+            if !self.generics.params.is_empty() {
+                bail!(method.sig.ident => "Getters and setters are not supported in generic `#[php_impl]` blocks.");
+            }
+            self.parse_property_method(method, &opts, docs);
+            return Ok(());
+        }
 
-                    let args = Args::parse_from_fnargs(method.sig.inputs.iter(), opts.defaults)?;
-                    let mut func = Function::new(&method.sig, opts.name, args, opts.optional, docs);
+        let args = Args::parse_from_fnargs(method.sig.inputs.iter(), opts.defaults)?;
+        let mut func = Function::new(&method.sig, opts.name, args, opts.optional, docs);
+        func.deprecation = deprecation;
 
-                    let mut modifiers: HashSet<MethodModifier> = HashSet::new();
+        let mut modifiers: HashSet<MethodModifier> = HashSet::new();
 
-                    if matches!(opts.ty, MethodTy::Constructor) {
-                        if self.constructor.replace((func, opts.vis.into())).is_some() {
-                            bail!(method => "Only one constructor can be provided per class.");
-                        }
+        if matches!(opts.ty, MethodTy::Constructor) {
+            if self.constructor.replace((func, opts.vis.into())).is_some() {
+                bail!(method => "Only one constructor can be provided per class.");
+            }
+        } else {
+            let call_type = CallType::Method {
+                class: self.path,
+                generics: self.generics,
+                receiver: if let Some(receiver) = &func.args.receiver {
+                    if receiver.by_value {
+                        // `self`, for `async` methods
+                        MethodReceiver::Cloned
                     } else {
-                        let call_type = CallType::Method {
-                            class: self.path,
-                            receiver: if func.args.receiver.is_some() {
-                                // `&self` or `&mut self`
-                                MethodReceiver::Class
-                            } else if func
-                                .args
-                                .typed
-                                .first()
-                                .is_some_and(|arg| arg.name == "self_")
-                            {
-                                // `self_: &[mut] ZendClassObject<Self>`
-                                // Need to remove arg from argument list
-                                func.args.typed.remove(0);
-                                MethodReceiver::ZendClassObject
-                            } else {
-                                modifiers.insert(MethodModifier::Static);
-                                // Static method
-                                MethodReceiver::Static
-                            },
-                        };
-                        let is_abstract = matches!(opts.ty, MethodTy::Abstract);
-                        if is_abstract {
-                            modifiers.insert(MethodModifier::Abstract);
-                            self.has_abstract_methods = true;
-                        }
-                        if opts.is_final {
-                            modifiers.insert(MethodModifier::Final);
-                        }
-
-                        // Abstract methods use a different builder that doesn't generate a handler
-                        let builder = if is_abstract {
-                            func.abstract_function_builder()
-                        } else {
-                            func.function_builder(&call_type)
-                        };
-
-                        self.functions.push(FnBuilder {
-                            builder,
-                            vis: opts.vis,
-                            modifiers,
-                        });
+                        // `&self` or `&mut self`
+                        MethodReceiver::Class
                     }
-                }
-                _ => {}
+                } else if func
+                    .args
+                    .typed
+                    .first()
+                    .is_some_and(|arg| arg.name == "self_")
+                {
+                    // `self_: &[mut] ZendClassObject<Self>`
+                    // Need to remove arg from argument list
+                    func.args.typed.remove(0);
+                    MethodReceiver::ZendClassObject
+                } else {
+                    modifiers.insert(MethodModifier::Static);
+                    // Static method
+                    MethodReceiver::Static
+                },
+            };
+            let is_abstract = matches!(opts.ty, MethodTy::Abstract);
+            if is_abstract {
+                modifiers.insert(MethodModifier::Abstract);
+                self.has_abstract_methods = true;
             }
+            if opts.is_final {
+                modifiers.insert(MethodModifier::Final);
+            }
+
+            // Abstract methods use a different builder that doesn't generate a handler
+            let builder = if is_abstract {
+                func.abstract_function_builder()
+            } else {
+                func.function_builder(&call_type)
+            };
+
+            self.functions.push(FnBuilder {
+                builder,
+                vis: opts.vis,
+                modifiers,
+            });
         }
         Ok(())
     }
@@ -390,19 +484,28 @@ impl<'a> ParsedImpl<'a> {
         let constructor = self
             .constructor
             .as_ref()
-            .map(|(func, vis)| func.constructor_meta(self.path, vis.as_ref()))
+            .map(|(func, vis)| func.constructor_meta(self.path, self.generics, vis.as_ref()))
             .option_tokens();
         let constants = self.constants.iter().map(|c| {
             let name = &c.name;
             let ident = c.ident;
             let docs = &c.docs;
-            quote! {
-                (#name, &#path::#ident, &[#(#docs),*])
+            if let Some(deprecation) = &c.deprecation {
+                quote! {
+                    (#name, &::ext_php_rs::deprecation::Deprecated {
+                        value: #path::#ident,
+                        deprecation: #deprecation,
+                    }, &[#(#docs),*])
+                }
+            } else {
+                quote! {
+                    (#name, &#path::#ident, &[#(#docs),*])
+                }
             }
         });
 
         // Compile-time check: abstract methods can only be in abstract classes
-        let abstract_check = if self.has_abstract_methods {
+        let abstract_check = if self.has_abstract_methods && self.generics.params.is_empty() {
             quote! {
                 const _: () = assert!(
                     <#path as ::ext_php_rs::class::RegisteredClass>::FLAGS
@@ -558,6 +661,7 @@ impl<'a> ParsedImpl<'a> {
                         docs: &[#(#docs),*],
                         #type_tokens
                         readonly: #readonly,
+                        default: ::std::option::Option::None,
                     }
                 };
 
@@ -576,11 +680,30 @@ impl<'a> ParsedImpl<'a> {
             .collect();
         let method_prop_count = method_prop_descriptors.len();
 
+        // Statics cannot use the parameters of generic `impl` blocks, which
+        // have no getters and setters.
+        let method_props = if self.generics.params.is_empty() {
+            quote! {
+                #(#method_prop_fn_defs)*
+
+                static METHOD_PROPS: [
+                    ::ext_php_rs::internal::property::PropertyDescriptor<#path>; #method_prop_count
+                ] = [
+                    #(#method_prop_descriptors,)*
+                ];
+                &METHOD_PROPS
+            }
+        } else {
+            quote! { &[] }
+        };
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+
         quote! {
             #abstract_check
 
-            impl ::ext_php_rs::internal::class::PhpClassImpl<#path>
+            impl #impl_generics ::ext_php_rs::internal::class::PhpClassImpl<#path>
                 for ::ext_php_rs::internal::class::PhpClassImplCollector<#path>
+                #where_clause
             {
                 fn get_methods(self) -> ::std::vec::Vec<
                     (::ext_php_rs::builders::FunctionBuilder<'static>, ::ext_php_rs::flags::MethodFlags)
@@ -589,14 +712,7 @@ impl<'a> ParsedImpl<'a> {
                 }
 
                 fn get_method_props(self) -> &'static [::ext_php_rs::internal::property::PropertyDescriptor<#path>] {
-                    #(#method_prop_fn_defs)*
-
-                    static METHOD_PROPS: [
-                        ::ext_php_rs::internal::property::PropertyDescriptor<#path>; #method_prop_count
-                    ] = [
-                        #(#method_prop_descriptors,)*
-                    ];
-                    &METHOD_PROPS
+                    #method_props
                 }
 
                 fn get_constructor(self) -> ::std::option::Option<::ext_php_rs::class::ConstructorMeta<#path>> {
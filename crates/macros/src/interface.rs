@@ -5,14 +5,15 @@ use crate::constant::PhpConstAttribute;
 use crate::function::{Args, Function};
 use crate::helpers::{CleanPhpAttr, get_docs};
 use darling::FromAttributes;
-use darling::util::Flag;
+use darling::util::{Flag, Override};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
 use syn::{Expr, Ident, ItemTrait, Path, TraitItem, TraitItemConst, TraitItemFn, TypeParamBound};
 
 use crate::impl_::{FnBuilder, MethodModifier};
 use crate::parsing::{
-    PhpNameContext, PhpRename, RenameRule, Visibility, ident_to_php_name, validate_php_name,
+    PhpNameContext, PhpRename, RenameRule, Visibility, deprecation_tokens, ident_to_php_name,
+    validate_php_name,
 };
 use crate::prelude::*;
 
@@ -97,7 +98,13 @@ impl ToTokens for InterfaceData<'_> {
         let _constructor = self
             .constructor
             .as_ref()
-            .map(|func| func.constructor_meta(&self.path, Some(&Visibility::Public)))
+            .map(|func| {
+                func.constructor_meta(
+                    &self.path,
+                    &syn::Generics::default(),
+                    Some(&Visibility::Public),
+                )
+            })
             .option_tokens();
 
         quote! {
@@ -302,6 +309,8 @@ fn parse_supertraits(
 pub struct PhpFunctionInterfaceAttribute {
     #[darling(flatten)]
     rename: PhpRename,
+    deprecated: Option<Override<String>>,
+    since: Option<String>,
     defaults: HashMap<Ident, Expr>,
     optional: Option<Ident>,
     vis: Option<Visibility>,
@@ -328,6 +337,9 @@ fn parse_trait_item_fn(
     fn_item.attrs.clean_php();
 
     let mut args = Args::parse_from_fnargs(fn_item.sig.inputs.iter(), php_attr.defaults)?;
+    if let Some(receiver) = args.receiver.as_ref().filter(|receiver| receiver.by_value) {
+        bail!(receiver.span => "PHP objects are heap-allocated and cannot be passed by value. Try using `&self` or `&mut self`.");
+    }
 
     let docs = get_docs(&php_attr.attrs)?;
 
@@ -349,7 +361,12 @@ fn parse_trait_item_fn(
         PhpNameContext::Method,
         fn_item.sig.ident.span(),
     )?;
-    let f = Function::new(&fn_item.sig, method_name, args, php_attr.optional, docs);
+    let mut f = Function::new(&fn_item.sig, method_name, args, php_attr.optional, docs);
+    f.deprecation = deprecation_tokens(
+        php_attr.deprecated.as_ref(),
+        php_attr.since.as_ref(),
+        &fn_item.sig.ident,
+    )?;
 
     if php_attr.constructor.is_present() {
         Ok(MethodKind::Constructor(f))
@@ -369,6 +386,7 @@ struct Constant<'a> {
     name: String,
     expr: &'a Expr,
     docs: Vec<String>,
+    deprecation: Option<TokenStream>,
 }
 
 impl ToTokens for Constant<'_> {
@@ -376,16 +394,35 @@ impl ToTokens for Constant<'_> {
         let name = &self.name;
         let expr = &self.expr;
         let docs = &self.docs;
-        quote! {
-            (#name, &#expr, &[#(#docs),*])
+        if let Some(deprecation) = &self.deprecation {
+            quote! {
+                (#name, &::ext_php_rs::deprecation::Deprecated {
+                    value: #expr,
+                    deprecation: #deprecation,
+                }, &[#(#docs),*])
+            }
+        } else {
+            quote! {
+                (#name, &#expr, &[#(#docs),*])
+            }
         }
         .to_tokens(tokens);
     }
 }
 
 impl<'a> Constant<'a> {
-    fn new(name: String, expr: &'a Expr, docs: Vec<String>) -> Self {
-        Self { name, expr, docs }
+    fn new(
+        name: String,
+        expr: &'a Expr,
+        docs: Vec<String>,
+        deprecation: Option<TokenStream>,
+    ) -> Self {
+        Self {
+            name,
+            expr,
+            docs,
+            deprecation,
+        }
     }
 }
 
@@ -404,8 +441,13 @@ fn parse_trait_item_const(
     );
     validate_php_name(&name, PhpNameContext::Constant, const_item.ident.span())?;
     let docs = get_docs(&attr.attrs)?;
+    let deprecation = deprecation_tokens(
+        attr.deprecated.as_ref(),
+        attr.since.as_ref(),
+        &const_item.ident,
+    )?;
     const_item.attrs.clean_php();
 
     let (_, expr) = const_item.default.as_ref().unwrap();
-    Ok(Constant::new(name, expr, docs))
+    Ok(Constant::new(name, expr, docs, deprecation))
 }
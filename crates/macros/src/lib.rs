@@ -11,8 +11,11 @@ mod impl_;
 mod impl_interface;
 mod interface;
 mod module;
+mod operator;
 mod parsing;
+mod resource;
 mod syn_ext;
+mod trait_;
 mod zval;
 
 use darling::FromMeta;
@@ -59,12 +62,31 @@ extern crate proc_macro;
 ///   - Explicit form: `#[php(implements(ce = ce_fn, stub = "InterfaceName"))]`
 ///     — For built-in PHP interfaces. `ce_fn` must be a valid function with the
 ///     signature `fn() -> &'static ClassEntry`.
+/// - `iterator` - Makes objects of the class traversable with `foreach` through
+///   the `Iterator` implementation of the struct. See
+///   [Native Iteration](#native-iteration).
+/// - `array_access` - Makes objects of the class accessible as arrays through
+///   the `PhpArrayAccess` implementation of the struct. See
+///   [Native Array Access](#native-array-access).
+/// - `operators` - Overloads PHP operators on objects of the class with the
+///   `std::ops` implementations of the struct, e.g. `#[php(operators(Add, Mul(i64)))]`.
+///   See [Operator Overloading](#operator-overloading).
+/// - `instantiate` - Registers a generic struct once per given type, e.g.
+///   `#[php(instantiate(Cache<i64> = "IntCache"))]`. See
+///   [Generic classes](#generic-classes).
 ///
 /// You may also use the `#[php(prop)]` attribute on a struct field to use the
 /// field as a PHP property. By default, the field will be accessible from PHP
 /// publicly with the same name as the field. Property types must implement
 /// `IntoZval` and `FromZval`.
 ///
+/// Properties are declared with the PHP type of the field, e.g. `int` for an
+/// `i64` field and `?string` for an `Option<String>` field, which is visible to
+/// reflection. Values assigned from PHP are checked against the type by the
+/// engine, as for userland typed properties: they are coerced unless the
+/// assigning code declares `strict_types=1`, and a `TypeError` is thrown for
+/// invalid values.
+///
 /// You can customize properties with these options:
 ///
 /// - `name` - Allows you to rename the property, e.g. `#[php(prop, name =
@@ -75,6 +97,22 @@ extern crate proc_macro;
 ///   `#[php(prop, static)]`
 /// - `flags` - Sets property visibility flags, e.g. `#[php(prop, flags =
 ///   ext_php_rs::flags::PropertyFlags::Private)]`
+/// - `set_vis` - Sets the [asymmetric visibility](https://www.php.net/manual/en/language.oop5.visibility.php#language.oop5.visibility-members-aviz)
+///   of the property, i.e. from where it can be modified, e.g.
+///   `#[php(prop, set_vis = "private")]` declares a `public private(set)`
+///   property. Requires PHP 8.4 or later, and cannot be used on static
+///   properties.
+/// - `readonly` - Makes the property readonly, e.g. `#[php(prop, readonly)]`.
+///   The field is set by the constructor, and modifying the property from PHP
+///   throws an `Error`. Requires PHP 8.1 or later, and cannot be used on static
+///   properties. The properties of readonly classes are readonly.
+/// - `default` - Sets the default value of the declared property, e.g.
+///   `#[php(prop, default = 42)]`, which is shown by
+///   `ReflectionProperty::getDefaultValue()` and the generated stubs. The value
+///   must be a constant expression. As the value of the property is read from
+///   the Rust struct, the default does not initialize the field. Typed
+///   properties without a default value are uninitialized, unless they are
+///   nullable.
 ///
 /// ### Known limitation: `#[php(prop)]` on owned refcounted types accessed via
 /// `Exception::getMessage`-style C methods leaks one `zend_string` per call.
@@ -136,14 +174,13 @@ extern crate proc_macro;
 /// instead of using borrowed references with lifetimes, consider using
 /// reference-counted smart pointers such as [Arc](https://doc.rust-lang.org/std/sync/struct.Arc.html).
 ///
-/// ### No generic parameters
+/// ### Generic parameters
 ///
 /// A Rust struct `Foo<T>` with a generic parameter `T` generates new compiled
-/// implementations each time it is used with a different concrete type for `T`.
-/// These new implementations are generated by the compiler at each usage site.
-/// This is incompatible with wrapping `Foo` in PHP,
-/// where there needs to be a single compiled implementation of `Foo` which is
-/// integrated with the PHP interpreter.
+/// implementations each time it is used with a different concrete type for
+/// `T`, while PHP needs a single compiled implementation of each class. Generic
+/// structs must therefore be instantiated explicitly, each instantiation being
+/// registered as a separate class. See [Generic classes](#generic-classes).
 ///
 /// ## Example
 ///
@@ -370,6 +407,210 @@ extern crate proc_macro;
 /// # fn main() {}
 /// ````
 ///
+/// ### Native Array Access
+///
+/// Classes can also be accessed as arrays with the `PhpArrayAccess`
+/// implementation of the struct, by adding the `array_access` option. `$obj[...]`,
+/// `isset()`, `empty()` and `unset()` then call the trait directly through the
+/// dimension object handlers, without dispatching to PHP methods, and the class
+/// implements `ArrayAccess` with its `offsetExists`, `offsetGet`, `offsetSet` and
+/// `offsetUnset` methods.
+///
+/// The offset given to `offset_set` is `None` when a value is appended with
+/// `$obj[] = $value`. Nested writes such as `$obj['a'][] = 1` modify the value
+/// returned by `offset_get_mut` in place, and by default modify a copy of the
+/// value returned by `offset_get`, for which PHP emits a notice. The
+/// `DimensionFetch` given to `offset_get_mut` tells whether the value is written
+/// to, read and written to, or has a nested value removed. Like on arrays, nested
+/// unsets of missing offsets do nothing without calling `offset_get_mut`, and
+/// nested reads and writes of missing offsets, e.g. `$obj['a']['b'] .= 'c'`,
+/// emit an "Undefined array key" warning.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{
+///     boxed::ZBox,
+///     convert::FromZval,
+///     prelude::*,
+///     types::{ArrayKey, ZendHashTable, Zval},
+/// };
+///
+/// #[php_class]
+/// #[php(array_access)]
+/// pub struct Bag {
+///     data: ZBox<ZendHashTable>,
+/// }
+///
+/// #[php_impl]
+/// impl Bag {
+///     pub fn __construct() -> Self {
+///         Self { data: ZendHashTable::new() }
+///     }
+/// }
+///
+/// fn key(offset: &Zval) -> PhpResult<ArrayKey<'static>> {
+///     ArrayKey::from_zval(offset).ok_or_else(|| "Invalid offset".into())
+/// }
+///
+/// impl PhpArrayAccess for Bag {
+///     fn offset_exists(&self, offset: &Zval) -> PhpResult<bool> {
+///         Ok(self.data.get(key(offset)?).is_some())
+///     }
+///
+///     fn offset_get(&self, offset: &Zval) -> PhpResult<Zval> {
+///         Ok(self.data.get(key(offset)?).map_or_else(Zval::new, Zval::shallow_clone))
+///     }
+///
+///     fn offset_set(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult {
+///         match offset {
+///             Some(offset) => self.data.insert(key(offset)?, value.shallow_clone())?,
+///             None => self.data.push(value.shallow_clone())?,
+///         }
+///         Ok(())
+///     }
+///
+///     fn offset_unset(&mut self, offset: &Zval) -> PhpResult {
+///         self.data.remove(key(offset)?);
+///         Ok(())
+///     }
+///
+///     fn offset_get_mut(
+///         &mut self,
+///         offset: Option<&Zval>,
+///         _fetch: DimensionFetch,
+///     ) -> PhpResult<Option<&mut Zval>> {
+///         match offset {
+///             Some(offset) => Ok(Some(self.data.entry(key(offset)?).or_default()?)),
+///             None => Ok(None),
+///         }
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.class::<Bag>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// $bag = new Bag();
+/// $bag['list'] = [];
+/// $bag['list'][] = 1;
+/// $bag[] = 'appended';
+///
+/// var_dump($bag instanceof ArrayAccess); // bool(true)
+/// var_dump($bag['list']); // array(1) { [0]=> int(1) }
+/// var_dump(isset($bag[0]), isset($bag[1])); // bool(true) bool(false)
+/// ```
+///
+/// Userland subclasses can still override the `ArrayAccess` methods, which are
+/// then called for the array syntax instead of the trait.
+///
+/// ### Operator Overloading
+///
+/// Objects of a class can be used as operands of arithmetic operators by adding
+/// the `operators` option, listing the operators to overload by the names of their
+/// traits. The operators are applied with the `std::ops` implementations of the
+/// struct, which must implement `Clone`:
+///
+/// | Option   | PHP        | Trait                       |
+/// | -------- | ---------- | --------------------------- |
+/// | `Add`    | `$a + $b`  | `std::ops::Add`             |
+/// | `Sub`    | `$a - $b`  | `std::ops::Sub`             |
+/// | `Mul`    | `$a * $b`  | `std::ops::Mul`             |
+/// | `Div`    | `$a / $b`  | `std::ops::Div`             |
+/// | `Mod`    | `$a % $b`  | `std::ops::Rem`             |
+/// | `Pow`    | `$a ** $b` | `ext_php_rs::operator::Pow` |
+/// | `Neg`    | `-$a`      | `std::ops::Neg`             |
+/// | `Concat` | `"$a"`     | `std::fmt::Display`         |
+///
+/// Binary operators take the types of the right operands they accept, e.g.
+/// `Mul(i64, f64)`, and accept objects of the class itself by default. The right
+/// operand is converted into the first type it can be converted into, and PHP
+/// throws a `TypeError` if there is none. The outputs of the traits are converted
+/// into PHP values, so `Option` outputs return `null`, and compound assignments
+/// such as `$a += $b` assign a new object to `$a`.
+///
+/// Operators are not assumed to be commutative: when the object is the right
+/// operand, e.g. `2 * $a`, PHP throws a `TypeError` unless the reversed operator,
+/// prefixed with `R`, is given with the types of the left operands it accepts,
+/// e.g. `RMul(i64)` for `impl Mul<Money> for i64`.
+///
+/// `Concat` converts the objects into strings wherever PHP does, e.g. with
+/// `$a . $b`, `echo $a`, string interpolation and `(string) $a`.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use std::{fmt, ops::{Add, Mul}};
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_class]
+/// #[php(operators(Add, Mul(i64), RMul(i64), Concat))]
+/// #[derive(Clone, Copy)]
+/// pub struct Money {
+///     cents: i64,
+/// }
+///
+/// #[php_impl]
+/// impl Money {
+///     pub fn __construct(cents: i64) -> Self {
+///         Self { cents }
+///     }
+/// }
+///
+/// impl Add for Money {
+///     type Output = Self;
+///
+///     fn add(self, rhs: Self) -> Self {
+///         Self { cents: self.cents + rhs.cents }
+///     }
+/// }
+///
+/// impl Mul<i64> for Money {
+///     type Output = Self;
+///
+///     fn mul(self, rhs: i64) -> Self {
+///         Self { cents: self.cents * rhs }
+///     }
+/// }
+///
+/// impl Mul<Money> for i64 {
+///     type Output = Money;
+///
+///     fn mul(self, rhs: Money) -> Money {
+///         rhs * self
+///     }
+/// }
+///
+/// impl fmt::Display for Money {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "${}.{:02}", self.cents / 100, self.cents % 100)
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.class::<Money>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// $total = new Money(150) + new Money(250);
+/// $total *= 2;
+/// $total = 2 * $total;
+///
+/// echo "Total: $total"; // Total: $16.00
+/// new Money(150) + 1; // TypeError: Unsupported operand types: Money + int
+/// ```
+///
 /// ## Static Properties
 ///
 /// Static properties are shared across all instances of a class. Use
@@ -847,6 +1088,151 @@ extern crate proc_macro;
 /// // second => two
 /// // third => three
 /// ```
+///
+/// ### Native Iteration
+///
+/// Classes can also be traversed with the `Iterator` implementation of the struct,
+/// by adding the `iterator` option. `foreach` then calls `Iterator::next` on the
+/// struct directly, without dispatching to PHP methods, and the class implements
+/// `IteratorAggregate`.
+///
+/// Items implementing `IntoZval` are given consecutive integer keys starting at
+/// 0, while `(K, V)` pairs are iterated as keys and values. As Rust iterators
+/// cannot be rewound, traversing an object again resumes where the previous
+/// traversal stopped.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_class]
+/// #[php(iterator)]
+/// pub struct Countdown {
+///     from: i64,
+/// }
+///
+/// #[php_impl]
+/// impl Countdown {
+///     pub fn __construct(from: i64) -> Self {
+///         Self { from }
+///     }
+/// }
+///
+/// impl Iterator for Countdown {
+///     type Item = (i64, String);
+///
+///     fn next(&mut self) -> Option<Self::Item> {
+///         if self.from == 0 {
+///             return None;
+///         }
+///         self.from -= 1;
+///         Some((self.from, format!("T-{}", self.from)))
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.class::<Countdown>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// foreach (new Countdown(3) as $key => $value) {
+///     echo "$key => $value\n";
+/// }
+/// // Output:
+/// // 2 => T-2
+/// // 1 => T-1
+/// // 0 => T-0
+/// ```
+///
+/// Functions returning Rust iterators are covered in
+/// [`RustIterator`](../types/rust_iterator.md).
+///
+/// ## Generic Classes
+///
+/// Generic structs are registered once per instantiation given with the
+/// `instantiate` option, as pairs of a concrete type and the name of the PHP
+/// class. Each instantiation is a separate class, with its own `RegisteredClass`
+/// implementation, and is added to the module with `class::<T>()`.
+///
+/// Methods are declared once in a generic `#[php_impl]` block, whose bounds must
+/// allow the generic parameters to be converted from and into PHP values.
+/// Constants, getters and setters are not supported in generic `#[php_impl]`
+/// blocks.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use std::collections::HashMap;
+///
+/// use ext_php_rs::{
+///     convert::{FromZvalMut, IntoZval},
+///     prelude::*,
+/// };
+///
+/// /// A cache of values by key.
+/// #[php_class]
+/// #[php(instantiate(Cache<i64> = "IntCache", Cache<String> = "StringCache"))]
+/// pub struct Cache<T: 'static> {
+///     entries: HashMap<String, T>,
+///     #[php(prop)]
+///     last: Option<T>,
+/// }
+///
+/// #[php_impl]
+/// impl<T> Cache<T>
+/// where
+///     T: Clone + IntoZval + for<'a> FromZvalMut<'a> + 'static,
+/// {
+///     pub fn __construct() -> Self {
+///         Self {
+///             entries: HashMap::new(),
+///             last: None,
+///         }
+///     }
+///
+///     pub fn set(&mut self, key: String, value: T) {
+///         self.last = Some(value.clone());
+///         self.entries.insert(key, value);
+///     }
+///
+///     pub fn get(&self, key: &str) -> Option<T> {
+///         self.entries.get(key).cloned()
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+///         .class::<Cache<i64>>()
+///         .class::<Cache<String>>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The generic parameters are rendered as `@template` tags in the generated
+/// stubs, bound to the PHP type of the instantiation, or unbound if the type has
+/// no PHP equivalent:
+///
+/// ```php
+/// <?php
+///
+/// /**
+///  * A cache of values by key.
+///  *
+///  * @template T of int
+///  */
+/// class IntCache {
+///     public ?int $last = null;
+///
+///     // ...
+/// }
+/// ```
 // END DOCS FROM classes.md
 #[proc_macro_attribute]
 pub fn php_class(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -860,7 +1246,150 @@ fn php_class_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2 {
         return err!(input => "`#[php_class(<args>)]` args are no longer supported. Please use `#[php(<args>)]` instead.").to_compile_error();
     }
 
-    class::parser(input).unwrap_or_else(|e| e.to_compile_error())
+    class::parser(input, false).unwrap_or_else(|e| e.to_compile_error())
+}
+
+// BEGIN DOCS FROM attribute.md
+/// # `#[php_attribute]` Attribute
+///
+/// Structs can be exported to PHP as attribute classes with the `#[php_attribute]`
+/// attribute macro. Attribute classes are regular classes which can also be
+/// applied to userland code, e.g. `#[Route('/login')]`, and instantiated through
+/// reflection with `ReflectionAttribute::newInstance()`.
+///
+/// The struct is exported the same way as a [`#[php_class]`](./classes.md) struct,
+/// and takes the same options. It is registered with the `class::<T>()` method of
+/// the `ModuleBuilder`.
+///
+/// ## Options
+///
+/// - `target` - The items the attribute can be applied to, as an expression of
+///   `AttributeFlags`, e.g.
+///   `#[php(target = AttributeFlags::Method | AttributeFlags::Function)]`.
+///   Defaults to `AttributeFlags::All`. Add `AttributeFlags::Repeatable` to allow
+///   applying the attribute several times to the same item.
+///
+/// ## Example
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{flags::AttributeFlags, prelude::*};
+///
+/// #[php_attribute]
+/// #[php(name = "Demo\\Route", target = AttributeFlags::Method | AttributeFlags::Function)]
+/// pub struct Route {
+///     #[php(prop)]
+///     path: String,
+/// }
+///
+/// #[php_impl]
+/// impl Route {
+///     pub fn __construct(path: String) -> Self {
+///         Self { path }
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.class::<Route>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// use Demo\Route;
+///
+/// #[Route('/login')]
+/// function login() {}
+///
+/// $attribute = (new ReflectionFunction('login'))->getAttributes(Route::class)[0];
+/// var_dump($attribute->newInstance()->path); // string(6) "/login"
+/// ```
+///
+/// ## Attaching attributes
+///
+/// Attributes, with their arguments, are attached to the items registered with
+/// the builders, so that `getAttributes()` returns them:
+///
+/// - `FunctionBuilder::attribute` attaches an attribute to a function or a method,
+///   and `FunctionBuilder::arg_attribute` to one of its parameters.
+/// - `Arg::attribute` attaches an attribute to a parameter.
+/// - `ClassBuilder::attribute` attaches an attribute to a class, and
+///   `ClassBuilder::method_attribute`, `ClassBuilder::property_attribute` and
+///   `ClassBuilder::constant_attribute` to its members.
+///
+/// Attributes are created with `Attribute::new`, given the name of the attribute
+/// class, and take positional arguments with `Attribute::arg` and named arguments
+/// with `Attribute::named_arg`. Arguments can be `null`, booleans, integers, floats
+/// and strings.
+///
+/// Classes exported with `#[php_class]` can be given attributes from their
+/// `modifier`, and functions exported with `#[php_function]` when they are added
+/// to the module:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{attribute::Attribute, builders::ClassBuilder, prelude::*};
+///
+/// #[php_function]
+/// pub fn login(user: String, password: String) -> bool {
+///     user == "admin" && password == "secret"
+/// }
+///
+/// #[php_class]
+/// #[php(modifier = counter_modifier)]
+/// pub struct Counter {
+///     #[php(prop)]
+///     count: i64,
+/// }
+///
+/// #[php_impl]
+/// impl Counter {
+///     pub fn count(&self) -> i64 {
+///         self.count
+///     }
+/// }
+///
+/// fn counter_modifier(builder: ClassBuilder) -> ClassBuilder {
+///     builder
+///         .attribute(Attribute::new("AllowDynamicProperties"))
+///         .method_attribute("count", Attribute::new("ReturnTypeWillChange"))
+///         .property_attribute("count", Attribute::new("Demo\\Persisted").named_arg("column", "count"))
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+///         .function(
+///             wrap_function!(login).arg_attribute("password", Attribute::new("SensitiveParameter")),
+///         )
+///         .class::<Counter>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Attributes which are not declared as attribute classes can be read by
+/// reflection, but not instantiated. Registering an item with an attribute
+/// attached to a missing parameter or class member fails with
+/// `Error::UnknownAttributeTarget`.
+// END DOCS FROM attribute.md
+#[proc_macro_attribute]
+pub fn php_attribute(args: TokenStream, input: TokenStream) -> TokenStream {
+    php_attribute_internal(args.into(), input.into()).into()
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn php_attribute_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    let input = parse_macro_input2!(input as ItemStruct);
+    if !args.is_empty() {
+        return err!(input => "`#[php_attribute(<args>)]` args are not supported. Please use `#[php(<args>)]` instead.").to_compile_error();
+    }
+
+    class::parser(input, true).unwrap_or_else(|e| e.to_compile_error())
 }
 
 // BEGIN DOCS FROM enum.md
@@ -1390,6 +1919,145 @@ fn php_interface_internal(_args: TokenStream2, input: TokenStream2) -> TokenStre
     interface::parser(input).unwrap_or_else(|e| e.to_compile_error())
 }
 
+// BEGIN DOCS FROM trait.md
+/// # `#[php_trait]` Attribute
+///
+/// You can export an `impl` block to PHP as a trait with the `#[php_trait]`
+/// attribute. The methods of the `impl` block are exported as native methods of
+/// the trait, which userland classes can `use` like any other trait. The `impl`
+/// block is written for a unit struct, which represents the trait in Rust and is
+/// registered with the `php_trait::<T>()` method of the `ModuleBuilder`.
+///
+/// As the trait is used by other classes, its methods cannot take `&self` or
+/// `&mut self`. Instead, methods taking `self_: &mut ZendObject` as their first
+/// argument receive the object of the class using the trait, and methods without
+/// it are exported as static methods.
+///
+/// ## Options
+///
+/// By default the trait is named after the struct, and all methods are renamed to
+/// `camelCase`. This can be changed by passing the `name` and `change_method_case`
+/// as `#[php]` attributes on the `impl` block.
+///
+/// Methods take the same options as the methods of [`php_impl`](./impl.md) blocks,
+/// e.g. `vis`, `defaults`, `final` and `abstract`. Constructors, getters, setters
+/// and constants are not supported in traits.
+///
+/// ## Example
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{prelude::*, types::ZendObject};
+///
+/// /// Greets people by the name of the object using the trait.
+/// pub struct Greets;
+///
+/// #[php_trait]
+/// #[php(name = "Demo\\Greets")]
+/// impl Greets {
+///     pub fn greet(self_: &mut ZendObject, greeting: Option<String>) -> String {
+///         let name: String = self_.get_property("name").unwrap_or_default();
+///         format!("{}, {name}!", greeting.as_deref().unwrap_or("Hello"))
+///     }
+///
+///     pub fn default_greeting() -> &'static str {
+///         "Hello"
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.php_trait::<Greets>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// class Person {
+///     use Demo\Greets;
+///
+///     public function __construct(public string $name) {}
+/// }
+///
+/// var_dump((new Person('David'))->greet('Hi')); // string(10) "Hi, David!"
+/// var_dump(Person::defaultGreeting()); // string(5) "Hello"
+/// ```
+///
+/// The generated stubs declare the trait:
+///
+/// ```php
+/// <?php
+///
+/// namespace Demo {
+///     /**
+///      * Greets people by the name of the object using the trait.
+///      */
+///     trait Greets {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// ## Using traits in Rust classes
+///
+/// Classes exported from Rust use traits with `ClassBuilder::use_trait`, e.g.
+/// from their `modifier`. The methods of the trait are added to the class, unless
+/// the class declares a method with the same name:
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{builders::ClassBuilder, class::RegisteredClass, prelude::*, types::ZendObject};
+///
+/// pub struct Greets;
+///
+/// #[php_trait]
+/// impl Greets {
+///     pub fn greet(self_: &mut ZendObject) -> String {
+///         let name: String = self_.get_property("name").unwrap_or_default();
+///         format!("Hello, {name}!")
+///     }
+/// }
+///
+/// #[php_class]
+/// #[php(modifier = robot_modifier)]
+/// pub struct Robot {
+///     #[php(prop)]
+///     name: String,
+/// }
+///
+/// fn robot_modifier(builder: ClassBuilder) -> ClassBuilder {
+///     builder.use_trait((|| Greets::get_metadata().ce(), Greets::CLASS_NAME))
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module.php_trait::<Greets>().class::<Robot>()
+/// }
+/// # fn main() {}
+/// ```
+///
+/// As the engine only binds traits to userland classes, `class_uses()` and
+/// `ReflectionClass::getTraits()` do not list the traits used by Rust classes.
+// END DOCS FROM trait.md
+#[proc_macro_attribute]
+pub fn php_trait(args: TokenStream, input: TokenStream) -> TokenStream {
+    php_trait_internal(args.into(), input.into()).into()
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn php_trait_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    let input = parse_macro_input2!(input as ItemImpl);
+    if !args.is_empty() {
+        return err!(input => "`#[php_trait(<args>)]` args are not supported. Please use `#[php(<args>)]` instead.").to_compile_error();
+    }
+
+    trait_::parser(input).unwrap_or_else(|e| e.to_compile_error())
+}
+
 // BEGIN DOCS FROM function.md
 /// # `#[php_function]` Attribute
 ///
@@ -1515,6 +2183,34 @@ fn php_interface_internal(_args: TokenStream2, input: TokenStream2) -> TokenStre
 /// # fn main() {}
 /// ```
 ///
+/// ## Type coercion
+///
+/// Scalar parameters (`bool`, `int`, `float` and `string`) follow the same
+/// coercion rules as the parameters of built-in PHP functions. Unless the caller
+/// declares `strict_types=1`, a numeric string is accepted for an integer, an
+/// integer for a string, and so on. Passing `null` to a non-nullable scalar
+/// parameter coerces it as well, with the deprecation notice emitted since PHP 8.1.
+///
+/// ```php
+/// <?php
+///
+/// var_dump(greet(42, '30')); // string(35) "Hello, 42! You are 30 years old."
+/// ```
+///
+/// When the caller declares `strict_types=1`, the arguments must have the type of
+/// their parameter, except that integers are accepted for floats.
+///
+/// Arguments which cannot be converted throw the same errors as built-in
+/// functions:
+///
+/// - A `TypeError` if the argument does not have the type of its parameter, e.g.
+///   `greet(): Argument #1 ($name) must be of type string, array given`.
+/// - A `ValueError` if it has the type but cannot be converted to the Rust type,
+///   e.g. `-1` given for a `u32`: `Argument #1 ($n) must be a valid int`.
+/// - An `ArgumentCountError` if too few or too many arguments are given.
+///
+/// Methods are named with their class, e.g. `Counter::add()`.
+///
 /// ## Variadic Functions
 ///
 /// Variadic functions can be implemented by specifying the last argument in the
@@ -1593,6 +2289,25 @@ fn php_interface_internal(_args: TokenStream2, input: TokenStream2) -> TokenStre
 /// You can also return a `Result` from the function. The error variant will be
 /// translated into an exception and thrown. See the section on
 /// [exceptions](../exceptions.md) for more details.
+///
+/// ## Async functions
+///
+/// With the `async` feature, functions can be `async`. When called from a PHP
+/// fiber, the fiber is suspended while the future is pending. Otherwise, the
+/// call blocks until the future completes. See the [async
+/// guide](../advanced/async_impl.md) for more details.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[php_function]
+/// pub async fn greet(name: String) -> String {
+///     format!("Hello, {name}!")
+/// }
+/// # fn main() {}
+/// ```
 // END DOCS FROM function.md
 #[proc_macro_attribute]
 pub fn php_function(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -1677,6 +2392,122 @@ fn php_const_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2 {
     constant::parser(input).unwrap_or_else(|e| e.to_compile_error())
 }
 
+// BEGIN DOCS FROM resource.md
+/// # `#[php_resource]` Attribute
+///
+/// Structs can be exported to PHP as resource types with the `#[php_resource]`
+/// attribute macro. Resources are opaque handles, like the ones returned by
+/// `fopen()` or `curl_init()`, which PHP code can only pass back to the
+/// functions of your extension. This attribute implements the
+/// `RegisteredResource` trait on your struct. To register the resource type,
+/// use the `resource::<StructName>()` method on the `ModuleBuilder` in the
+/// `#[php_module]` macro.
+///
+/// Values are wrapped into resources with the `ZendResource<T>` type:
+///
+/// - `ZendResource::new(value)` creates a request-scoped resource owning the
+///   value. The value is dropped when the last reference to the resource is
+///   released at the latest, at the end of the request.
+/// - `ZendResource::persistent(key, init)` creates a resource pointing at a
+///   persistent value stored under `key`, which is created with `init` if it
+///   does not exist yet. Persistent values are shared by all the requests
+///   handled by the same process and dropped on module shutdown, which makes
+///   them suitable for pooled connections. `ZendResource::try_persistent(key,
+///   init)` accepts a fallible `init` function.
+///
+/// Owned resources are returned inside a `ZBox`, which can be returned from
+/// functions. Functions can accept resources as `&ZendResource<T>`, or as
+/// `ZBox<ZendResource<T>>` to keep a reference to the resource. Resources of
+/// another type, and resources which have been closed with
+/// `ZendResource::close()`, are rejected as invalid arguments.
+///
+/// As `resource` cannot be used in type declarations, resource parameters and
+/// return values are untyped in the generated stubs, and documented as
+/// `resource` in their docblocks.
+///
+/// ## Options
+///
+/// - `#[php(name = "my resource")]` or `#[php(change_case = snake_case)]`: Sets
+///   the name of the resource type, as returned by `get_resource_type()`. The
+///   default is the `snake_case` name of the struct.
+///
+/// ## Example
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::{boxed::ZBox, prelude::*, types::ZendResource};
+///
+/// #[php_resource]
+/// #[php(name = "counter")]
+/// pub struct Counter {
+///     count: i64,
+/// }
+///
+/// #[php_function]
+/// pub fn counter_open(start: i64) -> ZBox<ZendResource<Counter>> {
+///     ZendResource::new(Counter { count: start })
+/// }
+///
+/// #[php_function]
+/// pub fn counter_shared() -> ZBox<ZendResource<Counter>> {
+///     ZendResource::persistent("my_extension.counter", || Counter { count: 0 })
+/// }
+///
+/// #[php_function]
+/// pub fn counter_increment(mut counter: ZBox<ZendResource<Counter>>) -> PhpResult<i64> {
+///     let counter = counter
+///         .get_mut()
+///         .ok_or("The counter has been closed")?;
+///     counter.count += 1;
+///     Ok(counter.count)
+/// }
+///
+/// #[php_function]
+/// pub fn counter_close(mut counter: ZBox<ZendResource<Counter>>) {
+///     counter.close();
+/// }
+///
+/// #[php_module]
+/// pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+///         .resource::<Counter>()
+///         .function(wrap_function!(counter_open))
+///         .function(wrap_function!(counter_shared))
+///         .function(wrap_function!(counter_increment))
+///         .function(wrap_function!(counter_close))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// $counter = counter_open(41);
+/// var_dump($counter); // resource(4) of type (counter)
+/// var_dump(counter_increment($counter)); // int(42)
+/// counter_close($counter);
+/// var_dump(get_resource_type($counter)); // string(7) "Unknown"
+///
+/// // Keeps counting across requests.
+/// var_dump(counter_increment(counter_shared()));
+/// ```
+// END DOCS FROM resource.md
+#[proc_macro_attribute]
+pub fn php_resource(args: TokenStream, input: TokenStream) -> TokenStream {
+    php_resource_internal(args.into(), input.into()).into()
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn php_resource_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    let input = parse_macro_input2!(input as ItemStruct);
+    if !args.is_empty() {
+        return err!(input => "`#[php_resource(<args>)]` args are not supported. Please use `#[php(<args>)]` instead.").to_compile_error();
+    }
+
+    resource::parser(input).unwrap_or_else(|e| e.to_compile_error())
+}
+
 // BEGIN DOCS FROM module.md
 /// # `#[php_module]` Attribute
 ///
@@ -1772,8 +2603,8 @@ fn php_module_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2
 /// If you do not want a function exported to PHP, you should place it in a
 /// separate `impl` block.
 ///
-/// If you want to use async Rust, use `#[php_async_impl]`, instead: see [here
-/// &raquo;](./async_impl.md) for more info.
+/// Methods can be `async` with the `async` feature: see [here
+/// &raquo;](../advanced/async_impl.md) for more info.
 ///
 /// ## Options
 ///
@@ -1974,6 +2805,12 @@ fn php_module_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2
 /// As the same as field properties, method property types must implement both
 /// `IntoZval` and `FromZval`.
 ///
+/// On PHP 8.4 and later, method properties are declared as virtual properties
+/// with native `get` and `set` [property hooks](https://www.php.net/manual/en/language.oop5.property-hooks.php),
+/// which call the getter and setter. They are visible to reflection, e.g. with
+/// `ReflectionProperty::getHook()`, and the generated stubs declare them as
+/// `public string $name { get; set; }`.
+///
 /// ### Overriding field properties with getters/setters
 ///
 /// If you have a field property defined with `#[php(prop)]` on your struct, you
@@ -2094,7 +2931,7 @@ fn php_module_internal(args: TokenStream2, input: TokenStream2) -> TokenStream2
 /// var_dump(Human::MAX_AGE); // int(100)
 /// ```
 ///
-/// [`php_async_impl`]: ./async_impl.md
+/// [`php_async_impl`]: ../advanced/async_impl.md
 // END DOCS FROM impl.md
 #[proc_macro_attribute]
 pub fn php_impl(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -2292,10 +3129,12 @@ fn php_extern_internal(_: TokenStream2, input: TokenStream2) -> TokenStream2 {
 /// ## Structs
 ///
 /// When used on a struct, the `FromZendObject` and `IntoZendObject` traits are
-/// also implemented, mapping fields to properties in both directions. All
-/// fields on the struct must implement `FromZval` as well. Generics are allowed
-/// on structs that use the derive macro, however, the implementation will add a
-/// `FromZval` bound to all generics types.
+/// also implemented, mapping fields to properties in both directions.
+/// Associative arrays with the same keys are also accepted, so functions taking
+/// the struct declare the type `object|array`. All fields on the struct must
+/// implement `FromZval` as well. Generics are allowed on structs that use the
+/// derive macro, however, the implementation will add a `FromZval` bound to all
+/// generics types.
 ///
 /// ### Examples
 ///
@@ -2375,6 +3214,71 @@ fn php_extern_internal(_: TokenStream2, input: TokenStream2) -> TokenStream2 {
 /// # fn main() {}
 /// ```
 ///
+/// ### Attributes
+///
+/// The properties of the object can be customized with `#[php]` attributes on
+/// the struct and its fields:
+///
+/// - `#[php(change_field_case = "camelCase")]` on the struct renames all the
+///   properties. The cases are the same as for `change_case`, and the
+///   properties are named after the fields by default.
+/// - `#[php(name = "...")]` or `#[php(change_case = "...")]` on a field renames
+///   its property.
+/// - `#[php(default)]` uses `Default::default()` when the property does not
+///   exist, and `#[php(default = expr)]` uses the given value.
+/// - `#[php(skip)]` does not convert the field, which is always set to its
+///   default value when converting from an object.
+/// - `#[php(flatten)]` stores the properties of the field, which must implement
+///   `FromZendObject` and `IntoZendObject` (for example, through
+///   `#[derive(ZvalConvert)]`), on the object itself instead of in a nested
+///   object.
+///
+/// When a property is missing or cannot be converted, `FromZendObject` returns
+/// an error naming it, for example ``Missing property `userName`.``.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[derive(ZvalConvert)]
+/// pub struct Origin {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(ZvalConvert)]
+/// #[php(change_field_case = "camelCase")]
+/// pub struct Request {
+///     user_name: String,
+///     #[php(name = "max_items")]
+///     limit: i64,
+///     #[php(default)]
+///     verbose: bool,
+///     #[php(skip)]
+///     cache: Vec<String>,
+///     #[php(flatten)]
+///     origin: Origin,
+/// }
+///
+/// #[php_function]
+/// pub fn handle(request: Request) -> String {
+///     format!("{}:{} for {}", request.origin.host, request.origin.port, request.user_name)
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// handle([
+///     'userName' => 'alice',
+///     'max_items' => 10,
+///     'host' => 'example.com',
+///     'port' => 443,
+/// ]); // example.com:443 for alice
+/// ```
+///
 /// ## Enums
 ///
 /// When used on an enum, the `FromZval` implementation will treat the enum as a
@@ -2391,6 +3295,11 @@ fn php_extern_internal(_: TokenStream2, input: TokenStream2) -> TokenStream2 {
 /// you put a `String` variant before an integer variant, the integer would be
 /// converted to a string and passed as the string variant.
 ///
+/// Functions taking or returning the enum declare the union of the types of
+/// its variants, e.g. `string|int`, which is shown by reflection and in the
+/// generated stubs. Enums with a default variant accept any value, and are
+/// declared as `mixed` when taken as a parameter.
+///
 /// ### Examples
 ///
 /// Basic example showing the importance of variant ordering and default field:
@@ -2436,8 +3345,72 @@ fn php_extern_internal(_: TokenStream2, input: TokenStream2) -> TokenStream2 {
 /// test_union(null); // UnionExample::None
 /// var_dump(give_union()); // int(5)
 /// ```
+///
+/// ### Tagged enums
+///
+/// Setting `#[php(tag = "...")]` on an enum converts it to and from an object
+/// holding the name of the variant in the given property, and the fields of the
+/// variant in the other properties. Variants can be unit variants, have named
+/// fields, which support the same attributes as the fields of structs, or have
+/// a single field implementing `FromZendObject` and `IntoZendObject`, whose
+/// properties are stored on the object.
+///
+/// With `#[php(content = "...")]` as well, the value of the variant is stored
+/// in the given property instead: a single field is converted with `FromZval`
+/// and `IntoZval`, and named fields are stored in a nested object.
+///
+/// Variants are named after their Rust names by default. They can be renamed
+/// with `#[php(name = "...")]` or `#[php(change_case = "...")]` on the variant,
+/// or `#[php(change_variant_case = "...")]` on the enum. Converting an object
+/// whose tag does not name any variant returns an `Unknown variant` error.
+///
+/// ```rust,no_run,ignore
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// # extern crate ext_php_rs;
+/// use ext_php_rs::prelude::*;
+///
+/// #[derive(ZvalConvert)]
+/// #[php(tag = "type", change_variant_case = "snake_case")]
+/// pub enum Shape {
+///     Circle { radius: f64 },
+///     Rectangle { width: f64, height: f64 },
+///     Point,
+/// }
+///
+/// #[derive(ZvalConvert)]
+/// #[php(tag = "kind", content = "value")]
+/// pub enum Message {
+///     Text(String),
+///     Ping,
+/// }
+///
+/// #[php_function]
+/// pub fn area(shape: Shape) -> f64 {
+///     match shape {
+///         Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+///         Shape::Rectangle { width, height } => width * height,
+///         Shape::Point => 0.0,
+///     }
+/// }
+///
+/// #[php_function]
+/// pub fn reply(message: Message) -> Message {
+///     match message {
+///         Message::Text(text) => Message::Text(format!("You said: {text}")),
+///         Message::Ping => Message::Ping,
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```php
+/// <?php
+///
+/// area(['type' => 'rectangle', 'width' => 2.0, 'height' => 3.0]); // 6.0
+/// reply((object) ['kind' => 'Text', 'value' => 'hi']); // {"kind": "Text", "value": "You said: hi"}
+/// ```
 // END DOCS FROM zval_convert.md
-#[proc_macro_derive(ZvalConvert)]
+#[proc_macro_derive(ZvalConvert, attributes(php))]
 pub fn zval_convert_derive(input: TokenStream) -> TokenStream {
     zval_convert_derive_internal(input.into()).into()
 }
@@ -2606,6 +3579,7 @@ mod tests {
         runtime_macros::emulate_attributelike_macro_expansion(
             file,
             &[
+                ("php_attribute", php_attribute_internal as AttributeFn),
                 ("php_class", php_class_internal as AttributeFn),
                 ("php_const", php_const_internal as AttributeFn),
                 ("php_enum", php_enum_internal as AttributeFn),
@@ -2618,6 +3592,8 @@ mod tests {
                     php_impl_interface_internal as AttributeFn,
                 ),
                 ("php_module", php_module_internal as AttributeFn),
+                ("php_resource", php_resource_internal as AttributeFn),
+                ("php_trait", php_trait_internal as AttributeFn),
             ],
         )
         .expect("Failed to expand attribute macros in test file");
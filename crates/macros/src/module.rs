@@ -74,6 +74,23 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
                 user_result
             }
 
+            // Stores the user's original post-deactivate callback so we can
+            // chain it.
+            static __EXT_PHP_RS_USER_POST_DEACTIVATE: ::std::sync::OnceLock<
+                Option<unsafe extern "C" fn() -> i32>,
+            > = ::std::sync::OnceLock::new();
+
+            extern "C" fn ext_php_rs_post_deactivate() -> i32 {
+                let user_result = __EXT_PHP_RS_USER_POST_DEACTIVATE
+                    .get()
+                    .and_then(|opt| *opt)
+                    .map_or(0, |f| unsafe { f() });
+
+                ::ext_php_rs::internal::ext_php_rs_post_deactivate();
+
+                user_result
+            }
+
             __EXT_PHP_RS_MODULE_ENTRY.get_or_init(|| {
                 #[inline]
                 fn internal(#inputs) #output {
@@ -93,6 +110,10 @@ pub fn parser(input: ItemFn) -> Result<TokenStream> {
                         // user's shutdown callback (if any).
                         let _ = __EXT_PHP_RS_USER_SHUTDOWN.set(entry.module_shutdown_func);
                         entry.module_shutdown_func = Some(ext_php_rs_shutdown);
+                        // Chain the end of request handling into the
+                        // post-deactivate callback.
+                        let _ = __EXT_PHP_RS_USER_POST_DEACTIVATE.set(entry.post_deactivate_func);
+                        entry.post_deactivate_func = Some(ext_php_rs_post_deactivate);
                         entry
                     },
                     Err(e) => panic!("Failed to build PHP module: {:?}", e),
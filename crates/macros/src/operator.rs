@@ -0,0 +1,210 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, Type};
+
+/// Operators of a class, given as the names of their traits. Binary operators
+/// take the types of the right operands they accept, `Self` by default.
+/// Reversed operators, prefixed with `R`, apply the operators to objects on the
+/// right, e.g. `2 * $a`, and take the types of the left operands they accept.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[php(operators(Add, Mul(i64, f64), RMul(i64), Neg))]
+/// ```
+#[derive(Debug)]
+pub struct Operators(Vec<Operator>);
+
+#[derive(Debug)]
+struct Operator {
+    name: Ident,
+    /// The types of the other operand.
+    rhs: Vec<Type>,
+}
+
+const UNARY_OPERATORS: &[&str] = &["Neg", "Concat"];
+
+/// A binary operator applied with a trait.
+struct BinaryOperator {
+    /// The variant of `Operator`.
+    variant: Ident,
+    /// The path to the method of the trait.
+    method: TokenStream,
+    /// Whether the object is the right operand.
+    reversed: bool,
+}
+
+/// Returns the binary operator of a name, e.g. `Mul` or `RMul`.
+fn binary_operator(name: &Ident) -> Option<BinaryOperator> {
+    let name = name.to_string();
+    let (reversed, variant) = match name.strip_prefix('R') {
+        Some(variant) if !variant.is_empty() => (true, variant),
+        _ => (false, name.as_str()),
+    };
+    let method = match variant {
+        "Add" => quote! { ::std::ops::Add::add },
+        "Sub" => quote! { ::std::ops::Sub::sub },
+        "Mul" => quote! { ::std::ops::Mul::mul },
+        "Div" => quote! { ::std::ops::Div::div },
+        "Mod" => quote! { ::std::ops::Rem::rem },
+        "Pow" => quote! { ::ext_php_rs::operator::Pow::pow },
+        _ => return None,
+    };
+    Some(BinaryOperator {
+        variant: format_ident!("{variant}"),
+        method,
+        reversed,
+    })
+}
+
+impl syn::parse::Parse for Operator {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let binary = binary_operator(&name);
+        if binary.is_none() && !UNARY_OPERATORS.iter().any(|op| name == op) {
+            return Err(syn::Error::new(
+                name.span(),
+                "Unknown operator, expected one of `Add`, `Sub`, `Mul`, `Div`, `Mod`, `Pow`, `Neg` or `Concat`, or a binary operator prefixed with `R`, e.g. `RMul`.",
+            ));
+        }
+        let rhs = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            let rhs = content.parse_terminated(Type::parse, syn::Token![,])?;
+            if binary.is_none() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("`{name}` is a unary operator, and takes no operand types."),
+                ));
+            }
+            rhs.into_iter().collect()
+        } else if binary.is_some_and(|binary| binary.reversed) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("`{name}` takes the types of the left operands it accepts."),
+            ));
+        } else {
+            vec![syn::parse_quote!(Self)]
+        };
+        Ok(Self { name, rhs })
+    }
+}
+
+impl FromMeta for Operators {
+    fn from_meta(item: &syn::Meta) -> darling::Result<Self> {
+        match item {
+            syn::Meta::List(list) => {
+                let operators = list.parse_args_with(
+                    syn::punctuated::Punctuated::<Operator, syn::Token![,]>::parse_terminated,
+                )?;
+                let mut seen = vec![];
+                for op in &operators {
+                    if seen.contains(&&op.name) {
+                        return Err(darling::Error::custom(format!(
+                            "Operator `{}` is given more than once.",
+                            op.name
+                        ))
+                        .with_span(&op.name));
+                    }
+                    seen.push(&op.name);
+                }
+                Ok(Self(operators.into_iter().collect()))
+            }
+            _ => Err(darling::Error::unsupported_format("expected list format")),
+        }
+    }
+}
+
+/// Generates the implementation of `PhpOperators` for a class, which applies
+/// the operators with the traits implemented by the struct.
+pub fn generate_operators_impl(ty: &Type, operators: &Operators) -> TokenStream {
+    let arms =
+        operators.0.iter().map(|op| {
+            let name = &op.name;
+            let Some(binary) = binary_operator(name) else {
+                let body = if name == "Neg" {
+                    quote! { ::std::ops::Neg::neg(::std::clone::Clone::clone(self)) }
+                } else {
+                    quote! { ::std::string::ToString::to_string(self) }
+                };
+                return quote! {
+                    (::ext_php_rs::operator::Operator::#name, _) => {
+                        ::std::option::Option::Some(__into_result(#body))
+                    }
+                };
+            };
+            let BinaryOperator {
+                variant,
+                method,
+                reversed,
+            } = binary;
+            let conversions = op.rhs.iter().map(|rhs| {
+            let (rhs, value) = if is_self(rhs) {
+                (quote! { &Self }, quote! { ::std::clone::Clone::clone(other) })
+            } else {
+                (quote! { #rhs }, quote! { other })
+            };
+            let this = quote! { ::std::clone::Clone::clone(self) };
+            let (lhs, rhs_value) = if reversed {
+                (value, this)
+            } else {
+                (this, value)
+            };
+            quote! {
+                if let ::std::option::Option::Some(other) =
+                    <#rhs as ::ext_php_rs::convert::FromZval>::from_zval(other)
+                {
+                    return ::std::option::Option::Some(__into_result(#method(#lhs, #rhs_value)));
+                }
+            }
+        });
+            let side = if reversed {
+                quote! { ::ext_php_rs::operator::Side::Right }
+            } else {
+                quote! { ::ext_php_rs::operator::Side::Left }
+            };
+            quote! {
+                (::ext_php_rs::operator::Operator::#variant, #side) => {
+                    #(#conversions)*
+                    ::std::option::Option::None
+                }
+            }
+        });
+
+    quote! {
+        impl ::ext_php_rs::operator::PhpOperators for #ty {
+            fn apply_operator(
+                &self,
+                op: ::ext_php_rs::operator::Operator,
+                other: &::ext_php_rs::types::Zval,
+                side: ::ext_php_rs::operator::Side,
+            ) -> ::std::option::Option<::ext_php_rs::exception::PhpResult<::ext_php_rs::types::Zval>> {
+                fn __into_result<T: ::ext_php_rs::convert::IntoZval>(
+                    value: T,
+                ) -> ::ext_php_rs::exception::PhpResult<::ext_php_rs::types::Zval> {
+                    ::ext_php_rs::convert::IntoZval::into_zval(value, false)
+                        .map_err(::std::convert::Into::into)
+                }
+
+                let _ = other;
+                #[allow(unreachable_patterns)]
+                match (op, side) {
+                    #(#arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
+impl Operators {
+    /// Returns whether the objects are converted into strings, with `Concat`.
+    pub fn converts_to_string(&self) -> bool {
+        self.0.iter().any(|op| op.name == "Concat")
+    }
+}
+
+fn is_self(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+}
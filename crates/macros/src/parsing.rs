@@ -1,8 +1,12 @@
 use convert_case::{Case, Casing};
 use darling::FromMeta;
+use darling::util::Override;
+use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 use syn::Ident;
 
+use crate::prelude::{OptionTokens, bail};
+
 /// Converts a Rust identifier to its PHP-compatible name.
 ///
 /// This function strips the `r#` prefix from raw identifiers, since that prefix
@@ -149,6 +153,8 @@ pub enum PhpNameContext {
     Class,
     /// An interface name (e.g., `interface Foo {}`)
     Interface,
+    /// A trait name (e.g., `trait Foo {}`)
+    Trait,
     /// An enum name (e.g., `enum Foo {}`)
     Enum,
     /// An enum case name (e.g., `case Foo;`)
@@ -168,6 +174,7 @@ impl PhpNameContext {
         match self {
             Self::Class => "class",
             Self::Interface => "interface",
+            Self::Trait => "trait",
             Self::Enum => "enum",
             Self::EnumCase => "enum case",
             Self::Function => "function",
@@ -250,6 +257,7 @@ pub fn validate_php_name(
     let is_forbidden = match context {
         PhpNameContext::Class
         | PhpNameContext::Interface
+        | PhpNameContext::Trait
         | PhpNameContext::Enum
         | PhpNameContext::EnumCase => is_reserved || is_type,
         PhpNameContext::Function => is_reserved && !is_function_allowed,
@@ -349,6 +357,38 @@ impl PhpRename {
     }
 }
 
+/// Returns the [`Deprecation`] of an item from its `deprecated` and `since`
+/// options, e.g. `#[php(deprecated = "use bar() instead", since = "2.0")]`.
+///
+/// [`Deprecation`]: ext_php_rs::deprecation::Deprecation
+///
+/// # Parameters
+///
+/// * `deprecated` - The `deprecated` option, with the message if given.
+/// * `since` - The `since` option.
+/// * `item` - The item, to which errors are attributed.
+pub fn deprecation_tokens(
+    deprecated: Option<&Override<String>>,
+    since: Option<&String>,
+    item: &impl ToTokens,
+) -> syn::Result<Option<TokenStream>> {
+    let Some(deprecated) = deprecated else {
+        if since.is_some() {
+            bail!(item => "`since` can only be used together with `deprecated`.");
+        }
+        return Ok(None);
+    };
+    let message = deprecated.as_ref().explicit().option_tokens();
+    let since = since.option_tokens();
+
+    Ok(Some(quote! {
+        ::ext_php_rs::deprecation::Deprecation {
+            message: #message,
+            since: #since,
+        }
+    }))
+}
+
 #[derive(Debug, Copy, Clone, FromMeta, Default)]
 pub enum RenameRule {
     /// Methods won't be renamed.
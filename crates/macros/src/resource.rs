@@ -0,0 +1,43 @@
+use darling::FromAttributes;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ItemStruct;
+
+use crate::parsing::{PhpRename, RenameRule, ident_to_php_name};
+use crate::prelude::*;
+
+#[derive(FromAttributes, Debug, Default)]
+#[darling(attributes(php), default)]
+pub struct ResourceAttributes {
+    /// The name of the resource type. Defaults to the `snake_case` name of the
+    /// struct.
+    #[darling(flatten)]
+    rename: PhpRename,
+}
+
+pub fn parser(mut input: ItemStruct) -> Result<TokenStream> {
+    let attr = ResourceAttributes::from_attributes(&input.attrs)?;
+    let ident = &input.ident;
+    if !input.generics.params.is_empty() {
+        bail!(input.generics => "`#[php_resource]` cannot be used on generic structs.");
+    }
+    let name = attr
+        .rename
+        .rename(ident_to_php_name(ident), RenameRule::Snake);
+    input.attrs.retain(|attr| !attr.path().is_ident("php"));
+
+    Ok(quote! {
+        #input
+
+        impl ::ext_php_rs::resource::RegisteredResource for #ident {
+            const RESOURCE_NAME: &'static str = #name;
+
+            #[inline]
+            fn get_metadata() -> &'static ::ext_php_rs::resource::ResourceMetadata<Self> {
+                static METADATA: ::ext_php_rs::resource::ResourceMetadata<#ident> =
+                    ::ext_php_rs::resource::ResourceMetadata::new();
+                &METADATA
+            }
+        }
+    })
+}
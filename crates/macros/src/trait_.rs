@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+
+use darling::FromAttributes;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ImplItem, ImplItemFn, ItemImpl};
+
+use crate::function::{Args, CallType, Function, MethodReceiver, returns_self, returns_self_ref};
+use crate::helpers::get_docs;
+use crate::impl_::{FnBuilder, MethodArgs, MethodModifier, MethodTy, PhpFunctionImplAttribute};
+use crate::parsing::{
+    PhpNameContext, PhpRename, RenameRule, deprecation_tokens, ident_to_php_name, validate_php_name,
+};
+use crate::prelude::*;
+
+#[derive(FromAttributes, Debug, Default)]
+#[darling(attributes(php), forward_attrs(doc), default)]
+pub struct PhpTrait {
+    #[darling(flatten)]
+    rename: PhpRename,
+    /// Rename methods to match the given rule.
+    change_method_case: Option<RenameRule>,
+    attrs: Vec<syn::Attribute>,
+}
+
+pub fn parser(mut input: ItemImpl) -> Result<TokenStream> {
+    let args = PhpTrait::from_attributes(&input.attrs)?;
+    input.attrs.retain(|attr| !attr.path().is_ident("php"));
+    if let Some((_, path, _)) = &input.trait_ {
+        bail!(path => "The `#[php_trait]` attribute is only valid for inherent implementations.");
+    }
+    if !input.generics.params.is_empty() {
+        bail!(input.generics => "Generic parameters are not supported on `#[php_trait]` blocks.");
+    }
+    let path = match &*input.self_ty {
+        syn::Type::Path(ty) if ty.qself.is_none() => ty.path.clone(),
+        _ => {
+            bail!(input.self_ty => "The `#[php_trait]` attribute is only valid for struct implementations.")
+        }
+    };
+    let Some(ident) = path.segments.last().map(|segment| &segment.ident) else {
+        bail!(path => "Expected a struct path.");
+    };
+
+    let name = args
+        .rename
+        .rename(ident_to_php_name(ident), RenameRule::Pascal);
+    validate_php_name(&name, PhpNameContext::Trait, ident.span())?;
+    let docs = get_docs(&args.attrs)?;
+    let change_method_case = args.change_method_case.unwrap_or(RenameRule::Camel);
+
+    let mut methods = vec![];
+    for item in &mut input.items {
+        match item {
+            ImplItem::Const(c) => {
+                bail!(c => "Constants are not supported in `#[php_trait]` blocks.");
+            }
+            ImplItem::Fn(method) => methods.push(parse_method(method, &path, change_method_case)?),
+            _ => {}
+        }
+    }
+
+    Ok(quote! {
+        #input
+
+        impl ::ext_php_rs::class::RegisteredClass for #path {
+            const CLASS_NAME: &'static str = #name;
+
+            const BUILDER_MODIFIER: Option<
+                fn(::ext_php_rs::builders::ClassBuilder) -> ::ext_php_rs::builders::ClassBuilder,
+            > = None;
+
+            const EXTENDS: Option<::ext_php_rs::class::ClassEntryInfo> = None;
+
+            const IMPLEMENTS: &'static [::ext_php_rs::class::ClassEntryInfo] = &[];
+
+            const FLAGS: ::ext_php_rs::flags::ClassFlags = ::ext_php_rs::flags::ClassFlags::Trait;
+
+            const DOC_COMMENTS: &'static [&'static str] = &[
+                #(#docs,)*
+            ];
+
+            fn get_metadata() -> &'static ::ext_php_rs::class::ClassMetadata<Self> {
+                static METADATA: ::ext_php_rs::class::ClassMetadata<#path> =
+                    ::ext_php_rs::class::ClassMetadata::new(&[]);
+
+                &METADATA
+            }
+
+            fn method_builders() -> Vec<(
+                ::ext_php_rs::builders::FunctionBuilder<'static>,
+                ::ext_php_rs::flags::MethodFlags,
+            )> {
+                vec![#(#methods),*]
+            }
+
+            fn constructor() -> Option<::ext_php_rs::class::ConstructorMeta<Self>> {
+                None
+            }
+
+            fn constants() -> &'static [(
+                &'static str,
+                &'static dyn ::ext_php_rs::convert::IntoZvalDyn,
+                ::ext_php_rs::describe::DocComments,
+            )] {
+                &[]
+            }
+        }
+    })
+}
+
+/// Parses a method of a trait. Methods taking `self_: &mut ZendObject` as
+/// their first argument receive the object of the class using the trait, and
+/// other methods are static.
+fn parse_method(
+    method: &mut ImplItemFn,
+    path: &syn::Path,
+    change_method_case: RenameRule,
+) -> Result<FnBuilder> {
+    let attr = PhpFunctionImplAttribute::from_attributes(&method.attrs)?;
+    let name = attr
+        .rename
+        .rename_method(ident_to_php_name(&method.sig.ident), change_method_case);
+    validate_php_name(&name, PhpNameContext::Method, method.sig.ident.span())?;
+    let docs = get_docs(&attr.attrs)?;
+    let deprecation = deprecation_tokens(
+        attr.deprecated.as_ref(),
+        attr.since.as_ref(),
+        &method.sig.ident,
+    )?;
+    method.attrs.retain(|attr| !attr.path().is_ident("php"));
+
+    let opts = MethodArgs::new(name, attr)?;
+    match opts.ty {
+        MethodTy::Constructor => {
+            bail!(method.sig.ident => "Constructors are not supported in `#[php_trait]` blocks.");
+        }
+        MethodTy::Getter | MethodTy::Setter => {
+            bail!(method.sig.ident => "Getters and setters are not supported in `#[php_trait]` blocks.");
+        }
+        MethodTy::Normal | MethodTy::Abstract => {}
+    }
+    if let Some(receiver) = method.sig.receiver() {
+        bail!(receiver => "Methods of `#[php_trait]` blocks cannot take `self`, as the object is an instance of the class using the trait. Take `self_: &mut ZendObject` instead.");
+    }
+    let output = match &method.sig.output {
+        syn::ReturnType::Default => None,
+        syn::ReturnType::Type(_, ty) => Some(&**ty),
+    };
+    if returns_self(output) || returns_self_ref(output) {
+        bail!(method.sig.output => "Methods of `#[php_trait]` blocks cannot return `Self`.");
+    }
+
+    let args = Args::parse_from_fnargs(method.sig.inputs.iter(), opts.defaults)?;
+    let mut func = Function::new(&method.sig, opts.name, args, opts.optional, docs);
+    func.deprecation = deprecation;
+
+    let mut modifiers = HashSet::new();
+    let receiver = if func
+        .args
+        .typed
+        .first()
+        .is_some_and(|arg| arg.name == "self_")
+    {
+        // `self_: &mut ZendObject`
+        func.args.typed.remove(0);
+        MethodReceiver::ZendObject
+    } else {
+        modifiers.insert(MethodModifier::Static);
+        MethodReceiver::Static
+    };
+    if opts.is_final {
+        modifiers.insert(MethodModifier::Final);
+    }
+
+    let builder = if matches!(opts.ty, MethodTy::Abstract) {
+        modifiers.insert(MethodModifier::Abstract);
+        func.abstract_function_builder()
+    } else {
+        func.function_builder(&CallType::Method {
+            class: path,
+            receiver,
+            generics: &syn::Generics::default(),
+        })
+    };
+
+    Ok(FnBuilder {
+        builder,
+        vis: opts.vis,
+        modifiers,
+    })
+}
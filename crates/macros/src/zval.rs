@@ -1,18 +1,70 @@
-use darling::ToTokens;
+use darling::util::{Flag, Override};
+use darling::{FromAttributes, ToTokens};
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
-    DataEnum, DataStruct, DeriveInput, GenericParam, Generics, Ident, ImplGenerics, Lifetime,
-    LifetimeParam, TypeGenerics, Variant, WhereClause, punctuated::Punctuated, token::Where,
+    DataEnum, DataStruct, DeriveInput, Expr, Fields, GenericParam, Generics, Ident, ImplGenerics,
+    Lifetime, LifetimeParam, Type, TypeGenerics, Variant, WhereClause, punctuated::Punctuated,
+    token::Where,
 };
 
-use crate::parsing::ident_to_php_name;
+use crate::parsing::{PhpRename, RenameRule, ident_to_php_name};
 use crate::prelude::*;
 
+#[derive(FromAttributes, Debug, Default)]
+#[darling(attributes(php), default)]
+struct ZvalConvertAttributes {
+    /// Rule to rename the fields of the struct, or of the variants of the
+    /// enum, with.
+    change_field_case: Option<RenameRule>,
+    /// Rule to rename the variants of a tagged enum with.
+    change_variant_case: Option<RenameRule>,
+    /// The property holding the name of the variant of a tagged enum.
+    tag: Option<String>,
+    /// The property holding the value of the variant of an adjacently tagged
+    /// enum.
+    content: Option<String>,
+}
+
+#[derive(FromAttributes, Debug, Default)]
+#[darling(attributes(php), default)]
+struct FieldAttributes {
+    #[darling(flatten)]
+    rename: PhpRename,
+    /// The value of the field when the property does not exist. Defaults to
+    /// `Default::default()` when no value is given.
+    default: Option<Override<Expr>>,
+    /// Whether the field is not converted, and always set to its default
+    /// value.
+    skip: Flag,
+    /// Whether the properties of the field are stored on the object itself.
+    flatten: Flag,
+}
+
+#[derive(FromAttributes, Debug, Default)]
+#[darling(attributes(php), default)]
+struct VariantAttributes {
+    #[darling(flatten)]
+    rename: PhpRename,
+}
+
+/// The generics of the implementations of the conversion traits.
+struct ConvertGenerics<'a> {
+    into_impl: ImplGenerics<'a>,
+    from_impl: Generics,
+    into_where: WhereClause,
+    from_where: WhereClause,
+    ty: TypeGenerics<'a>,
+}
+
 pub fn parser(input: DeriveInput) -> Result<TokenStream> {
     let DeriveInput {
-        generics, ident, ..
+        attrs,
+        generics,
+        ident,
+        ..
     } = input;
+    let attr = ZvalConvertAttributes::from_attributes(&attrs)?;
 
     let (into_impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
@@ -66,83 +118,191 @@ pub fn parser(input: DeriveInput) -> Result<TokenStream> {
         }
     }
 
+    let generics = ConvertGenerics {
+        into_impl: into_impl_generics,
+        from_impl: from_impl_generics,
+        into_where: into_where_clause,
+        from_where: from_where_clause,
+        ty: ty_generics,
+    };
+
     match input.data {
-        syn::Data::Struct(data) => parse_struct(
-            &data,
-            &ident,
-            &into_impl_generics,
-            &from_impl_generics,
-            &into_where_clause,
-            &from_where_clause,
-            &ty_generics,
-        ),
-        syn::Data::Enum(data) => parse_enum(
-            &data,
-            &ident,
-            &into_impl_generics,
-            &from_impl_generics,
-            &into_where_clause,
-            &from_where_clause,
-            &ty_generics,
-        ),
+        syn::Data::Struct(data) => parse_struct(&data, &ident, &attr, &generics),
+        syn::Data::Enum(data) => {
+            if attr.tag.is_some() {
+                parse_tagged_enum(&data, &ident, &attr, &generics)
+            } else if attr.content.is_some() {
+                bail!(ident => "`content` requires `tag` to be set when using `#[derive(ZvalConvert)]`.")
+            } else {
+                parse_enum(&data, &ident, &generics)
+            }
+        }
         syn::Data::Union(_) => {
             bail!(ident.span() => "Only structs and enums are supported by the `#[derive(ZvalConvert)]` macro.")
         }
     }
 }
 
+/// A named field converted to and from a property.
+struct Field<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    name: String,
+    attr: FieldAttributes,
+}
+
+impl<'a> Field<'a> {
+    fn parse(fields: &'a Fields, rule: RenameRule) -> Result<Vec<Self>> {
+        fields
+            .iter()
+            .map(|field| {
+                let ident = field.ident.as_ref().ok_or_else(|| {
+                    err!(field => "Fields require names when using the `#[derive(ZvalConvert)]` macro on a struct.")
+                })?;
+                let attr = FieldAttributes::from_attributes(&field.attrs)?;
+                if attr.flatten.is_present() && (attr.skip.is_present() || attr.default.is_some()) {
+                    bail!(field => "Flattened fields cannot be skipped or have a default value.");
+                }
+
+                Ok(Self {
+                    ident,
+                    ty: &field.ty,
+                    name: attr.rename.rename(ident_to_php_name(ident), rule),
+                    attr,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the statement setting the property from `value` on `obj`.
+    fn write(&self, value: &TokenStream) -> TokenStream {
+        let name = &self.name;
+        if self.attr.skip.is_present() {
+            quote! {}
+        } else if self.attr.flatten.is_present() {
+            quote! {
+                ::ext_php_rs::convert::IntoZendObject::write_properties(#value, obj)?;
+            }
+        } else {
+            quote! {
+                obj.set_property(#name, #value)?;
+            }
+        }
+    }
+
+    /// Returns the expression reading the field from `props`, the properties of
+    /// an object or the values of an array.
+    fn read(&self) -> TokenStream {
+        let name = &self.name;
+        let ty = self.ty;
+        if self.attr.skip.is_present() {
+            return self.default_value();
+        }
+        if self.attr.flatten.is_present() {
+            return quote! {
+                <#ty as ::ext_php_rs::convert::FromZendObject<'_zval>>::from_properties(props)?
+            };
+        }
+
+        let missing = if self.attr.default.is_some() {
+            self.default_value()
+        } else {
+            quote! {
+                return ::ext_php_rs::error::Result::Err(
+                    ::ext_php_rs::error::Error::MissingProperty(#name.to_string())
+                )
+            }
+        };
+        quote! {
+            match props.try_get_property::<#ty>(#name)? {
+                ::std::option::Option::Some(value) => value,
+                ::std::option::Option::None => #missing,
+            }
+        }
+    }
+
+    fn default_value(&self) -> TokenStream {
+        if let Some(Override::Explicit(expr)) = &self.attr.default {
+            quote! { #expr }
+        } else {
+            quote! { ::std::default::Default::default() }
+        }
+    }
+}
+
 fn parse_struct(
     data: &DataStruct,
     ident: &Ident,
-    into_impl_generics: &ImplGenerics,
-    from_impl_generics: &Generics,
-    into_where_clause: &WhereClause,
-    from_where_clause: &WhereClause,
-    ty_generics: &TypeGenerics,
+    attr: &ZvalConvertAttributes,
+    generics: &ConvertGenerics,
 ) -> Result<TokenStream> {
-    let into_fields = data
-        .fields
-        .iter()
-        .map(|field| {
-            let ident = field.ident.as_ref().ok_or_else(|| {
-                err!(field => "Fields require names when using the `#[derive(ZvalConvert)]` macro on a struct.")
-            })?;
-            let field_name = ident_to_php_name(ident);
-
-            Ok(quote! {
-                obj.set_property(#field_name, self.#ident)?;
-            })
-        })
-        .collect::<Result<Vec<_>>>()?;
-
-    let from_fields = data
-        .fields
-        .iter()
-        .map(|field| {
-            let ident = field.ident.as_ref().ok_or_else(|| {
-                err!(field => "Fields require names when using the `#[derive(ZvalConvert)]` macro on a struct.")
-            })?;
-            let field_name = ident_to_php_name(ident);
+    let fields = Field::parse(
+        &data.fields,
+        attr.change_field_case.unwrap_or(RenameRule::None),
+    )?;
+
+    let into_fields = fields.iter().map(|field| {
+        let ident = field.ident;
+        field.write(&quote! { self.#ident })
+    });
+    let from_fields = fields.iter().map(|field| {
+        let ident = field.ident;
+        let value = field.read();
+        quote! { #ident: #value, }
+    });
 
-            Ok(quote! {
-                #ident: obj.get_property(#field_name)?,
+    Ok(object_impls(
+        ident,
+        generics,
+        &quote! { #(#into_fields)* },
+        &quote! {
+            ::ext_php_rs::error::Result::Ok(Self {
+                #(#from_fields)*
             })
-        })
-        .collect::<Result<Vec<_>>>()?;
+        },
+    ))
+}
 
-    Ok(quote! {
+/// Returns the implementations of the conversion traits of a type converted
+/// into an object, and from an object or an array.
+///
+/// # Parameters
+///
+/// * `write` - The statements writing the properties of `self` to `obj`.
+/// * `read` - The expression reading `Self` from `props`.
+fn object_impls(
+    ident: &Ident,
+    generics: &ConvertGenerics,
+    write: &TokenStream,
+    read: &TokenStream,
+) -> TokenStream {
+    let ConvertGenerics {
+        into_impl: into_impl_generics,
+        from_impl: from_impl_generics,
+        into_where: into_where_clause,
+        from_where: from_where_clause,
+        ty: ty_generics,
+    } = generics;
+
+    quote! {
         impl #into_impl_generics ::ext_php_rs::convert::IntoZendObject for #ident #ty_generics #into_where_clause {
             fn into_zend_object(self) -> ::ext_php_rs::error::Result<
                 ::ext_php_rs::boxed::ZBox<
                     ::ext_php_rs::types::ZendObject
                 >
             > {
-                use ::ext_php_rs::convert::IntoZval;
-
                 let mut obj = ::ext_php_rs::types::ZendObject::new_stdclass();
-                #(#into_fields)*
+                ::ext_php_rs::convert::IntoZendObject::write_properties(self, &mut obj)?;
                 ::ext_php_rs::error::Result::Ok(obj)
             }
+
+            fn write_properties(
+                self,
+                obj: &mut ::ext_php_rs::types::ZendObject,
+            ) -> ::ext_php_rs::error::Result<()> {
+                #write
+                ::ext_php_rs::error::Result::Ok(())
+            }
         }
 
         impl #into_impl_generics ::ext_php_rs::convert::IntoZval for #ident #ty_generics #into_where_clause {
@@ -158,9 +318,13 @@ fn parse_struct(
 
         impl #from_impl_generics ::ext_php_rs::convert::FromZendObject<'_zval> for #ident #ty_generics #from_where_clause {
             fn from_zend_object(obj: &'_zval ::ext_php_rs::types::ZendObject) -> ::ext_php_rs::error::Result<Self> {
-                ::ext_php_rs::error::Result::Ok(Self {
-                    #(#from_fields)*
-                })
+                Self::from_properties(::ext_php_rs::convert::Properties::Object(obj))
+            }
+
+            fn from_properties(
+                props: ::ext_php_rs::convert::Properties<'_zval>,
+            ) -> ::ext_php_rs::error::Result<Self> {
+                #read
             }
         }
 
@@ -168,23 +332,205 @@ fn parse_struct(
             const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Object(None);
 
             fn from_zval(zv: &'_zval ::ext_php_rs::types::Zval) -> ::std::option::Option<Self> {
-                use ::ext_php_rs::convert::FromZendObject;
+                use ::ext_php_rs::convert::{FromZendObject, FromZval, Properties};
+
+                Self::from_properties(Properties::from_zval(zv)?).ok()
+            }
 
-                Self::from_zend_object(zv.object()?).ok()
+            fn type_decl() -> ::std::option::Option<::ext_php_rs::types::TypeDecl> {
+                <::ext_php_rs::convert::Properties as ::ext_php_rs::convert::FromZval>::type_decl()
             }
         }
-    })
+    }
 }
 
-fn parse_enum(
+/// Converts an enum into an object holding the name of its variant in the
+/// `tag` property, along with the value of the variant: in the same object,
+/// or in the `content` property when it is set.
+fn parse_tagged_enum(
     data: &DataEnum,
     ident: &Ident,
-    into_impl_generics: &ImplGenerics,
-    from_impl_generics: &Generics,
-    into_where_clause: &WhereClause,
-    from_where_clause: &WhereClause,
-    ty_generics: &TypeGenerics,
+    attr: &ZvalConvertAttributes,
+    generics: &ConvertGenerics,
 ) -> Result<TokenStream> {
+    let tag = attr.tag.as_deref().unwrap_or_default();
+    let (into_variants, from_variants): (Vec<_>, Vec<_>) = data
+        .variants
+        .iter()
+        .map(|variant| tagged_variant(variant, attr))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    Ok(object_impls(
+        ident,
+        generics,
+        &quote! {
+            match self {
+                #(#into_variants)*
+            }
+        },
+        &quote! {
+            let tag = props.try_get_property::<::std::string::String>(#tag)?.ok_or_else(|| {
+                ::ext_php_rs::error::Error::MissingProperty(#tag.to_string())
+            })?;
+            ::ext_php_rs::error::Result::Ok(match tag.as_str() {
+                #(#from_variants)*
+                _ => return ::ext_php_rs::error::Result::Err(
+                    ::ext_php_rs::error::Error::UnknownVariant(tag)
+                ),
+            })
+        },
+    ))
+}
+
+/// Returns the match arms writing a variant of a tagged enum to `obj`, and
+/// reading it from `props` given the name of the variant.
+fn tagged_variant(
+    variant: &Variant,
+    attr: &ZvalConvertAttributes,
+) -> Result<(TokenStream, TokenStream)> {
+    let Variant {
+        ident: variant_ident,
+        fields,
+        attrs,
+        ..
+    } = variant;
+    let tag = attr.tag.as_deref().unwrap_or_default();
+    let name = VariantAttributes::from_attributes(attrs)?.rename.rename(
+        ident_to_php_name(variant_ident),
+        attr.change_variant_case.unwrap_or(RenameRule::None),
+    );
+
+    let (write, read) = match fields {
+        Fields::Unit => (quote! {}, quote! { Self::#variant_ident }),
+        Fields::Unnamed(unnamed) => {
+            if unnamed.unnamed.len() != 1 {
+                bail!(fields => "Tuple variants of tagged enums must have exactly one field when using `#[derive(ZvalConvert)]`.");
+            }
+            tagged_newtype_variant(variant_ident, &unnamed.unnamed[0].ty, attr)
+        }
+        Fields::Named(_) => tagged_struct_variant(variant_ident, fields, attr)?,
+    };
+    let pattern = match fields {
+        Fields::Unit => quote! { Self::#variant_ident },
+        Fields::Unnamed(_) => quote! { Self::#variant_ident(value) },
+        Fields::Named(_) => {
+            let bindings = Field::parse(fields, RenameRule::None)?
+                .into_iter()
+                .map(|field| {
+                    let ident = field.ident;
+                    if field.attr.skip.is_present() {
+                        quote! { #ident: _ }
+                    } else {
+                        let binding = format_ident!("__{}", ident_to_php_name(ident));
+                        quote! { #ident: #binding }
+                    }
+                });
+            quote! { Self::#variant_ident { #(#bindings),* } }
+        }
+    };
+
+    Ok((
+        quote! {
+            #pattern => {
+                obj.set_property(#tag, #name)?;
+                #write
+            }
+        },
+        quote! {
+            #name => #read,
+        },
+    ))
+}
+
+/// Returns the statements writing the field of a tuple variant of a tagged
+/// enum, bound to `value`, and the expression reading the variant.
+fn tagged_newtype_variant(
+    variant_ident: &Ident,
+    ty: &Type,
+    attr: &ZvalConvertAttributes,
+) -> (TokenStream, TokenStream) {
+    if let Some(content) = attr.content.as_deref() {
+        (
+            quote! {
+                obj.set_property(#content, value)?;
+            },
+            quote! {
+                Self::#variant_ident(
+                    props.try_get_property::<#ty>(#content)?.ok_or_else(|| {
+                        ::ext_php_rs::error::Error::MissingProperty(#content.to_string())
+                    })?
+                )
+            },
+        )
+    } else {
+        (
+            quote! {
+                ::ext_php_rs::convert::IntoZendObject::write_properties(value, obj)?;
+            },
+            quote! {
+                Self::#variant_ident(
+                    <#ty as ::ext_php_rs::convert::FromZendObject<'_zval>>::from_properties(props)?
+                )
+            },
+        )
+    }
+}
+
+/// Returns the statements writing the named fields of a variant of a tagged
+/// enum, bound to `__<field>`, and the expression reading the variant.
+fn tagged_struct_variant(
+    variant_ident: &Ident,
+    fields: &Fields,
+    attr: &ZvalConvertAttributes,
+) -> Result<(TokenStream, TokenStream)> {
+    let fields = Field::parse(fields, attr.change_field_case.unwrap_or(RenameRule::None))?;
+    let writes = fields.iter().map(|field| {
+        let binding = format_ident!("__{}", ident_to_php_name(field.ident));
+        field.write(&quote! { #binding })
+    });
+    let reads = fields.iter().map(|field| {
+        let ident = field.ident;
+        let value = field.read();
+        quote! { #ident: #value, }
+    });
+
+    Ok(if let Some(content) = attr.content.as_deref() {
+        (
+            quote! {
+                let mut content = ::ext_php_rs::types::ZendObject::new_stdclass();
+                {
+                    let obj = &mut *content;
+                    #(#writes)*
+                }
+                obj.set_property(#content, content)?;
+            },
+            quote! {{
+                let props = props
+                    .try_get_property::<::ext_php_rs::convert::Properties<'_zval>>(#content)?
+                    .ok_or_else(|| {
+                        ::ext_php_rs::error::Error::MissingProperty(#content.to_string())
+                    })?;
+                Self::#variant_ident { #(#reads)* }
+            }},
+        )
+    } else {
+        (
+            quote! { #(#writes)* },
+            quote! { Self::#variant_ident { #(#reads)* } },
+        )
+    })
+}
+
+fn parse_enum(data: &DataEnum, ident: &Ident, generics: &ConvertGenerics) -> Result<TokenStream> {
+    let ConvertGenerics {
+        into_impl: into_impl_generics,
+        from_impl: from_impl_generics,
+        into_where: into_where_clause,
+        from_where: from_where_clause,
+        ty: ty_generics,
+    } = generics;
     let into_variants = data.variants.iter().filter_map(|variant| {
         // can have default fields - in this case, return `null`.
         if variant.fields.len() != 1 {
@@ -197,6 +543,8 @@ fn parse_enum(
         })
     });
 
+    let (into_type_decl, from_type_decl) = enum_type_decls(data);
+
     let mut default = None;
     let from_variants = data.variants.iter().map(|variant| {
         let Variant {
@@ -229,7 +577,7 @@ fn parse_enum(
                 });
                 Ok(None)
             }
-            syn::Fields::Named(_) => bail!(fields => "Enum variants must be unnamed and have only one field inside the variant when using `#[derive(ZvalConvert)]`.")
+            syn::Fields::Named(_) => bail!(fields => "Enum variants must be unnamed and have only one field inside the variant when using `#[derive(ZvalConvert)]`. Set `#[php(tag = \"...\")]` on the enum to convert variants with named fields.")
         }
     }).collect::<Result<Vec<_>>>()?;
     let default = default.unwrap_or_else(|| quote! { None });
@@ -239,6 +587,8 @@ fn parse_enum(
             const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Mixed;
             const NULLABLE: bool = false;
 
+            #into_type_decl
+
             fn set_zval(
                 self,
                 zv: &mut ::ext_php_rs::types::Zval,
@@ -259,6 +609,8 @@ fn parse_enum(
         impl #from_impl_generics ::ext_php_rs::convert::FromZval<'_zval> for #ident #ty_generics #from_where_clause {
             const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Mixed;
 
+            #from_type_decl
+
             fn from_zval(zval: &'_zval ::ext_php_rs::types::Zval) -> ::std::option::Option<Self> {
                 #(#from_variants)*
                 #default
@@ -266,3 +618,67 @@ fn parse_enum(
         }
     })
 }
+
+/// Returns the `type_decl()` functions of the `IntoZval` and `FromZval`
+/// implementations of an untagged enum, which declare the union of the types
+/// of its variants.
+fn enum_type_decls(data: &DataEnum) -> (TokenStream, Option<TokenStream>) {
+    let types = data.variants.iter().filter_map(|variant| {
+        variant
+            .fields
+            .iter()
+            .next()
+            .filter(|_| variant.fields.len() == 1)
+            .map(|field| &field.ty)
+    });
+    let into_types = types.clone().map(|ty| {
+        quote! {
+            {
+                let decl = <#ty as ::ext_php_rs::convert::IntoZval>::type_decl()
+                    .unwrap_or_else(|| <#ty as ::ext_php_rs::convert::IntoZval>::TYPE.into());
+                if <#ty as ::ext_php_rs::convert::IntoZval>::NULLABLE {
+                    decl.nullable()
+                } else {
+                    decl
+                }
+            }
+        }
+    });
+    let from_types = types.map(|ty| {
+        quote! {
+            <#ty as ::ext_php_rs::convert::FromZval<'_zval>>::type_decl().unwrap_or_else(
+                || <#ty as ::ext_php_rs::convert::FromZval<'_zval>>::TYPE.into(),
+            )
+        }
+    });
+    // Variants without a value are converted into `null`.
+    let into_null = data
+        .variants
+        .iter()
+        .any(|variant| variant.fields.len() != 1)
+        .then(|| quote! { ::ext_php_rs::flags::DataType::Null.into(), });
+
+    let into_type_decl = quote! {
+        fn type_decl() -> ::std::option::Option<::ext_php_rs::types::TypeDecl> {
+            ::std::option::Option::Some(::ext_php_rs::types::TypeDecl::union([
+                #(#into_types,)*
+                #into_null
+            ]))
+        }
+    };
+    // A unit variant accepts any value, so the union only applies without one.
+    let from_type_decl = data
+        .variants
+        .iter()
+        .all(|variant| !matches!(variant.fields, Fields::Unit))
+        .then(|| {
+            quote! {
+                fn type_decl() -> ::std::option::Option<::ext_php_rs::types::TypeDecl> {
+                    ::std::option::Option::Some(::ext_php_rs::types::TypeDecl::union([
+                        #(#from_types,)*
+                    ]))
+                }
+            }
+        });
+    (into_type_decl, from_type_decl)
+}
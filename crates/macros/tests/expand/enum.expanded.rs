@@ -63,16 +63,19 @@ impl ::ext_php_rs::enum_::RegisteredEnum for MyEnum {
             name: "Variant1",
             discriminant: None,
             docs: &[" Variant1 of MyEnum.", " This variant represents the first case."],
+            deprecation: ::std::option::Option::None,
         },
         ::ext_php_rs::enum_::EnumCase {
             name: "Variant_2",
             discriminant: None,
             docs: &[],
+            deprecation: ::std::option::Option::None,
         },
         ::ext_php_rs::enum_::EnumCase {
             name: "VARIANT_3",
             discriminant: None,
             docs: &[" Variant3 of MyEnum."],
+            deprecation: ::std::option::Option::None,
         },
     ];
     fn from_name(name: &str) -> ::ext_php_rs::error::Result<Self> {
@@ -91,6 +94,31 @@ impl ::ext_php_rs::enum_::RegisteredEnum for MyEnum {
         }
     }
 }
+impl<'a> ::ext_php_rs::convert::FromZval<'a> for MyEnum {
+    const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Object(
+        Some(<MyEnum as ::ext_php_rs::class::RegisteredClass>::CLASS_NAME),
+    );
+    #[inline]
+    fn from_zval(zval: &'a ::ext_php_rs::types::Zval) -> ::std::option::Option<Self> {
+        <Self as ::ext_php_rs::convert::FromZendObject>::from_zend_object(zval.object()?)
+            .ok()
+    }
+}
+impl ::ext_php_rs::convert::IntoZval for MyEnum {
+    const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Object(
+        Some(<MyEnum as ::ext_php_rs::class::RegisteredClass>::CLASS_NAME),
+    );
+    const NULLABLE: bool = false;
+    #[inline]
+    fn set_zval(
+        self,
+        zv: &mut ::ext_php_rs::types::Zval,
+        persistent: bool,
+    ) -> ::ext_php_rs::error::Result<()> {
+        use ::ext_php_rs::convert::IntoZendObject;
+        self.into_zend_object()?.set_zval(zv, persistent)
+    }
+}
 #[allow(dead_code)]
 enum MyEnumWithIntValues {
     Variant1,
@@ -145,11 +173,13 @@ impl ::ext_php_rs::enum_::RegisteredEnum for MyEnumWithIntValues {
             name: "Variant1",
             discriminant: Some(::ext_php_rs::enum_::Discriminant::Int(1i64)),
             docs: &[],
+            deprecation: ::std::option::Option::None,
         },
         ::ext_php_rs::enum_::EnumCase {
             name: "Variant2",
             discriminant: Some(::ext_php_rs::enum_::Discriminant::Int(42i64)),
             docs: &[],
+            deprecation: ::std::option::Option::None,
         },
     ];
     fn from_name(name: &str) -> ::ext_php_rs::error::Result<Self> {
@@ -184,6 +214,31 @@ impl Into<i64> for MyEnumWithIntValues {
         }
     }
 }
+impl<'a> ::ext_php_rs::convert::FromZval<'a> for MyEnumWithIntValues {
+    const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Object(
+        Some(<MyEnumWithIntValues as ::ext_php_rs::class::RegisteredClass>::CLASS_NAME),
+    );
+    #[inline]
+    fn from_zval(zval: &'a ::ext_php_rs::types::Zval) -> ::std::option::Option<Self> {
+        <Self as ::ext_php_rs::convert::FromZendObject>::from_zend_object(zval.object()?)
+            .ok()
+    }
+}
+impl ::ext_php_rs::convert::IntoZval for MyEnumWithIntValues {
+    const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Object(
+        Some(<MyEnumWithIntValues as ::ext_php_rs::class::RegisteredClass>::CLASS_NAME),
+    );
+    const NULLABLE: bool = false;
+    #[inline]
+    fn set_zval(
+        self,
+        zv: &mut ::ext_php_rs::types::Zval,
+        persistent: bool,
+    ) -> ::ext_php_rs::error::Result<()> {
+        use ::ext_php_rs::convert::IntoZendObject;
+        self.into_zend_object()?.set_zval(zv, persistent)
+    }
+}
 #[allow(dead_code)]
 enum MyEnumWithStringValues {
     Variant1,
@@ -238,11 +293,13 @@ impl ::ext_php_rs::enum_::RegisteredEnum for MyEnumWithStringValues {
             name: "Variant1",
             discriminant: Some(::ext_php_rs::enum_::Discriminant::String("foo")),
             docs: &[],
+            deprecation: ::std::option::Option::None,
         },
         ::ext_php_rs::enum_::EnumCase {
             name: "Variant2",
             discriminant: Some(::ext_php_rs::enum_::Discriminant::String("bar")),
             docs: &[],
+            deprecation: ::std::option::Option::None,
         },
     ];
     fn from_name(name: &str) -> ::ext_php_rs::error::Result<Self> {
@@ -277,3 +334,28 @@ impl Into<&'static str> for MyEnumWithStringValues {
         }
     }
 }
+impl<'a> ::ext_php_rs::convert::FromZval<'a> for MyEnumWithStringValues {
+    const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Object(
+        Some(<MyEnumWithStringValues as ::ext_php_rs::class::RegisteredClass>::CLASS_NAME),
+    );
+    #[inline]
+    fn from_zval(zval: &'a ::ext_php_rs::types::Zval) -> ::std::option::Option<Self> {
+        <Self as ::ext_php_rs::convert::FromZendObject>::from_zend_object(zval.object()?)
+            .ok()
+    }
+}
+impl ::ext_php_rs::convert::IntoZval for MyEnumWithStringValues {
+    const TYPE: ::ext_php_rs::flags::DataType = ::ext_php_rs::flags::DataType::Object(
+        Some(<MyEnumWithStringValues as ::ext_php_rs::class::RegisteredClass>::CLASS_NAME),
+    );
+    const NULLABLE: bool = false;
+    #[inline]
+    fn set_zval(
+        self,
+        zv: &mut ::ext_php_rs::types::Zval,
+        persistent: bool,
+    ) -> ::ext_php_rs::error::Result<()> {
+        use ::ext_php_rs::convert::IntoZendObject;
+        self.into_zend_object()?.set_zval(zv, persistent)
+    }
+}
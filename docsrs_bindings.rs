@@ -478,8 +478,13 @@ impl<const N: usize> __BindgenBitfieldUnit<[u8; N]> {
 pub const ZEND_DEBUG: u32 = 1;
 pub const _ZEND_TYPE_NAME_BIT: u32 = 16777216;
 pub const _ZEND_TYPE_LITERAL_NAME_BIT: u32 = 8388608;
+pub const _ZEND_TYPE_LIST_BIT: u32 = 4194304;
+pub const _ZEND_TYPE_INTERSECTION_BIT: u32 = 524288;
+pub const _ZEND_TYPE_UNION_BIT: u32 = 262144;
 pub const _ZEND_TYPE_NULLABLE_BIT: u32 = 2;
 pub const HT_MIN_SIZE: u32 = 8;
+pub const HASH_FLAG_PACKED: u32 = 4;
+pub const HASH_FLAG_UNINITIALIZED: u32 = 8;
 pub const IS_UNDEF: u32 = 0;
 pub const IS_NULL: u32 = 1;
 pub const IS_FALSE: u32 = 2;
@@ -503,6 +508,8 @@ pub const Z_TYPE_FLAGS_SHIFT: u32 = 8;
 pub const GC_FLAGS_MASK: u32 = 1008;
 pub const GC_FLAGS_SHIFT: u32 = 0;
 pub const GC_IMMUTABLE: u32 = 64;
+pub const GC_NOT_COLLECTABLE: u32 = 16;
+pub const GC_PERSISTENT: u32 = 128;
 pub const IS_TYPE_REFCOUNTED: u32 = 1;
 pub const IS_TYPE_COLLECTABLE: u32 = 2;
 pub const IS_INTERNED_STRING_EX: u32 = 6;
@@ -533,6 +540,13 @@ pub const ZEND_LAZY_OBJECT_INITIALIZED: u32 = 4;
 pub const ZEND_LAZY_OBJECT_SKIP_INITIALIZATION_ON_SERIALIZE: u32 = 8;
 pub const ZEND_PROPERTY_ISSET: u32 = 0;
 pub const ZEND_PROPERTY_EXISTS: u32 = 2;
+pub const ZEND_ADD: u32 = 1;
+pub const ZEND_SUB: u32 = 2;
+pub const ZEND_MUL: u32 = 3;
+pub const ZEND_DIV: u32 = 4;
+pub const ZEND_MOD: u32 = 5;
+pub const ZEND_CONCAT: u32 = 8;
+pub const ZEND_POW: u32 = 12;
 pub const ZEND_ACC_PUBLIC: u32 = 1;
 pub const ZEND_ACC_PROTECTED: u32 = 2;
 pub const ZEND_ACC_PRIVATE: u32 = 4;
@@ -540,12 +554,17 @@ pub const ZEND_ACC_CHANGED: u32 = 8;
 pub const ZEND_ACC_STATIC: u32 = 16;
 pub const ZEND_ACC_FINAL: u32 = 32;
 pub const ZEND_ACC_ABSTRACT: u32 = 64;
+pub const ZEND_ACC_READONLY: u32 = 128;
 pub const ZEND_ACC_IMMUTABLE: u32 = 128;
 pub const ZEND_ACC_HAS_TYPE_HINTS: u32 = 256;
 pub const ZEND_ACC_TOP_LEVEL: u32 = 512;
 pub const ZEND_ACC_PRELOADED: u32 = 1024;
 pub const ZEND_ACC_DEPRECATED: u32 = 2048;
 pub const ZEND_ACC_PROMOTED: u32 = 256;
+pub const ZEND_ACC_VIRTUAL: u32 = 512;
+pub const ZEND_ACC_PUBLIC_SET: u32 = 1024;
+pub const ZEND_ACC_PROTECTED_SET: u32 = 2048;
+pub const ZEND_ACC_PRIVATE_SET: u32 = 4096;
 pub const ZEND_ACC_INTERFACE: u32 = 1;
 pub const ZEND_ACC_TRAIT: u32 = 2;
 pub const ZEND_ACC_ANON_CLASS: u32 = 4;
@@ -581,9 +600,24 @@ pub const ZEND_ACC_STRICT_TYPES: u32 = 2147483648;
 pub const ZEND_INTERNAL_FUNCTION: u32 = 1;
 pub const ZEND_USER_FUNCTION: u32 = 2;
 pub const ZEND_EVAL_CODE: u32 = 4;
+pub const ZEND_INTERNAL_CLASS: u32 = 1;
+pub const BP_VAR_R: u32 = 0;
+pub const BP_VAR_W: u32 = 1;
+pub const BP_VAR_RW: u32 = 2;
+pub const BP_VAR_IS: u32 = 3;
+pub const BP_VAR_UNSET: u32 = 5;
 pub const ZEND_ISEMPTY: u32 = 1;
 pub const _ZEND_SEND_MODE_SHIFT: u32 = 25;
 pub const _ZEND_IS_VARIADIC_BIT: u32 = 134217728;
+pub const ZEND_ATTRIBUTE_TARGET_CLASS: u32 = 1;
+pub const ZEND_ATTRIBUTE_TARGET_FUNCTION: u32 = 2;
+pub const ZEND_ATTRIBUTE_TARGET_METHOD: u32 = 4;
+pub const ZEND_ATTRIBUTE_TARGET_PROPERTY: u32 = 8;
+pub const ZEND_ATTRIBUTE_TARGET_CLASS_CONST: u32 = 16;
+pub const ZEND_ATTRIBUTE_TARGET_PARAMETER: u32 = 32;
+pub const ZEND_ATTRIBUTE_TARGET_CONST: u32 = 64;
+pub const ZEND_ATTRIBUTE_TARGET_ALL: u32 = 127;
+pub const ZEND_ATTRIBUTE_IS_REPEATABLE: u32 = 128;
 pub const ZEND_MODULE_API_NO: u32 = 20250925;
 pub const USING_ZTS: u32 = 0;
 pub const CONST_CS: u32 = 0;
@@ -741,6 +775,24 @@ pub type zend_resource = _zend_resource;
 pub type zend_reference = _zend_reference;
 pub type zend_ast_ref = _zend_ast_ref;
 pub type zend_ast = _zend_ast;
+pub type compare_func_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        arg1: *const ::std::os::raw::c_void,
+        arg2: *const ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type swap_func_t = ::std::option::Option<
+    unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: *mut ::std::os::raw::c_void),
+>;
+pub type sort_func_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        arg1: *mut ::std::os::raw::c_void,
+        arg2: usize,
+        arg3: usize,
+        arg4: compare_func_t,
+        arg5: swap_func_t,
+    ),
+>;
 pub type dtor_func_t = ::std::option::Option<unsafe extern "C" fn(pDest: *mut zval)>;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -749,6 +801,29 @@ pub struct zend_type {
     pub type_mask: u32,
 }
 #[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct zend_type_list {
+    pub num_types: u32,
+    pub types: [zend_type; 1usize],
+}
+#[repr(C)]
+pub struct _zend_attribute_arg {
+    pub name: *mut zend_string,
+    pub value: zval,
+}
+pub type zend_attribute_arg = _zend_attribute_arg;
+#[repr(C)]
+pub struct _zend_attribute {
+    pub name: *mut zend_string,
+    pub lcname: *mut zend_string,
+    pub flags: u32,
+    pub lineno: u32,
+    pub offset: u32,
+    pub argc: u32,
+    pub args: [zend_attribute_arg; 1usize],
+}
+pub type zend_attribute = _zend_attribute;
+#[repr(C)]
 #[derive(Copy, Clone)]
 pub union _zend_value {
     pub lval: zend_long,
@@ -1112,6 +1187,17 @@ pub type zend_hash_key_type = ::std::os::raw::c_uint;
 unsafe extern "C" {
     pub static zend_empty_array: HashTable;
 }
+unsafe extern "C" {
+    pub fn _zend_hash_init(
+        ht: *mut HashTable,
+        nSize: u32,
+        pDestructor: dtor_func_t,
+        persistent: bool,
+    );
+}
+unsafe extern "C" {
+    pub fn zend_hash_destroy(ht: *mut HashTable);
+}
 unsafe extern "C" {
     pub fn zend_hash_clean(ht: *mut HashTable);
 }
@@ -1162,6 +1248,32 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub fn zend_hash_next_index_insert(ht: *mut HashTable, pData: *mut zval) -> *mut zval;
 }
+unsafe extern "C" {
+    pub fn zend_hash_real_init_packed(ht: *mut HashTable);
+}
+unsafe extern "C" {
+    pub fn zend_hash_extend(ht: *mut HashTable, nSize: u32, packed: bool);
+}
+pub type bucket_compare_func_t = ::std::option::Option<
+    unsafe extern "C" fn(a: *mut Bucket, b: *mut Bucket) -> ::std::os::raw::c_int,
+>;
+unsafe extern "C" {
+    pub fn zend_hash_sort_ex(
+        ht: *mut HashTable,
+        sort_func: sort_func_t,
+        compare_func: bucket_compare_func_t,
+        renumber: bool,
+    );
+}
+unsafe extern "C" {
+    pub fn zend_sort(
+        base: *mut ::std::os::raw::c_void,
+        nmemb: usize,
+        siz: usize,
+        cmp: compare_func_t,
+        swp: swap_func_t,
+    );
+}
 unsafe extern "C" {
     pub fn zend_hash_del(ht: *mut HashTable, key: *mut zend_string) -> zend_result;
 }
@@ -1305,6 +1417,9 @@ pub struct _zend_class_arrayaccess_funcs {
     pub zf_offsetunset: *mut zend_function,
 }
 pub type zend_class_arrayaccess_funcs = _zend_class_arrayaccess_funcs;
+unsafe extern "C" {
+    pub fn zend_iterator_init(iter: *mut zend_object_iterator);
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct stat {
@@ -1823,6 +1938,16 @@ unsafe extern "C" {
         cache_slot: *mut *mut ::std::os::raw::c_void,
     ) -> *mut zval;
 }
+unsafe extern "C" {
+    pub fn zend_verify_property_type(
+        info: *const zend_property_info,
+        property: *mut zval,
+        strict: bool,
+    ) -> bool;
+}
+unsafe extern "C" {
+    pub fn zend_readonly_property_modification_error(info: *const zend_property_info);
+}
 unsafe extern "C" {
     pub fn zend_std_has_property(
         object: *mut zend_object,
@@ -1831,6 +1956,27 @@ unsafe extern "C" {
         cache_slot: *mut *mut ::std::os::raw::c_void,
     ) -> ::std::os::raw::c_int;
 }
+unsafe extern "C" {
+    pub fn zend_std_read_dimension(
+        object: *mut zend_object,
+        offset: *mut zval,
+        type_: ::std::os::raw::c_int,
+        rv: *mut zval,
+    ) -> *mut zval;
+}
+unsafe extern "C" {
+    pub fn zend_std_write_dimension(object: *mut zend_object, offset: *mut zval, value: *mut zval);
+}
+unsafe extern "C" {
+    pub fn zend_std_has_dimension(
+        object: *mut zend_object,
+        offset: *mut zval,
+        check_empty: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn zend_std_unset_dimension(object: *mut zend_object, offset: *mut zval);
+}
 #[repr(C)]
 #[derive(Debug)]
 pub struct _zend_strtod_bigint {
@@ -1848,6 +1994,100 @@ pub type zend_strtod_state = _zend_strtod_state;
 unsafe extern "C" {
     pub fn zend_is_identical(op1: *const zval, op2: *const zval) -> bool;
 }
+unsafe extern "C" {
+    pub fn zend_compare(op1: *mut zval, op2: *mut zval) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn numeric_compare_function(op1: *mut zval, op2: *mut zval) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn string_compare_function(op1: *mut zval, op2: *mut zval) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn string_case_compare_function(op1: *mut zval, op2: *mut zval)
+        -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn add_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn sub_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn mul_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn div_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn mod_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn pow_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn concat_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn bitwise_and_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn bitwise_or_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn bitwise_xor_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn shift_left_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn shift_right_function(result: *mut zval, op1: *mut zval, op2: *mut zval) -> zend_result;
+}
+unsafe extern "C" {
+    pub fn bitwise_not_function(result: *mut zval, op1: *mut zval) -> zend_result;
+}
+pub type rsrc_dtor_func_t = ::std::option::Option<unsafe extern "C" fn(res: *mut zend_resource)>;
+unsafe extern "C" {
+    pub fn zend_list_delete(res: *mut zend_resource);
+}
+unsafe extern "C" {
+    pub fn zend_list_close(res: *mut zend_resource);
+}
+unsafe extern "C" {
+    pub fn zend_register_resource(
+        rsrc_pointer: *mut ::std::os::raw::c_void,
+        rsrc_type: ::std::os::raw::c_int,
+    ) -> *mut zend_resource;
+}
+unsafe extern "C" {
+    pub fn zend_register_persistent_resource(
+        key: *const ::std::os::raw::c_char,
+        key_len: usize,
+        rsrc_pointer: *mut ::std::os::raw::c_void,
+        rsrc_type: ::std::os::raw::c_int,
+    ) -> *mut zend_resource;
+}
+unsafe extern "C" {
+    pub fn zend_register_list_destructors_ex(
+        ld: rsrc_dtor_func_t,
+        pld: rsrc_dtor_func_t,
+        type_name: *const ::std::os::raw::c_char,
+        module_number: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn zend_string_hash_func(str_: *mut zend_string) -> zend_ulong;
+}
+unsafe extern "C" {
+    pub fn zend_weakrefs_hash_add(
+        ht: *mut HashTable,
+        key: *mut zend_object,
+        pData: *mut zval,
+    ) -> *mut zval;
+}
+unsafe extern "C" {
+    pub fn zend_weakrefs_hash_del(ht: *mut HashTable, key: *mut zend_object) -> zend_result;
+}
 unsafe extern "C" {
     pub fn instanceof_function_slow(
         instance_ce: *const zend_class_entry,
@@ -2550,6 +2790,16 @@ unsafe extern "C" {
         access_type: ::std::os::raw::c_int,
     );
 }
+unsafe extern "C" {
+    pub fn zend_declare_typed_property(
+        ce: *mut zend_class_entry,
+        name: *mut zend_string,
+        property: *mut zval,
+        access_type: ::std::os::raw::c_int,
+        doc_comment: *mut zend_string,
+        type_: zend_type,
+    ) -> *mut zend_property_info;
+}
 unsafe extern "C" {
     pub fn zend_declare_class_constant(
         ce: *mut zend_class_entry,
@@ -3601,6 +3851,16 @@ unsafe extern "C" {
 unsafe extern "C" {
     pub static mut zend_ce_stringable: *mut zend_class_entry;
 }
+unsafe extern "C" {
+    pub static mut zend_ce_fiber: *mut zend_class_entry;
+}
+unsafe extern "C" {
+    pub fn zend_fiber_switch_blocked() -> bool;
+}
+unsafe extern "C" {
+    pub fn zend_create_internal_iterator_zval(return_value: *mut zval, obj: *mut zval)
+    -> zend_result;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct sapi_header_struct {
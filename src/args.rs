@@ -1,8 +1,12 @@
 //! Builder and objects relating to function and method arguments.
 
-use std::{ffi::CString, ptr};
+use std::{
+    ffi::{CStr, CString},
+    ptr,
+};
 
 use crate::{
+    attribute::Attribute,
     convert::{FromZvalMut, IntoZvalDyn},
     describe::{Parameter, abi},
     error::{Error, Result},
@@ -11,25 +15,29 @@ use crate::{
         _zend_expected_type_Z_EXPECTED_BOOL, _zend_expected_type_Z_EXPECTED_DOUBLE,
         _zend_expected_type_Z_EXPECTED_LONG, _zend_expected_type_Z_EXPECTED_OBJECT,
         _zend_expected_type_Z_EXPECTED_RESOURCE, _zend_expected_type_Z_EXPECTED_STRING,
-        zend_internal_arg_info, zend_wrong_parameters_count_error,
+        ext_php_rs_argument_error, ext_php_rs_executor_globals, ext_php_rs_parse_arg_weak,
+        ext_php_rs_zval_value_name, zend_internal_arg_info, zend_wrong_parameters_count_error,
     },
     flags::DataType,
-    types::Zval,
-    zend::ZendType,
+    types::{TypeDecl, Zval},
+    zend::{ClassEntry, ExecuteData, ExecutorGlobals, ZendType, ce},
 };
 
 /// Represents an argument to a function.
 #[must_use]
 #[derive(Debug)]
 pub struct Arg<'a> {
-    name: String,
+    pub(crate) name: String,
     r#type: DataType,
     as_ref: bool,
     allow_null: bool,
     pub(crate) variadic: bool,
     default_value: Option<String>,
+    doc_type: Option<String>,
+    type_decl: Option<TypeDecl>,
     zval: Option<&'a mut Zval>,
     variadic_zvals: Vec<Option<&'a mut Zval>>,
+    pub(crate) attributes: Vec<Attribute>,
 }
 
 impl<'a> Arg<'a> {
@@ -47,8 +55,11 @@ impl<'a> Arg<'a> {
             allow_null: false,
             variadic: false,
             default_value: None,
+            doc_type: None,
+            type_decl: None,
             zval: None,
             variadic_zvals: vec![],
+            attributes: vec![],
         }
     }
 
@@ -77,6 +88,50 @@ impl<'a> Arg<'a> {
         self
     }
 
+    /// Sets the `PHPDoc` type of the argument, used in the generated stubs
+    /// instead of the PHP type of the argument.
+    ///
+    /// # Parameters
+    ///
+    /// * `doc_type` - The `PHPDoc` type, e.g. `array<string, int>`.
+    pub fn doc_type(mut self, doc_type: Option<String>) -> Self {
+        self.doc_type = doc_type;
+        self
+    }
+
+    /// Sets the type declaration of the argument, when it cannot be described
+    /// by the type of the argument, e.g. a union of types. Nullable arguments
+    /// allow `null` in addition to the declared types.
+    ///
+    /// # Parameters
+    ///
+    /// * `type_decl` - The type declaration, e.g. `int|string`.
+    pub fn type_decl(mut self, type_decl: Option<TypeDecl>) -> Self {
+        self.type_decl = type_decl;
+        self
+    }
+
+    /// Attaches an attribute to the parameter, e.g. `#[\SensitiveParameter]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `attribute` - The attribute to attach.
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Returns the type declaration of the argument, including `null` if the
+    /// argument is nullable.
+    fn expected_type(&self) -> TypeDecl {
+        let decl = self.type_decl.clone().unwrap_or_else(|| self.r#type.into());
+        if self.allow_null {
+            decl.nullable()
+        } else {
+            decl
+        }
+    }
+
     /// Attempts to consume the argument, converting the inner type into `T`.
     /// Upon success, the result is returned in a [`Result`].
     ///
@@ -87,6 +142,7 @@ impl<'a> Arg<'a> {
     ///
     /// If the conversion fails (or the argument contains no value), the
     /// argument is returned in an [`Err`] variant.
+    #[allow(clippy::result_large_err)]
     pub fn consume<T>(mut self) -> Result<T, Self>
     where
         for<'b> T: FromZvalMut<'b>,
@@ -109,6 +165,50 @@ impl<'a> Arg<'a> {
             .and_then(|zv| T::from_zval_mut(zv.dereference_mut()))
     }
 
+    /// Attempts to retrieve the value of the argument, throwing the error the
+    /// engine throws if it does not match the parameter, as
+    /// [`throw_invalid_arg`] does.
+    ///
+    /// # Parameters
+    ///
+    /// * `arg_num` - The position of the argument, starting at 1.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `null` was given for a nullable parameter, or if the argument
+    /// was not given for a nullable parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ZvalConversion`] if the value could not be converted,
+    /// or was not given, in which case the error has been thrown.
+    pub fn try_val<T>(&'a mut self, arg_num: u32) -> Result<Option<T>>
+    where
+        T: FromZvalMut<'a>,
+    {
+        let (ty, allow_null) = (self.r#type, self.allow_null);
+        let Some(zval) = self.zval.as_deref_mut() else {
+            if allow_null {
+                return Ok(None);
+            }
+            throw_invalid_arg(None, &self.expected_type(), arg_num);
+            return Err(Error::ZvalConversion(ty));
+        };
+        let zval = ptr::from_mut(zval);
+
+        // SAFETY: The zval is valid for `'a`. It is only borrowed again once the
+        // conversion failed, when the value returned does not borrow it.
+        if let Some(val) = T::from_zval_mut(unsafe { &mut *zval }.dereference_mut()) {
+            return Ok(Some(val));
+        }
+        let zval = unsafe { &*zval };
+        if allow_null && zval.dereference().is_null() {
+            return Ok(None);
+        }
+        throw_invalid_arg(Some(zval), &self.expected_type(), arg_num);
+        Err(Error::ZvalConversion(ty))
+    }
+
     /// Retrice all the variadic values for this Rust argument.
     pub fn variadic_vals<T>(&'a mut self) -> Vec<T>
     where
@@ -160,12 +260,17 @@ impl<'a> Arg<'a> {
     pub(crate) fn as_arg_info(&self) -> Result<ArgInfo> {
         Ok(ArgInfo {
             name: CString::new(self.name.as_str())?.into_raw(),
-            type_: ZendType::empty_from_type(
-                self.r#type,
-                self.as_ref,
-                self.variadic,
-                self.allow_null,
-            )
+            type_: match &self.type_decl {
+                Some(_) => {
+                    ZendType::empty_from_decl(&self.expected_type(), self.as_ref, self.variadic)
+                }
+                None => ZendType::empty_from_type(
+                    self.r#type,
+                    self.as_ref,
+                    self.variadic,
+                    self.allow_null,
+                ),
+            }
             .ok_or(Error::InvalidCString)?,
             default_value: match &self.default_value {
                 Some(val) if val.as_str() == "None" => CString::new("null")?.into_raw(),
@@ -195,12 +300,18 @@ impl From<Arg<'_>> for _zend_expected_type {
 
 impl From<Arg<'_>> for Parameter {
     fn from(val: Arg<'_>) -> Self {
+        let type_decl = val
+            .type_decl
+            .is_some()
+            .then(|| abi::RString::from(val.expected_type().to_stub()));
         Parameter {
             name: val.name.into(),
             ty: Some(val.r#type).into(),
             nullable: val.allow_null,
             variadic: val.variadic,
             default: val.default_value.map(abi::RString::from).into(),
+            doc_type: val.doc_type.map(abi::RString::from).into(),
+            type_decl: type_decl.into(),
         }
     }
 }
@@ -282,13 +393,31 @@ impl<'a, 'b> ArgParser<'a, 'b> {
             return Err(Error::IncorrectArguments(num_args, min_num_args));
         }
 
-        for (i, arg_zval) in self.arg_zvals.into_iter().enumerate() {
+        // The strictness of the caller is only looked up when an argument does
+        // not already have the type of its parameter.
+        let mut strict = None;
+        for (i, mut arg_zval) in self.arg_zvals.into_iter().enumerate() {
             let arg = match self.args.get_mut(i) {
                 Some(arg) => Some(arg),
                 // Only select the last item if it's variadic
                 None => self.args.last_mut().filter(|arg| arg.variadic),
             };
             if let Some(arg) = arg {
+                if let Some(zval) = arg_zval.as_deref_mut()
+                    && !arg.as_ref
+                {
+                    let coerced = coerce_arg(
+                        zval,
+                        arg.r#type,
+                        arg.allow_null,
+                        (i + 1).try_into()?,
+                        || *strict.get_or_insert_with(arg_uses_strict_types),
+                    );
+                    if !coerced {
+                        throw_invalid_arg(Some(zval), &arg.expected_type(), (i + 1).try_into()?);
+                        return Err(Error::ZvalConversion(arg.r#type));
+                    }
+                }
                 if arg.variadic {
                     arg.variadic_zvals.push(arg_zval);
                 } else {
@@ -301,6 +430,155 @@ impl<'a, 'b> ArgParser<'a, 'b> {
     }
 }
 
+/// Coerces the value of an argument to the type of its parameter in place,
+/// following the rules the engine applies to the parameters of internal
+/// functions.
+///
+/// Only scalar parameters are coerced. When the caller declares
+/// `strict_types=1`, integers are widened to floats and other values are left
+/// as they are. Otherwise, values are converted as `zend_parse_arg_*_weak()`
+/// do, e.g. `"5"` to `5` for an `int` parameter, and `null` is converted for
+/// non-nullable parameters with a deprecation notice.
+///
+/// This is done by [`ArgParser::parse`] and by the functions generated by the
+/// `#[php_function]` and `#[php_impl]` macros.
+///
+/// # Parameters
+///
+/// * `zval` - The value of the argument.
+/// * `ty` - The type of the parameter.
+/// * `allow_null` - Whether the parameter is nullable.
+/// * `arg_num` - The position of the argument, starting at 1, used in the
+///   deprecation notices.
+/// * `strict` - Returns whether the caller declares `strict_types=1`, e.g.
+///   [`ExecuteData::arg_uses_strict_types`]. Only called if the value does not
+///   already have the type of the parameter.
+///
+/// # Returns
+///
+/// `false` if the value of a scalar parameter could not be coerced, e.g. an
+/// array given for an `int`, or if an exception was thrown while coercing it.
+#[must_use]
+pub fn coerce_arg(
+    zval: &mut Zval,
+    ty: DataType,
+    allow_null: bool,
+    arg_num: u32,
+    strict: impl FnOnce() -> bool,
+) -> bool {
+    let actual = zval.get_type();
+    let matches = match ty {
+        DataType::Long | DataType::Double | DataType::String => actual == ty,
+        DataType::Bool => matches!(actual, DataType::True | DataType::False),
+        // Other parameters are checked when the argument is converted.
+        _ => return true,
+    };
+    if matches || (allow_null && actual == DataType::Null) {
+        return true;
+    }
+
+    if strict() {
+        // Integers are accepted for floats even in strict mode.
+        if let (DataType::Double, Some(long)) = (ty, zval.long()) {
+            #[allow(clippy::cast_precision_loss)]
+            zval.set_double(long as f64);
+            return true;
+        }
+        return false;
+    }
+
+    // SAFETY: The zval is valid, and the function only accepts scalar types.
+    unsafe { ext_php_rs_parse_arg_weak(zval, ty.as_u32(), arg_num) }
+}
+
+/// Throws the error the engine throws for an argument which does not match
+/// its parameter, with the same message, e.g. `foo(): Argument #2 ($bar) must
+/// be of type int, string given`:
+///
+/// * A `TypeError` if the value does not have the type of the parameter.
+/// * A `ValueError` if the value has the type of the parameter but could not be
+///   converted, e.g. a negative integer given for a `u32`.
+/// * An `ArgumentCountError` if no value was given.
+///
+/// Nothing is thrown if an exception is already pending, e.g. one thrown while
+/// the argument was coerced.
+///
+/// This is used by [`ArgParser::parse`] and by the functions generated by the
+/// `#[php_function]` and `#[php_impl]` macros.
+///
+/// # Parameters
+///
+/// * `arg` - The value of the argument, if one was given.
+/// * `expected` - The type declaration of the parameter.
+/// * `arg_num` - The position of the argument, starting at 1.
+pub fn throw_invalid_arg(arg: Option<&Zval>, expected: &TypeDecl, arg_num: u32) {
+    if ExecutorGlobals::has_exception() {
+        return;
+    }
+
+    let (error_ce, message) = match arg.map(Zval::dereference) {
+        None => (ce::argument_count_error(), "not passed".to_string()),
+        Some(zval) if has_type(zval, expected) => {
+            (ce::value_error(), format!("must be a valid {expected}"))
+        }
+        Some(zval) => {
+            // SAFETY: The engine returns a static string.
+            let given = unsafe { CStr::from_ptr(ext_php_rs_zval_value_name(zval)) };
+            (
+                ce::type_error(),
+                format!(
+                    "must be of type {expected}, {} given",
+                    given.to_string_lossy()
+                ),
+            )
+        }
+    };
+    let Ok(message) = CString::new(message) else {
+        return;
+    };
+    // SAFETY: The class entry and the message are valid, and the message is
+    // copied by the engine.
+    unsafe {
+        ext_php_rs_argument_error(
+            ptr::from_ref(error_ce).cast_mut(),
+            arg_num,
+            message.as_ptr(),
+        );
+    }
+}
+
+/// Returns whether a value has the given type, regardless of whether it could
+/// be converted to a Rust type.
+fn has_type(zval: &Zval, ty: &TypeDecl) -> bool {
+    let instance_of = |name: &str| {
+        zval.object()
+            .zip(ClassEntry::try_find(name))
+            .is_some_and(|(obj, ce)| obj.instance_of(ce))
+    };
+    match ty {
+        TypeDecl::Single(ty) => match *ty {
+            DataType::Bool | DataType::True | DataType::False => zval.is_bool(),
+            DataType::Object(Some(name)) => instance_of(name),
+            DataType::Object(None) => zval.is_object(),
+            DataType::Callable => zval.is_callable(),
+            DataType::Iterable => zval.is_array() || zval.is_traversable(),
+            DataType::Mixed => true,
+            ty => zval.get_type() == ty,
+        },
+        TypeDecl::Union(types) => types.iter().any(|ty| has_type(zval, ty)),
+        TypeDecl::Intersection(classes) => classes.iter().all(|name| instance_of(name)),
+    }
+}
+
+/// Returns whether the arguments of the function being executed were passed
+/// from code declaring `strict_types=1`.
+fn arg_uses_strict_types() -> bool {
+    // SAFETY: The executor globals are valid while a function is executed.
+    unsafe { ext_php_rs_executor_globals().as_ref() }
+        .and_then(|eg| unsafe { eg.current_execute_data.as_ref() })
+        .is_some_and(ExecuteData::arg_uses_strict_types)
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -0,0 +1,446 @@
+//! Types and functions used for accessing Rust objects as PHP arrays.
+//!
+//! Objects of classes with [`ArrayAccessHandlers`] are accessed with the array
+//! syntax through dimension object handlers, e.g. `$obj['key']`,
+//! `isset($obj['key'])` and `$obj[] = $value`, which call the
+//! [`PhpArrayAccess`] implementation of the class without calling `ArrayAccess`
+//! methods from PHP. Classes are exported with the `#[php(array_access)]`
+//! option of the [`php_class`](crate::php_class) macro.
+
+use std::{os::raw::c_int, ptr};
+
+use crate::{
+    args::Arg,
+    builders::FunctionHandler,
+    class::RegisteredClass,
+    error::php_error,
+    exception::PhpResult,
+    ffi::{
+        BP_VAR_IS, BP_VAR_RW, BP_VAR_UNSET, BP_VAR_W, ZEND_USER_FUNCTION,
+        ext_php_rs_executor_globals, ext_php_rs_zval_make_ref, zend_function,
+        zend_hash_str_find_ptr_lc, zend_std_has_dimension, zend_std_read_dimension,
+        zend_std_unset_dimension, zend_std_write_dimension,
+    },
+    flags::{DataType, ErrorType},
+    types::{ZendClassObject, ZendObject, Zval},
+    zend::ExecuteData,
+    zend_fastcall,
+};
+
+/// Implemented on classes whose objects are accessed as arrays from PHP.
+///
+/// The offsets and values are the zvals used by PHP, and can be of any type.
+/// Values which are stored by the object must be copied, e.g. with
+/// [`Zval::shallow_clone`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ext_php_rs::{array_access::PhpArrayAccess, exception::PhpResult, types::Zval};
+///
+/// #[derive(Default)]
+/// struct Bag(Vec<(String, Zval)>);
+///
+/// impl Bag {
+///     fn position(&self, offset: &Zval) -> Option<usize> {
+///         let key = offset.coerce_to_string()?;
+///         self.0.iter().position(|(k, _)| *k == key)
+///     }
+/// }
+///
+/// impl PhpArrayAccess for Bag {
+///     fn offset_exists(&self, offset: &Zval) -> PhpResult<bool> {
+///         Ok(self.position(offset).is_some())
+///     }
+///
+///     fn offset_get(&self, offset: &Zval) -> PhpResult<Zval> {
+///         Ok(self
+///             .position(offset)
+///             .map_or_else(Zval::new, |i| self.0[i].1.shallow_clone()))
+///     }
+///
+///     fn offset_set(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult {
+///         let key = offset
+///             .and_then(Zval::coerce_to_string)
+///             .unwrap_or_else(|| self.0.len().to_string());
+///         self.0.retain(|(k, _)| *k != key);
+///         self.0.push((key, value.shallow_clone()));
+///         Ok(())
+///     }
+///
+///     fn offset_unset(&mut self, offset: &Zval) -> PhpResult {
+///         if let Some(i) = self.position(offset) {
+///             self.0.remove(i);
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait PhpArrayAccess {
+    /// Returns whether a value exists at the offset, for
+    /// `isset($obj[$offset])`. `empty($obj[$offset])` also checks the value
+    /// returned by [`PhpArrayAccess::offset_get`].
+    ///
+    /// # Errors
+    ///
+    /// The error is thrown as an exception.
+    fn offset_exists(&self, offset: &Zval) -> PhpResult<bool>;
+
+    /// Returns the value at the offset, for `$obj[$offset]`.
+    ///
+    /// # Errors
+    ///
+    /// The error is thrown as an exception.
+    fn offset_get(&self, offset: &Zval) -> PhpResult<Zval>;
+
+    /// Sets the value at the offset, for `$obj[$offset] = $value`. The offset
+    /// is [`None`] when the value is appended, for `$obj[] = $value`.
+    ///
+    /// # Errors
+    ///
+    /// The error is thrown as an exception.
+    fn offset_set(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult;
+
+    /// Removes the value at the offset, for `unset($obj[$offset])`.
+    ///
+    /// # Errors
+    ///
+    /// The error is thrown as an exception.
+    fn offset_unset(&mut self, offset: &Zval) -> PhpResult;
+
+    /// Returns the value stored at the offset, which is modified in place by
+    /// nested writes such as `$obj['a'][] = 1` or `$obj['a']['b'] = 1`. The
+    /// offset is [`None`] for writes to a new element, e.g. `$obj[][] = 1`.
+    ///
+    /// The fetch tells how the engine uses the value. Missing offsets are
+    /// only given for [`DimensionFetch::Write`] and
+    /// [`DimensionFetch::ReadWrite`], after an "Undefined array key" warning
+    /// for the latter, as nested unsets of missing offsets do nothing.
+    ///
+    /// The value is turned into a PHP reference to be modified by the engine.
+    /// By default, nested writes modify a copy of the value returned by
+    /// [`PhpArrayAccess::offset_get`], and PHP emits an "Indirect modification
+    /// of overloaded element" notice.
+    ///
+    /// # Errors
+    ///
+    /// The error is thrown as an exception.
+    fn offset_get_mut(
+        &mut self,
+        offset: Option<&Zval>,
+        fetch: DimensionFetch,
+    ) -> PhpResult<Option<&mut Zval>> {
+        let _ = (offset, fetch);
+        Ok(None)
+    }
+}
+
+/// How the value returned by [`PhpArrayAccess::offset_get_mut`] is used by the
+/// engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimensionFetch {
+    /// The value is written to, e.g. `$obj['a']['b'] = 1`.
+    Write,
+    /// The value is read and written to, e.g. `$obj['a']['b'] .= 'c'`.
+    ReadWrite,
+    /// A nested value is removed, e.g. `unset($obj['a']['b'])`.
+    Unset,
+}
+
+impl DimensionFetch {
+    /// Returns the fetch of the `BP_VAR_*` type given to `read_dimension`.
+    fn from_type(type_: c_int) -> Option<Self> {
+        match u32::try_from(type_).ok()? {
+            BP_VAR_W => Some(Self::Write),
+            BP_VAR_RW => Some(Self::ReadWrite),
+            BP_VAR_UNSET => Some(Self::Unset),
+            _ => None,
+        }
+    }
+}
+
+/// Handler reading the value at an offset of an object.
+type ReadDimension =
+    unsafe extern "C" fn(*mut ZendObject, *mut Zval, c_int, *mut Zval) -> *mut Zval;
+
+/// Handler writing the value at an offset of an object.
+type WriteDimension = unsafe extern "C" fn(*mut ZendObject, *mut Zval, *mut Zval);
+
+/// Handler checking whether a value exists at an offset of an object.
+type HasDimension = unsafe extern "C" fn(*mut ZendObject, *mut Zval, c_int) -> c_int;
+
+/// Handler removing the value at an offset of an object.
+type UnsetDimension = unsafe extern "C" fn(*mut ZendObject, *mut Zval);
+
+/// The object handlers and the `ArrayAccess` methods of a class implementing
+/// [`PhpArrayAccess`].
+#[derive(Clone, Copy)]
+pub struct ArrayAccessHandlers {
+    pub(crate) read_dimension: ReadDimension,
+    pub(crate) write_dimension: WriteDimension,
+    pub(crate) has_dimension: HasDimension,
+    pub(crate) unset_dimension: UnsetDimension,
+    pub(crate) offset_exists: FunctionHandler,
+    pub(crate) offset_get: FunctionHandler,
+    pub(crate) offset_set: FunctionHandler,
+    pub(crate) offset_unset: FunctionHandler,
+}
+
+/// Returns the handlers of a class implementing [`PhpArrayAccess`].
+///
+/// The [`php_class`](crate::php_class) macro returns them from
+/// [`RegisteredClass::array_access`] when the `#[php(array_access)]` option is
+/// given.
+#[must_use]
+pub fn array_access<T: RegisteredClass + PhpArrayAccess>() -> ArrayAccessHandlers {
+    ArrayAccessHandlers {
+        read_dimension: read_dimension::<T>,
+        write_dimension: write_dimension::<T>,
+        has_dimension: has_dimension::<T>,
+        unset_dimension: unset_dimension::<T>,
+        offset_exists: offset_exists_method::<T>,
+        offset_get: offset_get_method::<T>,
+        offset_set: offset_set_method::<T>,
+        offset_unset: offset_unset_method::<T>,
+    }
+}
+
+/// Returns the Rust struct of an object.
+fn this<'a, T: RegisteredClass>(object: *mut ZendObject) -> PhpResult<&'a mut ZendClassObject<T>> {
+    unsafe { object.as_mut() }
+        .and_then(ZendClassObject::from_zend_obj_mut)
+        .ok_or_else(|| "Failed to retrieve reference to `this` object.".into())
+}
+
+/// Returns whether the object is an instance of a userland subclass
+/// overriding the given `ArrayAccess` method, in which case the standard
+/// handler is used to call the method.
+fn overrides<T: RegisteredClass>(object: *mut ZendObject, method: &str) -> bool {
+    let Some(ce) = (unsafe { object.as_ref() }).and_then(|object| unsafe { object.ce.as_ref() })
+    else {
+        return false;
+    };
+    if ptr::eq(ce, T::get_metadata().ce()) {
+        return false;
+    }
+    let func = unsafe {
+        zend_hash_str_find_ptr_lc(
+            &raw const ce.function_table,
+            method.as_ptr().cast(),
+            method.len(),
+        )
+    }
+    .cast::<zend_function>();
+    unsafe { func.as_ref() }
+        .is_some_and(|func| u32::from(unsafe { func.type_ }) == ZEND_USER_FUNCTION)
+}
+
+/// The `read_dimension` handler, for `$obj[$offset]` and nested writes.
+unsafe extern "C" fn read_dimension<T: RegisteredClass + PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    type_: c_int,
+    rv: *mut Zval,
+) -> *mut Zval {
+    if overrides::<T>(object, "offsetGet") {
+        return unsafe { zend_std_read_dimension(object, offset, type_, rv) };
+    }
+    let result = this::<T>(object).and_then(|this| {
+        let offset = unsafe { offset.as_ref() };
+        let null = Zval::new();
+        let uninitialized =
+            || unsafe { &raw mut (*ext_php_rs_executor_globals()).uninitialized_zval };
+        if let Some(fetch) = DimensionFetch::from_type(type_) {
+            let missing = match offset {
+                Some(offset) if fetch != DimensionFetch::Write => !this.offset_exists(offset)?,
+                _ => false,
+            };
+            if missing {
+                match fetch {
+                    DimensionFetch::Unset => return Ok(uninitialized()),
+                    _ => undefined_key(offset.unwrap_or(&null)),
+                }
+            }
+            // Returning a reference to the stored value lets the engine modify
+            // it in place.
+            if let Some(value) = this.offset_get_mut(offset, fetch)? {
+                unsafe { ext_php_rs_zval_make_ref(value) };
+                return Ok(ptr::from_mut(value));
+            }
+        } else if u32::try_from(type_) == Ok(BP_VAR_IS)
+            && !this.offset_exists(offset.unwrap_or(&null))?
+        {
+            return Ok(uninitialized());
+        }
+        let value = this.offset_get(offset.unwrap_or(&null))?;
+        // SAFETY: The engine passes an uninitialized zval for the result.
+        unsafe { ptr::write(rv, value) };
+        Ok(rv)
+    });
+    result.unwrap_or_else(|e| {
+        let _ = e.throw();
+        ptr::null_mut()
+    })
+}
+
+/// Emits the warning of reading a missing array key.
+fn undefined_key(offset: &Zval) {
+    let message = match (offset.long(), offset.str()) {
+        (Some(key), _) => format!("Undefined array key {key}"),
+        (_, Some(key)) => format!("Undefined array key \"{key}\""),
+        _ => "Undefined array key".to_string(),
+    };
+    php_error(&ErrorType::Warning, &message);
+}
+
+/// The `write_dimension` handler, for `$obj[$offset] = $value` and
+/// `$obj[] = $value`.
+unsafe extern "C" fn write_dimension<T: RegisteredClass + PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    value: *mut Zval,
+) {
+    if overrides::<T>(object, "offsetSet") {
+        return unsafe { zend_std_write_dimension(object, offset, value) };
+    }
+    let result = this::<T>(object).and_then(|this| {
+        let value = unsafe { value.as_ref() }.ok_or("No value given to the array access.")?;
+        this.offset_set(unsafe { offset.as_ref() }, value)
+    });
+    if let Err(e) = result {
+        let _ = e.throw();
+    }
+}
+
+/// The `has_dimension` handler, for `isset($obj[$offset])` and
+/// `empty($obj[$offset])`.
+unsafe extern "C" fn has_dimension<T: RegisteredClass + PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+    check_empty: c_int,
+) -> c_int {
+    if overrides::<T>(object, "offsetExists") {
+        return unsafe { zend_std_has_dimension(object, offset, check_empty) };
+    }
+    let result = this::<T>(object).and_then(|this| {
+        let offset = unsafe { offset.as_ref() }.ok_or("No offset given to the array access.")?;
+        Ok(this.offset_exists(offset)?
+            && (check_empty == 0 || this.offset_get(offset)?.coerce_to_bool()))
+    });
+    match result {
+        Ok(exists) => exists.into(),
+        Err(e) => {
+            let _ = e.throw();
+            0
+        }
+    }
+}
+
+/// The `unset_dimension` handler, for `unset($obj[$offset])`.
+unsafe extern "C" fn unset_dimension<T: RegisteredClass + PhpArrayAccess>(
+    object: *mut ZendObject,
+    offset: *mut Zval,
+) {
+    if overrides::<T>(object, "offsetUnset") {
+        return unsafe { zend_std_unset_dimension(object, offset) };
+    }
+    let result = this::<T>(object).and_then(|this| {
+        let offset = unsafe { offset.as_ref() }.ok_or("No offset given to the array access.")?;
+        this.offset_unset(offset)
+    });
+    if let Err(e) = result {
+        let _ = e.throw();
+    }
+}
+
+zend_fastcall! {
+    /// The `offsetExists()` method of classes implementing [`PhpArrayAccess`].
+    extern fn offset_exists_method<T: RegisteredClass + PhpArrayAccess>(
+        ex: &mut ExecuteData,
+        retval: &mut Zval,
+    ) {
+        let mut offset = Arg::new("offset", DataType::Mixed);
+        let (parser, this) = ex.parser_method::<T>();
+        if parser.arg(&mut offset).parse().is_err() {
+            return;
+        }
+        let result = this
+            .ok_or_else(|| "Failed to retrieve reference to `this` object.".into())
+            .and_then(|this| this.offset_exists(offset.zval().ok_or("No offset given.")?));
+        match result {
+            Ok(exists) => retval.set_bool(exists),
+            Err(e) => {
+                let _ = e.throw();
+            }
+        }
+    }
+}
+
+zend_fastcall! {
+    /// The `offsetGet()` method of classes implementing [`PhpArrayAccess`].
+    extern fn offset_get_method<T: RegisteredClass + PhpArrayAccess>(
+        ex: &mut ExecuteData,
+        retval: &mut Zval,
+    ) {
+        let mut offset = Arg::new("offset", DataType::Mixed);
+        let (parser, this) = ex.parser_method::<T>();
+        if parser.arg(&mut offset).parse().is_err() {
+            return;
+        }
+        let result = this
+            .ok_or_else(|| "Failed to retrieve reference to `this` object.".into())
+            .and_then(|this| this.offset_get(offset.zval().ok_or("No offset given.")?));
+        match result {
+            // Values made references by `offset_get_mut` are returned by value.
+            Ok(value) => *retval = value.dereference().shallow_clone(),
+            Err(e) => {
+                let _ = e.throw();
+            }
+        }
+    }
+}
+
+zend_fastcall! {
+    /// The `offsetSet()` method of classes implementing [`PhpArrayAccess`].
+    extern fn offset_set_method<T: RegisteredClass + PhpArrayAccess>(
+        ex: &mut ExecuteData,
+        _: &mut Zval,
+    ) {
+        let mut offset = Arg::new("offset", DataType::Mixed);
+        let mut value = Arg::new("value", DataType::Mixed);
+        let (parser, this) = ex.parser_method::<T>();
+        if parser.arg(&mut offset).arg(&mut value).parse().is_err() {
+            return;
+        }
+        let result = this
+            .ok_or_else(|| "Failed to retrieve reference to `this` object.".into())
+            .and_then(|this| {
+                // `$obj[] = $value` calls `offsetSet(null, $value)`.
+                let offset = offset.zval().map(|offset| &**offset).filter(|offset| !offset.is_null());
+                this.offset_set(offset, value.zval().ok_or("No value given.")?)
+            });
+        if let Err(e) = result {
+            let _ = e.throw();
+        }
+    }
+}
+
+zend_fastcall! {
+    /// The `offsetUnset()` method of classes implementing [`PhpArrayAccess`].
+    extern fn offset_unset_method<T: RegisteredClass + PhpArrayAccess>(
+        ex: &mut ExecuteData,
+        _: &mut Zval,
+    ) {
+        let mut offset = Arg::new("offset", DataType::Mixed);
+        let (parser, this) = ex.parser_method::<T>();
+        if parser.arg(&mut offset).parse().is_err() {
+            return;
+        }
+        let result = this
+            .ok_or_else(|| "Failed to retrieve reference to `this` object.".into())
+            .and_then(|this| this.offset_unset(offset.zval().ok_or("No offset given.")?));
+        if let Err(e) = result {
+            let _ = e.throw();
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Attributes attached to functions, parameters, classes, properties and
+//! class constants.
+//!
+//! Attributes are read by userland code through reflection, e.g.
+//! `ReflectionMethod::getAttributes()`. Attribute classes are declared with
+//! the [`php_attribute`](crate::php_attribute) macro, or with
+//! [`ClassBuilder::declare_attribute`](crate::builders::ClassBuilder::declare_attribute).
+
+use std::{
+    fmt::{self, Debug},
+    mem::ManuallyDrop,
+    ptr,
+};
+
+use crate::{
+    convert::IntoZvalDyn,
+    error::{Error, Result},
+    ffi::{
+        HashTable, ext_php_rs_add_attribute, ext_php_rs_attribute_set_arg,
+        ext_php_rs_class_constant_attributes, ext_php_rs_function_attributes,
+        ext_php_rs_property_attributes,
+    },
+    flags::DataType,
+    types::Zval,
+    zend::ClassEntry,
+};
+
+/// An attribute, with its arguments, to be attached to an item registered
+/// with PHP.
+///
+/// Arguments are stored in persistent memory, and can only be `null`,
+/// booleans, integers, floats and strings.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::{
+///     args::Arg,
+///     attribute::Attribute,
+///     builders::FunctionBuilder,
+///     flags::DataType,
+///     types::Zval,
+///     zend::ExecuteData,
+/// };
+///
+/// extern "C" fn login(_: &mut ExecuteData, _: &mut Zval) {}
+///
+/// let function = FunctionBuilder::new("login", login)
+///     .attribute(Attribute::new("Route").arg("/login").named_arg("method", "POST"))
+///     .arg(Arg::new("user", DataType::String))
+///     .arg(Arg::new("password", DataType::String).attribute(Attribute::new("SensitiveParameter")));
+/// ```
+pub struct Attribute {
+    name: String,
+    args: Vec<(Option<String>, Box<dyn IntoZvalDyn + Send>)>,
+}
+
+impl Attribute {
+    /// Creates an attribute without arguments.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The fully qualified name of the attribute class, e.g.
+    ///   `SensitiveParameter` or `App\Route`.
+    #[must_use]
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        let name: String = name.into();
+        Self {
+            name: name.trim_start_matches('\\').to_owned(),
+            args: vec![],
+        }
+    }
+
+    /// Adds a positional argument to the attribute.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value of the argument.
+    #[must_use]
+    pub fn arg(mut self, value: impl IntoZvalDyn + Send + 'static) -> Self {
+        self.args.push((None, Box::new(value)));
+        self
+    }
+
+    /// Adds a named argument to the attribute.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the parameter of the attribute constructor.
+    /// * `value` - The value of the argument.
+    #[must_use]
+    pub fn named_arg<T: Into<String>>(
+        mut self,
+        name: T,
+        value: impl IntoZvalDyn + Send + 'static,
+    ) -> Self {
+        self.args.push((Some(name.into()), Box::new(value)));
+        self
+    }
+
+    /// Returns the name of the attribute class.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Adds the attribute to a table of attributes.
+    ///
+    /// # Parameters
+    ///
+    /// * `attributes` - The table of attributes, which is allocated if null.
+    /// * `offset` - 0 for the item itself, or the position of the parameter
+    ///   plus one for parameters of functions.
+    fn add_to(&self, attributes: *mut *mut HashTable, offset: u32) -> Result<()> {
+        // Values are converted first, so that nothing is attached on error.
+        let values = self
+            .args
+            .iter()
+            .map(|(_, value)| {
+                let zv = value.as_zval(true)?;
+                match zv.get_type() {
+                    DataType::Null
+                    | DataType::False
+                    | DataType::True
+                    | DataType::Bool
+                    | DataType::Long
+                    | DataType::Double
+                    | DataType::String => Ok(ManuallyDrop::new(zv)),
+                    ty => Err(Error::NotPersistable(ty)),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let attr = unsafe {
+            ext_php_rs_add_attribute(
+                attributes,
+                self.name.as_ptr().cast(),
+                self.name.len(),
+                self.args.len().try_into()?,
+                offset,
+            )
+        };
+        for (i, ((name, _), mut value)) in self.args.iter().zip(values).enumerate() {
+            let (name_ptr, name_len) = name
+                .as_ref()
+                .map_or((ptr::null(), 0), |name| (name.as_ptr().cast(), name.len()));
+            // SAFETY: The attribute has been allocated with one slot per
+            // argument, and takes ownership of the value.
+            unsafe {
+                ext_php_rs_attribute_set_arg(
+                    attr,
+                    i.try_into()?,
+                    name_ptr,
+                    name_len,
+                    ptr::from_mut::<Zval>(&mut value),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Values are not formatted, as this would require the engine.
+        let args: Vec<_> = self.args.iter().map(|(name, _)| name).collect();
+        f.debug_struct("Attribute")
+            .field("name", &self.name)
+            .field("args", &args)
+            .finish()
+    }
+}
+
+/// Attaches attributes to a function or a method, and to its parameters. Must
+/// be called once the function has been registered.
+///
+/// # Parameters
+///
+/// * `function_table` - The function table of the class of the method, or
+///   [`None`] for a global function.
+/// * `name` - The name of the function.
+/// * `attributes` - The attributes, along with their offset: 0 for the
+///   function, or the position of the parameter plus one.
+///
+/// # Errors
+///
+/// * [`Error::UnknownAttributeTarget`] - If the function does not exist.
+/// * If the arguments of an attribute cannot be stored persistently.
+pub(crate) fn apply_to_function(
+    function_table: Option<&mut HashTable>,
+    name: &str,
+    attributes: &[(u32, Attribute)],
+) -> Result<()> {
+    if attributes.is_empty() {
+        return Ok(());
+    }
+    let table = function_table.map_or(ptr::null_mut(), ptr::from_mut);
+    let target = unsafe { ext_php_rs_function_attributes(table, name.as_ptr().cast(), name.len()) };
+    if target.is_null() {
+        return Err(Error::UnknownAttributeTarget(name.into()));
+    }
+    for (offset, attribute) in attributes {
+        attribute.add_to(target, *offset)?;
+    }
+    Ok(())
+}
+
+/// Attaches an attribute to a class.
+///
+/// # Parameters
+///
+/// * `ce` - The class.
+/// * `attribute` - The attribute to attach.
+///
+/// # Errors
+///
+/// * If the arguments of the attribute cannot be stored persistently.
+pub(crate) fn apply_to_class(ce: &mut ClassEntry, attribute: &Attribute) -> Result<()> {
+    attribute.add_to(&raw mut ce.attributes, 0)
+}
+
+/// Attaches an attribute to a property of a class. Must be called once the
+/// property has been declared.
+///
+/// # Parameters
+///
+/// * `ce` - The class declaring the property.
+/// * `name` - The name of the property.
+/// * `attribute` - The attribute to attach.
+///
+/// # Errors
+///
+/// * [`Error::UnknownAttributeTarget`] - If the property does not exist.
+/// * If the arguments of the attribute cannot be stored persistently.
+pub(crate) fn apply_to_property(
+    ce: &mut ClassEntry,
+    name: &str,
+    attribute: &Attribute,
+) -> Result<()> {
+    let target = unsafe { ext_php_rs_property_attributes(ce, name.as_ptr().cast(), name.len()) };
+    if target.is_null() {
+        return Err(Error::UnknownAttributeTarget(format!("${name}")));
+    }
+    attribute.add_to(target, 0)
+}
+
+/// Attaches an attribute to a class constant or an enum case. Must be called
+/// once the constant has been declared.
+///
+/// # Parameters
+///
+/// * `ce` - The class declaring the constant.
+/// * `name` - The name of the constant.
+/// * `attribute` - The attribute to attach.
+///
+/// # Errors
+///
+/// * [`Error::UnknownAttributeTarget`] - If the constant does not exist.
+/// * If the arguments of the attribute cannot be stored persistently.
+pub(crate) fn apply_to_class_constant(
+    ce: &mut ClassEntry,
+    name: &str,
+    attribute: &Attribute,
+) -> Result<()> {
+    let target =
+        unsafe { ext_php_rs_class_constant_attributes(ce, name.as_ptr().cast(), name.len()) };
+    if target.is_null() {
+        return Err(Error::UnknownAttributeTarget(name.into()));
+    }
+    attribute.add_to(target, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let attribute = Attribute::new("\\App\\Route")
+            .arg("/login")
+            .named_arg("method", "POST");
+        assert_eq!(attribute.name(), "App\\Route");
+        assert_eq!(
+            format!("{attribute:?}"),
+            r#"Attribute { name: "App\\Route", args: [None, Some("method")] }"#
+        );
+    }
+}
@@ -1,31 +1,89 @@
-use std::{ffi::CString, mem::MaybeUninit, ptr, rc::Rc};
+use std::{
+    ffi::{CStr, CString, c_char},
+    mem::{self, MaybeUninit},
+    ptr,
+    rc::Rc,
+};
 
 use crate::{
-    builders::FunctionBuilder,
+    args::Arg,
+    array_access::ArrayAccessHandlers,
+    attribute::{self, Attribute},
+    builders::{FunctionBuilder, FunctionHandler},
     class::{ClassEntryInfo, ConstructorMeta, ConstructorResult, RegisteredClass},
     convert::{IntoZval, IntoZvalDyn},
+    deprecation::Deprecation,
     describe::DocComments,
     error::{Error, Result},
     exception::PhpException,
+    ffi::ext_php_rs_declare_attribute_class,
     ffi::{
-        zend_declare_class_constant, zend_declare_property, zend_do_implement_interface,
-        zend_register_internal_class_ex, zend_register_internal_interface,
+        ZEND_INTERNAL_CLASS, zend_declare_class_constant, zend_declare_typed_property,
+        zend_do_implement_interface, zend_property_info, zend_register_internal_class_ex,
+        zend_register_internal_interface,
     },
-    flags::{ClassFlags, DataType, MethodFlags, PropertyFlags},
+    flags::{AttributeFlags, ClassFlags, DataType, MethodFlags, PropertyFlags},
+    internal::property::PropertyDescriptor,
+    iterator::{self, GetIterator},
     types::{ZendClassObject, ZendObject, ZendStr, Zval},
-    zend::{ClassEntry, ExecuteData, FunctionEntry},
+    zend::{ClassEntry, ExecuteData, FunctionEntry, ZendType, ce},
     zend_fastcall,
 };
 
-/// A constant entry: (name, `value_closure`, docs, `stub_value`)
+/// A constant entry: (name, `value_closure`, docs, `stub_value`, deprecation)
 type ConstantEntry = (
     String,
     Box<dyn FnOnce() -> Result<Zval>>,
     DocComments,
     String,
+    Option<Deprecation>,
 );
 type PropertyDefault = Option<Box<dyn FnOnce() -> Result<Zval>>>;
 
+/// A member of a class, other than a method, to which an attribute is
+/// attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Member {
+    Property,
+    Constant,
+}
+
+/// The deprecations and attributes of the members of a class, and the
+/// attributes of the class, applied once the class is registered.
+struct Annotations {
+    deprecated_methods: Vec<(String, Deprecation)>,
+    class: Vec<Attribute>,
+    methods: Vec<(String, Vec<(u32, Attribute)>)>,
+    members: Vec<(Member, String, Attribute)>,
+    flags: Option<AttributeFlags>,
+}
+
+impl Annotations {
+    /// Applies the deprecations and attaches the attributes to the registered
+    /// class, and declares it as an attribute class if it is one.
+    fn apply(self, class: &mut ClassEntry) -> Result<()> {
+        for (name, deprecation) in self.deprecated_methods {
+            deprecation.apply_to_function(Some(&mut class.function_table), &name);
+        }
+        for (name, attributes) in self.methods {
+            attribute::apply_to_function(Some(&mut class.function_table), &name, &attributes)?;
+        }
+        for attribute in &self.class {
+            attribute::apply_to_class(class, attribute)?;
+        }
+        for (member, name, attribute) in self.members {
+            match member {
+                Member::Property => attribute::apply_to_property(class, &name, &attribute)?,
+                Member::Constant => attribute::apply_to_class_constant(class, &name, &attribute)?,
+            }
+        }
+        if let Some(flags) = self.flags {
+            unsafe { ext_php_rs_declare_attribute_class(class, flags.bits()) };
+        }
+        Ok(())
+    }
+}
+
 /// Metadata for a class property to be registered with PHP.
 pub struct ClassProperty {
     /// Name of the property.
@@ -44,6 +102,70 @@ pub struct ClassProperty {
     pub readonly: bool,
     /// PHP stub representation of the default value (e.g. `"null"`, `"42"`).
     pub default_stub: Option<String>,
+    /// Hooks of the property. A property with hooks is declared as a virtual
+    /// property, whose accesses call the hooks (PHP 8.4+). Hooks are ignored
+    /// on earlier versions.
+    pub hooks: Option<PropertyHooks>,
+}
+
+impl ClassProperty {
+    /// Creates the declaration of a property of a Rust class, which is
+    /// accessed through the object handlers of the class.
+    ///
+    /// # Parameters
+    ///
+    /// * `desc` - The descriptor of the property.
+    pub(crate) fn from_descriptor<T>(desc: &PropertyDescriptor<T>) -> Self {
+        let default = desc.default.map(|value| {
+            Box::new(move || value.as_zval(true)) as Box<dyn FnOnce() -> Result<Zval>>
+        });
+        Self {
+            name: desc.name.into(),
+            flags: desc.flags,
+            default,
+            docs: desc.docs,
+            ty: Some(desc.ty),
+            nullable: desc.nullable,
+            readonly: desc.readonly,
+            default_stub: desc
+                .default
+                .map(IntoZvalDyn::stub_value)
+                .or_else(|| desc.nullable.then(|| "null".into())),
+            hooks: None,
+        }
+    }
+
+    /// Creates the declaration of a method property of a Rust class, whose
+    /// hooks call the getter and the setter of the property.
+    ///
+    /// # Parameters
+    ///
+    /// * `desc` - The descriptor of the property.
+    #[cfg(php84)]
+    pub(crate) fn hooked<T: RegisteredClass>(desc: &PropertyDescriptor<T>) -> Self {
+        use crate::internal::property::{get_hook, set_hook};
+
+        Self {
+            default_stub: None,
+            readonly: false,
+            hooks: Some(PropertyHooks {
+                get: desc.get.map(|_| get_hook::<T> as FunctionHandler),
+                set: desc.set.map(|_| set_hook::<T> as FunctionHandler),
+            }),
+            ..Self::from_descriptor(desc)
+        }
+    }
+}
+
+/// The native handlers of the `get` and `set` hooks of a property.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PropertyHooks {
+    /// Handler of the `get` hook, called with the object and returning the
+    /// value of the property. The property is write-only without it.
+    pub get: Option<FunctionHandler>,
+    /// Handler of the `set` hook, called with the object and the assigned
+    /// value. The property is read-only without it.
+    pub set: Option<FunctionHandler>,
 }
 
 /// Builder for registering a class in PHP.
@@ -53,12 +175,20 @@ pub struct ClassBuilder {
     ce: ClassEntry,
     pub(crate) extends: Option<ClassEntryInfo>,
     pub(crate) interfaces: Vec<ClassEntryInfo>,
+    pub(crate) traits: Vec<ClassEntryInfo>,
     pub(crate) methods: Vec<(FunctionBuilder<'static>, MethodFlags)>,
     object_override: Option<unsafe extern "C" fn(class_type: *mut ClassEntry) -> *mut ZendObject>,
+    get_iterator: Option<GetIterator>,
     pub(crate) properties: Vec<ClassProperty>,
     pub(crate) constants: Vec<ConstantEntry>,
     register: Option<fn(&'static mut ClassEntry)>,
     pub(crate) docs: DocComments,
+    pub(crate) deprecation: Option<Deprecation>,
+    pub(crate) templates: Vec<(String, DataType)>,
+    attributes: Vec<Attribute>,
+    method_attributes: Vec<(String, Attribute)>,
+    member_attributes: Vec<(Member, String, Attribute)>,
+    attribute_flags: Option<AttributeFlags>,
 }
 
 impl ClassBuilder {
@@ -76,12 +206,20 @@ impl ClassBuilder {
             ce: unsafe { MaybeUninit::zeroed().assume_init() },
             extends: None,
             interfaces: vec![],
+            traits: vec![],
             methods: vec![],
             object_override: None,
+            get_iterator: None,
             properties: vec![],
             constants: vec![],
             register: None,
             docs: &[],
+            deprecation: None,
+            templates: vec![],
+            attributes: vec![],
+            method_attributes: vec![],
+            member_attributes: vec![],
+            attribute_flags: None,
         }
     }
 
@@ -115,6 +253,25 @@ impl ClassBuilder {
         self
     }
 
+    /// Uses a trait registered by an extension in the class, e.g. one
+    /// declared with the [`php_trait`](crate::php_trait) macro.
+    ///
+    /// The methods of the trait are added to the class, unless the class
+    /// declares a method with the same name.
+    ///
+    /// # Parameters
+    ///
+    /// * `trait_` - Trait to use in the class.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the given class entry `trait_` is not a trait, or is not
+    /// registered by an extension.
+    pub fn use_trait(mut self, trait_: ClassEntryInfo) -> Self {
+        self.traits.push(trait_);
+        self
+    }
+
     /// Adds a method to the class.
     ///
     /// # Parameters
@@ -161,7 +318,7 @@ impl ClassBuilder {
         let zval = value.into_zval(true)?;
         let stub = crate::convert::zval_to_stub(&zval);
         self.constants
-            .push((name.into(), Box::new(|| Ok(zval)), docs, stub));
+            .push((name.into(), Box::new(|| Ok(zval)), docs, stub, None));
         Ok(self)
     }
 
@@ -187,16 +344,103 @@ impl ClassBuilder {
         docs: DocComments,
     ) -> Result<Self> {
         let stub = value.stub_value();
+        let deprecation = value.deprecation();
         let value = Rc::new(value);
         self.constants.push((
             name.into(),
             Box::new(move || value.as_zval(true)),
             docs,
             stub,
+            deprecation,
         ));
         Ok(self)
     }
 
+    /// Marks the class as deprecated in the generated stubs.
+    ///
+    /// PHP cannot deprecate classes, so using the class does not emit a
+    /// diagnostic.
+    ///
+    /// # Parameters
+    ///
+    /// * `deprecation` - The reason of the deprecation and the version since
+    ///   which the class is deprecated.
+    pub fn deprecated(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = Some(deprecation);
+        self
+    }
+
+    /// Adds a template parameter to the class, rendered as a `@template` tag
+    /// in the generated stubs. Used by generic classes, whose instantiations
+    /// are each registered as a separate class.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the template parameter, e.g. `T`.
+    /// * `ty` - The type the parameter is instantiated with, or
+    ///   [`DataType::Mixed`] if it has no PHP equivalent.
+    pub fn template<T: Into<String>>(mut self, name: T, ty: DataType) -> Self {
+        self.templates.push((name.into(), ty));
+        self
+    }
+
+    /// Attaches an attribute to the class.
+    ///
+    /// # Parameters
+    ///
+    /// * `attribute` - The attribute to attach.
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Attaches an attribute to a method of the class, e.g. `#[\Override]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the method, which must be added to the class.
+    /// * `attribute` - The attribute to attach.
+    pub fn method_attribute<T: Into<String>>(mut self, name: T, attribute: Attribute) -> Self {
+        self.method_attributes.push((name.into(), attribute));
+        self
+    }
+
+    /// Attaches an attribute to a property of the class.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the property, which must be added to the class.
+    /// * `attribute` - The attribute to attach.
+    pub fn property_attribute<T: Into<String>>(mut self, name: T, attribute: Attribute) -> Self {
+        self.member_attributes
+            .push((Member::Property, name.into(), attribute));
+        self
+    }
+
+    /// Attaches an attribute to a constant of the class.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the constant, which must be added to the class.
+    /// * `attribute` - The attribute to attach.
+    pub fn constant_attribute<T: Into<String>>(mut self, name: T, attribute: Attribute) -> Self {
+        self.member_attributes
+            .push((Member::Constant, name.into(), attribute));
+        self
+    }
+
+    /// Declares the class as an attribute class, which userland code can apply
+    /// with `#[ClassName(...)]` and instantiate through reflection.
+    ///
+    /// # Parameters
+    ///
+    /// * `flags` - The items the attribute can be applied to, and whether it
+    ///   can be applied several times. See [`AttributeFlags`].
+    pub fn declare_attribute(mut self, flags: AttributeFlags) -> Self {
+        self.attribute_flags = Some(flags);
+        self
+    }
+
     /// Sets the flags for the class.
     ///
     /// # Parameters
@@ -207,6 +451,74 @@ impl ClassBuilder {
         self
     }
 
+    /// Makes the objects of the class traversable with `foreach`, which
+    /// creates their iterators with the given handler rather than calling PHP
+    /// methods.
+    ///
+    /// The class implements `IteratorAggregate`, with a `getIterator()` method
+    /// returning an `InternalIterator` over the object.
+    ///
+    /// # Parameters
+    ///
+    /// * `handler` - The handler creating the iterators, e.g.
+    ///   [`iterator::get_iterator`] for classes implementing [`Iterator`].
+    pub fn iterator(mut self, handler: GetIterator) -> Self {
+        self.get_iterator = Some(handler);
+        self.interfaces.push((ce::aggregate, "\\IteratorAggregate"));
+        self.method(
+            FunctionBuilder::new("getIterator", iterator::get_iterator_method).returns(
+                DataType::Object(Some("Iterator")),
+                false,
+                false,
+            ),
+            MethodFlags::Public,
+        )
+    }
+
+    /// Makes the class implement `ArrayAccess`, with the `offsetExists()`,
+    /// `offsetGet()`, `offsetSet()` and `offsetUnset()` methods of the given
+    /// handlers.
+    ///
+    /// The objects of the class are only accessed with the array syntax
+    /// through the handlers if the class also uses them as object handlers,
+    /// which [`ClassBuilder::object_override`] does for classes returning them
+    /// from [`RegisteredClass::array_access`].
+    ///
+    /// # Parameters
+    ///
+    /// * `handlers` - The handlers of the class, e.g.
+    ///   [`array_access`](crate::array_access::array_access) for classes
+    ///   implementing [`PhpArrayAccess`](crate::array_access::PhpArrayAccess).
+    pub fn array_access(mut self, handlers: ArrayAccessHandlers) -> Self {
+        let offset = || Arg::new("offset", DataType::Mixed);
+        self.interfaces.push((ce::arrayaccess, "\\ArrayAccess"));
+        self.method(
+            FunctionBuilder::new("offsetExists", handlers.offset_exists)
+                .arg(offset())
+                .returns(DataType::Bool, false, false),
+            MethodFlags::Public,
+        )
+        .method(
+            FunctionBuilder::new("offsetGet", handlers.offset_get)
+                .arg(offset())
+                .returns(DataType::Mixed, false, true),
+            MethodFlags::Public,
+        )
+        .method(
+            FunctionBuilder::new("offsetSet", handlers.offset_set)
+                .arg(offset())
+                .arg(Arg::new("value", DataType::Mixed))
+                .returns(DataType::Void, false, false),
+            MethodFlags::Public,
+        )
+        .method(
+            FunctionBuilder::new("offsetUnset", handlers.offset_unset)
+                .arg(offset())
+                .returns(DataType::Void, false, false),
+            MethodFlags::Public,
+        )
+    }
+
     /// Overrides the creation of the Zend object which will represent an
     /// instance of this class.
     ///
@@ -342,6 +654,8 @@ impl ClassBuilder {
     /// * [`Error::InvalidPointer`] - If the class could not be registered.
     /// * [`Error::InvalidCString`] - If the class name is not a valid C string.
     /// * [`Error::IntegerOverflow`] - If the property flags are not valid.
+    /// * [`Error::UnknownAttributeTarget`] - If an attribute was attached to a
+    ///   member which does not exist.
     /// * If a method or property could not be built.
     ///
     /// # Panics
@@ -350,6 +664,13 @@ impl ClassBuilder {
     pub fn register(mut self) -> Result<()> {
         self.ce.name = ZendStr::new_interned(&self.name, true).into_raw();
 
+        // Classes defining both `__serialize()` and `__unserialize()` control how
+        // their associated object is serialized.
+        let has_method = |name: &str| self.methods.iter().any(|(m, _)| m.name == name);
+        let serializable = has_method("__serialize") && has_method("__unserialize");
+
+        let annotations = self.take_annotations()?;
+
         let mut methods = self
             .methods
             .into_iter()
@@ -361,6 +682,10 @@ impl ClassBuilder {
             })
             .collect::<Result<Vec<_>>>()?;
 
+        for (trait_, _) in &self.traits {
+            Self::add_trait_methods(trait_(), &mut methods);
+        }
+
         methods.push(FunctionEntry::end());
         let func = Box::into_raw(methods.into_boxed_slice()) as *const FunctionEntry;
         self.ce.info.internal.builtin_functions = func;
@@ -385,8 +710,9 @@ impl ClassBuilder {
             }
         };
 
-        // disable serialization if the class has an associated object
-        if self.object_override.is_some() {
+        // disable serialization if the class has an associated object, unless the
+        // class handles it itself
+        if self.object_override.is_some() && !serializable {
             cfg_if::cfg_if! {
                 if #[cfg(php81)] {
                     class.ce_flags |= ClassFlags::NotSerializable.bits();
@@ -407,23 +733,17 @@ impl ClassBuilder {
             unsafe { zend_do_implement_interface(class, ptr::from_ref(interface).cast_mut()) };
         }
 
+        // Set once `IteratorAggregate` is implemented, which would otherwise
+        // replace the handler with one calling `getIterator()`.
+        if let Some(get_iterator) = self.get_iterator {
+            class.get_iterator = Some(get_iterator);
+        }
+
         for prop in self.properties {
-            let mut default_zval = match prop.default {
-                Some(f) => f()?,
-                None => Zval::new(),
-            };
-            unsafe {
-                zend_declare_property(
-                    class,
-                    CString::new(prop.name.as_str())?.as_ptr(),
-                    prop.name.len() as _,
-                    &raw mut default_zval,
-                    prop.flags.bits().try_into()?,
-                );
-            }
+            Self::declare_property(class, prop)?;
         }
 
-        for (name, value, _, _) in self.constants {
+        for (name, value, _, _, deprecation) in self.constants {
             let value = Box::into_raw(Box::new(value()?));
             unsafe {
                 zend_declare_class_constant(
@@ -433,8 +753,13 @@ impl ClassBuilder {
                     value,
                 );
             };
+            if let Some(deprecation) = deprecation {
+                deprecation.apply_to_class_constant(class, &name);
+            }
         }
 
+        annotations.apply(class)?;
+
         if let Some(object_override) = self.object_override {
             class.__bindgen_anon_2.create_object = Some(object_override);
         }
@@ -447,6 +772,281 @@ impl ClassBuilder {
 
         Ok(())
     }
+
+    /// Declares a property of the registered class. Properties with a type
+    /// are declared as typed properties, whose assignments are checked by the
+    /// engine. Typed properties without a default value, which do not accept
+    /// null, are uninitialized by default.
+    ///
+    /// # Parameters
+    ///
+    /// * `class` - The registered class.
+    /// * `prop` - The property to declare.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidPointer`] - If the property could not be declared.
+    /// * [`Error::IntegerOverflow`] - If the property flags are not valid.
+    /// * If the default value of the property could not be converted.
+    fn declare_property(class: &mut ClassEntry, mut prop: ClassProperty) -> Result<()> {
+        #[cfg(php84)]
+        if let Some(hooks) = prop.hooks {
+            return Self::declare_hooked_property(class, &prop, hooks);
+        }
+
+        let ty = Self::property_type(&prop);
+        let mut default = match prop.default.take() {
+            Some(f) => f()?,
+            None if ty.type_mask != 0 && !prop.nullable => Zval::undef(),
+            None => Zval::new(),
+        };
+        Self::declare_typed_property(class, &prop, ty, &mut default, prop.flags)?;
+        Ok(())
+    }
+
+    /// Declares a virtual property, whose accesses call the given hooks.
+    ///
+    /// # Parameters
+    ///
+    /// * `class` - The registered class.
+    /// * `prop` - The property to declare.
+    /// * `hooks` - The handlers of the hooks of the property.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidPointer`] - If the property could not be declared.
+    /// * [`Error::IntegerOverflow`] - If the property flags are not valid.
+    #[cfg(php84)]
+    fn declare_hooked_property(
+        class: &mut ClassEntry,
+        prop: &ClassProperty,
+        hooks: PropertyHooks,
+    ) -> Result<()> {
+        use crate::ffi::{
+            zend_property_hook_kind_ZEND_PROPERTY_HOOK_GET,
+            zend_property_hook_kind_ZEND_PROPERTY_HOOK_SET,
+        };
+
+        // Virtual properties have no default value.
+        let info = Self::declare_typed_property(
+            class,
+            prop,
+            Self::property_type(prop),
+            &mut Zval::undef(),
+            prop.flags | PropertyFlags::Virtual,
+        )?;
+
+        let mut functions = [ptr::null_mut(); 2];
+        for (handler, kind) in [
+            (hooks.get, zend_property_hook_kind_ZEND_PROPERTY_HOOK_GET),
+            (hooks.set, zend_property_hook_kind_ZEND_PROPERTY_HOOK_SET),
+        ] {
+            if let Some(handler) = handler {
+                let function = Self::hook_function(class, info, &prop.name, handler, kind);
+                functions[kind as usize] = Box::into_raw(Box::new(function));
+            }
+        }
+        info.hooks = Box::into_raw(Box::new(functions)).cast();
+
+        class.num_hooked_props += 1;
+        // Hooks are guarded against recursion by the property guards.
+        class.ce_flags |= ClassFlags::UseGuards.bits();
+        Ok(())
+    }
+
+    /// Returns the declared type of a property, which is empty for properties
+    /// without a type.
+    fn property_type(prop: &ClassProperty) -> ZendType {
+        prop.ty.map_or_else(
+            || ZendType::empty(false, false),
+            |ty| ZendType::property_type(ty, prop.nullable),
+        )
+    }
+
+    /// Declares a property with the given type, default value and flags.
+    ///
+    /// # Parameters
+    ///
+    /// * `class` - The registered class.
+    /// * `prop` - The property to declare.
+    /// * `ty` - The type of the property.
+    /// * `default` - The default value of the property, which is undefined for
+    ///   uninitialized properties.
+    /// * `flags` - The flags of the property.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidPointer`] - If the property could not be declared.
+    /// * [`Error::IntegerOverflow`] - If the property flags are not valid.
+    fn declare_typed_property(
+        class: &mut ClassEntry,
+        prop: &ClassProperty,
+        ty: ZendType,
+        default: &mut Zval,
+        flags: PropertyFlags,
+    ) -> Result<&'static mut zend_property_info> {
+        // The property info lives as long as the class.
+        unsafe {
+            zend_declare_typed_property(
+                class,
+                ZendStr::new_interned(&prop.name, true).into_raw(),
+                default,
+                flags.bits().try_into()?,
+                ptr::null_mut(),
+                ty,
+            )
+            .as_mut()
+            .ok_or(Error::InvalidPointer)
+        }
+    }
+
+    /// Creates the internal function of a property hook, as compiled hooks are
+    /// named `$property::get` and `$property::set`, and take the assigned value
+    /// as their only argument.
+    ///
+    /// # Parameters
+    ///
+    /// * `class` - The class declaring the property.
+    /// * `info` - The declared property.
+    /// * `name` - The name of the property.
+    /// * `handler` - The handler of the hook.
+    /// * `kind` - Whether the hook is the `get` or the `set` hook.
+    #[cfg(php84)]
+    fn hook_function(
+        class: &mut ClassEntry,
+        info: &crate::ffi::zend_property_info,
+        name: &str,
+        handler: FunctionHandler,
+        kind: crate::ffi::zend_property_hook_kind,
+    ) -> crate::ffi::zend_function {
+        use crate::{
+            args::ArgInfo,
+            builders::function::FunctionPointerHandler,
+            ffi::{
+                ZEND_INTERNAL_FUNCTION, zend_function, zend_internal_function,
+                zend_property_hook_kind_ZEND_PROPERTY_HOOK_SET,
+            },
+            zend::ZendType,
+        };
+
+        let is_set = kind == zend_property_hook_kind_ZEND_PROPERTY_HOOK_SET;
+        let num_args = u32::from(is_set);
+        // The first argument is used as the `zend_internal_function_info` of the
+        // function, holding the number of required arguments.
+        let arg_info = [
+            ArgInfo {
+                name: ptr::without_provenance(num_args as usize),
+                type_: ZendType::empty(false, false),
+                default_value: ptr::null(),
+            },
+            ArgInfo {
+                name: c"value".as_ptr(),
+                type_: ZendType::empty(false, false),
+                default_value: ptr::null(),
+            },
+        ];
+        let arg_info = Box::into_raw(Box::new(arg_info)).cast::<ArgInfo>();
+
+        // SAFETY: All fields of the function are either pointers, numbers or
+        // optional function pointers, for which zeroes are valid.
+        let mut function: zend_internal_function = unsafe { mem::zeroed() };
+        function.type_ = ZEND_INTERNAL_FUNCTION
+            .try_into()
+            .expect("Invalid function type");
+        function.fn_flags = MethodFlags::Public.bits();
+        function.function_name = ZendStr::new_interned(
+            format!("${name}::{}", if is_set { "set" } else { "get" }),
+            true,
+        )
+        .into_raw();
+        function.scope = class;
+        function.num_args = num_args;
+        function.required_num_args = num_args;
+        function.arg_info = unsafe { arg_info.add(1) };
+        function.prop_info = info;
+        // SAFETY: See `FunctionBuilder::new`.
+        function.handler =
+            Some(unsafe { mem::transmute::<FunctionHandler, FunctionPointerHandler>(handler) });
+        zend_function {
+            internal_function: function,
+        }
+    }
+
+    /// Adds the methods of a trait to the function entries of a class, unless
+    /// the class declares a method with the same name.
+    ///
+    /// The function entries of the trait are registered again for the class, as
+    /// the engine only binds traits to userland classes.
+    ///
+    /// # Parameters
+    ///
+    /// * `trait_` - The trait used by the class.
+    /// * `methods` - The function entries of the class.
+    ///
+    /// # Panics
+    ///
+    /// If the class entry is not a trait registered by an extension.
+    fn add_trait_methods(trait_: &ClassEntry, methods: &mut Vec<FunctionEntry>) {
+        assert!(trait_.is_trait(), "Given class entry was not a trait.");
+        assert!(
+            c_char::try_from(ZEND_INTERNAL_CLASS).is_ok_and(|ty| ty == trait_.type_),
+            "Given trait was not registered by an extension."
+        );
+
+        let mut entry = unsafe { trait_.info.internal.builtin_functions };
+        while let Some(func) = unsafe { entry.as_ref() }
+            && !func.fname.is_null()
+        {
+            let name = unsafe { CStr::from_ptr(func.fname) };
+            let declared = methods.iter().any(|method| {
+                unsafe { CStr::from_ptr(method.fname) }
+                    .to_bytes()
+                    .eq_ignore_ascii_case(name.to_bytes())
+            });
+            if !declared {
+                methods.push(*func);
+            }
+            entry = unsafe { entry.add(1) };
+        }
+    }
+
+    /// Takes the deprecations of the methods, and the attributes of the class,
+    /// of its methods and of their parameters, and of its other members, to be
+    /// applied once the class is registered.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnknownAttributeTarget`] - If an attribute was attached to a
+    ///   method or a parameter which does not exist.
+    fn take_annotations(&mut self) -> Result<Annotations> {
+        let deprecated_methods = self
+            .methods
+            .iter()
+            .filter_map(|(m, _)| Some((m.name.clone(), m.deprecation?)))
+            .collect();
+        for (name, attribute) in mem::take(&mut self.method_attributes) {
+            let (method, _) = self
+                .methods
+                .iter_mut()
+                .find(|(m, _)| m.name.eq_ignore_ascii_case(&name))
+                .ok_or_else(|| Error::UnknownAttributeTarget(format!("{}::{name}()", self.name)))?;
+            method.attributes.push(attribute);
+        }
+        let mut methods = vec![];
+        for (method, _) in &mut self.methods {
+            let attributes = method.take_attributes()?;
+            if !attributes.is_empty() {
+                methods.push((method.name.clone(), attributes));
+            }
+        }
+        Ok(Annotations {
+            deprecated_methods,
+            class: mem::take(&mut self.attributes),
+            methods,
+            members: mem::take(&mut self.member_attributes),
+            flags: self.attribute_flags,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -502,6 +1102,7 @@ mod tests {
             nullable: false,
             readonly: false,
             default_stub: None,
+            hooks: None,
         });
         assert_eq!(class.properties.len(), 1);
         assert_eq!(class.properties[0].name, "bar");
@@ -511,6 +1112,37 @@ mod tests {
         assert_eq!(class.properties[0].ty, Some(DataType::String));
     }
 
+    #[test]
+    fn test_property_from_descriptor() {
+        static ANSWER: i64 = 42;
+        let mut desc = PropertyDescriptor::<()> {
+            name: "answer",
+            mangled_name: "answer",
+            get: None,
+            set: None,
+            flags: PropertyFlags::Public,
+            docs: &[],
+            ty: DataType::Long,
+            nullable: false,
+            readonly: false,
+            default: Some(&ANSWER),
+        };
+        let prop = ClassProperty::from_descriptor(&desc);
+        assert!(prop.default.is_some());
+        assert_eq!(prop.default_stub.as_deref(), Some("42"));
+        assert_eq!(prop.ty, Some(DataType::Long));
+
+        desc.default = None;
+        assert!(ClassProperty::from_descriptor(&desc).default_stub.is_none());
+        desc.nullable = true;
+        assert_eq!(
+            ClassProperty::from_descriptor(&desc)
+                .default_stub
+                .as_deref(),
+            Some("null")
+        );
+    }
+
     #[test]
     #[cfg(feature = "embed")]
     fn test_constant() {
@@ -559,5 +1191,18 @@ mod tests {
         assert_eq!(class.docs, &["Doc 1"] as DocComments);
     }
 
+    #[test]
+    fn test_attributes() {
+        let class = ClassBuilder::new("Foo")
+            .attribute(Attribute::new("Bar"))
+            .method_attribute("baz", Attribute::new("Override"))
+            .property_attribute("qux", Attribute::new("Bar"))
+            .declare_attribute(AttributeFlags::Method);
+        assert_eq!(class.attributes.len(), 1);
+        assert_eq!(class.method_attributes[0].0, "baz");
+        assert_eq!(class.member_attributes[0].0, Member::Property);
+        assert_eq!(class.attribute_flags, Some(AttributeFlags::Method));
+    }
+
     // TODO: Test the register function
 }
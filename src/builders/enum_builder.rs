@@ -91,6 +91,12 @@ impl EnumBuilder {
     /// If the enum could not be registered, e.g. due to an invalid name or
     /// data type.
     pub fn register(self) -> Result<()> {
+        let deprecated_methods: Vec<_> = self
+            .methods
+            .iter()
+            .filter_map(|(m, _)| Some((m.name.clone(), m.deprecation?)))
+            .collect();
+
         let mut methods = self
             .methods
             .into_iter()
@@ -120,6 +126,13 @@ impl EnumBuilder {
             unsafe {
                 zend_enum_add_case(class, name.into_raw(), value);
             }
+            if let Some(deprecation) = case.deprecation {
+                deprecation.apply_to_class_constant(unsafe { &mut *class }, case.name);
+            }
+        }
+
+        for (name, deprecation) in deprecated_methods {
+            deprecation.apply_to_function(Some(unsafe { &mut (*class).function_table }), &name);
         }
 
         if let Some(register) = self.register {
@@ -151,16 +164,19 @@ mod tests {
         name: "Variant1",
         discriminant: None,
         docs: &[],
+        deprecation: None,
     };
     const case2: EnumCase = EnumCase {
         name: "Variant2",
         discriminant: Some(Discriminant::Int(42)),
         docs: &[],
+        deprecation: None,
     };
     const case3: EnumCase = EnumCase {
         name: "Variant3",
         discriminant: Some(Discriminant::String("foo")),
         docs: &[],
+        deprecation: None,
     };
 
     #[test]
@@ -1,9 +1,11 @@
 use crate::{
     args::{Arg, ArgInfo},
+    attribute::Attribute,
+    deprecation::Deprecation,
     describe::DocComments,
     error::{Error, Result},
     flags::{DataType, MethodFlags},
-    types::Zval,
+    types::{TypeDecl, Zval},
     zend::{ExecuteData, FunctionEntry, ZendType},
 };
 use std::{ffi::CString, mem, ptr};
@@ -17,9 +19,10 @@ pub type FunctionHandler =
 
 /// Function representation in Rust using pointers.
 #[cfg(not(windows))]
-type FunctionPointerHandler = extern "C" fn(execute_data: *mut ExecuteData, retval: *mut Zval);
+pub(crate) type FunctionPointerHandler =
+    extern "C" fn(execute_data: *mut ExecuteData, retval: *mut Zval);
 #[cfg(windows)]
-type FunctionPointerHandler =
+pub(crate) type FunctionPointerHandler =
     extern "vectorcall" fn(execute_data: *mut ExecuteData, retval: *mut Zval);
 
 /// Builder for registering a function in PHP.
@@ -33,7 +36,12 @@ pub struct FunctionBuilder<'a> {
     pub(crate) retval: Option<DataType>,
     ret_as_ref: bool,
     pub(crate) ret_as_null: bool,
+    pub(crate) ret_doc_type: Option<String>,
+    ret_type_decl: Option<TypeDecl>,
     pub(crate) docs: DocComments,
+    pub(crate) deprecation: Option<Deprecation>,
+    pub(crate) attributes: Vec<Attribute>,
+    arg_attributes: Vec<(String, Attribute)>,
 }
 
 impl<'a> FunctionBuilder<'a> {
@@ -68,7 +76,12 @@ impl<'a> FunctionBuilder<'a> {
             retval: None,
             ret_as_ref: false,
             ret_as_null: false,
+            ret_doc_type: None,
+            ret_type_decl: None,
             docs: &[],
+            deprecation: None,
+            attributes: vec![],
+            arg_attributes: vec![],
         }
     }
 
@@ -97,7 +110,12 @@ impl<'a> FunctionBuilder<'a> {
             retval: None,
             ret_as_ref: false,
             ret_as_null: false,
+            ret_doc_type: None,
+            ret_type_decl: None,
             docs: &[],
+            deprecation: None,
+            attributes: vec![],
+            arg_attributes: vec![],
         }
     }
 
@@ -142,6 +160,39 @@ impl<'a> FunctionBuilder<'a> {
         self
     }
 
+    /// Sets the `PHPDoc` type of the return value, used in the generated stubs
+    /// instead of the return type of the function.
+    ///
+    /// # Parameters
+    ///
+    /// * `doc_type` - The `PHPDoc` type, e.g. `array<string, int>`.
+    pub fn returns_doc_type(mut self, doc_type: Option<String>) -> Self {
+        self.ret_doc_type = doc_type;
+        self
+    }
+
+    /// Sets the type declaration of the return value, when it cannot be
+    /// described by the return type of the function, e.g. a union of types.
+    ///
+    /// # Parameters
+    ///
+    /// * `type_decl` - The type declaration, e.g. `int|string`.
+    pub fn returns_type_decl(mut self, type_decl: Option<TypeDecl>) -> Self {
+        self.ret_type_decl = type_decl;
+        self
+    }
+
+    /// Returns the type declaration of the return value, if one was set,
+    /// including `null` if the return value is nullable.
+    pub(crate) fn ret_type_decl(&self) -> Option<TypeDecl> {
+        let decl = self.ret_type_decl.clone()?;
+        Some(if self.ret_as_null {
+            decl.nullable()
+        } else {
+            decl
+        })
+    }
+
     /// Sets the documentation for the function.
     /// This is used to generate the PHP stubs for the function.
     ///
@@ -153,6 +204,71 @@ impl<'a> FunctionBuilder<'a> {
         self
     }
 
+    /// Marks the function as deprecated, so that calling it emits an
+    /// `E_DEPRECATED` diagnostic.
+    ///
+    /// # Parameters
+    ///
+    /// * `deprecation` - The reason of the deprecation and the version since
+    ///   which the function is deprecated.
+    pub fn deprecated(mut self, deprecation: Deprecation) -> Self {
+        self.function.flags |= MethodFlags::Deprecated.bits();
+        self.deprecation = Some(deprecation);
+        self
+    }
+
+    /// Attaches an attribute to the function, e.g. `#[\ReturnTypeWillChange]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `attribute` - The attribute to attach.
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Attaches an attribute to a parameter of the function, e.g.
+    /// `#[\SensitiveParameter]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the parameter.
+    /// * `attribute` - The attribute to attach.
+    pub fn arg_attribute<T: Into<String>>(mut self, name: T, attribute: Attribute) -> Self {
+        self.arg_attributes.push((name.into(), attribute));
+        self
+    }
+
+    /// Takes the attributes of the function and of its parameters, along with
+    /// their offset: 0 for the function, or the position of the parameter plus
+    /// one.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnknownAttributeTarget`] - If an attribute was attached to a
+    ///   parameter which does not exist.
+    pub(crate) fn take_attributes(&mut self) -> Result<Vec<(u32, Attribute)>> {
+        for (name, attribute) in self.arg_attributes.drain(..) {
+            let arg = self
+                .args
+                .iter_mut()
+                .find(|arg| arg.name == name)
+                .ok_or_else(|| Error::UnknownAttributeTarget(format!("{}(${name})", self.name)))?;
+            arg.attributes.push(attribute);
+        }
+        let params = self.args.iter_mut().zip(1..).flat_map(|(arg, offset)| {
+            arg.attributes
+                .drain(..)
+                .map(move |attribute| (offset, attribute))
+        });
+        Ok(self
+            .attributes
+            .drain(..)
+            .map(|attribute| (0, attribute))
+            .chain(params)
+            .collect())
+    }
+
     /// Builds the function converting it into a Zend function entry.
     ///
     /// Returns a result containing the function entry if successful.
@@ -181,12 +297,14 @@ impl<'a> FunctionBuilder<'a> {
         args.push(ArgInfo {
             // required_num_args
             name: n_req as *const _,
-            type_: match self.retval {
-                Some(retval) => {
+            type_: match (self.retval, self.ret_type_decl()) {
+                (Some(_), Some(decl)) => ZendType::empty_from_decl(&decl, self.ret_as_ref, false)
+                    .ok_or(Error::InvalidCString)?,
+                (Some(retval), None) => {
                     ZendType::empty_from_type(retval, self.ret_as_ref, false, self.ret_as_null)
                         .ok_or(Error::InvalidCString)?
                 }
-                None => ZendType::empty(false, false),
+                (None, _) => ZendType::empty(false, false),
             },
             default_value: ptr::null(),
         });
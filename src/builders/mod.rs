@@ -10,13 +10,15 @@ mod ini;
 mod module;
 #[cfg(feature = "embed")]
 mod sapi;
+mod trait_builder;
 
-pub use class::{ClassBuilder, ClassProperty};
+pub use class::{ClassBuilder, ClassProperty, PropertyHooks};
 #[cfg(feature = "enum")]
 pub use enum_builder::EnumBuilder;
-pub use function::FunctionBuilder;
+pub use function::{FunctionBuilder, FunctionHandler};
 #[cfg(all(php82, feature = "embed"))]
 pub use ini::IniBuilder;
 pub use module::{ModuleBuilder, ModuleStartup};
 #[cfg(feature = "embed")]
 pub use sapi::SapiBuilder;
+pub use trait_builder::TraitBuilder;
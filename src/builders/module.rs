@@ -1,14 +1,17 @@
 use std::{convert::TryFrom, ffi::CString, mem, ptr};
 
-use super::{ClassBuilder, FunctionBuilder};
+use super::{ClassBuilder, ClassProperty, FunctionBuilder, TraitBuilder};
 use crate::{
     PHP_DEBUG, PHP_ZTS,
+    attribute::{self, Attribute},
     class::RegisteredClass,
     constant::IntoConst,
+    deprecation::Deprecation,
     describe::DocComments,
     error::Result,
     ffi::{ZEND_MODULE_API_NO, ext_php_rs_php_build_id},
     flags::ClassFlags,
+    resource::RegisteredResource,
     zend::{FunctionEntry, ModuleEntry, ModuleGlobal, ModuleGlobals},
 };
 #[cfg(feature = "enum")]
@@ -53,8 +56,10 @@ pub struct ModuleBuilder<'a> {
     pub(crate) constants: Vec<(String, Box<dyn IntoConst + Send>, DocComments)>,
     pub(crate) classes: Vec<fn() -> ClassBuilder>,
     pub(crate) interfaces: Vec<fn() -> ClassBuilder>,
+    pub(crate) traits: Vec<fn() -> TraitBuilder>,
     #[cfg(feature = "enum")]
     pub(crate) enums: Vec<fn() -> EnumBuilder>,
+    pub(crate) resources: Vec<fn(i32) -> Result<()>>,
     startup_func: Option<StartupShutdownFunc>,
     shutdown_func: Option<StartupShutdownFunc>,
     request_startup_func: Option<StartupShutdownFunc>,
@@ -79,8 +84,10 @@ impl Default for ModuleBuilder<'_> {
             constants: vec![],
             classes: vec![],
             interfaces: vec![],
+            traits: vec![],
             #[cfg(feature = "enum")]
             enums: vec![],
+            resources: vec![],
             startup_func: None,
             shutdown_func: None,
             request_startup_func: None,
@@ -451,6 +458,9 @@ impl ModuleBuilder<'_> {
                     .expect("Failed to register constant");
             }
 
+            if let Some(deprecation) = T::DEPRECATION {
+                builder = builder.deprecated(deprecation);
+            }
             if let Some(modifier) = T::BUILDER_MODIFIER {
                 builder = modifier(builder);
             }
@@ -467,6 +477,27 @@ impl ModuleBuilder<'_> {
         self
     }
 
+    /// Adds a trait to the extension, e.g. one declared with the
+    /// [`php_trait`](crate::php_trait) macro.
+    pub fn php_trait<T: RegisteredClass>(mut self) -> Self {
+        self.traits.push(|| {
+            let mut builder = TraitBuilder::new(T::CLASS_NAME);
+            for (method, flags) in T::method_builders() {
+                builder = builder.method(method, flags);
+            }
+            if let Some(deprecation) = T::DEPRECATION {
+                builder = builder.deprecated(deprecation);
+            }
+
+            builder
+                .registration(|ce| {
+                    T::get_metadata().set_ce(ce);
+                })
+                .docs(T::DOC_COMMENTS)
+        });
+        self
+    }
+
     /// Adds a class to the extension.
     ///
     /// # Panics
@@ -500,22 +531,28 @@ impl ModuleBuilder<'_> {
                     .dyn_constant(*name, *value, docs)
                     .expect("Failed to register constant");
             }
-            for desc in T::get_metadata().all_properties() {
-                let default_stub = if desc.nullable {
-                    Some("null".into())
-                } else {
-                    None
-                };
-                builder = builder.property(crate::builders::ClassProperty {
-                    name: desc.name.into(),
-                    flags: desc.flags,
-                    default: None,
-                    docs: desc.docs,
-                    ty: Some(desc.ty),
-                    nullable: desc.nullable,
-                    readonly: desc.readonly,
-                    default_stub,
-                });
+            let metadata = T::get_metadata();
+            for desc in metadata.field_properties() {
+                // From PHP 8.4, method properties overriding field properties
+                // are declared with hooks instead.
+                #[cfg(php84)]
+                if metadata
+                    .method_properties()
+                    .iter()
+                    .any(|method| method.name == desc.name)
+                {
+                    continue;
+                }
+                builder = builder.property(ClassProperty::from_descriptor(desc));
+            }
+            for desc in metadata.method_properties() {
+                cfg_if::cfg_if! {
+                    if #[cfg(php84)] {
+                        builder = builder.property(ClassProperty::hooked(desc));
+                    } else {
+                        builder = builder.property(ClassProperty::from_descriptor(desc));
+                    }
+                }
             }
             for (name, flags, default, docs) in T::static_properties() {
                 let default_stub = default.map(crate::convert::IntoZvalDyn::stub_value);
@@ -523,7 +560,7 @@ impl ModuleBuilder<'_> {
                     Box::new(move || v.as_zval(true))
                         as Box<dyn FnOnce() -> crate::error::Result<crate::types::Zval>>
                 });
-                builder = builder.property(crate::builders::ClassProperty {
+                builder = builder.property(ClassProperty {
                     name: (*name).into(),
                     flags: *flags,
                     default: default_fn,
@@ -532,8 +569,24 @@ impl ModuleBuilder<'_> {
                     nullable: false,
                     readonly: false,
                     default_stub,
+                    hooks: None,
                 });
             }
+            if let Some(deprecation) = T::DEPRECATION {
+                builder = builder.deprecated(deprecation);
+            }
+            for (name, ty) in T::templates() {
+                builder = builder.template(name, ty);
+            }
+            if let Some(flags) = T::attribute_flags() {
+                builder = builder.declare_attribute(flags);
+            }
+            if let Some(handler) = T::get_iterator() {
+                builder = builder.iterator(handler);
+            }
+            if let Some(handlers) = T::array_access() {
+                builder = builder.array_access(handlers);
+            }
             if let Some(modifier) = T::BUILDER_MODIFIER {
                 builder = modifier(builder);
             }
@@ -549,6 +602,18 @@ impl ModuleBuilder<'_> {
         self
     }
 
+    /// Adds a resource type to the extension.
+    ///
+    /// The list destructors of the resource type are registered on module
+    /// startup, after which [`ZendResource`]s of the type can be created.
+    ///
+    /// [`ZendResource`]: crate::types::ZendResource
+    pub fn resource<T: RegisteredResource>(mut self) -> Self {
+        self.resources
+            .push(|module_number| T::get_metadata().register(module_number));
+        self
+    }
+
     /// Adds an enum to the extension.
     #[cfg(feature = "enum")]
     pub fn enumeration<T>(mut self) -> Self
@@ -620,10 +685,14 @@ pub struct ModuleStartup {
     #[cfg(feature = "observer")]
     version: String,
     constants: Vec<(String, Box<dyn IntoConst + Send>)>,
+    deprecated_functions: Vec<(String, Deprecation)>,
+    function_attributes: Vec<(String, Vec<(u32, Attribute)>)>,
     classes: Vec<fn() -> ClassBuilder>,
     interfaces: Vec<fn() -> ClassBuilder>,
+    traits: Vec<fn() -> TraitBuilder>,
     #[cfg(feature = "enum")]
     enums: Vec<fn() -> EnumBuilder>,
+    resources: Vec<fn(i32) -> Result<()>>,
 }
 
 impl ModuleStartup {
@@ -632,7 +701,8 @@ impl ModuleStartup {
     ///
     /// # Errors
     ///
-    /// * Returns an error if a constant could not be registered.
+    /// * Returns an error if a constant or a resource type could not be
+    ///   registered, or if an attribute could not be attached to a function.
     ///
     /// # Panics
     ///
@@ -642,12 +712,29 @@ impl ModuleStartup {
             val.register_constant(&name, mod_num)?;
         }
 
+        // Functions are registered by the engine before the module starts up.
+        for (name, deprecation) in self.deprecated_functions {
+            deprecation.apply_to_function(None, &name);
+        }
+        for (name, attributes) in self.function_attributes {
+            attribute::apply_to_function(None, &name, &attributes)?;
+        }
+
+        for register in self.resources {
+            register(mod_num)?;
+        }
+
         // Interfaces must be registered before classes so that classes can implement
         // them
         self.interfaces.into_iter().map(|c| c()).for_each(|c| {
             c.register().expect("Failed to build interface");
         });
 
+        // Traits must be registered before the classes using them
+        self.traits.into_iter().map(|t| t()).for_each(|t| {
+            t.register().expect("Failed to build trait");
+        });
+
         self.classes.into_iter().map(|c| c()).for_each(|c| {
             c.register().expect("Failed to build class");
         });
@@ -684,7 +771,19 @@ pub type InfoFunc = unsafe extern "C" fn(zend_module: *mut ModuleEntry);
 impl TryFrom<ModuleBuilder<'_>> for (ModuleEntry, ModuleStartup) {
     type Error = crate::error::Error;
 
-    fn try_from(builder: ModuleBuilder) -> Result<Self, Self::Error> {
+    fn try_from(mut builder: ModuleBuilder) -> Result<Self, Self::Error> {
+        let deprecated_functions = builder
+            .functions
+            .iter()
+            .filter_map(|f| Some((f.name.clone(), f.deprecation?)))
+            .collect();
+        let mut function_attributes = vec![];
+        for f in &mut builder.functions {
+            let attributes = f.take_attributes()?;
+            if !attributes.is_empty() {
+                function_attributes.push((f.name.clone(), attributes));
+            }
+        }
         let mut functions = builder
             .functions
             .into_iter()
@@ -711,10 +810,14 @@ impl TryFrom<ModuleBuilder<'_>> for (ModuleEntry, ModuleStartup) {
                 .into_iter()
                 .map(|(n, v, _)| (n, v))
                 .collect(),
+            deprecated_functions,
+            function_attributes,
             classes: builder.classes,
             interfaces: builder.interfaces,
+            traits: builder.traits,
             #[cfg(feature = "enum")]
             enums: builder.enums,
+            resources: builder.resources,
         };
 
         #[cfg(not(php_zts))]
@@ -794,6 +897,8 @@ mod tests {
         assert!(builder.constants.is_empty());
         assert!(builder.classes.is_empty());
         assert!(builder.interfaces.is_empty());
+        assert!(builder.traits.is_empty());
+        assert!(builder.resources.is_empty());
         assert!(builder.startup_func.is_none());
         assert!(builder.shutdown_func.is_none());
         assert!(builder.request_startup_func.is_none());
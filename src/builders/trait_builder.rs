@@ -0,0 +1,111 @@
+use crate::{
+    builders::{ClassBuilder, FunctionBuilder},
+    deprecation::Deprecation,
+    describe::DocComments,
+    error::Result,
+    flags::{ClassFlags, MethodFlags},
+    zend::ClassEntry,
+};
+
+/// A builder for PHP traits, whose methods are native handlers.
+///
+/// Userland classes use the trait with a `use` statement, and classes
+/// registered by the extension with [`ClassBuilder::use_trait`].
+#[must_use]
+pub struct TraitBuilder {
+    pub(crate) class: ClassBuilder,
+}
+
+impl TraitBuilder {
+    /// Creates a new trait builder with the given name.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the trait.
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        Self {
+            class: ClassBuilder::new(name).flags(ClassFlags::Trait),
+        }
+    }
+
+    /// Adds a method to the trait.
+    ///
+    /// # Parameters
+    ///
+    /// * `func` - The function builder to add to the trait.
+    /// * `flags` - Flags relating to the function. See [`MethodFlags`].
+    pub fn method(mut self, func: FunctionBuilder<'static>, flags: MethodFlags) -> Self {
+        self.class = self.class.method(func, flags);
+        self
+    }
+
+    /// Function to register the trait with PHP. This function is called after
+    /// the trait is built.
+    ///
+    /// # Parameters
+    ///
+    /// * `register` - The function to call to register the trait.
+    pub fn registration(mut self, register: fn(&'static mut ClassEntry)) -> Self {
+        self.class = self.class.registration(register);
+        self
+    }
+
+    /// Sets the documentation for the trait.
+    ///
+    /// # Parameters
+    ///
+    /// * `docs` - The documentation comments for the trait.
+    pub fn docs(mut self, docs: DocComments) -> Self {
+        self.class = self.class.docs(docs);
+        self
+    }
+
+    /// Marks the trait as deprecated.
+    ///
+    /// # Parameters
+    ///
+    /// * `deprecation` - The deprecation of the trait.
+    pub fn deprecated(mut self, deprecation: Deprecation) -> Self {
+        self.class = self.class.deprecated(deprecation);
+        self
+    }
+
+    /// Builds and registers the trait.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidPointer`] - If the trait could not be registered.
+    /// * [`Error::InvalidCString`] - If the trait name is not a valid C string.
+    /// * If a method could not be built.
+    ///
+    /// # Panics
+    ///
+    /// If no registration function was provided.
+    ///
+    /// [`Error::InvalidPointer`]: crate::error::Error::InvalidPointer
+    /// [`Error::InvalidCString`]: crate::error::Error::InvalidCString
+    pub fn register(self) -> Result<()> {
+        self.class.register()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::test_function;
+
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let builder = TraitBuilder::new("Greets");
+        assert_eq!(builder.class.name, "Greets");
+        assert_eq!(builder.class.get_flags(), ClassFlags::Trait.bits());
+    }
+
+    #[test]
+    fn test_method() {
+        let method = FunctionBuilder::new("greet", test_function);
+        let builder = TraitBuilder::new("Greets").method(method, MethodFlags::Public);
+        assert_eq!(builder.class.methods.len(), 1);
+    }
+}
@@ -8,12 +8,16 @@ use std::{
 use once_cell::sync::OnceCell;
 
 use crate::{
+    array_access::ArrayAccessHandlers,
     builders::{ClassBuilder, FunctionBuilder},
     convert::IntoZvalDyn,
+    deprecation::Deprecation,
     describe::DocComments,
     exception::PhpException,
-    flags::{ClassFlags, MethodFlags, PropertyFlags},
+    flags::{AttributeFlags, ClassFlags, DataType, MethodFlags, PropertyFlags},
     internal::property::PropertyDescriptor,
+    iterator::GetIterator,
+    operator::{CastObject, DoOperation},
     zend::{ClassEntry, ExecuteData, ZendObjectHandlers},
 };
 
@@ -43,6 +47,9 @@ pub trait RegisteredClass: Sized + 'static {
     /// Doc comments for the class.
     const DOC_COMMENTS: DocComments = &[];
 
+    /// The deprecation of the class, rendered in the generated stubs.
+    const DEPRECATION: Option<Deprecation> = None;
+
     /// Returns a reference to the class metadata, which stores the class entry,
     /// handlers, and property descriptors.
     ///
@@ -109,6 +116,71 @@ pub trait RegisteredClass: Sized + 'static {
         Vec::new()
     }
 
+    /// Returns the template parameters of the class, along with the types they
+    /// are instantiated with, if the class is an instantiation of a generic
+    /// struct.
+    ///
+    /// The [`php_class`](crate::php_class) macro overrides this when the
+    /// `#[php(instantiate(...))]` option is given. The parameters are only
+    /// rendered as `@template` tags in the generated stubs.
+    #[must_use]
+    fn templates() -> Vec<(&'static str, DataType)> {
+        Vec::new()
+    }
+
+    /// Returns the targets of the class if it is an attribute class.
+    ///
+    /// The [`php_attribute`](crate::php_attribute) macro overrides this to
+    /// return the `target` option of the class.
+    #[must_use]
+    fn attribute_flags() -> Option<AttributeFlags> {
+        None
+    }
+
+    /// Returns the handler creating the iterators of the objects of the class,
+    /// if they are traversable with `foreach`.
+    ///
+    /// The [`php_class`](crate::php_class) macro overrides this when the
+    /// `#[php(iterator)]` option is given, returning
+    /// [`iterator::get_iterator`](crate::iterator::get_iterator).
+    #[must_use]
+    fn get_iterator() -> Option<GetIterator> {
+        None
+    }
+
+    /// Returns the handlers accessing the objects of the class as arrays, if
+    /// the class implements `ArrayAccess`.
+    ///
+    /// The [`php_class`](crate::php_class) macro overrides this when the
+    /// `#[php(array_access)]` option is given, returning
+    /// [`array_access::array_access`](crate::array_access::array_access).
+    #[must_use]
+    fn array_access() -> Option<ArrayAccessHandlers> {
+        None
+    }
+
+    /// Returns the handler applying operators to the objects of the class, if
+    /// they are operands of arithmetic operators.
+    ///
+    /// The [`php_class`](crate::php_class) macro overrides this when the
+    /// `#[php(operators(...))]` option is given, returning
+    /// [`operator::do_operation`](crate::operator::do_operation).
+    #[must_use]
+    fn do_operation() -> Option<DoOperation> {
+        None
+    }
+
+    /// Returns the handler converting the objects of the class into strings,
+    /// if they can be, e.g. with `echo`.
+    ///
+    /// The [`php_class`](crate::php_class) macro overrides this when the
+    /// `#[php(operators(...))]` option includes `Concat`, returning
+    /// [`operator::cast_object`](crate::operator::cast_object).
+    #[must_use]
+    fn cast_object() -> Option<CastObject> {
+        None
+    }
+
     /// Returns a default instance of the class for immediate initialization.
     ///
     /// This is used when PHP creates an object without calling the constructor,
@@ -265,6 +337,27 @@ impl<T: RegisteredClass> ClassMetadata<T> {
             .or_else(|| self.method_properties().iter().find(|p| p.name == name))
     }
 
+    /// Finds the descriptor of a property accessed through the object
+    /// handlers by name.
+    ///
+    /// From PHP 8.4, method properties are declared with property hooks, which
+    /// are called by the engine instead, including when they override a field
+    /// property.
+    #[must_use]
+    #[inline]
+    pub(crate) fn find_handled_property(&self, name: &str) -> Option<&PropertyDescriptor<T>> {
+        cfg_if::cfg_if! {
+            if #[cfg(php84)] {
+                if self.method_properties().iter().any(|p| p.name == name) {
+                    return None;
+                }
+                self.field_properties.iter().find(|p| p.name == name)
+            } else {
+                self.find_property(name)
+            }
+        }
+    }
+
     /// Returns the field properties (from `#[php(prop)]` struct fields).
     #[must_use]
     #[inline]
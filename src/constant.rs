@@ -5,6 +5,7 @@ use std::ffi::CString;
 use std::fmt::Debug;
 
 use super::flags::GlobalConstantFlags;
+use crate::deprecation::Deprecation;
 use crate::error::Result;
 use crate::ffi::{
     zend_register_bool_constant, zend_register_double_constant, zend_register_long_constant,
@@ -93,6 +94,11 @@ pub trait IntoConst: Debug {
         module_number: i32,
         flags: GlobalConstantFlags,
     ) -> Result<()>;
+
+    /// Returns the deprecation of the constant, if it is deprecated.
+    fn deprecation(&self) -> Option<Deprecation> {
+        None
+    }
 }
 
 impl IntoConst for String {
@@ -1,11 +1,14 @@
 //! Traits used to convert between Zend/PHP and Rust types.
 
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
 use crate::{
     boxed::ZBox,
-    error::Result,
+    deprecation::Deprecation,
+    error::{Error, Result},
     exception::PhpException,
     flags::DataType,
-    types::{ZendObject, Zval},
+    types::{TypeDecl, ZendHashTable, ZendObject, Zval},
 };
 
 /// Allows zvals to be converted into Rust types in a fallible way. Reciprocal
@@ -21,6 +24,22 @@ pub trait FromZval<'a>: Sized {
     ///
     /// * `zval` - Zval to get value from.
     fn from_zval(zval: &'a Zval) -> Option<Self>;
+
+    /// The `PHPDoc` type of the implemented value, when it is more precise
+    /// than [`Self::TYPE`], e.g. `array<string, int>`. Used when generating
+    /// PHP stubs.
+    #[must_use]
+    fn doc_type() -> Option<String> {
+        None
+    }
+
+    /// The type declaration of the implemented value, when it cannot be
+    /// described by [`Self::TYPE`] alone, e.g. the union `int|string`. Used
+    /// in the arginfo of functions and when generating PHP stubs.
+    #[must_use]
+    fn type_decl() -> Option<TypeDecl> {
+        None
+    }
 }
 
 impl<'a, T> FromZval<'a> for Option<T>
@@ -32,6 +51,14 @@ where
     fn from_zval(zval: &'a Zval) -> Option<Self> {
         Some(T::from_zval(zval))
     }
+
+    fn doc_type() -> Option<String> {
+        T::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        T::type_decl()
+    }
 }
 
 /// Allows mutable zvals to be converted into Rust types in a fallible way.
@@ -50,6 +77,22 @@ pub trait FromZvalMut<'a>: Sized {
     ///
     /// * `zval` - Zval to get value from.
     fn from_zval_mut(zval: &'a mut Zval) -> Option<Self>;
+
+    /// The `PHPDoc` type of the implemented value, when it is more precise
+    /// than [`Self::TYPE`], e.g. `array<string, int>`. Used when generating
+    /// PHP stubs.
+    #[must_use]
+    fn doc_type() -> Option<String> {
+        None
+    }
+
+    /// The type declaration of the implemented value, when it cannot be
+    /// described by [`Self::TYPE`] alone, e.g. the union `int|string`. Used
+    /// in the arginfo of functions and when generating PHP stubs.
+    #[must_use]
+    fn type_decl() -> Option<TypeDecl> {
+        None
+    }
 }
 
 impl<'a, T> FromZvalMut<'a> for T
@@ -62,6 +105,14 @@ where
     fn from_zval_mut(zval: &'a mut Zval) -> Option<Self> {
         Self::from_zval(zval)
     }
+
+    fn doc_type() -> Option<String> {
+        <T as FromZval>::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        <T as FromZval>::type_decl()
+    }
 }
 
 /// `FromZendObject` is implemented by types which can be extracted from a Zend
@@ -87,6 +138,82 @@ pub trait FromZendObject<'a>: Sized {
     /// [`Error`]: crate::error::Error
     // TODO: Expand on error information
     fn from_zend_object(obj: &'a ZendObject) -> Result<Self>;
+
+    /// Extracts `Self` from the properties of an object, or from the values of
+    /// an array with the same keys. The default implementation only accepts
+    /// objects.
+    ///
+    /// # Parameters
+    ///
+    /// * `props` - The object or array to read the values from.
+    ///
+    /// # Errors
+    ///
+    /// If the conversion fails, an [`Error`] is returned.
+    fn from_properties(props: Properties<'a>) -> Result<Self> {
+        match props {
+            Properties::Object(obj) => Self::from_zend_object(obj),
+            Properties::Array(_) => Err(Error::ZvalConversion(DataType::Array)),
+        }
+    }
+}
+
+/// The named values a type is extracted from by
+/// [`FromZendObject::from_properties`]: the properties of an object, or the
+/// values of an array with string keys, such as `['type' => 'circle']`.
+#[derive(Clone, Copy)]
+pub enum Properties<'a> {
+    /// The properties of an object.
+    Object(&'a ZendObject),
+    /// The values of an array.
+    Array(&'a ZendHashTable),
+}
+
+impl<'a> Properties<'a> {
+    /// Attempts to read a value, returning [`None`] if it does not exist.
+    /// Errors name the value which could not be read.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the property, or the key of the array.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::InvalidScope` - If the properties of the object cannot be
+    ///   retrieved.
+    /// * `Error::InvalidPropertyValue` - If the value could not be converted.
+    pub fn try_get_property<T>(self, name: &str) -> Result<Option<T>>
+    where
+        T: FromZval<'a>,
+    {
+        match self {
+            Self::Object(obj) => obj.try_get_property(name),
+            Self::Array(ht) => ht
+                .get(name)
+                .map(|zv| {
+                    T::from_zval(zv)
+                        .ok_or_else(|| Error::InvalidPropertyValue(name.to_string(), zv.get_type()))
+                })
+                .transpose(),
+        }
+    }
+}
+
+impl<'a> FromZval<'a> for Properties<'a> {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.object()
+            .map(Self::Object)
+            .or_else(|| zval.array().map(Self::Array))
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        Some(TypeDecl::union([
+            DataType::Object(None).into(),
+            DataType::Array.into(),
+        ]))
+    }
 }
 
 /// Implemented on types which can be extracted from a mutable zend object.
@@ -128,6 +255,31 @@ pub trait IntoZendObject {
     /// [`Error`]: crate::error::Error
     // TODO: Expand on error information
     fn into_zend_object(self) -> Result<ZBox<ZendObject>>;
+
+    /// Sets the properties of the object `self` is converted into on an
+    /// existing object. This is used to flatten values into the object of
+    /// another value with `#[derive(ZvalConvert)]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `obj` - The object to set the properties on.
+    ///
+    /// # Errors
+    ///
+    /// If the conversion fails, or a property cannot be set, an [`Error`] is
+    /// returned.
+    ///
+    /// [`Error`]: crate::error::Error
+    fn write_properties(self, obj: &mut ZendObject) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let source = self.into_zend_object()?;
+        for (key, value) in source.get_properties()? {
+            obj.set_property(&key.to_string(), value.dereference().shallow_clone())?;
+        }
+        Ok(())
+    }
 }
 
 /// Provides implementations for converting Rust primitive types into PHP zvals.
@@ -179,6 +331,22 @@ pub trait IntoZval: Sized {
     /// [`Error`]: crate::error::Error
     // TODO: Expand on error information
     fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()>;
+
+    /// The `PHPDoc` type of the implemented value, when it is more precise
+    /// than [`Self::TYPE`], e.g. `array<string, int>`. Used when generating
+    /// PHP stubs.
+    #[must_use]
+    fn doc_type() -> Option<String> {
+        None
+    }
+
+    /// The type declaration of the implemented value, when it cannot be
+    /// described by [`Self::TYPE`] alone, e.g. the union `int|string`. Used
+    /// in the arginfo of functions and when generating PHP stubs.
+    #[must_use]
+    fn type_decl() -> Option<TypeDecl> {
+        None
+    }
 }
 
 impl IntoZval for () {
@@ -199,6 +367,14 @@ where
     const TYPE: DataType = T::TYPE;
     const NULLABLE: bool = true;
 
+    fn doc_type() -> Option<String> {
+        T::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        T::type_decl().map(TypeDecl::nullable)
+    }
+
     #[inline]
     fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
         if let Some(val) = self {
@@ -218,6 +394,14 @@ where
     const TYPE: DataType = T::TYPE;
     const NULLABLE: bool = T::NULLABLE;
 
+    fn doc_type() -> Option<String> {
+        T::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        T::type_decl()
+    }
+
     fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
         match self {
             Ok(val) => val.set_zval(zv, persistent),
@@ -229,6 +413,135 @@ where
     }
 }
 
+impl<'a, T> FromZval<'a> for Box<T>
+where
+    T: FromZval<'a>,
+{
+    const TYPE: DataType = T::TYPE;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        T::from_zval(zval).map(Box::new)
+    }
+
+    fn doc_type() -> Option<String> {
+        T::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        T::type_decl()
+    }
+}
+
+impl<T> IntoZval for Box<T>
+where
+    T: IntoZval,
+{
+    const TYPE: DataType = T::TYPE;
+    const NULLABLE: bool = T::NULLABLE;
+
+    fn doc_type() -> Option<String> {
+        T::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        T::type_decl()
+    }
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        (*self).set_zval(zv, persistent)
+    }
+}
+
+macro_rules! shared_pointer_conversions {
+    ($($ptr:ident),+) => {
+        $(
+            impl<'a, T> FromZval<'a> for $ptr<T>
+            where
+                T: FromZval<'a>,
+            {
+                const TYPE: DataType = T::TYPE;
+
+                fn from_zval(zval: &'a Zval) -> Option<Self> {
+                    T::from_zval(zval).map($ptr::new)
+                }
+
+                fn doc_type() -> Option<String> {
+                    T::doc_type()
+                }
+
+                fn type_decl() -> Option<TypeDecl> {
+                    T::type_decl()
+                }
+            }
+
+            /// The value is cloned if it is shared.
+            impl<T> IntoZval for $ptr<T>
+            where
+                T: IntoZval + Clone,
+            {
+                const TYPE: DataType = T::TYPE;
+                const NULLABLE: bool = T::NULLABLE;
+
+                fn doc_type() -> Option<String> {
+                    T::doc_type()
+                }
+
+                fn type_decl() -> Option<TypeDecl> {
+                    T::type_decl()
+                }
+
+                fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+                    $ptr::unwrap_or_clone(self).set_zval(zv, persistent)
+                }
+            }
+        )+
+    };
+}
+
+shared_pointer_conversions!(Rc, Arc);
+
+/// Borrows the value from the zval, e.g. as a `Cow<'a, str>`.
+impl<'a, B> FromZval<'a> for Cow<'a, B>
+where
+    B: ToOwned + ?Sized,
+    &'a B: FromZval<'a>,
+{
+    const TYPE: DataType = <&'a B as FromZval<'a>>::TYPE;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        <&'a B as FromZval<'a>>::from_zval(zval).map(Cow::Borrowed)
+    }
+
+    fn doc_type() -> Option<String> {
+        <&'a B as FromZval<'a>>::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        <&'a B as FromZval<'a>>::type_decl()
+    }
+}
+
+impl<B> IntoZval for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: IntoZval,
+{
+    const TYPE: DataType = B::Owned::TYPE;
+    const NULLABLE: bool = B::Owned::NULLABLE;
+
+    fn doc_type() -> Option<String> {
+        B::Owned::doc_type()
+    }
+
+    fn type_decl() -> Option<TypeDecl> {
+        B::Owned::type_decl()
+    }
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        self.into_owned().set_zval(zv, persistent)
+    }
+}
+
 /// An object-safe version of the [`IntoZval`] trait.
 ///
 /// This trait is automatically implemented on any type that implements both
@@ -265,6 +578,12 @@ pub trait IntoZvalDyn {
             Err(_) => "null".to_string(),
         }
     }
+
+    /// Returns the deprecation of the value when used as a class constant, if
+    /// it is deprecated.
+    fn deprecation(&self) -> Option<Deprecation> {
+        None
+    }
 }
 
 /// Converts a Zval to its PHP stub representation.
@@ -362,3 +681,34 @@ impl IntoZvalDyn for Zval {
         self.get_type()
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_smart_pointers() {
+        Embed::run(|| {
+            let zval = Box::new(42).into_zval(false).unwrap();
+            assert_eq!(<Box<i64>>::from_zval(&zval).as_deref(), Some(&42));
+
+            let shared = Rc::new(String::from("shared"));
+            let zval = Rc::clone(&shared).into_zval(false).unwrap();
+            assert_eq!(zval.str(), Some("shared"));
+            assert_eq!(
+                <Arc<String>>::from_zval(&zval)
+                    .as_deref()
+                    .map(String::as_str),
+                Some("shared")
+            );
+
+            let cow = <Cow<'_, str>>::from_zval(&zval).unwrap();
+            assert!(matches!(cow, Cow::Borrowed("shared")));
+            let zval = Cow::<str>::Owned("owned".into()).into_zval(false).unwrap();
+            assert_eq!(zval.str(), Some("owned"));
+        });
+    }
+}
@@ -0,0 +1,210 @@
+//! Deprecation of functions, methods, constants and enum cases.
+//!
+//! Deprecated functions and constants emit an `E_DEPRECATED` diagnostic when
+//! they are used. From PHP 8.4, the message and the version are attached to
+//! the function or constant with a `#[\Deprecated]` attribute, which the
+//! engine includes in the diagnostic, as it does for userland code.
+
+use std::{
+    ffi::{CString, c_char},
+    fmt::Debug,
+    ptr,
+};
+
+use crate::{
+    constant::IntoConst,
+    convert::IntoZvalDyn,
+    error::Result,
+    ffi::{
+        HashTable, ext_php_rs_deprecate_class_constant, ext_php_rs_deprecate_constant,
+        ext_php_rs_deprecate_function,
+    },
+    flags::{DataType, GlobalConstantFlags},
+    types::Zval,
+    zend::ClassEntry,
+};
+
+/// The deprecation of a function, method, constant or enum case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Deprecation {
+    /// The reason of the deprecation, or what to use instead.
+    pub message: Option<&'static str>,
+    /// The version since which the item is deprecated.
+    pub since: Option<&'static str>,
+}
+
+impl Deprecation {
+    /// Creates a deprecation.
+    ///
+    /// # Parameters
+    ///
+    /// * `message` - The reason of the deprecation, or what to use instead.
+    /// * `since` - The version since which the item is deprecated.
+    #[must_use]
+    pub const fn new(message: Option<&'static str>, since: Option<&'static str>) -> Self {
+        Self { message, since }
+    }
+
+    /// Returns the text of the `@deprecated` tag of the item in stubs.
+    #[must_use]
+    pub fn phpdoc(&self) -> String {
+        [self.since, self.message]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Marks a function or method as deprecated. Must be called once the
+    /// function has been registered.
+    ///
+    /// # Parameters
+    ///
+    /// * `function_table` - The function table of the class of the method, or
+    ///   [`None`] for a global function.
+    /// * `name` - The name of the function.
+    pub(crate) fn apply_to_function(&self, function_table: Option<&mut HashTable>, name: &str) {
+        let table = function_table.map_or(ptr::null_mut(), ptr::from_mut);
+        self.with_c_strings(|message, since| unsafe {
+            ext_php_rs_deprecate_function(table, name.as_ptr().cast(), name.len(), message, since);
+        });
+    }
+
+    /// Marks a class constant or an enum case as deprecated. Must be called
+    /// once the constant has been declared.
+    ///
+    /// Class constants can only be deprecated from PHP 8.4.
+    ///
+    /// # Parameters
+    ///
+    /// * `ce` - The class declaring the constant.
+    /// * `name` - The name of the constant.
+    pub(crate) fn apply_to_class_constant(&self, ce: &mut ClassEntry, name: &str) {
+        self.with_c_strings(|message, since| unsafe {
+            ext_php_rs_deprecate_class_constant(
+                ce,
+                name.as_ptr().cast(),
+                name.len(),
+                message,
+                since,
+            );
+        });
+    }
+
+    /// Marks a global constant as deprecated. Must be called once the constant
+    /// has been registered.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the constant.
+    fn apply_to_constant(&self, name: &str) {
+        self.with_c_strings(|message, since| unsafe {
+            ext_php_rs_deprecate_constant(name.as_ptr().cast(), name.len(), message, since);
+        });
+    }
+
+    /// Calls `f` with the message and the version as C strings, which are null
+    /// when not given.
+    fn with_c_strings(&self, f: impl FnOnce(*const c_char, *const c_char)) {
+        // Interior nul bytes truncate the strings rather than dropping them.
+        let to_c = |s: Option<&str>| {
+            s.map(|s| CString::new(s.split('\0').next().unwrap_or_default()).unwrap_or_default())
+        };
+        let (message, since) = (to_c(self.message), to_c(self.since));
+        f(
+            message.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            since.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+        );
+    }
+}
+
+/// A constant value which is deprecated.
+///
+/// Global constants registered with this wrapper are flagged as deprecated,
+/// and class constants are deprecated from PHP 8.4. The
+/// [`php_const`](crate::php_const) macro and the `#[php(deprecated)]`
+/// attribute of class constants wrap values in it.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::{
+///     builders::ModuleBuilder,
+///     deprecation::{Deprecated, Deprecation},
+/// };
+///
+/// const OLD_LIMIT: Deprecated<i32> = Deprecated {
+///     value: 10,
+///     deprecation: Deprecation::new(Some("use LIMIT instead"), Some("2.0")),
+/// };
+///
+/// let module = ModuleBuilder::new("ext", "0.1.0").constant(("OLD_LIMIT", OLD_LIMIT, &[]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecated<T> {
+    /// The value of the constant.
+    pub value: T,
+    /// The deprecation of the constant.
+    pub deprecation: Deprecation,
+}
+
+impl<T: IntoConst> IntoConst for Deprecated<T> {
+    fn stub_value(&self) -> String {
+        self.value.stub_value()
+    }
+
+    fn register_constant_flags(
+        &self,
+        name: &str,
+        module_number: i32,
+        flags: GlobalConstantFlags,
+    ) -> Result<()> {
+        self.value.register_constant_flags(
+            name,
+            module_number,
+            flags | GlobalConstantFlags::Deprecated,
+        )?;
+        self.deprecation.apply_to_constant(name);
+        Ok(())
+    }
+
+    fn deprecation(&self) -> Option<Deprecation> {
+        Some(self.deprecation)
+    }
+}
+
+impl<T: IntoZvalDyn> IntoZvalDyn for Deprecated<T> {
+    fn as_zval(&self, persistent: bool) -> Result<Zval> {
+        self.value.as_zval(persistent)
+    }
+
+    fn get_type(&self) -> DataType {
+        self.value.get_type()
+    }
+
+    fn stub_value(&self) -> String {
+        self.value.stub_value()
+    }
+
+    fn deprecation(&self) -> Option<Deprecation> {
+        Some(self.deprecation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phpdoc() {
+        assert_eq!(Deprecation::default().phpdoc(), "");
+        assert_eq!(
+            Deprecation::new(Some("use bar()"), None).phpdoc(),
+            "use bar()"
+        );
+        assert_eq!(
+            Deprecation::new(Some("use bar()"), Some("2.0")).phpdoc(),
+            "2.0 use bar()"
+        );
+    }
+}
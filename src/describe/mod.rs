@@ -5,9 +5,9 @@ use std::vec::Vec as StdVec;
 #[cfg(feature = "enum")]
 use crate::builders::EnumBuilder;
 use crate::{
-    builders::{ClassBuilder, FunctionBuilder},
+    builders::{ClassBuilder, FunctionBuilder, TraitBuilder},
     constant::IntoConst,
-    flags::{DataType, MethodFlags, PropertyFlags},
+    flags::{ClassFlags, DataType, MethodFlags, PropertyFlags},
     prelude::ModuleBuilder,
 };
 use abi::{Option, RString, Str, Vec};
@@ -16,6 +16,7 @@ pub mod abi;
 mod stub;
 
 pub use stub::ToStub;
+pub(crate) use stub::datatype_to_phpdoc;
 
 /// A slice of strings containing documentation comments.
 pub type DocComments = &'static [&'static str];
@@ -82,18 +83,19 @@ impl From<ModuleBuilder<'_>> for Module {
     fn from(builder: ModuleBuilder) -> Self {
         let functions = builder.functions;
 
-        // Include both classes and interfaces in the classes list.
-        // Interfaces are distinguished by ClassFlags::Interface.
-        #[allow(unused_mut)]
+        // Include classes, interfaces and traits in the classes list.
+        // Interfaces and traits are distinguished by their ClassFlags.
         let mut classes = builder
             .interfaces
             .into_iter()
-            .chain(builder.classes)
             .map(|c| c().into())
+            .chain(builder.traits.into_iter().map(|t| t().into()))
+            .chain(builder.classes.into_iter().map(|c| c().into()))
             .collect::<StdVec<_>>();
 
         #[cfg(feature = "closure")]
         classes.push(Class::closure());
+        classes.push(Class::rust_iterator());
 
         Self {
             name: builder.name.into(),
@@ -131,11 +133,18 @@ pub struct Function {
     pub ret: Option<Retval>,
     /// Parameters of the function.
     pub params: Vec<Parameter>,
+    /// Text of the `@deprecated` tag, if the function is deprecated.
+    pub deprecated: Option<RString>,
 }
 
 impl From<FunctionBuilder<'_>> for Function {
     fn from(val: FunctionBuilder<'_>) -> Self {
         let ret_allow_null = val.ret_as_null;
+        let ret_type_decl = val
+            .ret_type_decl()
+            .map(|decl| RString::from(decl.to_stub()));
+        let ret_doc_type = val.ret_doc_type;
+        let deprecated = val.deprecation.map(|d| RString::from(d.phpdoc()));
         Function {
             name: val.name.into(),
             docs: DocBlock(
@@ -150,6 +159,8 @@ impl From<FunctionBuilder<'_>> for Function {
                 .map(|r| Retval {
                     ty: r,
                     nullable: r != DataType::Mixed && ret_allow_null,
+                    doc_type: ret_doc_type.map(RString::from).into(),
+                    type_decl: ret_type_decl.into(),
                 })
                 .into(),
             params: val
@@ -158,6 +169,7 @@ impl From<FunctionBuilder<'_>> for Function {
                 .map(Parameter::from)
                 .collect::<StdVec<_>>()
                 .into(),
+            deprecated: deprecated.into(),
         }
     }
 }
@@ -176,6 +188,11 @@ pub struct Parameter {
     pub variadic: bool,
     /// Default value of the parameter.
     pub default: Option<RString>,
+    /// `PHPDoc` type of the parameter, when it is more precise than its type.
+    pub doc_type: Option<RString>,
+    /// Type declaration of the parameter, when it cannot be described by its
+    /// type alone, e.g. `int|string`.
+    pub type_decl: Option<RString>,
 }
 
 /// Represents an exported class.
@@ -190,6 +207,8 @@ pub struct Class {
     /// Names of the interfaces the exported class implements. (Not implemented
     /// #326)
     pub implements: Vec<RString>,
+    /// Names of the traits the exported class uses.
+    pub uses: Vec<RString>,
     /// Properties of the class.
     pub properties: Vec<Property>,
     /// Methods of the class.
@@ -198,6 +217,22 @@ pub struct Class {
     pub constants: Vec<Constant>,
     /// Class flags
     pub flags: u32,
+    /// Text of the `@deprecated` tag, if the class is deprecated.
+    pub deprecated: Option<RString>,
+    /// Template parameters of the class, if it is an instantiation of a
+    /// generic class.
+    pub templates: Vec<Template>,
+}
+
+/// Represents a template parameter of an instantiation of a generic class.
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct Template {
+    /// Name of the template parameter.
+    pub name: RString,
+    /// Type the parameter is instantiated with, or [`DataType::Mixed`] if it
+    /// has no PHP equivalent.
+    pub ty: DataType,
 }
 
 #[cfg(feature = "closure")]
@@ -211,6 +246,7 @@ impl Class {
             docs: DocBlock(StdVec::new().into()),
             extends: Option::None,
             implements: StdVec::new().into(),
+            uses: StdVec::new().into(),
             properties: StdVec::new().into(),
             methods: vec![Method {
                 name: "__invoke".into(),
@@ -222,23 +258,73 @@ impl Class {
                     nullable: false,
                     variadic: true,
                     default: Option::None,
+                    doc_type: Option::None,
+                    type_decl: Option::None,
                 }]
                 .into(),
                 retval: Option::Some(Retval {
                     ty: DataType::Mixed,
                     nullable: false,
+                    doc_type: Option::None,
+                    type_decl: Option::None,
                 }),
                 r#static: false,
                 visibility: Visibility::Public,
                 r#abstract: false,
+                deprecated: Option::None,
             }]
             .into(),
             constants: StdVec::new().into(),
             flags: 0,
+            deprecated: Option::None,
+            templates: StdVec::new().into(),
+        }
+    }
+}
+
+impl Class {
+    /// Creates a new class representing a Rust iterator used for generating
+    /// the stubs.
+    #[must_use]
+    pub fn rust_iterator() -> Self {
+        Self {
+            name: "RustIterator".into(),
+            docs: DocBlock(StdVec::new().into()),
+            extends: Option::None,
+            implements: vec!["\\IteratorAggregate".into()].into(),
+            uses: StdVec::new().into(),
+            properties: StdVec::new().into(),
+            methods: vec![Method {
+                name: "getIterator".into(),
+                docs: DocBlock(StdVec::new().into()),
+                ty: MethodType::Member,
+                params: StdVec::new().into(),
+                retval: Option::Some(Retval {
+                    ty: DataType::Object(Some("Iterator")),
+                    nullable: false,
+                    doc_type: Option::None,
+                    type_decl: Option::None,
+                }),
+                r#static: false,
+                visibility: Visibility::Public,
+                r#abstract: false,
+                deprecated: Option::None,
+            }]
+            .into(),
+            constants: StdVec::new().into(),
+            flags: ClassFlags::Final.bits(),
+            deprecated: Option::None,
+            templates: StdVec::new().into(),
         }
     }
 }
 
+impl From<TraitBuilder> for Class {
+    fn from(val: TraitBuilder) -> Self {
+        val.class.into()
+    }
+}
+
 impl From<ClassBuilder> for Class {
     fn from(val: ClassBuilder) -> Self {
         let flags = val.get_flags();
@@ -258,6 +344,12 @@ impl From<ClassBuilder> for Class {
                 .map(|(_, stub)| stub.into())
                 .collect::<StdVec<_>>()
                 .into(),
+            uses: val
+                .traits
+                .into_iter()
+                .map(|(_, stub)| stub.into())
+                .collect::<StdVec<_>>()
+                .into(),
             properties: val
                 .properties
                 .into_iter()
@@ -273,14 +365,25 @@ impl From<ClassBuilder> for Class {
             constants: val
                 .constants
                 .into_iter()
-                .map(|(name, _, docs, stub)| Constant {
+                .map(|(name, _, docs, stub, deprecation)| Constant {
                     name: name.into(),
                     value: Option::Some(stub.into()),
                     docs: docs.into(),
+                    deprecated: deprecation.map(|d| RString::from(d.phpdoc())).into(),
                 })
                 .collect::<StdVec<_>>()
                 .into(),
             flags,
+            deprecated: val.deprecation.map(|d| RString::from(d.phpdoc())).into(),
+            templates: val
+                .templates
+                .into_iter()
+                .map(|(name, ty)| Template {
+                    name: name.into(),
+                    ty,
+                })
+                .collect::<StdVec<_>>()
+                .into(),
         }
     }
 }
@@ -339,6 +442,8 @@ pub struct EnumCase {
     pub docs: DocBlock,
     /// Value of the enum case.
     pub value: Option<RString>,
+    /// Text of the `@deprecated` tag, if the enum case is deprecated.
+    pub deprecated: Option<RString>,
 }
 
 #[cfg(feature = "enum")]
@@ -361,6 +466,7 @@ impl From<&'static crate::enum_::EnumCase> for EnumCase {
                     crate::enum_::Discriminant::String(s) => format!("'{s}'").into(),
                 })
                 .into(),
+            deprecated: val.deprecation.map(|d| RString::from(d.phpdoc())).into(),
         }
     }
 }
@@ -385,6 +491,11 @@ pub struct Property {
     pub readonly: bool,
     /// Default value of the property as a PHP stub string.
     pub default: Option<RString>,
+    /// Visibility of the property when it is modified, if it differs from its
+    /// visibility (PHP 8.4+).
+    pub set_vis: Option<Visibility>,
+    /// Hooks of the property (PHP 8.4+).
+    pub hooks: Option<PropertyHooks>,
 }
 
 impl From<crate::builders::ClassProperty> for Property {
@@ -402,10 +513,28 @@ impl From<crate::builders::ClassProperty> for Property {
             nullable: val.nullable,
             readonly: val.readonly,
             default: val.default_stub.map(RString::from).into(),
+            set_vis: Visibility::from_set_flags(val.flags).into(),
+            hooks: val
+                .hooks
+                .map(|hooks| PropertyHooks {
+                    get: hooks.get.is_some(),
+                    set: hooks.set.is_some(),
+                })
+                .into(),
         }
     }
 }
 
+/// Represents the hooks of a property.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PropertyHooks {
+    /// Whether the property has a `get` hook.
+    pub get: bool,
+    /// Whether the property has a `set` hook.
+    pub set: bool,
+}
+
 /// Represents a method attached to an exported class.
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -426,12 +555,19 @@ pub struct Method {
     pub visibility: Visibility,
     /// Not describe method body, if is abstract.
     pub r#abstract: bool,
+    /// Text of the `@deprecated` tag, if the method is deprecated.
+    pub deprecated: Option<RString>,
 }
 
 impl From<(FunctionBuilder<'_>, MethodFlags)> for Method {
     fn from(val: (FunctionBuilder<'_>, MethodFlags)) -> Self {
         let (builder, flags) = val;
         let ret_allow_null = builder.ret_as_null;
+        let ret_type_decl = builder
+            .ret_type_decl()
+            .map(|decl| RString::from(decl.to_stub()));
+        let ret_doc_type = builder.ret_doc_type;
+        let deprecated = builder.deprecation.map(|d| RString::from(d.phpdoc()));
         Method {
             name: builder.name.into(),
             docs: DocBlock(
@@ -447,6 +583,8 @@ impl From<(FunctionBuilder<'_>, MethodFlags)> for Method {
                 .map(|r| Retval {
                     ty: r,
                     nullable: r != DataType::Mixed && ret_allow_null,
+                    doc_type: ret_doc_type.map(RString::from).into(),
+                    type_decl: ret_type_decl.into(),
                 })
                 .into(),
             params: builder
@@ -459,6 +597,7 @@ impl From<(FunctionBuilder<'_>, MethodFlags)> for Method {
             r#static: flags.contains(MethodFlags::Static),
             visibility: flags.into(),
             r#abstract: flags.contains(MethodFlags::Abstract),
+            deprecated: deprecated.into(),
         }
     }
 }
@@ -471,6 +610,12 @@ pub struct Retval {
     pub ty: DataType,
     /// Whether the return value is nullable.
     pub nullable: bool,
+    /// `PHPDoc` type of the return value, when it is more precise than its
+    /// type.
+    pub doc_type: Option<RString>,
+    /// Type declaration of the return value, when it cannot be described by
+    /// its type alone, e.g. `int|string`.
+    pub type_decl: Option<RString>,
 }
 
 /// Enumerator used to differentiate between methods.
@@ -524,6 +669,34 @@ impl From<PropertyFlags> for Visibility {
     }
 }
 
+impl Visibility {
+    /// Returns the set visibility given by the flags of a property with
+    /// asymmetric visibility, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `flags` - The flags of the property.
+    #[must_use]
+    pub fn from_set_flags(flags: PropertyFlags) -> std::option::Option<Self> {
+        cfg_if::cfg_if! {
+            if #[cfg(php84)] {
+                if flags.contains(PropertyFlags::PrivateSet) {
+                    Some(Self::Private)
+                } else if flags.contains(PropertyFlags::ProtectedSet) {
+                    Some(Self::Protected)
+                } else if flags.contains(PropertyFlags::PublicSet) {
+                    Some(Self::Public)
+                } else {
+                    None
+                }
+            } else {
+                let _ = flags;
+                None
+            }
+        }
+    }
+}
+
 impl From<MethodFlags> for Visibility {
     fn from(value: MethodFlags) -> Self {
         if value.contains(MethodFlags::Protected) {
@@ -547,6 +720,8 @@ pub struct Constant {
     pub docs: DocBlock,
     /// Value of the constant.
     pub value: Option<RString>,
+    /// Text of the `@deprecated` tag, if the constant is deprecated.
+    pub deprecated: Option<RString>,
 }
 
 impl From<(String, DocComments)> for Constant {
@@ -556,6 +731,7 @@ impl From<(String, DocComments)> for Constant {
             name: name.into(),
             value: Option::None,
             docs: docs.into(),
+            deprecated: Option::None,
         }
     }
 }
@@ -567,6 +743,10 @@ impl From<(String, Box<dyn IntoConst + Send>, DocComments)> for Constant {
             name: name.into(),
             value: Option::Some(value.stub_value().into()),
             docs: docs.into(),
+            deprecated: value
+                .deprecation()
+                .map(|d| RString::from(d.phpdoc()))
+                .into(),
         }
     }
 }
@@ -614,9 +794,9 @@ mod tests {
         assert_eq!(module.functions.len(), 1);
         cfg_if! {
             if #[cfg(feature = "closure")] {
-                assert_eq!(module.classes.len(), 1);
+                assert_eq!(module.classes.len(), 2);
             } else {
-                assert_eq!(module.classes.len(), 0);
+                assert_eq!(module.classes.len(), 1);
             }
         }
         assert_eq!(module.constants.len(), 0);
@@ -639,6 +819,8 @@ mod tests {
                 nullable: false,
                 variadic: false,
                 default: Option::None,
+                doc_type: Option::None,
+                type_decl: Option::None,
             }]
             .into()
         );
@@ -647,6 +829,8 @@ mod tests {
             Option::Some(Retval {
                 ty: DataType::Bool,
                 nullable: true,
+                doc_type: Option::None,
+                type_decl: Option::None,
             })
         );
     }
@@ -658,6 +842,7 @@ mod tests {
             .extends((|| todo!(), "BaseClass"))
             .implements((|| todo!(), "Interface1"))
             .implements((|| todo!(), "Interface2"))
+            .use_trait((|| todo!(), "Trait1"))
             .property(crate::builders::ClassProperty {
                 name: "prop1".into(),
                 flags: PropertyFlags::Public,
@@ -667,6 +852,7 @@ mod tests {
                 nullable: false,
                 readonly: false,
                 default_stub: None,
+                hooks: None,
             })
             .method(
                 FunctionBuilder::new("test_function", test_function),
@@ -681,6 +867,7 @@ mod tests {
             class.implements,
             vec!["Interface1".into(), "Interface2".into()].into()
         );
+        assert_eq!(class.uses, vec!["Trait1".into()].into());
         assert_eq!(class.properties.len(), 1);
         assert_eq!(
             class.properties[0],
@@ -693,6 +880,8 @@ mod tests {
                 nullable: false,
                 readonly: false,
                 default: Option::None,
+                set_vis: Option::None,
+                hooks: Option::None,
             }
         );
         assert_eq!(class.methods.len(), 1);
@@ -706,7 +895,8 @@ mod tests {
                 retval: Option::None,
                 r#static: false,
                 visibility: Visibility::Protected,
-                r#abstract: false
+                r#abstract: false,
+                deprecated: Option::None,
             }
         );
     }
@@ -722,6 +912,7 @@ mod tests {
             nullable: true,
             readonly: false,
             default_stub: Some("null".into()),
+            hooks: None,
         }
         .into();
         assert_eq!(property.name, "test_property".into());
@@ -731,6 +922,48 @@ mod tests {
         assert!(property.nullable);
         assert_eq!(property.default, Option::Some("null".into()));
         assert_eq!(property.ty, Option::Some(DataType::String));
+        assert_eq!(property.set_vis, Option::None);
+        assert_eq!(property.hooks, Option::None);
+    }
+
+    #[test]
+    fn test_property_from_hooks() {
+        let property: Property = crate::builders::ClassProperty {
+            name: "test_property".into(),
+            flags: PropertyFlags::Public,
+            default: None,
+            docs: &[],
+            ty: Some(DataType::Long),
+            nullable: false,
+            readonly: false,
+            default_stub: None,
+            hooks: Some(crate::builders::PropertyHooks {
+                get: Some(test_function),
+                set: None,
+            }),
+        }
+        .into();
+        assert_eq!(
+            property.hooks,
+            Option::Some(PropertyHooks {
+                get: true,
+                set: false,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(php84)]
+    fn test_visibility_from_set_flags() {
+        assert_eq!(
+            Visibility::from_set_flags(PropertyFlags::Public | PropertyFlags::PrivateSet),
+            Some(Visibility::Private)
+        );
+        assert_eq!(
+            Visibility::from_set_flags(PropertyFlags::ProtectedSet),
+            Some(Visibility::Protected)
+        );
+        assert_eq!(Visibility::from_set_flags(PropertyFlags::Public), None);
     }
 
     #[test]
@@ -750,6 +983,8 @@ mod tests {
                 nullable: false,
                 variadic: false,
                 default: Option::None,
+                doc_type: Option::None,
+                type_decl: Option::None,
             }]
             .into()
         );
@@ -758,6 +993,8 @@ mod tests {
             Option::Some(Retval {
                 ty: DataType::Bool,
                 nullable: true,
+                doc_type: Option::None,
+                type_decl: Option::None,
             })
         );
         assert!(method.r#static);
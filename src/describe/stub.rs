@@ -200,6 +200,49 @@ fn parse_typed_param_line(line: &str) -> StdOption<(String, String, String)> {
     Some((name, ty, desc))
 }
 
+/// Writes the `@deprecated` tag of a docblock.
+fn fmt_deprecated_tag(deprecated: &RString, buf: &mut String) -> FmtResult {
+    if deprecated.as_ref().is_empty() {
+        writeln!(buf, " * @deprecated")
+    } else {
+        writeln!(buf, " * @deprecated {deprecated}")
+    }
+}
+
+/// Writes the docblock of a class, constant or enum case, including the
+/// `@deprecated` tag if it is deprecated.
+fn fmt_docblock(docs: &DocBlock, deprecated: &Option<RString>, buf: &mut String) -> FmtResult {
+    fmt_tagged_docblock(docs, &[], deprecated, buf)
+}
+
+/// Writes a docblock followed by the given tags, and by the `@deprecated` tag
+/// if the item is deprecated.
+fn fmt_tagged_docblock(
+    docs: &DocBlock,
+    tags: &[String],
+    deprecated: &Option<RString>,
+    buf: &mut String,
+) -> FmtResult {
+    if tags.is_empty() && matches!(deprecated, Option::None) {
+        return docs.fmt_stub(buf);
+    }
+
+    writeln!(buf, "/**")?;
+    for comment in docs.0.iter() {
+        writeln!(buf, " *{comment}")?;
+    }
+    if !docs.0.is_empty() {
+        writeln!(buf, " *")?;
+    }
+    for tag in tags {
+        writeln!(buf, " * {tag}")?;
+    }
+    if let Option::Some(deprecated) = deprecated {
+        fmt_deprecated_tag(deprecated, buf)?;
+    }
+    writeln!(buf, " */")
+}
+
 /// Format a `PHPDoc` comment block for a function or method.
 ///
 /// Converts rustdoc-style documentation to `PHPDoc` format, including:
@@ -207,15 +250,18 @@ fn parse_typed_param_line(line: &str) -> StdOption<(String, String, String)> {
 /// - @param tags from `# Arguments` section
 /// - @return tag from `# Returns` section
 /// - @throws tags from `# Errors` section
+/// - @deprecated tag if the function is deprecated
 ///
 /// Returns the parameter type overrides map for use in stub signature generation.
 fn format_phpdoc(
     docs: &DocBlock,
     params: &[Parameter],
     ret: StdOption<&Retval>,
+    deprecated: &Option<RString>,
     buf: &mut String,
 ) -> Result<HashMap<String, String>, FmtError> {
-    if docs.0.is_empty() && params.is_empty() && ret.is_none() {
+    let has_deprecated = matches!(deprecated, Option::Some(_));
+    if docs.0.is_empty() && params.is_empty() && ret.is_none() && !has_deprecated {
         return Ok(HashMap::new());
     }
 
@@ -227,7 +273,7 @@ fn format_phpdoc(
     let has_return = ret.is_some();
     let has_errors = !parsed.errors.is_empty();
 
-    if !has_summary && !has_params && !has_return && !has_errors {
+    if !has_summary && !has_params && !has_return && !has_errors && !has_deprecated {
         return Ok(parsed.param_types);
     }
 
@@ -249,7 +295,7 @@ fn format_phpdoc(
     }
 
     // Add blank line before tags if we have summary and tags
-    if !summary_lines.is_empty() && (has_params || has_return || has_errors) {
+    if !summary_lines.is_empty() && (has_params || has_return || has_errors || has_deprecated) {
         writeln!(buf, " *")?;
     }
 
@@ -259,6 +305,10 @@ fn format_phpdoc(
         let type_str = if let Some(type_override) = parsed.param_types.get(param.name.as_ref()) {
             // Extract just the type part (strip reference like &$name)
             extract_php_type(type_override)
+        } else if let Option::Some(doc_type) = &param.doc_type {
+            nullable_phpdoc(doc_type.as_ref(), param.nullable)
+        } else if let Option::Some(type_decl) = &param.type_decl {
+            type_decl.to_string()
         } else {
             match &param.ty {
                 Option::Some(ty) => datatype_to_phpdoc(ty, param.nullable),
@@ -276,7 +326,13 @@ fn format_phpdoc(
 
     // Output @return tag
     if let Some(retval) = ret {
-        let type_str = datatype_to_phpdoc(&retval.ty, retval.nullable);
+        let type_str = match &retval.doc_type {
+            Option::Some(doc_type) => nullable_phpdoc(doc_type.as_ref(), retval.nullable),
+            Option::None => match &retval.type_decl {
+                Option::Some(type_decl) => type_decl.to_string(),
+                Option::None => datatype_to_phpdoc(&retval.ty, retval.nullable),
+            },
+        };
         if let Some(desc) = &parsed.returns {
             writeln!(buf, " * @return {type_str} {desc}")?;
         } else {
@@ -289,6 +345,10 @@ fn format_phpdoc(
         writeln!(buf, " * @throws \\Exception {error}")?;
     }
 
+    if let Option::Some(deprecated) = deprecated {
+        fmt_deprecated_tag(deprecated, buf)?;
+    }
+
     writeln!(buf, " */")?;
     Ok(parsed.param_types)
 }
@@ -305,8 +365,23 @@ fn extract_php_type(type_str: &str) -> String {
         .to_string()
 }
 
+/// Returns false for types which can only be documented in `PHPDoc`, and not
+/// used in type declarations, i.e. `resource`.
+fn is_declarable(ty: &DataType) -> bool {
+    !matches!(ty, DataType::Resource)
+}
+
+/// Appends `|null` to a `PHPDoc` type if it is nullable.
+fn nullable_phpdoc(ty: &str, nullable: bool) -> String {
+    if nullable {
+        format!("{ty}|null")
+    } else {
+        ty.to_string()
+    }
+}
+
 /// Convert a `DataType` to `PHPDoc` type string.
-fn datatype_to_phpdoc(ty: &DataType, nullable: bool) -> String {
+pub(crate) fn datatype_to_phpdoc(ty: &DataType, nullable: bool) -> String {
     let base = match ty {
         DataType::Bool | DataType::True | DataType::False => "bool",
         DataType::Long => "int",
@@ -469,7 +544,8 @@ impl ToStub for Function {
             Option::Some(r) => Some(r),
             Option::None => None,
         };
-        let type_overrides = format_phpdoc(&self.docs, &self.params, ret_ref, buf)?;
+        let type_overrides =
+            format_phpdoc(&self.docs, &self.params, ret_ref, &self.deprecated, buf)?;
 
         let (_, name) = split_namespace(self.name.as_ref());
 
@@ -483,21 +559,29 @@ impl ToStub for Function {
 
         write!(buf, "function {name}({params_str})")?;
 
-        if let Option::Some(retval) = &self.ret {
-            write!(buf, ": ")?;
-            // Don't add ? for mixed/null/void - they already include null or can't be nullable
-            if retval.nullable
-                && !matches!(retval.ty, DataType::Mixed | DataType::Null | DataType::Void)
-            {
-                write!(buf, "?")?;
-            }
-            retval.ty.fmt_stub(buf)?;
+        if let Option::Some(retval) = &self.ret
+            && is_declarable(&retval.ty)
+        {
+            retval_to_stub(retval, buf)?;
         }
 
         writeln!(buf, " {{}}")
     }
 }
 
+/// Render the return type of a function or method to stub format.
+fn retval_to_stub(retval: &Retval, buf: &mut String) -> FmtResult {
+    write!(buf, ": ")?;
+    if let Option::Some(type_decl) = &retval.type_decl {
+        return write!(buf, "{type_decl}");
+    }
+    // Don't add ? for mixed/null/void - they already include null or can't be nullable
+    if retval.nullable && !matches!(retval.ty, DataType::Mixed | DataType::Null | DataType::Void) {
+        write!(buf, "?")?;
+    }
+    retval.ty.fmt_stub(buf)
+}
+
 /// Render a parameter to stub format, with optional type overrides from rustdoc.
 ///
 /// When a parameter's Rust type is `Zval` (which maps to `mixed` in PHP), the
@@ -518,7 +602,11 @@ fn param_to_stub(
         // Use the documented type from # Parameters
         let type_str = extract_php_type(override_str);
         write!(buf, "{type_str} ")?;
-    } else if let Option::Some(ty) = &param.ty {
+    } else if let Option::Some(type_decl) = &param.type_decl {
+        write!(buf, "{type_decl} ")?;
+    } else if let Option::Some(ty) = &param.ty
+        && is_declarable(ty)
+    {
         // Don't add ? for mixed/null/void - they already include null or can't be nullable
         if param.nullable && !matches!(ty, DataType::Mixed | DataType::Null | DataType::Void) {
             write!(buf, "?")?;
@@ -602,7 +690,19 @@ impl ToStub for DocBlock {
 
 impl ToStub for Class {
     fn fmt_stub(&self, buf: &mut String) -> FmtResult {
-        self.docs.fmt_stub(buf)?;
+        let templates = self
+            .templates
+            .iter()
+            .map(|template| match template.ty {
+                DataType::Mixed => format!("@template {}", template.name),
+                ref ty => format!(
+                    "@template {} of {}",
+                    template.name,
+                    datatype_to_phpdoc(ty, false)
+                ),
+            })
+            .collect::<StdVec<_>>();
+        fmt_tagged_docblock(&self.docs, &templates, &self.deprecated, buf)?;
 
         let (_, name) = split_namespace(self.name.as_ref());
         let flags = ClassFlags::from_bits(self.flags).unwrap_or(ClassFlags::empty());
@@ -610,6 +710,8 @@ impl ToStub for Class {
 
         if is_interface {
             write!(buf, "interface {name} ")?;
+        } else if flags.contains(ClassFlags::Trait) {
+            write!(buf, "trait {name} ")?;
         } else {
             write!(buf, "class {name} ")?;
         }
@@ -644,6 +746,10 @@ impl ToStub for Class {
 
         writeln!(buf, "{{")?;
 
+        for trait_ in self.uses.iter() {
+            writeln!(buf, "    use {trait_};")?;
+        }
+
         // Collect (sort_key, stub) tuples to sort by name, not by rendered output
         let mut constants: StdVec<_> = self
             .constants
@@ -675,15 +781,16 @@ impl ToStub for Class {
         properties.sort_by(|(a, _), (b, _)| a.cmp(b));
         methods.sort_by(|(a, _), (b, _)| a.cmp(b));
 
-        buf.push_str(
-            &constants
-                .into_iter()
-                .chain(properties)
-                .chain(methods)
-                .map(|(_, stub)| stub)
-                .collect::<StdVec<_>>()
-                .join(NEW_LINE_SEPARATOR),
-        );
+        let members = constants
+            .into_iter()
+            .chain(properties)
+            .chain(methods)
+            .map(|(_, stub)| stub)
+            .collect::<StdVec<_>>();
+        if !self.uses.is_empty() && !members.is_empty() {
+            writeln!(buf)?;
+        }
+        buf.push_str(&members.join(NEW_LINE_SEPARATOR));
 
         writeln!(buf, "}}")
     }
@@ -714,7 +821,7 @@ impl ToStub for Enum {
 #[cfg(feature = "enum")]
 impl ToStub for EnumCase {
     fn fmt_stub(&self, buf: &mut String) -> FmtResult {
-        self.docs.fmt_stub(buf)?;
+        fmt_docblock(&self.docs, &self.deprecated, buf)?;
 
         write!(buf, "  case {}", self.name)?;
         if let Option::Some(value) = &self.value {
@@ -726,13 +833,17 @@ impl ToStub for EnumCase {
 
 impl ToStub for Property {
     fn fmt_stub(&self, buf: &mut String) -> FmtResult {
-        if !self.docs.0.is_empty() {
+        // Types which cannot be declared are always documented.
+        let undeclared = matches!(&self.ty, Option::Some(ty) if !is_declarable(ty));
+        if !self.docs.0.is_empty() || undeclared {
             writeln!(buf, "/**")?;
             for comment in self.docs.0.iter() {
                 writeln!(buf, " *{comment}")?;
             }
             if let Option::Some(ty) = &self.ty {
-                writeln!(buf, " *")?;
+                if !self.docs.0.is_empty() {
+                    writeln!(buf, " *")?;
+                }
                 writeln!(buf, " * @var {}", datatype_to_phpdoc(ty, self.nullable))?;
             }
             writeln!(buf, " */")?;
@@ -740,13 +851,19 @@ impl ToStub for Property {
 
         self.vis.fmt_stub(buf)?;
         write!(buf, " ")?;
+        if let Option::Some(set_vis) = &self.set_vis {
+            set_vis.fmt_stub(buf)?;
+            write!(buf, "(set) ")?;
+        }
         if self.static_ {
             write!(buf, "static ")?;
         }
         if self.readonly {
             write!(buf, "readonly ")?;
         }
-        if let Option::Some(ty) = &self.ty {
+        if let Option::Some(ty) = &self.ty
+            && is_declarable(ty)
+        {
             let nullable = self.nullable && !matches!(ty, DataType::Mixed | DataType::Null);
             if nullable {
                 write!(buf, "?")?;
@@ -758,6 +875,16 @@ impl ToStub for Property {
         if let Option::Some(default) = &self.default {
             write!(buf, " = {default}")?;
         }
+        if let Option::Some(hooks) = &self.hooks {
+            write!(buf, " {{")?;
+            if hooks.get {
+                write!(buf, " get;")?;
+            }
+            if hooks.set {
+                write!(buf, " set;")?;
+            }
+            return writeln!(buf, " }}");
+        }
         writeln!(buf, ";")
     }
 }
@@ -788,7 +915,8 @@ impl ToStub for Method {
                 Option::None => None,
             }
         };
-        let type_overrides = format_phpdoc(&self.docs, &self.params, ret_ref, buf)?;
+        let type_overrides =
+            format_phpdoc(&self.docs, &self.params, ret_ref, &self.deprecated, buf)?;
 
         self.visibility.fmt_stub(buf)?;
 
@@ -810,15 +938,9 @@ impl ToStub for Method {
 
         if !matches!(self.ty, MethodType::Constructor)
             && let Option::Some(retval) = &self.retval
+            && is_declarable(&retval.ty)
         {
-            write!(buf, ": ")?;
-            // Don't add ? for mixed/null/void - they already include null or can't be nullable
-            if retval.nullable
-                && !matches!(retval.ty, DataType::Mixed | DataType::Null | DataType::Void)
-            {
-                write!(buf, "?")?;
-            }
-            retval.ty.fmt_stub(buf)?;
+            retval_to_stub(retval, buf)?;
         }
 
         if self.r#abstract {
@@ -831,7 +953,7 @@ impl ToStub for Method {
 
 impl ToStub for Constant {
     fn fmt_stub(&self, buf: &mut String) -> FmtResult {
-        self.docs.fmt_stub(buf)?;
+        fmt_docblock(&self.docs, &self.deprecated, buf)?;
 
         write!(buf, "const {} = ", self.name)?;
         if let Option::Some(value) = &self.value {
@@ -961,6 +1083,8 @@ mod test {
             nullable: false,
             readonly: false,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         // No docs → no docblock at all (type declaration is sufficient in PHP 8.0+)
@@ -982,6 +1106,8 @@ mod test {
             nullable: true,
             readonly: false,
             default: Option::Some("null".into()),
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(
@@ -1004,6 +1130,8 @@ mod test {
             nullable: false,
             readonly: false,
             default: Option::Some("100".into()),
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(
@@ -1026,6 +1154,8 @@ mod test {
             nullable: false,
             readonly: false,
             default: Option::Some("'hello'".into()),
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(
@@ -1048,6 +1178,8 @@ mod test {
             nullable: true,
             readonly: false,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(stub.contains("The user name."), "missing doc: {stub}");
@@ -1075,6 +1207,8 @@ mod test {
             nullable: false,
             readonly: false,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(stub.contains("Some value."), "missing doc: {stub}");
@@ -1096,11 +1230,65 @@ mod test {
             nullable: false,
             readonly: true,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert_eq!(stub, "public readonly array $baz;\n");
     }
 
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_property_stub_hooks() {
+        use crate::describe::{Property, PropertyHooks, Visibility, abi::Option};
+
+        let mut prop = Property {
+            name: "baz".into(),
+            docs: super::DocBlock(vec![].into()),
+            ty: Option::Some(DataType::Long),
+            vis: Visibility::Public,
+            static_: false,
+            nullable: false,
+            readonly: false,
+            default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::Some(PropertyHooks {
+                get: true,
+                set: true,
+            }),
+        };
+        assert_eq!(prop.to_stub().unwrap(), "public int $baz { get; set; }\n");
+
+        prop.hooks = Option::Some(PropertyHooks {
+            get: true,
+            set: false,
+        });
+        assert_eq!(prop.to_stub().unwrap(), "public int $baz { get; }\n");
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_property_stub_set_visibility() {
+        use crate::describe::{Property, Visibility, abi::Option};
+
+        let prop = Property {
+            name: "baz".into(),
+            docs: super::DocBlock(vec![].into()),
+            ty: Option::Some(DataType::String),
+            vis: Visibility::Public,
+            static_: false,
+            nullable: false,
+            readonly: false,
+            default: Option::None,
+            set_vis: Option::Some(Visibility::Private),
+            hooks: Option::None,
+        };
+        assert_eq!(
+            prop.to_stub().unwrap(),
+            "public private(set) string $baz;\n"
+        );
+    }
+
     #[test]
     #[allow(clippy::unwrap_used)]
     fn test_property_stub_untyped_no_docblock() {
@@ -1115,6 +1303,8 @@ mod test {
             nullable: false,
             readonly: false,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(
@@ -1138,6 +1328,8 @@ mod test {
             nullable: false,
             readonly: false,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(
@@ -1160,6 +1352,8 @@ mod test {
             nullable: true,
             readonly: false,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         // mixed already includes null, no ? prefix
@@ -1180,6 +1374,8 @@ mod test {
             nullable: true,
             readonly: false,
             default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
         };
         let stub = prop.to_stub().unwrap();
         assert!(stub.contains("The related entity."), "missing doc: {stub}");
@@ -1258,6 +1454,246 @@ mod test {
         assert_eq!(parse_param_line("no separator here"), None);
     }
 
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_resource_stub() {
+        use crate::describe::{Function, Parameter, Property, Retval, Visibility, abi::Option};
+
+        let func = Function {
+            name: "stream_read".into(),
+            docs: super::DocBlock(vec![].into()),
+            ret: Option::Some(Retval {
+                ty: DataType::Resource,
+                nullable: false,
+                doc_type: Option::None,
+                type_decl: Option::None,
+            }),
+            params: vec![Parameter {
+                name: "stream".into(),
+                ty: Option::Some(DataType::Resource),
+                nullable: true,
+                variadic: false,
+                default: Option::None,
+                doc_type: Option::None,
+                type_decl: Option::None,
+            }]
+            .into(),
+            deprecated: Option::None,
+        };
+        let stub = func.to_stub().unwrap();
+        assert!(stub.contains(" * @param resource|null $stream\n"), "{stub}");
+        assert!(stub.contains(" * @return resource\n"), "{stub}");
+        assert!(
+            stub.contains("function stream_read($stream = null) {}"),
+            "{stub}"
+        );
+
+        let prop = Property {
+            name: "handle".into(),
+            docs: super::DocBlock(vec![].into()),
+            ty: Option::Some(DataType::Resource),
+            vis: Visibility::Public,
+            static_: false,
+            nullable: false,
+            readonly: false,
+            default: Option::None,
+            set_vis: Option::None,
+            hooks: Option::None,
+        };
+        assert_eq!(
+            prop.to_stub().unwrap(),
+            "/**\n * @var resource\n */\npublic $handle;\n"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_doc_type_stub() {
+        use crate::describe::{Function, Parameter, Retval, abi::Option};
+
+        let func = Function {
+            name: "count_words".into(),
+            docs: super::DocBlock(vec![].into()),
+            ret: Option::Some(Retval {
+                ty: DataType::Array,
+                nullable: true,
+                doc_type: Option::Some("array<string, int>".into()),
+                type_decl: Option::None,
+            }),
+            params: vec![Parameter {
+                name: "words".into(),
+                ty: Option::Some(DataType::Array),
+                nullable: false,
+                variadic: false,
+                default: Option::None,
+                doc_type: Option::Some("array<int, string>".into()),
+                type_decl: Option::None,
+            }]
+            .into(),
+            deprecated: Option::None,
+        };
+        let stub = func.to_stub().unwrap();
+        assert!(
+            stub.contains(" * @param array<int, string> $words\n"),
+            "{stub}"
+        );
+        assert!(
+            stub.contains(" * @return array<string, int>|null\n"),
+            "{stub}"
+        );
+        assert!(
+            stub.contains("function count_words(array $words): ?array {}"),
+            "{stub}"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_type_decl_stub() {
+        use crate::describe::{Function, Parameter, Retval, abi::Option};
+
+        let func = Function {
+            name: "first".into(),
+            docs: super::DocBlock(vec![].into()),
+            ret: Option::Some(Retval {
+                ty: DataType::Mixed,
+                nullable: false,
+                doc_type: Option::None,
+                type_decl: Option::Some("int|string|null".into()),
+            }),
+            params: vec![Parameter {
+                name: "items".into(),
+                ty: Option::Some(DataType::Mixed),
+                nullable: false,
+                variadic: false,
+                default: Option::None,
+                doc_type: Option::None,
+                type_decl: Option::Some("(\\Countable&\\Traversable)|array".into()),
+            }]
+            .into(),
+            deprecated: Option::None,
+        };
+        let stub = func.to_stub().unwrap();
+        assert!(
+            stub.contains(" * @param (\\Countable&\\Traversable)|array $items\n"),
+            "{stub}"
+        );
+        assert!(stub.contains(" * @return int|string|null\n"), "{stub}");
+        assert!(
+            stub.contains(
+                "function first((\\Countable&\\Traversable)|array $items): int|string|null {}"
+            ),
+            "{stub}"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_deprecated_stub() {
+        use crate::describe::{Constant, Function, abi::Option};
+
+        let func = Function {
+            name: "old".into(),
+            docs: super::DocBlock(vec![" Does something.".into()].into()),
+            ret: Option::None,
+            params: vec![].into(),
+            deprecated: Option::Some("2.0 use new() instead".into()),
+        };
+        assert_eq!(
+            func.to_stub().unwrap(),
+            "/**\n * Does something.\n *\n * @deprecated 2.0 use new() instead\n */\nfunction old() {}\n"
+        );
+
+        let constant = Constant {
+            name: "OLD".into(),
+            docs: super::DocBlock(vec![].into()),
+            value: Option::Some("1".into()),
+            deprecated: Option::Some(String::new().into()),
+        };
+        assert_eq!(
+            constant.to_stub().unwrap(),
+            "/**\n * @deprecated\n */\nconst OLD = 1;\n"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_template_stub() {
+        use crate::describe::{Class, Template, abi::Option};
+
+        let class = Class {
+            name: "IntCache".into(),
+            docs: super::DocBlock(vec![" A cache.".into()].into()),
+            extends: Option::None,
+            implements: vec![].into(),
+            uses: vec![].into(),
+            properties: vec![].into(),
+            methods: vec![].into(),
+            constants: vec![].into(),
+            flags: 0,
+            deprecated: Option::None,
+            templates: vec![
+                Template {
+                    name: "T".into(),
+                    ty: DataType::Long,
+                },
+                Template {
+                    name: "S".into(),
+                    ty: DataType::Mixed,
+                },
+            ]
+            .into(),
+        };
+        let stub = class.to_stub().unwrap();
+        assert!(
+            stub.starts_with(
+                "/**\n * A cache.\n *\n * @template T of int\n * @template S\n */\nclass IntCache {"
+            ),
+            "{stub}"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_trait_stub() {
+        use crate::describe::{
+            Class,
+            abi::{Option, RString},
+        };
+        use crate::flags::ClassFlags;
+
+        let class = |name: &str, uses: Vec<RString>, flags: ClassFlags| Class {
+            name: name.into(),
+            docs: super::DocBlock(vec![].into()),
+            extends: Option::None,
+            implements: vec![].into(),
+            uses: uses.into(),
+            properties: vec![].into(),
+            methods: vec![].into(),
+            constants: vec![].into(),
+            flags: flags.bits(),
+            deprecated: Option::None,
+            templates: vec![].into(),
+        };
+
+        assert_eq!(
+            class("Demo\\Greets", vec![], ClassFlags::Trait)
+                .to_stub()
+                .unwrap(),
+            "trait Greets {\n}\n"
+        );
+        assert_eq!(
+            class(
+                "Greeter",
+                vec!["\\Demo\\Greets".into()],
+                ClassFlags::empty()
+            )
+            .to_stub()
+            .unwrap(),
+            "class Greeter {\n    use \\Demo\\Greets;\n}\n"
+        );
+    }
+
     #[test]
     fn test_format_phpdoc() {
         use super::{DocBlock, Parameter, Retval, Str, format_phpdoc};
@@ -1286,15 +1722,20 @@ mod test {
             nullable: false,
             variadic: false,
             default: Option::None,
+            doc_type: Option::None,
+            type_decl: Option::None,
         }];
 
         let retval = Retval {
             ty: DataType::String,
             nullable: false,
+            doc_type: Option::None,
+            type_decl: Option::None,
         };
 
         let mut buf = String::new();
-        format_phpdoc(&docs, &params, Some(&retval), &mut buf).expect("format_phpdoc failed");
+        format_phpdoc(&docs, &params, Some(&retval), &Option::None, &mut buf)
+            .expect("format_phpdoc failed");
 
         // Check that PHPDoc format is produced
         assert!(buf.contains("/**"));
@@ -5,12 +5,12 @@ use std::ptr;
 use crate::{
     boxed::ZBox,
     class::RegisteredClass,
-    convert::{FromZendObject, FromZval, IntoZendObject, IntoZval},
+    convert::{FromZendObject, IntoZendObject, IntoZval},
+    deprecation::Deprecation,
     describe::DocComments,
     error::{Error, Result},
     ffi::zend_enum_get_case,
     flags::{ClassFlags, DataType},
-    rc::PhpRc,
     types::{ZendObject, ZendStr, Zval},
 };
 
@@ -52,18 +52,6 @@ where
     }
 }
 
-impl<T> FromZval<'_> for T
-where
-    T: RegisteredEnum + RegisteredClass,
-{
-    const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
-
-    fn from_zval(zval: &Zval) -> Option<Self> {
-        zval.object()
-            .and_then(|obj| Self::from_zend_object(obj).ok())
-    }
-}
-
 impl<T> IntoZendObject for T
 where
     T: RegisteredEnum + RegisteredClass,
@@ -81,36 +69,6 @@ where
     }
 }
 
-impl<T> IntoZval for T
-where
-    T: RegisteredEnum + RegisteredClass,
-{
-    const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
-    const NULLABLE: bool = false;
-
-    fn set_zval(self, zv: &mut Zval, _persistent: bool) -> Result<()> {
-        // `set_object` increments the object refcount; the ZBox returned by
-        // `into_zend_object` already owns one ref, so we drop one before
-        // handing the pointer over to keep the net count at 1. Same pattern as
-        // `ZBox<ZendObject>::set_zval` in `object.rs`.
-        let mut obj = self.into_zend_object()?;
-        obj.dec_count();
-        zv.set_object(obj.into_raw());
-        Ok(())
-    }
-}
-// impl<'a, T> IntoZval for T
-// where
-//     T: RegisteredEnum + RegisteredClass + IntoZendObject
-// {
-//     const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
-//     const NULLABLE: bool = false;
-//
-//     fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
-//         let obj = self.into_zend_object()?;
-//     }
-// }
-
 /// Represents a case in a PHP enum.
 pub struct EnumCase {
     /// The identifier of the enum case, e.g. `Bar` in `enum Foo { Bar }`.
@@ -119,6 +77,8 @@ pub struct EnumCase {
     pub discriminant: Option<Discriminant>,
     /// The documentation comments for the enum case.
     pub docs: DocComments,
+    /// The deprecation of the enum case, if it is deprecated.
+    pub deprecation: Option<Deprecation>,
 }
 
 impl EnumCase {
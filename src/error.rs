@@ -74,6 +74,37 @@ pub enum Error {
     SapiWriteUnavailable,
     /// Failed to make an object lazy (PHP 8.4+)
     LazyObjectFailed,
+    /// An operator could not be applied to the given operands.
+    UnsupportedOperands,
+    /// The value cannot be stored in persistent memory.
+    NotPersistable(DataType),
+    /// A property required to convert an object does not exist.
+    ///
+    /// The enum carries the name of the property.
+    MissingProperty(String),
+    /// The value of a property could not be converted.
+    ///
+    /// The enum carries the name of the property and the data type of its
+    /// value.
+    InvalidPropertyValue(String, DataType),
+    /// The tag of an object does not name any variant of the enum it is
+    /// converted into.
+    ///
+    /// The enum carries the tag.
+    UnknownVariant(String),
+    /// An array is not a list of the expected number of elements, e.g. when
+    /// converting it into a tuple.
+    ///
+    /// The enum carries the expected number of elements.
+    NotAList(usize),
+    /// An attribute was attached to a function, a parameter or a class member
+    /// which does not exist.
+    ///
+    /// The enum carries the name of the item.
+    UnknownAttributeTarget(String),
+    /// A fiber could not be suspended, or was resumed with an exception. The
+    /// exception is left pending.
+    FiberInterrupted,
 }
 
 impl Display for Error {
@@ -123,6 +154,20 @@ impl Display for Error {
             Error::LazyObjectFailed => {
                 write!(f, "Failed to make the object lazy")
             }
+            Error::UnsupportedOperands => write!(f, "Unsupported operand types."),
+            Error::NotPersistable(ty) => {
+                write!(f, "Values of type {ty} cannot be stored persistently.")
+            }
+            Error::MissingProperty(name) => write!(f, "Missing property `{name}`."),
+            Error::InvalidPropertyValue(name, ty) => {
+                write!(f, "Invalid value of type {ty} for property `{name}`.")
+            }
+            Error::UnknownVariant(tag) => write!(f, "Unknown variant `{tag}`."),
+            Error::NotAList(len) => write!(f, "Expected a list of {len} elements."),
+            Error::UnknownAttributeTarget(name) => {
+                write!(f, "Cannot attach an attribute to unknown `{name}`.")
+            }
+            Error::FiberInterrupted => write!(f, "The fiber was interrupted."),
         }
     }
 }
@@ -0,0 +1,315 @@
+//! Executors running the futures of `async` functions and methods exported to
+//! PHP.
+//!
+//! When an `async` function is called from a PHP
+//! [`Fiber`](https://www.php.net/manual/en/class.fiber.php), the fiber is
+//! suspended each time the future is pending, and the future is polled again
+//! when the fiber is resumed, usually by an event loop. The fiber suspends
+//! with a [`RustWaker`] object, which tells the event loop whether the future
+//! has been woken since, i.e. whether it is worth resuming. Outside of a fiber,
+//! the future is run to completion with the [`Executor`] set with
+//! [`set_executor`], blocking PHP until it completes.
+//!
+//! Fibers require PHP 8.1. On earlier versions, futures are always run with
+//! the executor.
+
+use std::{
+    future::Future,
+    pin::{Pin, pin},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use parking_lot::RwLock;
+
+use crate::{
+    builders::{ClassBuilder, FunctionBuilder},
+    class::{ClassEntryInfo, ClassMetadata, ConstructorMeta, RegisteredClass},
+    convert::IntoZvalDyn,
+    describe::DocComments,
+    error::Result,
+    flags::{ClassFlags, DataType, MethodFlags},
+    types::Zval,
+    zend::ExecuteData,
+    zend_fastcall,
+};
+#[cfg(php81)]
+use crate::{convert::IntoZval, types::ZendClassObject, zend::ZendFiber};
+
+/// Class entry and handlers for Rust wakers.
+static RUST_WAKER_META: ClassMetadata<RustWaker> = ClassMetadata::new(&[]);
+
+/// The executor, if one has been set.
+static EXECUTOR: RwLock<Option<Arc<dyn Executor>>> = RwLock::new(None);
+
+/// Runs the futures of `async` functions exported to PHP.
+///
+/// # Example
+///
+/// An executor entering a Tokio runtime, so that Tokio resources can be used
+/// by the futures:
+///
+/// ```ignore
+/// use std::{future::Future, pin::Pin, task::{Context, Poll}};
+/// use ext_php_rs::executor::Executor;
+/// use tokio::runtime::Runtime;
+///
+/// struct TokioExecutor(Runtime);
+///
+/// impl Executor for TokioExecutor {
+///     fn block_on(&self, future: Pin<&mut dyn Future<Output = ()>>) {
+///         self.0.block_on(future);
+///     }
+///
+///     fn poll(&self, future: Pin<&mut dyn Future<Output = ()>>, cx: &mut Context<'_>) -> Poll<()> {
+///         let _guard = self.0.enter();
+///         future.poll(cx)
+///     }
+/// }
+/// ```
+pub trait Executor: Send + Sync {
+    /// Runs a future to completion, blocking the current thread.
+    ///
+    /// # Parameters
+    ///
+    /// * `future` - The future to run.
+    fn block_on(&self, future: Pin<&mut dyn Future<Output = ()>>);
+
+    /// Polls a future once, on behalf of the fiber it runs in.
+    ///
+    /// Executors which must be entered for their futures to make progress,
+    /// e.g. to register with a reactor, override this method to enter them.
+    ///
+    /// # Parameters
+    ///
+    /// * `future` - The future to poll.
+    /// * `cx` - The context of the task.
+    fn poll(&self, future: Pin<&mut dyn Future<Output = ()>>, cx: &mut Context<'_>) -> Poll<()> {
+        future.poll(cx)
+    }
+}
+
+/// An executor running futures on the current thread, which is parked while
+/// the future is pending.
+///
+/// This is the executor used until another one is set with [`set_executor`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalExecutor;
+
+impl Executor for LocalExecutor {
+    fn block_on(&self, mut future: Pin<&mut dyn Future<Output = ()>>) {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        while future.as_mut().poll(&mut cx).is_pending() {
+            thread::park();
+        }
+    }
+}
+
+/// Wakes a thread parked by the [`LocalExecutor`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// The waker of a future run in a fiber, which the fiber suspends with.
+///
+/// Internally, a final class `RustWaker` is registered. Event loops resuming
+/// the fiber can check whether the future has been woken since it was last
+/// polled, and resume the fiber once it has:
+///
+/// ```php
+/// <?php
+///
+/// final class RustWaker {
+///     public function isWoken(): bool {}
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct RustWaker(Arc<WakeFlag>);
+
+impl RustWaker {
+    /// Returns whether the future has been woken since it was last polled.
+    #[must_use]
+    pub fn is_woken(&self) -> bool {
+        self.0.0.load(Ordering::Acquire)
+    }
+
+    /// Returns a [`Waker`] setting the flag returned by
+    /// [`RustWaker::is_woken`].
+    #[must_use]
+    pub fn waker(&self) -> Waker {
+        Waker::from(self.0.clone())
+    }
+
+    /// Clears the flag returned by [`RustWaker::is_woken`], before the future
+    /// is polled again.
+    fn reset(&self) {
+        self.0.0.store(false, Ordering::Release);
+    }
+
+    /// Builds the class entry for [`RustWaker`], registering it with PHP. This
+    /// function should only be called once inside your module startup
+    /// function.
+    ///
+    /// If the class has already been built, this function returns early without
+    /// doing anything. This allows for safe repeated calls in test
+    /// environments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `RustWaker` PHP class cannot be registered.
+    pub fn build() {
+        if RUST_WAKER_META.has_ce() {
+            return;
+        }
+
+        ClassBuilder::new(Self::CLASS_NAME)
+            .flags(Self::FLAGS)
+            .method(
+                FunctionBuilder::new("isWoken", Self::is_woken_method).returns(
+                    DataType::Bool,
+                    false,
+                    false,
+                ),
+                MethodFlags::Public,
+            )
+            .object_override::<Self>()
+            .registration(|ce| RUST_WAKER_META.set_ce(ce))
+            .register()
+            .expect("Failed to build `RustWaker` PHP class.");
+    }
+
+    zend_fastcall! {
+        /// The `isWoken()` method of the `RustWaker` class.
+        extern "C" fn is_woken_method(ex: &mut ExecuteData, retval: &mut Zval) {
+            let (parser, this) = ex.parser_method::<Self>();
+            if parser.parse().is_err() {
+                return;
+            }
+            retval.set_bool(this.is_some_and(|this| this.is_woken()));
+        }
+    }
+}
+
+impl RegisteredClass for RustWaker {
+    const CLASS_NAME: &'static str = "RustWaker";
+
+    const BUILDER_MODIFIER: Option<fn(ClassBuilder) -> ClassBuilder> = None;
+    const EXTENDS: Option<ClassEntryInfo> = None;
+    const IMPLEMENTS: &'static [ClassEntryInfo] = &[];
+    const FLAGS: ClassFlags = ClassFlags::Final;
+
+    fn get_metadata() -> &'static ClassMetadata<Self> {
+        &RUST_WAKER_META
+    }
+
+    fn method_builders() -> Vec<(FunctionBuilder<'static>, MethodFlags)> {
+        unimplemented!()
+    }
+
+    fn constructor() -> Option<ConstructorMeta<Self>> {
+        None
+    }
+
+    fn constants() -> &'static [(&'static str, &'static dyn IntoZvalDyn, DocComments)] {
+        unimplemented!()
+    }
+}
+
+class_derives!(RustWaker);
+
+/// Records whether the future of a [`RustWaker`] has been woken.
+#[derive(Debug, Default)]
+struct WakeFlag(AtomicBool);
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// Sets the executor running the futures of `async` functions, replacing the
+/// [`LocalExecutor`]. Usually called in the module startup function.
+///
+/// # Parameters
+///
+/// * `executor` - The executor.
+pub fn set_executor<E: Executor + 'static>(executor: E) {
+    *EXECUTOR.write() = Some(Arc::new(executor));
+}
+
+/// Runs a future to completion.
+///
+/// In a fiber which can be suspended, the fiber is suspended with a
+/// [`RustWaker`] each time the future is pending. Otherwise, the future is run with the executor set with
+/// [`set_executor`], blocking the current thread.
+///
+/// Used by the functions generated for `async` functions and methods.
+///
+/// # Parameters
+///
+/// * `future` - The future to run.
+///
+/// # Errors
+///
+/// * [`Error::FiberInterrupted`] - If the fiber is resumed with an exception,
+///   e.g. when it is destroyed. The future is dropped, and the exception is
+///   left pending.
+///
+/// # Panics
+///
+/// * If the executor returns before the future has completed.
+///
+/// [`Error::FiberInterrupted`]: crate::error::Error::FiberInterrupted
+pub fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    // The executor is cloned so that the lock is not held while PHP code runs.
+    let executor = EXECUTOR
+        .read()
+        .clone()
+        .unwrap_or_else(|| Arc::new(LocalExecutor));
+
+    let mut output = None;
+    {
+        let mut future = pin!(async {
+            output = Some(future.await);
+        });
+
+        #[cfg(php81)]
+        if ZendFiber::can_suspend() {
+            let handle = RustWaker::default();
+            let object = ZendClassObject::new(handle.clone()).into_zval(false)?;
+            let waker = handle.waker();
+            let mut cx = Context::from_waker(&waker);
+            loop {
+                handle.reset();
+                if executor.poll(future.as_mut(), &mut cx).is_ready() {
+                    break;
+                }
+                ZendFiber::suspend(&object)?;
+            }
+        } else {
+            executor.block_on(future);
+        }
+
+        #[cfg(not(php81))]
+        executor.block_on(future);
+    }
+
+    Ok(output.expect("The executor returned before the future completed"))
+}
@@ -3,7 +3,10 @@
 #![allow(clippy::all)]
 #![allow(warnings)]
 
-use std::{ffi::c_void, os::raw::c_char};
+use std::{
+    ffi::c_void,
+    os::raw::{c_char, c_int},
+};
 
 pub const ZEND_MM_ALIGNMENT: isize = 8;
 pub const ZEND_MM_ALIGNMENT_MASK: isize = -8;
@@ -49,6 +52,78 @@ unsafe extern "C" {
         filename: *const c_char,
     ) -> *mut zend_op_array;
     pub fn ext_php_rs_zend_execute(op_array: *mut zend_op_array);
+    pub fn ext_php_rs_var_serialize(value: *mut zval) -> *mut zend_string;
+    pub fn ext_php_rs_var_unserialize(
+        retval: *mut zval,
+        buf: *const c_char,
+        len: usize,
+        offset: *mut usize,
+    ) -> bool;
+    pub fn ext_php_rs_zend_object_to_weakref_key(object: *const zend_object) -> zend_ulong;
+    pub fn ext_php_rs_zval_natural_compare(
+        op1: *mut zval,
+        op2: *mut zval,
+        fold_case: bool,
+    ) -> c_int;
+    pub fn ext_php_rs_parse_arg_weak(arg: *mut zval, type_: u32, arg_num: u32) -> bool;
+    pub fn ext_php_rs_argument_error(
+        error_ce: *mut zend_class_entry,
+        arg_num: u32,
+        message: *const c_char,
+    );
+    pub fn ext_php_rs_zval_value_name(arg: *const zval) -> *const c_char;
+    pub fn ext_php_rs_type_list_alloc(num_types: u32) -> *mut zend_type_list;
+    pub fn ext_php_rs_deprecate_function(
+        function_table: *mut HashTable,
+        name: *const c_char,
+        len: usize,
+        message: *const c_char,
+        since: *const c_char,
+    ) -> bool;
+    pub fn ext_php_rs_deprecate_class_constant(
+        ce: *mut zend_class_entry,
+        name: *const c_char,
+        len: usize,
+        message: *const c_char,
+        since: *const c_char,
+    ) -> bool;
+    pub fn ext_php_rs_deprecate_constant(
+        name: *const c_char,
+        len: usize,
+        message: *const c_char,
+        since: *const c_char,
+    ) -> bool;
+    pub fn ext_php_rs_add_attribute(
+        attributes: *mut *mut HashTable,
+        name: *const c_char,
+        len: usize,
+        argc: u32,
+        offset: u32,
+    ) -> *mut zend_attribute;
+    pub fn ext_php_rs_attribute_set_arg(
+        attr: *mut zend_attribute,
+        index: u32,
+        name: *const c_char,
+        len: usize,
+        value: *mut zval,
+    );
+    pub fn ext_php_rs_function_attributes(
+        function_table: *mut HashTable,
+        name: *const c_char,
+        len: usize,
+    ) -> *mut *mut HashTable;
+    pub fn ext_php_rs_property_attributes(
+        ce: *mut zend_class_entry,
+        name: *const c_char,
+        len: usize,
+    ) -> *mut *mut HashTable;
+    pub fn ext_php_rs_class_constant_attributes(
+        ce: *mut zend_class_entry,
+        name: *const c_char,
+        len: usize,
+    ) -> *mut *mut HashTable;
+    pub fn ext_php_rs_declare_attribute_class(ce: *mut zend_class_entry, flags: u32);
+    pub fn ext_php_rs_zval_make_ref(zv: *mut zval);
 }
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
@@ -2,10 +2,10 @@
 
 use bitflags::bitflags;
 
-#[cfg(php81)]
-use crate::ffi::ZEND_ACC_ENUM;
 #[cfg(not(php82))]
 use crate::ffi::ZEND_ACC_REUSE_GET_ITERATOR;
+#[cfg(php85)]
+use crate::ffi::ZEND_ATTRIBUTE_TARGET_CONST;
 use crate::ffi::{
     _IS_BOOL, CONST_CS, CONST_DEPRECATED, CONST_NO_FILE_CACHE, CONST_PERSISTENT, E_COMPILE_ERROR,
     E_COMPILE_WARNING, E_CORE_ERROR, E_CORE_WARNING, E_DEPRECATED, E_ERROR, E_NOTICE, E_PARSE,
@@ -24,8 +24,17 @@ use crate::ffi::{
     ZEND_ACC_PROMOTED, ZEND_ACC_PROTECTED, ZEND_ACC_PUBLIC, ZEND_ACC_RESOLVED_INTERFACES,
     ZEND_ACC_RESOLVED_PARENT, ZEND_ACC_RETURN_REFERENCE, ZEND_ACC_STATIC, ZEND_ACC_STRICT_TYPES,
     ZEND_ACC_TOP_LEVEL, ZEND_ACC_TRAIT, ZEND_ACC_TRAIT_CLONE, ZEND_ACC_UNRESOLVED_VARIANCE,
-    ZEND_ACC_USE_GUARDS, ZEND_ACC_USES_THIS, ZEND_ACC_VARIADIC, ZEND_EVAL_CODE,
-    ZEND_HAS_STATIC_IN_METHODS, ZEND_INTERNAL_FUNCTION, ZEND_USER_FUNCTION,
+    ZEND_ACC_USE_GUARDS, ZEND_ACC_USES_THIS, ZEND_ACC_VARIADIC, ZEND_ATTRIBUTE_IS_REPEATABLE,
+    ZEND_ATTRIBUTE_TARGET_ALL, ZEND_ATTRIBUTE_TARGET_CLASS, ZEND_ATTRIBUTE_TARGET_CLASS_CONST,
+    ZEND_ATTRIBUTE_TARGET_FUNCTION, ZEND_ATTRIBUTE_TARGET_METHOD, ZEND_ATTRIBUTE_TARGET_PARAMETER,
+    ZEND_ATTRIBUTE_TARGET_PROPERTY, ZEND_EVAL_CODE, ZEND_HAS_STATIC_IN_METHODS,
+    ZEND_INTERNAL_FUNCTION, ZEND_USER_FUNCTION,
+};
+#[cfg(php81)]
+use crate::ffi::{ZEND_ACC_ENUM, ZEND_ACC_READONLY};
+#[cfg(php84)]
+use crate::ffi::{
+    ZEND_ACC_PRIVATE_SET, ZEND_ACC_PROTECTED_SET, ZEND_ACC_PUBLIC_SET, ZEND_ACC_VIRTUAL,
 };
 
 use std::{convert::TryFrom, fmt::Display};
@@ -242,6 +251,21 @@ bitflags! {
         const Static = ZEND_ACC_STATIC;
         /// Promoted property
         const Promoted = ZEND_ACC_PROMOTED;
+        /// Readonly property (PHP 8.1+)
+        #[cfg(php81)]
+        const Readonly = ZEND_ACC_READONLY;
+        /// Virtual property, without backing storage (PHP 8.4+)
+        #[cfg(php84)]
+        const Virtual = ZEND_ACC_VIRTUAL;
+        /// Set visibility public (PHP 8.4+)
+        #[cfg(php84)]
+        const PublicSet = ZEND_ACC_PUBLIC_SET;
+        /// Set visibility protected (PHP 8.4+)
+        #[cfg(php84)]
+        const ProtectedSet = ZEND_ACC_PROTECTED_SET;
+        /// Set visibility private (PHP 8.4+)
+        #[cfg(php84)]
+        const PrivateSet = ZEND_ACC_PRIVATE_SET;
     }
 }
 
@@ -276,6 +300,34 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags of attribute classes, the `Attribute::TARGET_*` and
+    /// `Attribute::IS_REPEATABLE` constants, which restrict where an attribute
+    /// can be applied.
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+    pub struct AttributeFlags: u32 {
+        /// Classes, interfaces, traits and enums
+        const Class = ZEND_ATTRIBUTE_TARGET_CLASS;
+        /// Functions
+        const Function = ZEND_ATTRIBUTE_TARGET_FUNCTION;
+        /// Methods
+        const Method = ZEND_ATTRIBUTE_TARGET_METHOD;
+        /// Properties
+        const Property = ZEND_ATTRIBUTE_TARGET_PROPERTY;
+        /// Class constants and enum cases
+        const ClassConstant = ZEND_ATTRIBUTE_TARGET_CLASS_CONST;
+        /// Parameters
+        const Parameter = ZEND_ATTRIBUTE_TARGET_PARAMETER;
+        /// Global constants (PHP 8.5+)
+        #[cfg(php85)]
+        const Constant = ZEND_ATTRIBUTE_TARGET_CONST;
+        /// All targets
+        const All = ZEND_ATTRIBUTE_TARGET_ALL;
+        /// The attribute can be applied several times to the same item
+        const Repeatable = ZEND_ATTRIBUTE_IS_REPEATABLE;
+    }
+}
+
 bitflags! {
     /// Represents the result of a function.
     #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
@@ -361,6 +413,28 @@ impl From<u8> for FunctionType {
     }
 }
 
+/// Comparisons used to sort arrays, as the `$flags` parameter of PHP's
+/// `sort()` function.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+pub enum SortFlags {
+    /// `SORT_REGULAR`: compares values with the comparison operators.
+    #[default]
+    Regular,
+    /// `SORT_NUMERIC`: compares values as numbers.
+    Numeric,
+    /// `SORT_STRING`: compares values as strings.
+    String,
+    /// `SORT_STRING | SORT_FLAG_CASE`: compares values as strings, case
+    /// insensitively.
+    StringCaseInsensitive,
+    /// `SORT_NATURAL`: compares values as strings, using natural ordering like
+    /// `natsort()`.
+    Natural,
+    /// `SORT_NATURAL | SORT_FLAG_CASE`: compares values as strings, using case
+    /// insensitive natural ordering like `natcasesort()`.
+    NaturalCaseInsensitive,
+}
+
 /// Valid data types for PHP.
 #[repr(C, u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
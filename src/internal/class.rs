@@ -5,7 +5,7 @@ use crate::{
     class::{ClassEntryInfo, ConstructorMeta, RegisteredClass},
     convert::{IntoZval, IntoZvalDyn},
     describe::DocComments,
-    flags::MethodFlags,
+    flags::{DataType, MethodFlags},
     internal::property::PropertyDescriptor,
 };
 
@@ -80,6 +80,37 @@ impl<T: RegisteredClass> PhpClassImpl<T> for &'_ PhpClassImplCollector<T> {
     }
 }
 
+/// Collector used to resolve the PHP type a template parameter of a generic
+/// class is instantiated with.
+pub struct TemplateTypeCollector<T>(PhantomData<T>);
+
+impl<T> Default for TemplateTypeCollector<T> {
+    #[inline]
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+pub trait TemplateType {
+    fn template_type(self) -> DataType;
+}
+
+/// Types which can be converted into zvals are rendered with their PHP type.
+impl<T: IntoZval> TemplateType for TemplateTypeCollector<T> {
+    #[inline]
+    fn template_type(self) -> DataType {
+        T::TYPE
+    }
+}
+
+/// Other types are rendered as `mixed`, through autoref specialization.
+impl<T> TemplateType for &'_ TemplateTypeCollector<T> {
+    #[inline]
+    fn template_type(self) -> DataType {
+        DataType::Mixed
+    }
+}
+
 // This implementation is only used for `TYPE` and `NULLABLE`.
 impl<T: RegisteredClass + IntoZval> IntoZval for PhpClassImplCollector<T> {
     const TYPE: crate::flags::DataType = T::TYPE;
@@ -16,7 +16,8 @@ pub type ModuleStartupMutex = Mutex<Option<ModuleStartup>>;
 pub const MODULE_STARTUP_INIT: ModuleStartupMutex = const_mutex(None);
 
 /// Called by startup functions registered with the [`#[php_startup]`] macro.
-/// Initializes all classes that are defined by ext-php-rs (i.e. `Closure`).
+/// Initializes all classes that are defined by ext-php-rs (i.e. `Closure`,
+/// `RustIterator` and `RustWaker`).
 ///
 /// [`#[php_startup]`]: `crate::php_startup`
 // TODO: Measure this
@@ -25,4 +26,15 @@ pub const MODULE_STARTUP_INIT: ModuleStartupMutex = const_mutex(None);
 pub fn ext_php_rs_startup() {
     #[cfg(feature = "closure")]
     crate::closure::Closure::build();
+    crate::iterator::RustIterator::build();
+    #[cfg(feature = "async")]
+    crate::executor::RustWaker::build();
+}
+
+/// Called by the post-deactivate function registered by the [`#[php_module]`]
+/// macro, once a request has been shut down.
+///
+/// [`#[php_module]`]: `crate::php_module`
+pub fn ext_php_rs_post_deactivate() {
+    crate::types::end_request();
 }
@@ -1,5 +1,8 @@
+#[cfg(php84)]
+use crate::{class::RegisteredClass, zend::ExecuteData};
 use crate::{
-    describe::DocComments, exception::PhpResult, flags::DataType, flags::PropertyFlags, types::Zval,
+    convert::IntoZvalDyn, describe::DocComments, exception::PhpResult, flags::DataType,
+    flags::PropertyFlags, types::Zval, zend_fastcall,
 };
 
 /// Describes a property on a PHP class backed by Rust.
@@ -37,11 +40,72 @@ pub struct PropertyDescriptor<T: 'static> {
     pub nullable: bool,
     /// Whether the property is read-only.
     pub readonly: bool,
+    /// Default value of the declared property, e.g. for
+    /// `ReflectionProperty::getDefaultValue()`. The value of the property is
+    /// always read from the Rust struct.
+    pub default: Option<&'static (dyn IntoZvalDyn + Sync)>,
 }
 
-// 64-bit: 96 bytes, 32-bit: ~56 bytes.
-// Bound: 12 pointer-sized words = 96 on 64-bit, 48 on 32-bit.
+// 64-bit: 112 bytes, 32-bit: ~64 bytes.
+// Bound: 14 pointer-sized words = 112 on 64-bit, 56 on 32-bit.
 const _: () = assert!(
-    std::mem::size_of::<PropertyDescriptor<()>>() <= 12 * std::mem::size_of::<usize>(),
+    std::mem::size_of::<PropertyDescriptor<()>>() <= 14 * std::mem::size_of::<usize>(),
     "PropertyDescriptor grew beyond expected size"
 );
+
+zend_fastcall! {
+    /// The handler of the `get` hook of a method property, which calls the
+    /// getter of the property (PHP 8.4+).
+    #[cfg(php84)]
+    pub(crate) extern fn get_hook<T: RegisteredClass>(ex: &mut ExecuteData, retval: &mut Zval) {
+        let result = hooked_property::<T>(ex).and_then(|(this, desc)| {
+            let getter = desc.get.ok_or("No getter available for this property.")?;
+            getter(this, retval)
+        });
+        if let Err(e) = result {
+            let _ = e.throw();
+        }
+    }
+}
+
+zend_fastcall! {
+    /// The handler of the `set` hook of a method property, which calls the
+    /// setter of the property with the assigned value (PHP 8.4+).
+    #[cfg(php84)]
+    pub(crate) extern fn set_hook<T: RegisteredClass>(ex: &mut ExecuteData, _: &mut Zval) {
+        // SAFETY: The value is the only argument of the hook, and outlives the
+        // call.
+        let value = unsafe { ex.zend_call_arg(0) };
+        let result = hooked_property::<T>(ex).and_then(|(this, desc)| {
+            let setter = desc.set.ok_or("No setter available for this property.")?;
+            setter(this, value.ok_or("No value given to the property hook.")?)
+        });
+        if let Err(e) = result {
+            let _ = e.throw();
+        }
+    }
+}
+
+/// Returns the object and the descriptor of the method property whose hook is
+/// called.
+#[cfg(php84)]
+fn hooked_property<T: RegisteredClass>(
+    ex: &mut ExecuteData,
+) -> PhpResult<(&mut T, &'static PropertyDescriptor<T>)> {
+    let name = ex
+        .function()
+        .and_then(|func| unsafe { func.common.prop_info.as_ref() })
+        .and_then(|info| unsafe { info.name.as_ref() })
+        .ok_or("Property hook called without a property.")?;
+    // The names of private and protected properties are mangled.
+    let name = name.as_str()?.rsplit('\0').next().unwrap_or_default();
+    let desc = T::get_metadata()
+        .method_properties()
+        .iter()
+        .find(|desc| desc.name == name)
+        .ok_or("Property hook called for an unknown property.")?;
+    let this = ex
+        .get_object::<T>()
+        .ok_or("Failed to retrieve reference to `this` object.")?;
+    Ok((&mut **this, desc))
+}
@@ -0,0 +1,373 @@
+//! Types and functions used for exporting Rust iterators to PHP.
+//!
+//! Objects of classes with a `get_iterator` handler are traversed natively by
+//! `foreach`, without calling `Iterator` methods from PHP. Rust iterators are
+//! exported as [`RustIterator`] objects, and classes exported with the
+//! `#[php(iterator)]` option of the [`php_class`](crate::php_class) macro are
+//! traversed with their [`Iterator`] implementation.
+
+use std::{alloc::Layout, os::raw::c_int, ptr};
+
+use crate::{
+    alloc::emalloc,
+    builders::{ClassBuilder, FunctionBuilder},
+    class::{ClassEntryInfo, ClassMetadata, ConstructorMeta, RegisteredClass},
+    convert::{IntoZval, IntoZvalDyn},
+    describe::DocComments,
+    error::{Error, Result},
+    exception::PhpException,
+    ffi::{
+        ZEND_RESULT_CODE, ZEND_RESULT_CODE_FAILURE, ZEND_RESULT_CODE_SUCCESS,
+        zend_create_internal_iterator_zval, zend_iterator_init, zend_object_iterator_funcs,
+    },
+    flags::{ClassFlags, MethodFlags},
+    types::{ZendClassObject, ZendIterator, ZendObject, Zval},
+    zend::{ClassEntry, ExecuteData, ExecutorGlobals},
+    zend_fastcall,
+};
+
+/// Class entry and handlers for Rust iterators.
+static RUST_ITERATOR_META: ClassMetadata<RustIterator> = ClassMetadata::new(&[]);
+
+/// Handler creating the iterator of an object, used by `foreach`.
+///
+/// The handler is called with the class of the object, the object, and
+/// whether the object is traversed by reference.
+pub type GetIterator = unsafe extern "C" fn(*mut ClassEntry, *mut Zval, c_int) -> *mut ZendIterator;
+
+/// The key, if any, and the value of an item of an iterator.
+type Entry = Result<(Option<Zval>, Zval)>;
+
+/// Returns the next entry of the iterator of an object, or [`None`] once the
+/// iterator is exhausted.
+type NextEntry = fn(&mut ZendObject) -> Option<Entry>;
+
+/// Implemented on the items of iterators exported to PHP.
+///
+/// Items which implement [`IntoZval`] are the values of the iteration, and are
+/// given consecutive integer keys starting at 0. `(K, V)` pairs are iterated
+/// as keys and values, and keys can be of any type, as with generators.
+pub trait IteratorItem {
+    /// Converts the item into its key, if it has one, and its value.
+    ///
+    /// # Errors
+    ///
+    /// If the key or the value cannot be converted into a [`Zval`].
+    fn into_entry(self) -> Result<(Option<Zval>, Zval)>;
+}
+
+impl<T: IntoZval> IteratorItem for T {
+    fn into_entry(self) -> Result<(Option<Zval>, Zval)> {
+        Ok((None, self.into_zval(false)?))
+    }
+}
+
+impl<K: IntoZval, V: IntoZval> IteratorItem for (K, V) {
+    fn into_entry(self) -> Result<(Option<Zval>, Zval)> {
+        Ok((Some(self.0.into_zval(false)?), self.1.into_zval(false)?))
+    }
+}
+
+/// Wrapper around a Rust iterator, which can be exported to PHP.
+///
+/// Items are produced lazily while PHP traverses the iterator, and must
+/// implement [`IteratorItem`]: values implementing [`IntoZval`], or `(K, V)`
+/// pairs for keyed iteration. Functions exported with the
+/// [`php_function`](crate::php_function) macro which return
+/// `impl Iterator<Item = T>` wrap the iterator automatically.
+///
+/// Internally, a final class `RustIterator` implementing `IteratorAggregate`
+/// is registered. Its objects are traversed by `foreach` through a
+/// `get_iterator` handler, without calling PHP methods:
+///
+/// ```php
+/// <?php
+///
+/// final class RustIterator implements IteratorAggregate {
+///     public function getIterator(): Iterator {}
+/// }
+/// ```
+///
+/// Rust iterators cannot be rewound. Traversing a `RustIterator` again
+/// resumes where the previous traversal stopped, and yields nothing once the
+/// iterator is exhausted.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ext_php_rs::iterator::RustIterator;
+///
+/// let squares = RustIterator::new((1..=10).map(|i: i64| i * i));
+/// let named = RustIterator::new([("one", 1), ("two", 2)]);
+/// ```
+pub struct RustIterator(Box<dyn Iterator<Item = Entry>>);
+
+impl RustIterator {
+    /// Wraps a Rust iterator into a type which can be returned to PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `iter` - The iterator to wrap, which must have a static lifetime.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: IteratorItem + 'static,
+    {
+        Self(Box::new(iter.into_iter().map(IteratorItem::into_entry)))
+    }
+
+    /// Builds the class entry for [`RustIterator`], registering it with PHP.
+    /// This function should only be called once inside your module startup
+    /// function.
+    ///
+    /// If the class has already been built, this function returns early without
+    /// doing anything. This allows for safe repeated calls in test
+    /// environments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `RustIterator` PHP class cannot be registered.
+    pub fn build() {
+        if RUST_ITERATOR_META.has_ce() {
+            return;
+        }
+
+        ClassBuilder::new(Self::CLASS_NAME)
+            .flags(Self::FLAGS)
+            .iterator(Self::get_iterator)
+            .object_override::<Self>()
+            .registration(|ce| RUST_ITERATOR_META.set_ce(ce))
+            .register()
+            .expect("Failed to build `RustIterator` PHP class.");
+    }
+
+    /// The `get_iterator` handler of the `RustIterator` class.
+    unsafe extern "C" fn get_iterator(
+        _: *mut ClassEntry,
+        object: *mut Zval,
+        by_ref: c_int,
+    ) -> *mut ZendIterator {
+        ObjectIterator::create(object, by_ref, |obj| {
+            match ZendClassObject::<Self>::from_zend_obj_mut(obj) {
+                Some(this) => this.0.next(),
+                None => Some(Err(Error::InvalidScope)),
+            }
+        })
+    }
+}
+
+impl RegisteredClass for RustIterator {
+    const CLASS_NAME: &'static str = "RustIterator";
+
+    const BUILDER_MODIFIER: Option<fn(ClassBuilder) -> ClassBuilder> = None;
+    const EXTENDS: Option<ClassEntryInfo> = None;
+    const IMPLEMENTS: &'static [ClassEntryInfo] = &[];
+    const FLAGS: ClassFlags = ClassFlags::Final;
+
+    fn get_metadata() -> &'static ClassMetadata<Self> {
+        &RUST_ITERATOR_META
+    }
+
+    fn method_builders() -> Vec<(FunctionBuilder<'static>, MethodFlags)> {
+        unimplemented!()
+    }
+
+    fn constructor() -> Option<ConstructorMeta<Self>> {
+        None
+    }
+
+    fn constants() -> &'static [(&'static str, &'static dyn IntoZvalDyn, DocComments)] {
+        unimplemented!()
+    }
+}
+
+class_derives!(RustIterator);
+
+/// Returns the `get_iterator` handler of a class implementing [`Iterator`],
+/// which traverses its objects by calling [`Iterator::next`].
+///
+/// The [`php_class`](crate::php_class) macro returns it from
+/// [`RegisteredClass::get_iterator`] when the `#[php(iterator)]` option is
+/// given.
+#[must_use]
+pub fn get_iterator<T>() -> GetIterator
+where
+    T: RegisteredClass + Iterator,
+    T::Item: IteratorItem,
+{
+    unsafe extern "C" fn handler<T>(
+        _: *mut ClassEntry,
+        object: *mut Zval,
+        by_ref: c_int,
+    ) -> *mut ZendIterator
+    where
+        T: RegisteredClass + Iterator,
+        T::Item: IteratorItem,
+    {
+        ObjectIterator::create(
+            object,
+            by_ref,
+            |obj| match ZendClassObject::<T>::from_zend_obj_mut(obj) {
+                Some(this) => this.next().map(IteratorItem::into_entry),
+                None => Some(Err(Error::InvalidScope)),
+            },
+        )
+    }
+
+    handler::<T>
+}
+
+zend_fastcall! {
+    /// The `getIterator()` method of classes with a `get_iterator` handler,
+    /// which wraps the handler into an `InternalIterator`.
+    pub(crate) extern fn get_iterator_method(ex: &mut ExecuteData, retval: &mut Zval) {
+        if ex.parser().parse().is_err() {
+            return;
+        }
+        unsafe { zend_create_internal_iterator_zval(retval, &raw mut ex.This) };
+    }
+}
+
+/// Iterator over an object, with the key and the value of its current item.
+///
+/// Allocated by the Zend memory manager, and freed by the engine once its
+/// `dtor` has been called.
+#[repr(C)]
+struct ObjectIterator {
+    /// Must be the first field, as the engine frees the iterator through it.
+    it: ZendIterator,
+    next: NextEntry,
+    started: bool,
+    valid: bool,
+    position: i64,
+    key: Zval,
+    value: Zval,
+}
+
+/// Functions of [`ObjectIterator`]s, called by the engine.
+static OBJECT_ITERATOR_FUNCS: zend_object_iterator_funcs = zend_object_iterator_funcs {
+    dtor: Some(ObjectIterator::dtor),
+    valid: Some(ObjectIterator::valid),
+    get_current_data: Some(ObjectIterator::get_current_data),
+    get_current_key: Some(ObjectIterator::get_current_key),
+    move_forward: Some(ObjectIterator::move_forward),
+    rewind: Some(ObjectIterator::rewind),
+    invalidate_current: None,
+    get_gc: None,
+};
+
+impl ObjectIterator {
+    /// Creates an iterator over an object, or throws an exception and returns
+    /// null if the object is traversed by reference.
+    fn create(object: *mut Zval, by_ref: c_int, next: NextEntry) -> *mut ZendIterator {
+        if by_ref != 0 {
+            let _ = PhpException::default(
+                "An iterator cannot be used with foreach by reference".into(),
+            )
+            .throw();
+            return ptr::null_mut();
+        }
+        let Some(obj) = (unsafe { object.as_mut() }).and_then(Zval::object_mut) else {
+            return ptr::null_mut();
+        };
+
+        // Allocations of the Zend memory manager are aligned to 8 bytes.
+        #[allow(clippy::cast_ptr_alignment)]
+        let iter = emalloc(Layout::new::<Self>()).cast::<Self>();
+        // SAFETY: The iterator has been allocated, and its fields are
+        // initialized without reading or dropping the uninitialized memory.
+        unsafe {
+            ptr::write(&raw mut (*iter).next, next);
+            ptr::write(&raw mut (*iter).started, false);
+            ptr::write(&raw mut (*iter).valid, false);
+            ptr::write(&raw mut (*iter).position, 0);
+            ptr::write(&raw mut (*iter).key, Zval::new());
+            ptr::write(&raw mut (*iter).value, Zval::new());
+
+            zend_iterator_init(&raw mut (*iter).it);
+            let mut data = Zval::new();
+            data.set_object(obj);
+            ptr::write(&raw mut (*iter).it.data, data);
+            (*iter).it.funcs = &raw const OBJECT_ITERATOR_FUNCS;
+            (*iter).it.index = 0;
+
+            &raw mut (*iter).it
+        }
+    }
+
+    /// Fetches the next item of the iterator.
+    fn fetch(&mut self) {
+        self.started = true;
+        self.valid = false;
+        let Some(obj) = self.it.data.object_mut() else {
+            return;
+        };
+        match (self.next)(obj) {
+            Some(Ok((key, value))) => {
+                self.key = key.unwrap_or_else(|| {
+                    let mut key = Zval::new();
+                    key.set_long(self.position);
+                    key
+                });
+                self.value = value;
+                self.position += 1;
+                self.valid = true;
+            }
+            Some(Err(e)) => {
+                let _ = PhpException::from(e).throw();
+            }
+            None => {}
+        }
+    }
+
+    /// Returns the [`ObjectIterator`] of an iterator created by
+    /// [`ObjectIterator::create`].
+    unsafe fn from_raw<'a>(iter: *mut ZendIterator) -> &'a mut Self {
+        unsafe { &mut *iter.cast::<Self>() }
+    }
+
+    unsafe extern "C" fn dtor(iter: *mut ZendIterator) {
+        let iter = unsafe { Self::from_raw(iter) };
+        // The iterator itself is freed by the engine, so only its fields are
+        // dropped, releasing the object and the current item.
+        unsafe {
+            ptr::drop_in_place(&raw mut iter.it.data);
+            ptr::drop_in_place(&raw mut iter.key);
+            ptr::drop_in_place(&raw mut iter.value);
+        }
+    }
+
+    unsafe extern "C" fn valid(iter: *mut ZendIterator) -> ZEND_RESULT_CODE {
+        let iter = unsafe { Self::from_raw(iter) };
+        if !iter.started {
+            iter.fetch();
+        }
+        if iter.valid && !ExecutorGlobals::has_exception() {
+            ZEND_RESULT_CODE_SUCCESS
+        } else {
+            ZEND_RESULT_CODE_FAILURE
+        }
+    }
+
+    unsafe extern "C" fn get_current_data(iter: *mut ZendIterator) -> *mut Zval {
+        &raw mut unsafe { Self::from_raw(iter) }.value
+    }
+
+    unsafe extern "C" fn get_current_key(iter: *mut ZendIterator, key: *mut Zval) {
+        let iter = unsafe { Self::from_raw(iter) };
+        unsafe { ptr::write(key, iter.key.shallow_clone()) };
+    }
+
+    unsafe extern "C" fn move_forward(iter: *mut ZendIterator) {
+        unsafe { Self::from_raw(iter) }.fetch();
+    }
+
+    unsafe extern "C" fn rewind(iter: *mut ZendIterator) {
+        // Rust iterators cannot be rewound, so only the first item is fetched.
+        let iter = unsafe { Self::from_raw(iter) };
+        if !iter.started {
+            iter.fetch();
+        }
+    }
+}
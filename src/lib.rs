@@ -10,6 +10,8 @@
 
 pub mod alloc;
 pub mod args;
+pub mod array_access;
+pub mod attribute;
 pub mod binary;
 pub mod binary_slice;
 pub mod builders;
@@ -26,12 +28,17 @@ pub mod class;
 #[cfg_attr(docs, doc(cfg(feature = "closure")))]
 pub mod closure;
 pub mod constant;
+pub mod deprecation;
 pub mod describe;
 #[cfg(feature = "embed")]
 pub mod embed;
 #[cfg(feature = "enum")]
 pub mod enum_;
+#[cfg(feature = "async")]
+#[cfg_attr(docs, doc(cfg(feature = "async")))]
+pub mod executor;
 pub mod php_eval;
+pub mod php_serialize;
 #[cfg(feature = "observer")]
 #[cfg_attr(docs, doc(cfg(feature = "observer")))]
 pub mod observer {
@@ -43,11 +50,17 @@ pub mod observer {
 }
 #[doc(hidden)]
 pub mod internal;
+pub mod iterator;
+pub mod operator;
 
 // Re-export inventory for use by macros
 #[doc(hidden)]
 pub use inventory;
 pub mod rc;
+pub mod resource;
+#[cfg(feature = "serde")]
+#[cfg_attr(docs, doc(cfg(feature = "serde")))]
+pub mod serde;
 #[cfg(test)]
 pub mod test;
 pub mod types;
@@ -58,11 +71,13 @@ pub mod zend;
 /// and imports.
 pub mod prelude {
 
+    pub use crate::array_access::{DimensionFetch, PhpArrayAccess};
     pub use crate::builders::ModuleBuilder;
     #[cfg(any(docs, feature = "closure"))]
     #[cfg_attr(docs, doc(cfg(feature = "closure")))]
     pub use crate::closure::Closure;
     pub use crate::exception::{PhpException, PhpResult};
+    pub use crate::iterator::RustIterator;
     #[cfg(feature = "enum")]
     pub use crate::php_enum;
     pub use crate::php_print;
@@ -76,8 +91,9 @@ pub mod prelude {
     };
     pub use crate::zend::{BailoutGuard, ModuleGlobal, ModuleGlobals};
     pub use crate::{
-        ZvalConvert, php_class, php_const, php_extern, php_function, php_impl, php_impl_interface,
-        php_interface, php_module, wrap_constant, wrap_function, zend_fastcall,
+        ZvalConvert, php_attribute, php_class, php_const, php_extern, php_function, php_impl,
+        php_impl_interface, php_interface, php_module, php_resource, php_trait, wrap_constant,
+        wrap_function, zend_fastcall,
     };
 }
 
@@ -108,6 +124,7 @@ pub const PHP_85: bool = cfg!(php85);
 #[cfg(feature = "enum")]
 pub use ext_php_rs_derive::php_enum;
 pub use ext_php_rs_derive::{
-    ZvalConvert, php_class, php_const, php_extern, php_function, php_impl, php_impl_interface,
-    php_interface, php_module, wrap_constant, wrap_function, zend_fastcall,
+    ZvalConvert, php_attribute, php_class, php_const, php_extern, php_function, php_impl,
+    php_impl_interface, php_interface, php_module, php_resource, php_trait, wrap_constant,
+    wrap_function, zend_fastcall,
 };
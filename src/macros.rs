@@ -389,6 +389,51 @@ macro_rules! class_derives {
     };
 }
 
+/// Implements `FromZval` and `IntoZval` for a type which implements
+/// [`RegisteredEnum`] and [`RegisteredClass`], converting it to and from the
+/// cases of the PHP enum. This is done automatically by the [`php_enum`]
+/// macro.
+///
+/// These implementations are required while we wait on the stabilisation of
+/// specialisation.
+///
+/// [`RegisteredEnum`]: crate::enum_::RegisteredEnum
+/// [`RegisteredClass`]: crate::class::RegisteredClass
+/// [`php_enum`]: crate::php_enum
+#[macro_export]
+macro_rules! enum_derives {
+    ($type: ty) => {
+        impl<'a> $crate::convert::FromZval<'a> for $type {
+            const TYPE: $crate::flags::DataType = $crate::flags::DataType::Object(Some(
+                <$type as $crate::class::RegisteredClass>::CLASS_NAME,
+            ));
+
+            #[inline]
+            fn from_zval(zval: &'a $crate::types::Zval) -> ::std::option::Option<Self> {
+                <Self as $crate::convert::FromZendObject>::from_zend_object(zval.object()?).ok()
+            }
+        }
+
+        impl $crate::convert::IntoZval for $type {
+            const TYPE: $crate::flags::DataType = $crate::flags::DataType::Object(Some(
+                <$type as $crate::class::RegisteredClass>::CLASS_NAME,
+            ));
+            const NULLABLE: bool = false;
+
+            #[inline]
+            fn set_zval(
+                self,
+                zv: &mut $crate::types::Zval,
+                persistent: bool,
+            ) -> $crate::error::Result<()> {
+                use $crate::convert::IntoZendObject;
+
+                self.into_zend_object()?.set_zval(zv, persistent)
+            }
+        }
+    };
+}
+
 /// Derives `From<T> for Zval` and `IntoZval` for a given type.
 macro_rules! into_zval {
     ($type: ty, $fn: ident, $dt: ident) => {
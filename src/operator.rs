@@ -0,0 +1,229 @@
+//! Types and functions used for overloading PHP operators on Rust classes.
+//!
+//! Objects of classes with a `do_operation` handler are used as operands of
+//! arithmetic operators, e.g. `$a + $b` or `$a * 2`, like the objects of the
+//! `GMP` class. Classes exported with the `#[php(operators(...))]` option of
+//! the [`php_class`](crate::php_class) macro apply the operators with their
+//! [`std::ops`] implementations. Objects of classes with a `cast_object`
+//! handler are converted into strings, e.g. by `$a . $b` or `echo $a`.
+
+use std::{os::raw::c_int, ptr};
+
+use crate::{
+    class::RegisteredClass,
+    exception::PhpResult,
+    ffi::{
+        IS_STRING, ZEND_ADD, ZEND_DIV, ZEND_MOD, ZEND_MUL, ZEND_POW, ZEND_RESULT_CODE,
+        ZEND_RESULT_CODE_FAILURE, ZEND_RESULT_CODE_SUCCESS, ZEND_SUB, std_object_handlers,
+    },
+    types::{ZendClassObject, ZendObject, Zval},
+};
+
+/// Handler applying an operator to two operands, of which at least one is an
+/// object with the handler.
+///
+/// The handler is called with the opcode of the operator, the zval receiving
+/// the result, which is the left operand for compound assignments, and the
+/// left and right operands.
+pub type DoOperation =
+    unsafe extern "C" fn(u8, *mut Zval, *mut Zval, *mut Zval) -> ZEND_RESULT_CODE;
+
+/// Handler converting an object into another type, e.g. a string.
+///
+/// The handler is called with the object, the zval receiving the result, and
+/// the type to convert the object into.
+pub type CastObject = unsafe extern "C" fn(*mut ZendObject, *mut Zval, c_int) -> ZEND_RESULT_CODE;
+
+/// The PHP operators which can be overloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// `$a + $b`, applied with [`std::ops::Add`].
+    Add,
+    /// `$a - $b`, applied with [`std::ops::Sub`].
+    Sub,
+    /// `$a * $b`, applied with [`std::ops::Mul`].
+    Mul,
+    /// `$a / $b`, applied with [`std::ops::Div`].
+    Div,
+    /// `$a % $b`, applied with [`std::ops::Rem`].
+    Mod,
+    /// `$a ** $b`, applied with [`Pow`].
+    Pow,
+    /// `-$a`, applied with [`std::ops::Neg`]. PHP compiles it to `$a * -1`.
+    Neg,
+    /// `(string) $a`, used by `$a . $b`, `echo $a` and string interpolation,
+    /// which converts the object with [`std::fmt::Display`].
+    Concat,
+}
+
+/// The side of a binary operator the object is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The object is the left operand, e.g. `$a * 2`.
+    Left,
+    /// The object is the right operand, e.g. `2 * $a`.
+    Right,
+}
+
+impl Operator {
+    /// Returns the binary operator of an opcode, if it can be overloaded by
+    /// the `do_operation` handler. `Concat` is applied by the `cast_object`
+    /// handler instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `opcode` - The opcode of the operator.
+    #[must_use]
+    pub fn from_opcode(opcode: u8) -> Option<Self> {
+        Some(match u32::from(opcode) {
+            ZEND_ADD => Self::Add,
+            ZEND_SUB => Self::Sub,
+            ZEND_MUL => Self::Mul,
+            ZEND_DIV => Self::Div,
+            ZEND_MOD => Self::Mod,
+            ZEND_POW => Self::Pow,
+            _ => return None,
+        })
+    }
+}
+
+/// The power operator, which has no trait in [`std::ops`].
+pub trait Pow<Rhs = Self> {
+    /// The type of the result.
+    type Output;
+
+    /// Raises `self` to the power of `rhs`.
+    ///
+    /// # Parameters
+    ///
+    /// * `rhs` - The exponent.
+    fn pow(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Implemented on classes whose objects are operands of PHP operators.
+///
+/// The [`php_class`](crate::php_class) macro implements it when the
+/// `#[php(operators(...))]` option is given.
+pub trait PhpOperators {
+    /// Applies an operator to the object and another operand, returning
+    /// [`None`] if the operator is not supported for the operand, in which
+    /// case PHP throws a `TypeError`.
+    ///
+    /// The other operand is ignored by `Neg`, and by `Concat`, which returns
+    /// the object converted into a string.
+    ///
+    /// # Parameters
+    ///
+    /// * `op` - The operator to apply.
+    /// * `other` - The other operand.
+    /// * `side` - The side of the operator the object is on. Operators are not
+    ///   assumed to be commutative, so `2 * $a` is applied with `Side::Right`.
+    fn apply_operator(&self, op: Operator, other: &Zval, side: Side) -> Option<PhpResult<Zval>>;
+}
+
+/// Returns the `do_operation` handler of a class implementing [`PhpOperators`].
+///
+/// The [`php_class`](crate::php_class) macro returns it from
+/// [`RegisteredClass::do_operation`] when the `#[php(operators(...))]` option
+/// is given.
+#[must_use]
+pub fn do_operation<T: RegisteredClass + PhpOperators>() -> DoOperation {
+    unsafe extern "C" fn handler<T: RegisteredClass + PhpOperators>(
+        opcode: u8,
+        result: *mut Zval,
+        op1: *mut Zval,
+        op2: *mut Zval,
+    ) -> ZEND_RESULT_CODE {
+        let (Some(lhs), Some(rhs)) = (unsafe { op1.as_ref() }, unsafe { op2.as_ref() }) else {
+            return ZEND_RESULT_CODE_FAILURE;
+        };
+        let Some(value) = apply::<T>(opcode, lhs, rhs) else {
+            return ZEND_RESULT_CODE_FAILURE;
+        };
+        // The result is the left operand for compound assignments, which is
+        // released, and is uninitialized otherwise.
+        let compound = ptr::eq(result, op1);
+        let value = value.unwrap_or_else(|e| {
+            let _ = e.throw();
+            Zval::new()
+        });
+        if compound {
+            unsafe { *result = value };
+        } else {
+            unsafe { ptr::write(result, value) };
+        }
+        ZEND_RESULT_CODE_SUCCESS
+    }
+
+    handler::<T>
+}
+
+/// Applies an operator to two operands, of which at least one is an object of
+/// the class.
+fn apply<T: RegisteredClass + PhpOperators>(
+    opcode: u8,
+    lhs: &Zval,
+    rhs: &Zval,
+) -> Option<PhpResult<Zval>> {
+    let op = Operator::from_opcode(opcode)?;
+    if let Some(this) = object::<T>(lhs) {
+        if op == Operator::Mul
+            && rhs.long() == Some(-1)
+            && let Some(result) = this.apply_operator(Operator::Neg, rhs, Side::Left)
+        {
+            return Some(result);
+        }
+        return this.apply_operator(op, rhs, Side::Left);
+    }
+    object::<T>(rhs)?.apply_operator(op, lhs, Side::Right)
+}
+
+/// Returns the `cast_object` handler of a class implementing [`PhpOperators`]
+/// with `Concat`, converting its objects into strings.
+///
+/// Other conversions, e.g. into booleans, are left to the standard handler.
+///
+/// The [`php_class`](crate::php_class) macro returns it from
+/// [`RegisteredClass::cast_object`] when the `#[php(operators(...))]` option
+/// includes `Concat`.
+#[must_use]
+pub fn cast_object<T: RegisteredClass + PhpOperators>() -> CastObject {
+    unsafe extern "C" fn handler<T: RegisteredClass + PhpOperators>(
+        object: *mut ZendObject,
+        retval: *mut Zval,
+        ty: c_int,
+    ) -> ZEND_RESULT_CODE {
+        let this = unsafe { object.as_ref() }.and_then(ZendClassObject::<T>::from_zend_obj);
+        let string = match this {
+            Some(this) if u32::try_from(ty) == Ok(IS_STRING) => {
+                this.apply_operator(Operator::Concat, &Zval::new(), Side::Left)
+            }
+            _ => None,
+        };
+        match string {
+            Some(Ok(value)) => {
+                // The result is uninitialized.
+                unsafe { ptr::write(retval, value) };
+                ZEND_RESULT_CODE_SUCCESS
+            }
+            Some(Err(e)) => {
+                let _ = e.throw();
+                ZEND_RESULT_CODE_FAILURE
+            }
+            None => match unsafe { std_object_handlers.cast_object } {
+                Some(cast) => unsafe { cast(object, retval, ty) },
+                None => ZEND_RESULT_CODE_FAILURE,
+            },
+        }
+    }
+
+    handler::<T>
+}
+
+/// Returns the Rust struct of an operand, if it is an object of the class.
+fn object<T: RegisteredClass>(operand: &Zval) -> Option<&T> {
+    operand
+        .object()
+        .and_then(ZendClassObject::<T>::from_zend_obj)
+        .map(|object| &**object)
+}
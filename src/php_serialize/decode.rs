@@ -0,0 +1,356 @@
+use super::{Error, Key, Result, Value};
+
+/// Maximum nesting depth of arrays and objects, to avoid overflowing the stack
+/// on malicious input.
+const MAX_DEPTH: usize = 128;
+
+/// Decodes a [`Value`] from data in the PHP serialization format.
+///
+/// Besides the output of `serialize()`, the legacy `S:` escaped string
+/// representation is accepted.
+///
+/// # Parameters
+///
+/// * `data` - The serialized data.
+///
+/// # Errors
+///
+/// * [`Error::Syntax`] - If the data is not valid serialized data, or if a
+///   back-reference points at a value which does not exist.
+/// * [`Error::TrailingData`] - If there is data after the end of the value.
+///
+/// # Example
+///
+/// ```
+/// use ext_php_rs::php_serialize::{Value, decode};
+///
+/// let value = decode(br#"a:1:{s:4:"name";s:5:"Alice";}"#).unwrap();
+/// assert_eq!(value.get("name"), Some(&Value::from("Alice")));
+/// ```
+pub fn decode(data: &[u8]) -> Result<Value> {
+    let mut decoder = Decoder {
+        data,
+        pos: 0,
+        slots: 0,
+        depth: 0,
+    };
+    let value = decoder.value()?;
+    if decoder.pos != data.len() {
+        return Err(Error::TrailingData(decoder.pos));
+    }
+    Ok(value)
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Number of slots available to back-references.
+    slots: usize,
+    depth: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn error<T>(&self, message: &'static str) -> Result<T> {
+        Err(Error::syntax(self.pos, message))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<()> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(message)
+        }
+    }
+
+    /// Reads bytes until the given delimiter, consuming the delimiter.
+    fn until(&mut self, delimiter: u8) -> Result<&'a [u8]> {
+        let start = self.pos;
+        let Some(len) = self.data[start..].iter().position(|b| *b == delimiter) else {
+            return self.error("unexpected end of data");
+        };
+        self.pos += len + 1;
+        Ok(&self.data[start..start + len])
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let start = self.pos;
+        match start.checked_add(len) {
+            Some(end) if end <= self.data.len() => {
+                self.pos = end;
+                Ok(&self.data[start..end])
+            }
+            _ => self.error("unexpected end of data"),
+        }
+    }
+
+    fn int(&mut self, delimiter: u8) -> Result<i64> {
+        let start = self.pos;
+        let digits = self.until(delimiter)?;
+        std::str::from_utf8(digits)
+            .ok()
+            .filter(|s| !s.starts_with('+'))
+            .and_then(|s| s.parse().ok())
+            .ok_or(Error::syntax(start, "invalid integer"))
+    }
+
+    fn len(&mut self, delimiter: u8) -> Result<usize> {
+        let start = self.pos;
+        let len = self.int(delimiter)?;
+        usize::try_from(len).map_err(|_| Error::syntax(start, "invalid length"))
+    }
+
+    fn float(&mut self) -> Result<f64> {
+        let start = self.pos;
+        let digits = self.until(b';')?;
+        let d = match digits {
+            b"NAN" => f64::NAN,
+            b"INF" => f64::INFINITY,
+            b"-INF" => f64::NEG_INFINITY,
+            digits => std::str::from_utf8(digits)
+                .ok()
+                .filter(|s| {
+                    s.bytes()
+                        .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b))
+                })
+                .and_then(|s| s.parse().ok())
+                .ok_or(Error::syntax(start, "invalid float"))?,
+        };
+        Ok(d)
+    }
+
+    /// Reads a length-prefixed string after its tag, e.g. `5:"hello"`.
+    fn str(&mut self) -> Result<Vec<u8>> {
+        let len = self.len(b':')?;
+        self.expect(b'"', "expected '\"'")?;
+        let s = self.take(len)?.to_vec();
+        self.expect(b'"', "string length does not match")?;
+        Ok(s)
+    }
+
+    /// Reads an escaped string after its tag, e.g. `5:"h\65llo"`.
+    fn escaped_str(&mut self) -> Result<Vec<u8>> {
+        let len = self.len(b':')?;
+        self.expect(b'"', "expected '\"'")?;
+        let mut s = Vec::with_capacity(len);
+        while s.len() < len {
+            match self.take(1)?[0] {
+                b'\\' => {
+                    let start = self.pos;
+                    let hex = self.take(2)?;
+                    let byte = std::str::from_utf8(hex)
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                        .ok_or(Error::syntax(start, "invalid escape sequence"))?;
+                    s.push(byte);
+                }
+                byte => s.push(byte),
+            }
+        }
+        self.expect(b'"', "string length does not match")?;
+        Ok(s)
+    }
+
+    fn class_name(&mut self) -> Result<String> {
+        let start = self.pos;
+        let name = self.str()?;
+        String::from_utf8(name).map_err(|_| Error::syntax(start, "invalid class name"))
+    }
+
+    fn key(&mut self) -> Result<Key> {
+        let tag = self.take(2)?;
+        let key = match tag {
+            b"i:" => Key::Int(self.int(b';')?),
+            b"s:" => Key::String(self.str()?),
+            b"S:" => Key::String(self.escaped_str()?),
+            _ => {
+                self.pos -= 2;
+                return self.error("expected an int or a string key");
+            }
+        };
+        if !matches!(key, Key::Int(_)) {
+            self.expect(b';', "expected ';'")?;
+        }
+        Ok(key)
+    }
+
+    /// Reads `count` entries enclosed in braces.
+    fn entries(&mut self, count: usize) -> Result<Vec<(Key, Value)>> {
+        self.expect(b'{', "expected '{'")?;
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return self.error("maximum depth exceeded");
+        }
+        // Do not trust the count for the allocation, each entry takes at least
+        // four bytes.
+        let mut entries = Vec::with_capacity(count.min(self.data.len() / 4));
+        for _ in 0..count {
+            let key = self.key()?;
+            let value = self.value()?;
+            entries.push((key, value));
+        }
+        self.depth -= 1;
+        self.expect(b'}', "expected '}'")?;
+        Ok(entries)
+    }
+
+    fn slot(&mut self) -> Result<usize> {
+        let start = self.pos;
+        let slot = self.len(b';')?;
+        if slot == 0 || slot > self.slots {
+            return Err(Error::syntax(start, "invalid back-reference"));
+        }
+        Ok(slot)
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let start = self.pos;
+        let tag = self.take(2)?;
+        match tag {
+            // References do not occupy a slot.
+            b"R:" => return Ok(Value::Reference(self.slot()?)),
+            // Object references cannot point at themselves.
+            b"r:" => {
+                let slot = self.slot()?;
+                self.slots += 1;
+                return Ok(Value::ObjectReference(slot));
+            }
+            _ => self.slots += 1,
+        }
+
+        let value = match tag {
+            b"N;" => Value::Null,
+            b"b:" => {
+                let b = match self.until(b';')? {
+                    b"0" => false,
+                    b"1" => true,
+                    _ => return Err(Error::syntax(start + 2, "invalid boolean")),
+                };
+                Value::Bool(b)
+            }
+            b"i:" => Value::Int(self.int(b';')?),
+            b"d:" => Value::Float(self.float()?),
+            b"s:" => {
+                let s = self.str()?;
+                self.expect(b';', "expected ';'")?;
+                Value::String(s)
+            }
+            b"S:" => {
+                let s = self.escaped_str()?;
+                self.expect(b';', "expected ';'")?;
+                Value::String(s)
+            }
+            b"a:" => {
+                let count = self.len(b':')?;
+                Value::Array(self.entries(count)?)
+            }
+            b"O:" => {
+                let class = self.class_name()?;
+                self.expect(b':', "expected ':'")?;
+                let count = self.len(b':')?;
+                let properties = self.entries(count)?;
+                Value::Object { class, properties }
+            }
+            b"C:" => {
+                let class = self.class_name()?;
+                self.expect(b':', "expected ':'")?;
+                let len = self.len(b':')?;
+                self.expect(b'{', "expected '{'")?;
+                let data = self.take(len)?.to_vec();
+                self.expect(b'}', "expected '}'")?;
+                Value::Custom { class, data }
+            }
+            b"E:" => {
+                let name_start = self.pos;
+                let name = self.str()?;
+                self.expect(b';', "expected ';'")?;
+                let name = String::from_utf8(name)
+                    .map_err(|_| Error::syntax(name_start, "invalid enum case"))?;
+                let Some((class, case)) = name.split_once(':') else {
+                    return Err(Error::syntax(name_start, "invalid enum case"));
+                };
+                Value::Enum {
+                    class: class.to_string(),
+                    case: case.to_string(),
+                }
+            }
+            _ => return Err(Error::syntax(start, "unknown type")),
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::php_serialize::encode;
+
+    #[test]
+    fn test_decode_scalars() {
+        assert_eq!(decode(b"N;").unwrap(), Value::Null);
+        assert_eq!(decode(b"b:0;").unwrap(), Value::Bool(false));
+        assert_eq!(decode(b"i:-42;").unwrap(), Value::Int(-42));
+        assert_eq!(decode(b"d:1.0E+25;").unwrap(), Value::Float(1e25));
+        assert_eq!(decode(b"d:0.1;").unwrap(), Value::Float(0.1));
+        assert_eq!(decode(b"d:-INF;").unwrap(), Value::Float(f64::NEG_INFINITY));
+        assert_eq!(decode(b"s:3:\"a\"b\";").unwrap(), Value::from("a\"b"));
+        assert_eq!(decode(b"S:3:\"a\\62c\";").unwrap(), Value::from("abc"));
+    }
+
+    #[test]
+    fn test_decode_nested() {
+        let data = b"a:2:{i:0;O:8:\"stdClass\":1:{s:1:\"a\";E:11:\"Suit:Hearts\";}i:1;r:2;}";
+        let value = decode(data).unwrap();
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                (
+                    Key::Int(0),
+                    Value::Object {
+                        class: "stdClass".into(),
+                        properties: vec![(
+                            Key::from("a"),
+                            Value::Enum {
+                                class: "Suit".into(),
+                                case: "Hearts".into(),
+                            }
+                        )],
+                    }
+                ),
+                (Key::Int(1), Value::ObjectReference(2)),
+            ])
+        );
+        assert_eq!(encode(&value), data);
+
+        let data = b"C:11:\"ArrayObject\":6:{x:i:0;}";
+        assert_eq!(encode(&decode(data).unwrap()), data);
+    }
+
+    #[test]
+    fn test_decode_references() {
+        // `$a = [1]; $a[] = &$a[0];`
+        let data = b"a:2:{i:0;i:1;i:1;R:2;}";
+        assert_eq!(encode(&decode(data).unwrap()), data);
+
+        assert!(decode(b"a:1:{i:0;R:3;}").is_err());
+        assert!(decode(b"r:1;").is_err());
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        match decode(b"a:1:{i:0;x:1;}") {
+            Err(Error::Syntax { offset, .. }) => assert_eq!(offset, 9),
+            res => panic!("unexpected result: {res:?}"),
+        }
+        assert!(matches!(decode(b"i:1;i:2;"), Err(Error::TrailingData(4))));
+        assert!(decode(b"s:10:\"short\";").is_err());
+        assert!(decode(b"a:1:{d:1.5;i:1;}").is_err());
+        assert!(decode(b"i:+1;").is_err());
+        assert!(decode(&b"a:1:{i:0;".repeat(MAX_DEPTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use std::io::Write;
+
+use super::{Key, Value};
+
+/// Encodes a [`Value`] in the PHP serialization format.
+///
+/// The output is identical to the output of `serialize()` for the equivalent
+/// PHP value with the default `serialize_precision` of `-1`.
+///
+/// # Parameters
+///
+/// * `value` - The value to encode.
+///
+/// # Example
+///
+/// ```
+/// use ext_php_rs::php_serialize::{Key, Value, encode};
+///
+/// let value = Value::Array(vec![
+///     (Key::Int(0), Value::from("a")),
+///     (Key::from("b"), Value::Int(1)),
+/// ]);
+/// assert_eq!(encode(&value), br#"a:2:{i:0;s:1:"a";s:1:"b";i:1;}"#);
+/// ```
+#[must_use]
+pub fn encode(value: &Value) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_value(&mut buf, value);
+    buf
+}
+
+// Writing into a `Vec<u8>` cannot fail, so the results of `write!` are ignored.
+
+fn write_value(buf: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => buf.extend_from_slice(b"N;"),
+        Value::Bool(b) => {
+            let _ = write!(buf, "b:{};", u8::from(*b));
+        }
+        Value::Int(i) => {
+            let _ = write!(buf, "i:{i};");
+        }
+        Value::Float(d) => {
+            buf.extend_from_slice(b"d:");
+            write_float(buf, *d);
+            buf.push(b';');
+        }
+        Value::String(s) => {
+            write_str(buf, b's', s);
+            buf.push(b';');
+        }
+        Value::Array(entries) => {
+            let _ = write!(buf, "a:{}:", entries.len());
+            write_entries(buf, entries);
+        }
+        Value::Object { class, properties } => {
+            write_str(buf, b'O', class.as_bytes());
+            let _ = write!(buf, ":{}:", properties.len());
+            write_entries(buf, properties);
+        }
+        Value::Custom { class, data } => {
+            write_str(buf, b'C', class.as_bytes());
+            let _ = write!(buf, ":{}:{{", data.len());
+            buf.extend_from_slice(data);
+            buf.push(b'}');
+        }
+        Value::Enum { class, case } => {
+            let _ = write!(
+                buf,
+                "E:{}:\"{class}:{case}\";",
+                class.len() + case.len() + 1
+            );
+        }
+        Value::ObjectReference(slot) => {
+            let _ = write!(buf, "r:{slot};");
+        }
+        Value::Reference(slot) => {
+            let _ = write!(buf, "R:{slot};");
+        }
+    }
+}
+
+/// Writes a length-prefixed string, e.g. `s:5:"hello"`.
+fn write_str(buf: &mut Vec<u8>, tag: u8, s: &[u8]) {
+    buf.push(tag);
+    let _ = write!(buf, ":{}:\"", s.len());
+    buf.extend_from_slice(s);
+    buf.push(b'"');
+}
+
+fn write_entries(buf: &mut Vec<u8>, entries: &[(Key, Value)]) {
+    buf.push(b'{');
+    for (key, value) in entries {
+        match key {
+            Key::Int(i) => {
+                let _ = write!(buf, "i:{i};");
+            }
+            Key::String(s) => {
+                write_str(buf, b's', s);
+                buf.push(b';');
+            }
+        }
+        write_value(buf, value);
+    }
+    buf.push(b'}');
+}
+
+/// Writes a float the way `serialize()` does with a `serialize_precision` of
+/// `-1`: the shortest representation which round-trips, in exponential
+/// notation if the exponent is less than -4 or greater than 16, e.g. `0.1`,
+/// `1.0E-5` or `1.0E+25`.
+fn write_float(buf: &mut Vec<u8>, d: f64) {
+    if d.is_nan() {
+        buf.extend_from_slice(b"NAN");
+        return;
+    }
+    if d.is_infinite() {
+        buf.extend_from_slice(if d > 0.0 { b"INF" } else { b"-INF" });
+        return;
+    }
+    if d.is_sign_negative() {
+        buf.push(b'-');
+    }
+
+    // Rust formats the shortest round-trip representation in exponential
+    // notation as `d.ddde[-]x`.
+    let formatted = format!("{:e}", d.abs());
+    let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let exponent: i32 = exponent.parse().unwrap_or_default();
+    // Position of the decimal point relative to the digits.
+    let decpt = exponent + 1;
+
+    if !(-3..=17).contains(&decpt) {
+        let (first, rest) = digits.split_at(1);
+        let rest = if rest.is_empty() { "0" } else { rest };
+        let sign = if exponent < 0 { '-' } else { '+' };
+        let _ = write!(buf, "{first}.{rest}E{sign}{}", exponent.unsigned_abs());
+    } else if decpt <= 0 {
+        buf.extend_from_slice(b"0.");
+        buf.extend(std::iter::repeat_n(b'0', decpt.unsigned_abs() as usize));
+        buf.extend_from_slice(digits.as_bytes());
+    } else {
+        let decpt = decpt.unsigned_abs() as usize;
+        if digits.len() > decpt {
+            let (int, frac) = digits.split_at(decpt);
+            let _ = write!(buf, "{int}.{frac}");
+        } else {
+            buf.extend_from_slice(digits.as_bytes());
+            buf.extend(std::iter::repeat_n(b'0', decpt - digits.len()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn float(d: f64) -> String {
+        let mut buf = Vec::new();
+        write_float(&mut buf, d);
+        String::from_utf8(buf).expect("floats are ASCII")
+    }
+
+    #[test]
+    fn test_encode_scalars() {
+        assert_eq!(encode(&Value::Null), b"N;");
+        assert_eq!(encode(&Value::Bool(true)), b"b:1;");
+        assert_eq!(encode(&Value::Int(-42)), b"i:-42;");
+        assert_eq!(encode(&Value::from("héllo")), "s:6:\"héllo\";".as_bytes());
+    }
+
+    #[test]
+    fn test_encode_floats() {
+        assert_eq!(float(1.5), "1.5");
+        assert_eq!(float(1.0), "1");
+        assert_eq!(float(-0.0), "-0");
+        assert_eq!(float(0.1), "0.1");
+        assert_eq!(float(0.0001), "0.0001");
+        assert_eq!(float(0.00001), "1.0E-5");
+        assert_eq!(float(123_456_789.25), "123456789.25");
+        assert_eq!(float(1e16), "10000000000000000");
+        assert_eq!(float(1e17), "1.0E+17");
+        assert_eq!(float(1e25), "1.0E+25");
+        assert_eq!(float(1.5e-7), "1.5E-7");
+        assert_eq!(float(f64::INFINITY), "INF");
+        assert_eq!(float(f64::NEG_INFINITY), "-INF");
+        assert_eq!(float(f64::NAN), "NAN");
+    }
+
+    #[test]
+    fn test_encode_objects() {
+        let value = Value::Object {
+            class: "Foo".into(),
+            properties: vec![
+                (Key::from("a"), Value::Int(1)),
+                (Key::from("\0Foo\0b"), Value::ObjectReference(1)),
+            ],
+        };
+        assert_eq!(
+            encode(&value),
+            b"O:3:\"Foo\":2:{s:1:\"a\";i:1;s:6:\"\0Foo\0b\";r:1;}"
+        );
+
+        let value = Value::Enum {
+            class: "Suit".into(),
+            case: "Hearts".into(),
+        };
+        assert_eq!(encode(&value), b"E:11:\"Suit:Hearts\";");
+
+        let value = Value::Custom {
+            class: "ArrayObject".into(),
+            data: b"x:i:0;".to_vec(),
+        };
+        assert_eq!(encode(&value), b"C:11:\"ArrayObject\":6:{x:i:0;}");
+    }
+}
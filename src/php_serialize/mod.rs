@@ -0,0 +1,92 @@
+//! Encoding and decoding of the format used by PHP's
+//! [`serialize()`](https://www.php.net/manual/en/function.serialize.php) and
+//! [`unserialize()`](https://www.php.net/manual/en/function.unserialize.php)
+//! functions.
+//!
+//! The format can be converted in two ways:
+//!
+//! * [`serialize`] and [`unserialize`] convert between [`Zval`]s and
+//!   serialized data through the engine, with the exact same semantics as the
+//!   PHP functions. Magic methods such as `__serialize()` and
+//!   `__unserialize()` are called, and objects of `#[php_class]` types
+//!   implementing them round-trip.
+//! * [`encode`] and [`decode`] convert between [`Value`]s and serialized data
+//!   in pure Rust, and can be used without a running engine, e.g. to read
+//!   `serialize()` blobs stored in a cache or a database.
+//!
+//! [`Value::from_zval`] and [`Value::to_zval`] convert between the two
+//! representations.
+//!
+//! [`Zval`]: crate::types::Zval
+
+mod decode;
+mod encode;
+mod value;
+mod zval;
+
+use std::fmt::{self, Display};
+
+pub use decode::decode;
+pub use encode::encode;
+pub use value::{Key, Value};
+pub use zval::{serialize, unserialize};
+
+use crate::{boxed::ZBox, convert::IntoZval, exception::PhpException, types::ZendObject};
+
+/// Result type returned by the serialization functions.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Error returned when encoding or decoding serialized data.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The data is not valid serialized data.
+    Syntax {
+        /// Offset in bytes of the error in the data.
+        offset: usize,
+        /// Description of the error.
+        message: &'static str,
+    },
+    /// The data is valid, but contains bytes after the end of the value.
+    TrailingData(usize),
+    /// An exception was thrown by the engine, e.g. when serializing a closure
+    /// or when `__unserialize()` failed.
+    Exception(ZBox<ZendObject>),
+}
+
+impl Error {
+    pub(crate) fn syntax(offset: usize, message: &'static str) -> Self {
+        Self::Syntax { offset, message }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax { offset, message } => write!(f, "Error at offset {offset}: {message}"),
+            Error::TrailingData(offset) => {
+                write!(
+                    f,
+                    "Unexpected data after the end of the value at offset {offset}"
+                )
+            }
+            Error::Exception(e) => write!(f, "Exception was thrown: {e:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for PhpException {
+    fn from(err: Error) -> Self {
+        let message = err.to_string();
+        match err {
+            // Rethrow the original exception.
+            Error::Exception(e) => match e.into_zval(false) {
+                Ok(zv) => Self::default(message).with_object(zv),
+                Err(_) => Self::default(message),
+            },
+            _ => Self::default(message),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::{Result, decode, encode, serialize, unserialize};
+use crate::types::Zval;
+
+/// A value in the PHP serialization format, which can be used without a
+/// running engine.
+///
+/// Back-references refer to previously decoded values by their 1-based slot
+/// number. Every value occupies a slot in the order it appears in the data,
+/// except array keys, property names and [`Value::Reference`]s.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `null`, serialized as `N;`.
+    Null,
+    /// A boolean, serialized as `b:1;`.
+    Bool(bool),
+    /// An integer, serialized as `i:42;`.
+    Int(i64),
+    /// A float, serialized as `d:1.5;`.
+    Float(f64),
+    /// A binary string, serialized as `s:5:"hello";`.
+    String(Vec<u8>),
+    /// An array, serialized as `a:1:{i:0;s:1:"a";}`.
+    Array(Vec<(Key, Value)>),
+    /// An object, serialized as `O:8:"stdClass":1:{s:1:"a";i:1;}`.
+    ///
+    /// For classes implementing `__serialize()`, the properties contain the
+    /// array returned by the method. Otherwise, the names of private and
+    /// protected properties are mangled as `"\0Class\0name"` and
+    /// `"\0*\0name"` respectively.
+    Object {
+        /// Name of the class of the object.
+        class: String,
+        /// Properties of the object.
+        properties: Vec<(Key, Value)>,
+    },
+    /// An object of a class implementing the `Serializable` interface,
+    /// serialized as `C:11:"ArrayObject":4:{data}`.
+    Custom {
+        /// Name of the class of the object.
+        class: String,
+        /// Data returned by `Serializable::serialize()`.
+        data: Vec<u8>,
+    },
+    /// An enum case, serialized as `E:11:"Suit:Hearts";`.
+    Enum {
+        /// Name of the enum.
+        class: String,
+        /// Name of the case.
+        case: String,
+    },
+    /// A back-reference to a previously serialized object, serialized as
+    /// `r:1;`. The object is shared, as with an assignment.
+    ObjectReference(usize),
+    /// A back-reference to a previously serialized value, serialized as
+    /// `R:1;`. The value is shared, as with a PHP reference (`&$value`).
+    Reference(usize),
+}
+
+/// A key of a [`Value::Array`] or a property name of a [`Value::Object`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Key {
+    /// An integer key.
+    Int(i64),
+    /// A string key.
+    String(Vec<u8>),
+}
+
+impl Value {
+    /// Converts a [`Zval`] into a value, as `serialize()` would.
+    ///
+    /// # Parameters
+    ///
+    /// * `zval` - The value to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be serialized, see [`serialize`].
+    pub fn from_zval(zval: &Zval) -> Result<Self> {
+        decode(&serialize(zval)?)
+    }
+
+    /// Converts the value into a [`Zval`], as `unserialize()` would.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be unserialized, see
+    /// [`unserialize`].
+    pub fn to_zval(&self) -> Result<Zval> {
+        unserialize(&encode(self))
+    }
+
+    /// Returns the value of an array entry or of a property by its key.
+    ///
+    /// Returns [`None`] if the value is not an array or an object, or if the
+    /// key does not exist.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key to look up.
+    #[must_use]
+    pub fn get(&self, key: impl Into<Key>) -> Option<&Value> {
+        let key = key.into();
+        match self {
+            Value::Array(entries)
+            | Value::Object {
+                properties: entries,
+                ..
+            } => entries.iter().find_map(|(k, v)| (*k == key).then_some(v)),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(value.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value.into_bytes())
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(value: Vec<u8>) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+impl From<i64> for Key {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<&str> for Key {
+    fn from(value: &str) -> Self {
+        Self::String(value.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for Key {
+    fn from(value: String) -> Self {
+        Self::String(value.into_bytes())
+    }
+}
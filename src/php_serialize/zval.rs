@@ -0,0 +1,143 @@
+use std::ptr;
+
+use super::{Error, Result};
+use crate::{
+    boxed::ZBox,
+    ffi::{ext_php_rs_var_serialize, ext_php_rs_var_unserialize},
+    types::{ZendStr, Zval},
+    zend::ExecutorGlobals,
+};
+
+/// Serializes a [`Zval`] through the engine, as `serialize()` does.
+///
+/// Objects are serialized with their `__serialize()`, `__sleep()` or
+/// `Serializable::serialize()` methods when they implement them.
+///
+/// # Parameters
+///
+/// * `zval` - The value to serialize.
+///
+/// # Errors
+///
+/// Returns [`Error::Exception`] if an exception was thrown while serializing
+/// the value, e.g. if it contains a closure or an object of a class which
+/// cannot be serialized.
+pub fn serialize(zval: &Zval) -> Result<Vec<u8>> {
+    let result = unsafe { ext_php_rs_var_serialize(ptr::from_ref(zval).cast_mut()) };
+
+    if let Some(e) = ExecutorGlobals::take_exception() {
+        return Err(Error::Exception(e));
+    }
+
+    if result.is_null() {
+        return Err(Error::syntax(0, "value could not be serialized"));
+    }
+
+    // SAFETY: The string returned by the engine is owned by us, and released
+    // when the box is dropped.
+    let zs: ZBox<ZendStr> = unsafe { ZBox::from_raw(result) };
+    Ok(zs.as_bytes().to_vec())
+}
+
+/// Unserializes a [`Zval`] through the engine, as `unserialize()` does.
+///
+/// Objects are created with their `__unserialize()`, `__wakeup()` or
+/// `Serializable::unserialize()` methods when they implement them.
+///
+/// # Parameters
+///
+/// * `data` - The serialized data.
+///
+/// # Errors
+///
+/// * [`Error::Syntax`] - If the data is not valid serialized data.
+/// * [`Error::TrailingData`] - If there is data after the end of the value.
+/// * [`Error::Exception`] - If an exception was thrown while unserializing
+///   the value, e.g. by `__unserialize()`.
+pub fn unserialize(data: &[u8]) -> Result<Zval> {
+    let mut zv = Zval::new();
+    let mut offset = 0;
+    let result = unsafe {
+        ext_php_rs_var_unserialize(
+            &raw mut zv,
+            data.as_ptr().cast(),
+            data.len(),
+            &raw mut offset,
+        )
+    };
+
+    if let Some(e) = ExecutorGlobals::take_exception() {
+        return Err(Error::Exception(e));
+    }
+    if !result {
+        return Err(Error::syntax(offset, "invalid serialized data"));
+    }
+    if offset != data.len() {
+        return Err(Error::TrailingData(offset));
+    }
+    Ok(zv)
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+    use crate::php_serialize::{Key, Value, decode};
+
+    #[test]
+    fn test_round_trip() {
+        Embed::run(|| {
+            let zv = Embed::eval("['a' => 1, 'b' => [1.5, null, true], 'c' => 'str'];").unwrap();
+            let data = serialize(&zv).unwrap();
+            assert_eq!(
+                data,
+                b"a:3:{s:1:\"a\";i:1;s:1:\"b\";a:3:{i:0;d:1.5;i:1;N;i:2;b:1;}s:1:\"c\";s:3:\"str\";}"
+            );
+
+            let zv = unserialize(&data).unwrap();
+            assert_eq!(zv.array().unwrap().get("c").unwrap().str(), Some("str"));
+        });
+    }
+
+    #[test]
+    fn test_value_conversion() {
+        Embed::run(|| {
+            let zv = Embed::eval("$o = new stdClass; $o->a = 1; [$o, $o];").unwrap();
+            let value = Value::from_zval(&zv).unwrap();
+            assert_eq!(
+                value,
+                decode(b"a:2:{i:0;O:8:\"stdClass\":1:{s:1:\"a\";i:1;}i:1;r:2;}").unwrap()
+            );
+
+            let zv = value.to_zval().unwrap();
+            let ht = zv.array().unwrap();
+            assert_eq!(
+                ht.get_index(0).unwrap().object().unwrap().get_id(),
+                ht.get_index(1).unwrap().object().unwrap().get_id()
+            );
+
+            let value = Value::Array(vec![(Key::from("x"), Value::Float(0.5))]);
+            let zv = value.to_zval().unwrap();
+            assert_eq!(zv.array().unwrap().get("x").unwrap().double(), Some(0.5));
+        });
+    }
+
+    #[test]
+    fn test_errors() {
+        Embed::run(|| {
+            assert!(matches!(
+                unserialize(b"a:1:{i:0;"),
+                Err(Error::Syntax { .. })
+            ));
+            assert!(matches!(
+                unserialize(b"i:1;i:2;"),
+                Err(Error::TrailingData(4))
+            ));
+
+            let zv = Embed::eval("function () {};").unwrap();
+            assert!(matches!(serialize(&zv), Err(Error::Exception(_))));
+        });
+    }
+}
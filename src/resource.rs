@@ -0,0 +1,146 @@
+//! Types and traits used for registering resource types with PHP.
+//!
+//! Resources are opaque handles to data owned by an extension, such as the
+//! ones returned by `fopen()` or `curl_init()`. Rust types are turned into
+//! resource types with the [`macro@php_resource`] macro, registered with
+//! [`ModuleBuilder::resource`] and wrapped into PHP values with
+//! [`ZendResource`].
+//!
+//! [`macro@php_resource`]: crate::php_resource
+//! [`ModuleBuilder::resource`]: crate::builders::ModuleBuilder::resource
+//! [`ZendResource`]: crate::types::ZendResource
+
+use std::{
+    ffi::CString,
+    marker::PhantomData,
+    ptr,
+    sync::atomic::{AtomicI32, AtomicPtr, Ordering},
+};
+
+use crate::{
+    error::Result,
+    ffi::{zend_register_list_destructors_ex, zend_resource},
+};
+
+/// Implemented on Rust types which are exported to PHP as resources.
+pub trait RegisteredResource: Sized + 'static {
+    /// Name of the resource type, as returned by `get_resource_type()`.
+    const RESOURCE_NAME: &'static str;
+
+    /// Returns a reference to the resource metadata, which stores the list
+    /// destructor identifiers of the resource type.
+    ///
+    /// This must be statically allocated, and is usually done through the
+    /// [`macro@php_resource`] macro.
+    ///
+    /// [`macro@php_resource`]: crate::php_resource
+    fn get_metadata() -> &'static ResourceMetadata<Self>;
+}
+
+/// Stores the list destructor identifiers of a resource type.
+///
+/// Two identifiers are registered for each type: one for request-scoped
+/// resources, which own their value, and one for resources pointing at a
+/// persistent value, which is owned by the persistent list and released on
+/// module shutdown.
+pub struct ResourceMetadata<T: 'static> {
+    id: AtomicI32,
+    persistent_id: AtomicI32,
+
+    // `AtomicPtr` is used here because it is `Send + Sync`.
+    phantom: PhantomData<AtomicPtr<T>>,
+}
+
+impl<T: 'static> ResourceMetadata<T> {
+    /// Creates a new, unregistered resource metadata instance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            id: AtomicI32::new(-1),
+            persistent_id: AtomicI32::new(-1),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static> Default for ResourceMetadata<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RegisteredResource> ResourceMetadata<T> {
+    /// Checks if the resource type has been registered, returning a boolean.
+    pub fn is_registered(&self) -> bool {
+        self.id.load(Ordering::SeqCst) >= 0
+    }
+
+    /// Retrieves the list destructor identifier of request-scoped resources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource type has not been registered.
+    pub fn id(&self) -> i32 {
+        let id = self.id.load(Ordering::SeqCst);
+        assert!(
+            id >= 0,
+            "Attempted to use resource type `{}` before it has been registered.",
+            T::RESOURCE_NAME
+        );
+        id
+    }
+
+    /// Retrieves the list destructor identifier of resources pointing at a
+    /// persistent value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource type has not been registered.
+    pub fn persistent_id(&self) -> i32 {
+        let id = self.persistent_id.load(Ordering::SeqCst);
+        assert!(
+            id >= 0,
+            "Attempted to use resource type `{}` before it has been registered.",
+            T::RESOURCE_NAME
+        );
+        id
+    }
+
+    /// Registers the list destructors of the resource type. Should only be
+    /// called during module startup.
+    ///
+    /// # Parameters
+    ///
+    /// * `module_number` - The module number of the extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource name contains a NUL byte.
+    pub(crate) fn register(&self, module_number: i32) -> Result<()> {
+        // The engine keeps a pointer to the name for the lifetime of the
+        // process.
+        let name = CString::new(T::RESOURCE_NAME)?.into_raw();
+        let id = unsafe {
+            zend_register_list_destructors_ex(Some(dtor::<T>), None, name, module_number)
+        };
+        let persistent_id = unsafe {
+            zend_register_list_destructors_ex(None, Some(dtor::<T>), name, module_number)
+        };
+        self.id.store(id, Ordering::SeqCst);
+        self.persistent_id.store(persistent_id, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// Drops the value owned by a resource.
+unsafe extern "C" fn dtor<T: RegisteredResource>(res: *mut zend_resource) {
+    let Some(res) = (unsafe { res.as_mut() }) else {
+        return;
+    };
+    let value = std::mem::replace(&mut res.ptr, ptr::null_mut());
+    if !value.is_null() {
+        // SAFETY: Values of resources of this type are always created from a
+        // `Box<T>`.
+        drop(unsafe { Box::from_raw(value.cast::<T>()) });
+    }
+}
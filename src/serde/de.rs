@@ -0,0 +1,795 @@
+use ::serde::de::{
+    self, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+use super::{Error, Result};
+use crate::{
+    flags::DataType,
+    types::{ArrayKey, ZendHashTable, ZendObject, Zval, array::Iter},
+};
+
+/// A borrowed PHP value the [`Deserializer`] reads from.
+#[derive(Clone, Copy)]
+enum Value<'de> {
+    Zval(&'de Zval),
+    Array(&'de ZendHashTable),
+    Object(&'de ZendObject),
+}
+
+impl<'de> Value<'de> {
+    fn new(zval: &'de Zval) -> Self {
+        let zval = zval.dereference();
+        if let Some(ht) = zval.array() {
+            Self::Array(ht)
+        } else if let Some(obj) = zval.object() {
+            Self::Object(obj)
+        } else {
+            Self::Zval(zval)
+        }
+    }
+
+    /// Returns the PHP name of the type of the value, used in error messages.
+    fn type_name(self) -> &'static str {
+        match self {
+            Self::Array(_) => "array",
+            Self::Object(_) => "object",
+            Self::Zval(zval) => match zval.get_type() {
+                DataType::Undef | DataType::Null => "null",
+                DataType::True | DataType::False | DataType::Bool => "bool",
+                DataType::Long => "int",
+                DataType::Double => "float",
+                DataType::String => "string",
+                DataType::Resource => "resource",
+                _ => "unknown",
+            },
+        }
+    }
+
+    fn zval(self) -> Option<&'de Zval> {
+        match self {
+            Self::Zval(zval) => Some(zval),
+            Self::Array(_) | Self::Object(_) => None,
+        }
+    }
+
+    fn entries(self) -> Option<Entries<'de>> {
+        match self {
+            Self::Array(ht) => Some(Entries::new(ht, false)),
+            Self::Object(obj) => obj
+                .get_properties()
+                .ok()
+                .map(|props| Entries::new(props, true)),
+            Self::Zval(_) => None,
+        }
+    }
+}
+
+/// Deserializes Rust values from borrowed [`Zval`]s, [`ZendHashTable`]s and
+/// [`ZendObject`]s.
+///
+/// * Integers, floats, booleans and strings are read from their PHP
+///   counterparts. Integers are accepted where floats are expected.
+/// * `null` is read as `None` or `()`.
+/// * Sequences are read from the values of an array, in order.
+/// * Maps and structs are read from arrays or from the properties of an
+///   object.
+/// * Enums are read following serde's externally tagged representation: a
+///   string for unit variants, or a single entry array keyed by the variant
+///   name. The internally tagged, adjacently tagged and untagged
+///   representations are handled by `serde` itself.
+///
+/// Strings are borrowed from the PHP value where possible, so `&str` fields
+/// do not allocate.
+pub struct Deserializer<'de> {
+    value: Value<'de>,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer reading from a [`Zval`]. References are
+    /// dereferenced transparently.
+    #[must_use]
+    pub fn from_zval(zval: &'de Zval) -> Self {
+        Self {
+            value: Value::new(zval),
+        }
+    }
+
+    /// Creates a deserializer reading from a [`ZendHashTable`].
+    #[must_use]
+    pub fn from_array(ht: &'de ZendHashTable) -> Self {
+        Self {
+            value: Value::Array(ht),
+        }
+    }
+
+    /// Creates a deserializer reading from the properties of a
+    /// [`ZendObject`].
+    #[must_use]
+    pub fn from_object(obj: &'de ZendObject) -> Self {
+        Self {
+            value: Value::Object(obj),
+        }
+    }
+
+    fn invalid_type(&self, expected: &str) -> Error {
+        Error::new(format!(
+            "expected {expected}, found {}",
+            self.value.type_name()
+        ))
+    }
+
+    fn long(&self) -> Result<i64> {
+        self.value
+            .zval()
+            .and_then(Zval::long)
+            .ok_or_else(|| self.invalid_type("int"))
+    }
+
+    fn str(&self) -> Result<&'de str> {
+        let zs = self
+            .value
+            .zval()
+            .and_then(Zval::zend_str)
+            .ok_or_else(|| self.invalid_type("string"))?;
+        zs.as_str()
+            .map_err(|_| Error::new("expected string, found non UTF-8 string"))
+    }
+
+    fn visit_entries<V>(self, visitor: V, expected: &str) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let entries = self
+            .value
+            .entries()
+            .ok_or_else(|| self.invalid_type(expected))?;
+        visitor.visit_map(entries)
+    }
+}
+
+/// Deserializes a Rust value from a [`Zval`].
+///
+/// # Parameters
+///
+/// * `zval` - The value to deserialize from.
+///
+/// # Errors
+///
+/// Returns an [`Error`] carrying the path of the first value which could not
+/// be converted, e.g. `$.items[3].price: expected int, found string`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::{serde::from_zval, types::Zval};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Item<'a> {
+///     name: &'a str,
+///     price: i64,
+/// }
+///
+/// fn total(items: &Zval) -> ext_php_rs::serde::Result<i64> {
+///     let items: Vec<Item> = from_zval(items)?;
+///     Ok(items.iter().map(|item| item.price).sum())
+/// }
+/// ```
+pub fn from_zval<'de, T>(zval: &'de Zval) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(Deserializer::from_zval(zval))
+}
+
+/// Deserializes a Rust value from a [`ZendHashTable`].
+///
+/// # Parameters
+///
+/// * `ht` - The array to deserialize from.
+///
+/// # Errors
+///
+/// Returns an [`Error`] carrying the path of the first value which could not
+/// be converted.
+pub fn from_array<'de, T>(ht: &'de ZendHashTable) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(Deserializer::from_array(ht))
+}
+
+/// Deserializes a Rust value from the properties of a [`ZendObject`].
+///
+/// # Parameters
+///
+/// * `obj` - The object to deserialize from.
+///
+/// # Errors
+///
+/// Returns an [`Error`] carrying the path of the first value which could not
+/// be converted.
+pub fn from_object<'de, T>(obj: &'de ZendObject) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(Deserializer::from_object(obj))
+}
+
+macro_rules! deserialize_long {
+    ($($method: ident),*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.visit_i64(self.long()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let zval = match self.value {
+            Value::Array(ht) => {
+                return if ht.has_sequential_keys() {
+                    visitor.visit_seq(Entries::new(ht, false))
+                } else {
+                    visitor.visit_map(Entries::new(ht, false))
+                };
+            }
+            Value::Object(_) => return self.visit_entries(visitor, "object"),
+            Value::Zval(zval) => zval,
+        };
+
+        match zval.get_type() {
+            DataType::Undef | DataType::Null => visitor.visit_unit(),
+            DataType::True => visitor.visit_bool(true),
+            DataType::False => visitor.visit_bool(false),
+            DataType::Long => visitor.visit_i64(self.long()?),
+            DataType::Double => visitor.visit_f64(zval.double().unwrap_or_default()),
+            DataType::String => {
+                let zs = zval.zend_str().ok_or_else(|| self.invalid_type("string"))?;
+                match zs.as_str() {
+                    Ok(s) => visitor.visit_borrowed_str(s),
+                    Err(_) => visitor.visit_borrowed_bytes(zs.as_bytes()),
+                }
+            }
+            _ => Err(Error::new(format!(
+                "cannot deserialize value of type {}",
+                self.value.type_name()
+            ))),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let b = self
+            .value
+            .zval()
+            .and_then(Zval::bool)
+            .ok_or_else(|| self.invalid_type("bool"))?;
+        visitor.visit_bool(b)
+    }
+
+    deserialize_long!(
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64
+    );
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let zval = self.value.zval();
+        if let Some(d) = zval.and_then(Zval::double) {
+            return visitor.visit_f64(d);
+        }
+        if let Some(l) = zval.and_then(Zval::long) {
+            #[allow(clippy::cast_precision_loss)]
+            return visitor.visit_f64(l as f64);
+        }
+        Err(self.invalid_type("float"))
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.str()?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if let Value::Array(ht) = self.value {
+            return visitor.visit_seq(Entries::new(ht, false));
+        }
+        let zs = self
+            .value
+            .zval()
+            .and_then(Zval::zend_str)
+            .ok_or_else(|| self.invalid_type("string"))?;
+        visitor.visit_borrowed_bytes(zs.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value.zval().map(Zval::get_type) {
+            Some(DataType::Null | DataType::Undef) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value.zval().map(Zval::get_type) {
+            Some(DataType::Null | DataType::Undef) => visitor.visit_unit(),
+            _ => Err(self.invalid_type("null")),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Array(ht) => visitor.visit_seq(Entries::new(ht, false)),
+            _ => Err(self.invalid_type("array")),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.visit_entries(visitor, "array")
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.visit_entries(visitor, "array or object")
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(s) = self.value.zval().and_then(Zval::str) {
+            return visitor.visit_enum(Enum {
+                variant: ArrayKey::Str(s),
+                value: None,
+            });
+        }
+
+        let single = match self.value {
+            Value::Array(ht) if ht.len() == 1 => ht.iter().next(),
+            _ => None,
+        };
+        match single {
+            Some((variant, value)) => visitor.visit_enum(Enum {
+                variant,
+                value: Some(value),
+            }),
+            None => Err(self.invalid_type("string or array with a single key")),
+        }
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+/// Attaches an array key to the path of an error.
+fn at(err: Error, key: &ArrayKey<'_>) -> Error {
+    match key {
+        ArrayKey::Long(index) => err.at_index(*index),
+        key => err.at_key(key.to_string()),
+    }
+}
+
+/// Provides access to the entries of an array or to the properties of an
+/// object, as a sequence or as a map.
+struct Entries<'de> {
+    iter: Iter<'de>,
+    properties: bool,
+    pending: Option<(ArrayKey<'de>, &'de Zval)>,
+}
+
+impl<'de> Entries<'de> {
+    fn new(ht: &'de ZendHashTable, properties: bool) -> Self {
+        Self {
+            iter: ht.iter(),
+            properties,
+            pending: None,
+        }
+    }
+
+    fn next_entry(&mut self) -> Option<(ArrayKey<'de>, &'de Zval)> {
+        loop {
+            let (key, value) = self.iter.next()?;
+            if !self.properties {
+                return Some((key, value));
+            }
+
+            // Declared properties are stored as indirect zvals pointing into the
+            // object property table, and uninitialized typed properties are
+            // `UNDEF`.
+            let value = value.dereference();
+            if value.get_type() == DataType::Undef {
+                continue;
+            }
+
+            // Private and protected property names are mangled as
+            // `\0Class\0name` and `\0*\0name` respectively.
+            let key = match key {
+                ArrayKey::String(name) if name.starts_with('\0') => {
+                    let name = name.rsplit('\0').next().unwrap_or_default();
+                    ArrayKey::String(name.to_string())
+                }
+                key => key,
+            };
+            return Some((key, value));
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for Entries<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let Some((key, value)) = self.next_entry() else {
+            return Ok(None);
+        };
+        seed.deserialize(Deserializer::from_zval(value))
+            .map(Some)
+            .map_err(|e| at(e, &key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+impl<'de> MapAccess<'de> for Entries<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some((key, value)) = self.next_entry() else {
+            return Ok(None);
+        };
+        let res = seed
+            .deserialize(KeyDeserializer(key.clone()))
+            .map_err(|e| at(e, &key))?;
+        self.pending = Some((key, value));
+        Ok(Some(res))
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .pending
+            .take()
+            .ok_or_else(|| Error::new("map value requested before its key"))?;
+        seed.deserialize(Deserializer::from_zval(value))
+            .map_err(|e| at(e, &key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes map keys and enum variant names from [`ArrayKey`]s.
+///
+/// Integer keys are accepted where strings are expected and numeric string
+/// keys are accepted where integers are expected, as PHP does not distinguish
+/// between the two.
+struct KeyDeserializer<'de>(ArrayKey<'de>);
+
+impl KeyDeserializer<'_> {
+    fn long(&self) -> Result<i64> {
+        self.0
+            .clone()
+            .try_into()
+            .map_err(|_| Error::new(format!("expected int key, found `{}`", self.0)))
+    }
+}
+
+macro_rules! deserialize_key_long {
+    ($($method: ident),*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.visit_i64(self.long()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            ArrayKey::Long(l) => visitor.visit_i64(l),
+            ArrayKey::String(s) => visitor.visit_string(s),
+            ArrayKey::Str(s) => visitor.visit_borrowed_str(s),
+            ArrayKey::ZendString(s) => match s.as_str() {
+                Ok(s) => visitor.visit_borrowed_str(s),
+                Err(_) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            },
+        }
+    }
+
+    deserialize_key_long!(
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64
+    );
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            ArrayKey::Long(l) => visitor.visit_string(l.to_string()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(Enum {
+            variant: self.0,
+            value: None,
+        })
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool f32 f64 char bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for KeyDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Provides access to an externally tagged enum.
+struct Enum<'de> {
+    variant: ArrayKey<'de>,
+    value: Option<&'de Zval>,
+}
+
+impl<'de> EnumAccess<'de> for Enum<'de> {
+    type Error = Error;
+    type Variant = Variant<'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(KeyDeserializer(self.variant.clone()))?;
+        Ok((
+            variant,
+            Variant {
+                key: self.variant,
+                value: self.value,
+            },
+        ))
+    }
+}
+
+/// Provides access to the content of an externally tagged enum variant.
+struct Variant<'de> {
+    key: ArrayKey<'de>,
+    value: Option<&'de Zval>,
+}
+
+impl<'de> Variant<'de> {
+    fn value(&self) -> Result<&'de Zval> {
+        self.value.ok_or_else(|| {
+            Error::new(format!(
+                "expected array with a single key for enum variant `{}`, found string",
+                self.key
+            ))
+        })
+    }
+}
+
+impl<'de> VariantAccess<'de> for Variant<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            None => Ok(()),
+            Some(value) => de::Deserialize::deserialize(Deserializer::from_zval(value))
+                .map_err(|e| at(e, &self.key)),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Deserializer::from_zval(self.value()?))
+            .map_err(|e| at(e, &self.key))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(Deserializer::from_zval(self.value()?), visitor)
+            .map_err(|e| at(e, &self.key))
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(Deserializer::from_zval(self.value()?), visitor)
+            .map_err(|e| at(e, &self.key))
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::{self, Display, Write};
+
+use ::serde::{de, ser};
+
+use crate::{error::Error as ZendError, exception::PhpException};
+
+/// A segment of the path pointing at the value that caused an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// A string key of an array or a property of an object.
+    Key(String),
+    /// An integer key of an array.
+    Index(i64),
+}
+
+/// Error returned when converting between Rust values and zvals through
+/// `serde`.
+///
+/// The error carries the path of the offending value, relative to the value
+/// passed to [`to_zval`] or [`from_zval`], e.g. `$.items[3].price: expected
+/// int, found string`.
+///
+/// [`to_zval`]: super::to_zval
+/// [`from_zval`]: super::from_zval
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// Path segments, innermost segment first.
+    path: Vec<Segment>,
+    msg: String,
+}
+
+impl Error {
+    pub(crate) fn new(msg: impl Into<String>) -> Self {
+        Self {
+            path: Vec::new(),
+            msg: msg.into(),
+        }
+    }
+
+    /// Prepends an array key or property name to the path of the error.
+    #[must_use]
+    pub(crate) fn at_key(mut self, key: impl Into<String>) -> Self {
+        self.path.push(Segment::Key(key.into()));
+        self
+    }
+
+    /// Prepends an array index to the path of the error.
+    #[must_use]
+    pub(crate) fn at_index(mut self, index: i64) -> Self {
+        self.path.push(Segment::Index(index));
+        self
+    }
+
+    /// Returns the message of the error, without the path.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+
+    /// Returns the path of the value that caused the error, e.g.
+    /// `$.items[3].price`. The root value is represented by `$`.
+    #[must_use]
+    pub fn path(&self) -> String {
+        let mut path = String::from("$");
+        for segment in self.path.iter().rev() {
+            // Writing into a `String` cannot fail.
+            let _ = match segment {
+                Segment::Key(key) => write!(path, ".{key}"),
+                Segment::Index(index) => write!(path, "[{index}]"),
+            };
+        }
+        path
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path(), self.msg)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::new(msg.to_string())
+    }
+}
+
+impl From<ZendError> for Error {
+    fn from(err: ZendError) -> Self {
+        Self::new(err.to_string())
+    }
+}
+
+impl From<Error> for PhpException {
+    fn from(err: Error) -> Self {
+        Self::default(err.to_string())
+    }
+}
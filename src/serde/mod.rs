@@ -0,0 +1,185 @@
+//! Conversions between Rust values and PHP values through [`serde`].
+//!
+//! Any type implementing [`serde::Serialize`] can be converted into a
+//! [`Zval`] with [`to_zval`], and any type implementing
+//! [`serde::Deserialize`] can be read from a [`Zval`] with [`from_zval`]:
+//!
+//! | Rust (serde data model)       | PHP                                         |
+//! |-------------------------------|---------------------------------------------|
+//! | `bool`                        | `bool`                                      |
+//! | integers                      | `int`                                       |
+//! | `f32`, `f64`                  | `float`                                     |
+//! | `char`, `str`, `String`       | `string`                                    |
+//! | bytes                         | `string`                                    |
+//! | `None`, `()`, unit structs    | `null`                                      |
+//! | sequences, tuples             | packed `array`                              |
+//! | maps                          | `array`                                     |
+//! | structs                       | associative `array` or `stdClass` object    |
+//! | enums                         | serde's externally tagged representation    |
+//!
+//! Errors carry the path of the offending value, e.g.
+//! `$.items[3].price: expected int, found string`.
+//!
+//! [`Zval`]: crate::types::Zval
+
+mod de;
+mod error;
+mod ser;
+
+pub use de::{Deserializer, from_array, from_object, from_zval};
+pub use error::Error;
+pub use ser::{SerializeMap, SerializeStruct, SerializeVec, Serializer, to_zval};
+
+/// Result type returned by the `serde` conversions.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ::serde::{Deserialize, Serialize};
+
+    use super::{Serializer, from_zval, to_zval};
+    use crate::embed::Embed;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        name: String,
+        price: i64,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { w: i64, h: i64 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    enum Event {
+        Click { x: i64, y: i64 },
+    }
+
+    #[test]
+    fn test_struct_round_trip() {
+        Embed::run(|| {
+            let item = Item {
+                name: "apple".into(),
+                price: 3,
+                tags: vec!["fruit".into(), "red".into()],
+            };
+
+            let zv = to_zval(&item).unwrap();
+            let ht = zv.array().unwrap();
+            assert_eq!(ht.get("name").unwrap().str(), Some("apple"));
+            assert_eq!(ht.get("price").unwrap().long(), Some(3));
+            assert!(
+                ht.get("tags")
+                    .unwrap()
+                    .array()
+                    .unwrap()
+                    .has_sequential_keys()
+            );
+
+            assert_eq!(from_zval::<Item>(&zv).unwrap(), item);
+        });
+    }
+
+    #[test]
+    fn test_struct_as_object() {
+        Embed::run(|| {
+            let item = Item {
+                name: "pear".into(),
+                price: 5,
+                tags: vec![],
+            };
+
+            let zv = item
+                .serialize(&Serializer::new().structs_as_objects(true))
+                .unwrap();
+            let obj = zv.object().unwrap();
+            assert_eq!(obj.get_class_name().unwrap(), "stdClass");
+            assert_eq!(obj.get_property::<i64>("price").unwrap(), 5);
+
+            assert_eq!(from_zval::<Item>(&zv).unwrap(), item);
+        });
+    }
+
+    #[test]
+    fn test_from_php_values() {
+        Embed::run(|| {
+            let zv = Embed::eval("['b' => 2, 'a' => 1];").unwrap();
+            let map: BTreeMap<String, i64> = from_zval(&zv).unwrap();
+            assert_eq!(map.get("a"), Some(&1));
+            assert_eq!(map.get("b"), Some(&2));
+
+            let zv =
+                Embed::eval("(object) ['name' => 'x', 'price' => 1, 'tags' => ['y']];").unwrap();
+            let item: Item = from_zval(&zv).unwrap();
+            assert_eq!(item.tags, vec!["y".to_string()]);
+
+            let zv = Embed::eval("[1, 2.5, null];").unwrap();
+            let values: (i64, f64, Option<i64>) = from_zval(&zv).unwrap();
+            assert_eq!(values, (1, 2.5, None));
+        });
+    }
+
+    #[test]
+    fn test_enums() {
+        Embed::run(|| {
+            for shape in [Shape::Empty, Shape::Circle(1.5), Shape::Rect { w: 2, h: 3 }] {
+                let zv = to_zval(&shape).unwrap();
+                assert_eq!(from_zval::<Shape>(&zv).unwrap(), shape);
+            }
+
+            let zv = to_zval(&Shape::Empty).unwrap();
+            assert_eq!(zv.str(), Some("Empty"));
+
+            let zv = Embed::eval("['type' => 'Click', 'x' => 1, 'y' => 2];").unwrap();
+            assert_eq!(
+                from_zval::<Event>(&zv).unwrap(),
+                Event::Click { x: 1, y: 2 }
+            );
+        });
+    }
+
+    #[test]
+    fn test_error_path() {
+        Embed::run(|| {
+            let zv = Embed::eval(
+                "['items' => [
+                    ['name' => 'a', 'price' => 1, 'tags' => []],
+                    ['name' => 'b', 'price' => 2, 'tags' => []],
+                    ['name' => 'c', 'price' => 3, 'tags' => []],
+                    ['name' => 'd', 'price' => 'free', 'tags' => []],
+                ]];",
+            )
+            .unwrap();
+
+            let err = from_zval::<BTreeMap<String, Vec<Item>>>(&zv).unwrap_err();
+            assert_eq!(err.path(), "$.items[3].price");
+            assert_eq!(
+                err.to_string(),
+                "$.items[3].price: expected int, found string"
+            );
+        });
+    }
+
+    #[test]
+    fn test_invalid_map_key() {
+        Embed::run(|| {
+            let mut map = BTreeMap::new();
+            map.insert((1, 2), "value");
+
+            let err = to_zval(&map).unwrap_err();
+            assert_eq!(
+                err.message(),
+                "array keys must be either an int or a string"
+            );
+        });
+    }
+}
@@ -0,0 +1,698 @@
+use ::serde::ser::{self, Impossible, Serialize};
+
+use super::{Error, Result};
+use crate::{
+    boxed::ZBox,
+    convert::IntoZval,
+    types::{ArrayKey, ZendHashTable, ZendObject, ZendStr, Zval},
+};
+
+/// Serializes Rust values into [`Zval`]s.
+///
+/// * Booleans, integers, floats and strings are converted to their PHP
+///   counterparts. Byte slices are converted to binary strings.
+/// * `None`, `()` and unit structs are converted to `null`.
+/// * Sequences and tuples are converted to packed arrays.
+/// * Maps are converted to associative arrays. Keys must serialize to an
+///   integer or a string.
+/// * Structs are converted to associative arrays, or to `stdClass` objects
+///   when [`Serializer::structs_as_objects`] is enabled.
+/// * Enums are converted following serde's externally tagged representation:
+///   unit variants become the variant name, other variants become a single
+///   entry array keyed by the variant name. The internally tagged, adjacently
+///   tagged and untagged representations are handled by `serde` itself.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::serde::Serializer;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// let zv = Point { x: 1, y: 2 }
+///     .serialize(&Serializer::new().structs_as_objects(true))
+///     .unwrap();
+/// assert!(zv.is_object());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Serializer {
+    structs_as_objects: bool,
+}
+
+impl Serializer {
+    /// Creates a new serializer converting structs into associative arrays.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether structs (and struct variants) are converted into
+    /// `stdClass` objects instead of associative arrays.
+    ///
+    /// # Parameters
+    ///
+    /// * `enabled` - Whether to create objects for structs.
+    #[must_use]
+    pub fn structs_as_objects(mut self, enabled: bool) -> Self {
+        self.structs_as_objects = enabled;
+        self
+    }
+}
+
+/// Serializes a Rust value into a [`Zval`] with the default [`Serializer`]
+/// configuration.
+///
+/// # Parameters
+///
+/// * `value` - The value to serialize.
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the value cannot be represented in PHP, e.g. a map
+/// key which is neither an integer nor a string, or an integer which does not
+/// fit into a PHP integer.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::collections::BTreeMap;
+/// use ext_php_rs::serde::to_zval;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert("answer", 42);
+///
+/// let zv = to_zval(&map).unwrap();
+/// assert_eq!(zv.array().and_then(|ht| ht.get("answer")).and_then(|v| v.long()), Some(42));
+/// ```
+pub fn to_zval<T>(value: &T) -> Result<Zval>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(&Serializer::new())
+}
+
+/// Wraps a value into a single entry array keyed by an enum variant name.
+fn tagged(variant: &'static str, value: Zval) -> Result<Zval> {
+    let mut ht = ZendHashTable::with_capacity(1);
+    ht.insert(variant, value)?;
+    Ok(array(ht))
+}
+
+fn array(ht: ZBox<ZendHashTable>) -> Zval {
+    let mut zv = Zval::new();
+    zv.set_hashtable(ht);
+    zv
+}
+
+fn capacity(len: Option<usize>) -> u32 {
+    len.and_then(|len| u32::try_from(len).ok()).unwrap_or(0)
+}
+
+fn long<T>(v: T) -> Result<Zval>
+where
+    T: TryInto<i64> + std::fmt::Display + Copy,
+{
+    let Ok(l) = v.try_into() else {
+        return Err(Error::new(format!(
+            "integer {v} does not fit into a PHP int"
+        )));
+    };
+    let mut zv = Zval::new();
+    zv.set_long(l);
+    Ok(zv)
+}
+
+impl<'a> ser::Serializer for &'a Serializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVec<'a>;
+    type SerializeTuple = SerializeVec<'a>;
+    type SerializeTupleStruct = SerializeVec<'a>;
+    type SerializeTupleVariant = SerializeVec<'a>;
+    type SerializeMap = SerializeMap<'a>;
+    type SerializeStruct = SerializeStruct<'a>;
+    type SerializeStructVariant = SerializeStruct<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Zval> {
+        long(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Zval> {
+        long(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Zval> {
+        long(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Zval> {
+        long(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Zval> {
+        long(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Zval> {
+        Ok(v.into())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Zval> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Zval> {
+        Ok(v.into_zval(false)?)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Zval> {
+        let mut zv = Zval::new();
+        zv.set_zend_string(ZendStr::new(v, false));
+        Ok(zv)
+    }
+
+    fn serialize_none(self) -> Result<Zval> {
+        Ok(Zval::null())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Zval>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Zval> {
+        Ok(Zval::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Zval> {
+        Ok(Zval::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Zval> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Zval>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Zval>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(self).map_err(|e| e.at_key(variant))?;
+        tagged(variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(SerializeVec {
+            ser: self,
+            ht: ZendHashTable::with_capacity(capacity(len)),
+            index: 0,
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        let mut seq = self.serialize_seq(Some(len))?;
+        seq.variant = Some(variant);
+        Ok(seq)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(SerializeMap {
+            ser: self,
+            ht: ZendHashTable::with_capacity(capacity(len)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        let target = if self.structs_as_objects {
+            StructTarget::Object(ZendObject::new_stdclass())
+        } else {
+            StructTarget::Array(ZendHashTable::with_capacity(capacity(Some(len))))
+        };
+        Ok(SerializeStruct {
+            ser: self,
+            target,
+            variant: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        let mut st = self.serialize_struct(name, len)?;
+        st.variant = Some(variant);
+        Ok(st)
+    }
+}
+
+/// Serializes sequences, tuples and tuple variants into packed arrays.
+#[doc(hidden)]
+pub struct SerializeVec<'a> {
+    ser: &'a Serializer,
+    ht: ZBox<ZendHashTable>,
+    index: i64,
+    variant: Option<&'static str>,
+}
+
+impl SerializeVec<'_> {
+    fn push<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let index = self.index;
+        let value = value.serialize(self.ser).map_err(|e| e.at_index(index))?;
+        self.ht.push(value)?;
+        self.index += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Zval> {
+        let value = array(self.ht);
+        match self.variant {
+            Some(variant) => tagged(variant, value),
+            None => Ok(value),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeVec<'_> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec<'_> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec<'_> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVec<'_> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+/// Serializes maps into associative arrays.
+#[doc(hidden)]
+pub struct SerializeMap<'a> {
+    ser: &'a Serializer,
+    ht: ZBox<ZendHashTable>,
+    key: Option<ArrayKey<'static>>,
+}
+
+impl ser::SerializeMap for SerializeMap<'_> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("map value serialized before its key"))?;
+        let value = value.serialize(self.ser).map_err(|e| match &key {
+            ArrayKey::Long(index) => e.at_index(*index),
+            key => e.at_key(key.to_string()),
+        })?;
+        self.ht.insert(key, value)?;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Zval> {
+        Ok(array(self.ht))
+    }
+}
+
+enum StructTarget {
+    Array(ZBox<ZendHashTable>),
+    Object(ZBox<ZendObject>),
+}
+
+/// Serializes structs and struct variants into associative arrays or
+/// `stdClass` objects.
+#[doc(hidden)]
+pub struct SerializeStruct<'a> {
+    ser: &'a Serializer,
+    target: StructTarget,
+    variant: Option<&'static str>,
+}
+
+impl SerializeStruct<'_> {
+    fn field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(self.ser).map_err(|e| e.at_key(key))?;
+        match &mut self.target {
+            StructTarget::Array(ht) => ht.insert(key, value)?,
+            StructTarget::Object(obj) => obj.set_property(key, value)?,
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Zval> {
+        let value = match self.target {
+            StructTarget::Array(ht) => array(ht),
+            StructTarget::Object(obj) => obj.into_zval(false)?,
+        };
+        match self.variant {
+            Some(variant) => tagged(variant, value),
+            None => Ok(value),
+        }
+    }
+}
+
+impl ser::SerializeStruct for SerializeStruct<'_> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeStruct<'_> {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+/// Serializes map keys into [`ArrayKey`]s.
+struct KeySerializer;
+
+fn key_error() -> Error {
+    Error::new("array keys must be either an int or a string")
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = ArrayKey<'static>;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<ArrayKey<'static>, Error>;
+    type SerializeTuple = Impossible<ArrayKey<'static>, Error>;
+    type SerializeTupleStruct = Impossible<ArrayKey<'static>, Error>;
+    type SerializeTupleVariant = Impossible<ArrayKey<'static>, Error>;
+    type SerializeMap = Impossible<ArrayKey<'static>, Error>;
+    type SerializeStruct = Impossible<ArrayKey<'static>, Error>;
+    type SerializeStructVariant = Impossible<ArrayKey<'static>, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok> {
+        Err(key_error())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
+        Ok(ArrayKey::Long(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
+        Ok(ArrayKey::Long(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
+        Ok(ArrayKey::Long(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+        Ok(ArrayKey::Long(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        Ok(ArrayKey::Long(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
+        Ok(ArrayKey::Long(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
+        Ok(ArrayKey::Long(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        Ok(ArrayKey::from(v))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok> {
+        Err(key_error())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok> {
+        Err(key_error())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok> {
+        Ok(ArrayKey::from(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        Ok(ArrayKey::from(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        std::str::from_utf8(v)
+            .map_err(|_| Error::new("array keys must be valid UTF-8"))
+            .and_then(|s| self.serialize_str(s))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(key_error())
+    }
+}
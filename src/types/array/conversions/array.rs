@@ -0,0 +1,97 @@
+//! Conversions between fixed-size arrays and PHP lists, e.g. `[i64; 3]` and
+//! `[1, 2, 3]`.
+
+use std::convert::TryFrom;
+
+use crate::{
+    boxed::ZBox,
+    convert::{FromZval, IntoZval},
+    error::{Error, Result},
+    flags::DataType,
+    types::Zval,
+};
+
+use super::super::ZendHashTable;
+use super::{convert, into_value_doc_type, list_values, value_doc_type};
+
+impl<'a, T, const N: usize> TryFrom<&'a ZendHashTable> for [T; N]
+where
+    T: FromZval<'a>,
+{
+    type Error = Error;
+
+    fn try_from(value: &'a ZendHashTable) -> Result<Self> {
+        let values = list_values(value, N)?
+            .map(convert)
+            .collect::<Result<Vec<T>>>()?;
+        values.try_into().map_err(|_| Error::NotAList(N))
+    }
+}
+
+impl<T, const N: usize> TryFrom<[T; N]> for ZBox<ZendHashTable>
+where
+    T: IntoZval,
+{
+    type Error = Error;
+
+    fn try_from(value: [T; N]) -> Result<Self> {
+        let mut ht = ZendHashTable::new_packed(N.try_into().map_err(|_| Error::IntegerOverflow)?);
+        ht.fill_packed(value)?;
+        Ok(ht)
+    }
+}
+
+impl<T, const N: usize> IntoZval for [T; N]
+where
+    T: IntoZval,
+{
+    const TYPE: DataType = DataType::Array;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        let arr = self.try_into()?;
+        zv.set_hashtable(arr);
+        Ok(())
+    }
+
+    fn doc_type() -> Option<String> {
+        Some(format!("array<int, {}>", into_value_doc_type::<T>()))
+    }
+}
+
+impl<'a, T, const N: usize> FromZval<'a> for [T; N]
+where
+    T: FromZval<'a>,
+{
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array().and_then(|arr| arr.try_into().ok())
+    }
+
+    fn doc_type() -> Option<String> {
+        Some(format!("array<int, {}>", value_doc_type::<T>()))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::convert::{FromZval, IntoZval};
+    use crate::embed::Embed;
+    use crate::error::Error;
+
+    #[test]
+    fn test_array_conversions() {
+        Embed::run(|| {
+            let zval = [1.5, 2.5, 3.5].into_zval(false).unwrap();
+            let expected = Embed::eval("[1.5, 2.5, 3.5];").unwrap();
+            assert!(zval.is_identical(&expected));
+            assert_eq!(<[f64; 3]>::from_zval(&zval), Some([1.5, 2.5, 3.5]));
+
+            let result: crate::error::Result<[f64; 2]> = zval.array().unwrap().try_into();
+            assert!(matches!(result, Err(Error::NotAList(2))));
+        });
+    }
+}
@@ -63,9 +63,11 @@ where
             value.len().try_into().map_err(|_| Error::IntegerOverflow)?,
         );
 
-        for (k, v) in value {
-            ht.insert(k, v)?;
-        }
+        ht.try_extend_entries(
+            value
+                .into_iter()
+                .map(|(k, v)| v.into_zval(false).map(|v| (k, v))),
+        )?;
 
         Ok(ht)
     }
@@ -33,13 +33,10 @@ where
     type Error = Error;
 
     fn try_from(value: BTreeSet<V>) -> Result<Self> {
-        let mut set = ZendHashTable::with_capacity(
-            value.len().try_into().map_err(|_| Error::IntegerOverflow)?,
-        );
+        let mut set =
+            ZendHashTable::new_packed(value.len().try_into().map_err(|_| Error::IntegerOverflow)?);
 
-        for (k, v) in value.into_iter().enumerate() {
-            set.insert(k, v)?;
-        }
+        set.fill_packed(value)?;
 
         Ok(set)
     }
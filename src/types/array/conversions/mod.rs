@@ -13,7 +13,20 @@
 //! - `IndexMap<K, V>` ↔ `ZendHashTable` (via `index_map` module, requires `indexmap` feature)
 //! - `IndexSet<V>` ↔ `ZendHashTable` (via `index_set` module, requires `indexmap` feature)
 //! - `Vec<T>` and `Vec<(K, V)>` ↔ `ZendHashTable` (via `vec` module)
+//! - `[T; N]` ↔ `ZendHashTable` (via `array` module)
+//! - `(A, B, ...)` ↔ `ZendHashTable`, for tuples of up to 12 elements, and
+//!   `Pair<A, B>` ↔ `Zval` (via `tuple` module)
 
+use crate::{
+    convert::{FromZval, IntoZval},
+    describe::datatype_to_phpdoc,
+    error::{Error, Result},
+    types::Zval,
+};
+
+use super::{Values, ZendHashTable};
+
+mod array;
 mod btree_map;
 mod btree_set;
 mod hash_map;
@@ -22,4 +35,33 @@ mod hash_set;
 mod index_map;
 #[cfg(feature = "indexmap")]
 mod index_set;
+mod tuple;
 mod vec;
+
+pub use tuple::Pair;
+
+/// Returns the `PHPDoc` type of the values converted to `T`.
+pub(super) fn value_doc_type<'a, T: FromZval<'a>>() -> String {
+    T::doc_type().unwrap_or_else(|| datatype_to_phpdoc(&T::TYPE, false))
+}
+
+/// Returns the `PHPDoc` type of the values converted from `T`.
+fn into_value_doc_type<T: IntoZval>() -> String {
+    T::doc_type().unwrap_or_else(|| datatype_to_phpdoc(&T::TYPE, T::NULLABLE))
+}
+
+/// Converts a value of an array, returning an error if it is not a valid
+/// `T`.
+pub(super) fn convert<'a, T: FromZval<'a>>(value: &'a Zval) -> Result<T> {
+    T::from_zval(value).ok_or_else(|| Error::ZvalConversion(value.get_type()))
+}
+
+/// Returns the values of a hash table, checking that it is a list of exactly
+/// `len` elements.
+fn list_values(ht: &ZendHashTable, len: usize) -> Result<Values<'_>> {
+    if ht.len() == len && ht.has_sequential_keys() {
+        Ok(ht.values())
+    } else {
+        Err(Error::NotAList(len))
+    }
+}
@@ -0,0 +1,296 @@
+//! Conversions between tuples and PHP lists, e.g. `(i64, bool, String)` and
+//! `[42, true, 'answer']`.
+//!
+//! Pairs do not implement [`IntoZval`] and [`FromZval`], as `Vec<(K, V)>`
+//! converts associative arrays, which would conflict with vectors of pairs.
+//! They convert to and from [`ZendHashTable`], and [`Pair`] converts them to
+//! and from zvals, e.g. to return `[$ok, $value]` from a function.
+
+use std::convert::TryFrom;
+
+use crate::{
+    boxed::ZBox,
+    convert::{FromZval, IntoZval},
+    error::{Error, Result},
+    flags::DataType,
+    types::Zval,
+};
+
+use super::super::ZendHashTable;
+use super::{convert, into_value_doc_type, list_values, value_doc_type};
+
+/// Returns the `PHPDoc` shape of a list with elements of the given types,
+/// e.g. `array{0: int, 1: string}`.
+fn shape_doc_type(types: &[String]) -> String {
+    let elements: Vec<_> = types
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| format!("{i}: {ty}"))
+        .collect();
+    format!("array{{{}}}", elements.join(", "))
+}
+
+macro_rules! tuple_conversions {
+    ($len:literal => $($index:tt $ty:ident),+) => {
+        impl<'a, $($ty),+> TryFrom<&'a ZendHashTable> for ($($ty,)+)
+        where
+            $($ty: FromZval<'a>,)+
+        {
+            type Error = Error;
+
+            fn try_from(value: &'a ZendHashTable) -> Result<Self> {
+                let mut values = list_values(value, $len)?;
+                Ok(($(
+                    convert::<$ty>(values.next().ok_or(Error::NotAList($len))?)?,
+                )+))
+            }
+        }
+
+        impl<$($ty),+> TryFrom<($($ty,)+)> for ZBox<ZendHashTable>
+        where
+            $($ty: IntoZval,)+
+        {
+            type Error = Error;
+
+            fn try_from(value: ($($ty,)+)) -> Result<Self> {
+                let mut ht = ZendHashTable::new_packed($len);
+                ht.try_fill_packed([$(value.$index.into_zval(false)),+].into_iter())?;
+                Ok(ht)
+            }
+        }
+    };
+}
+
+macro_rules! tuple_zval_conversions {
+    ($($ty:ident),+) => {
+        impl<$($ty),+> IntoZval for ($($ty,)+)
+        where
+            $($ty: IntoZval,)+
+        {
+            const TYPE: DataType = DataType::Array;
+            const NULLABLE: bool = false;
+
+            fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+                let arr = self.try_into()?;
+                zv.set_hashtable(arr);
+                Ok(())
+            }
+
+            fn doc_type() -> Option<String> {
+                Some(shape_doc_type(&[$(into_value_doc_type::<$ty>()),+]))
+            }
+        }
+
+        impl<'a, $($ty),+> FromZval<'a> for ($($ty,)+)
+        where
+            $($ty: FromZval<'a>,)+
+        {
+            const TYPE: DataType = DataType::Array;
+
+            fn from_zval(zval: &'a Zval) -> Option<Self> {
+                zval.array().and_then(|arr| arr.try_into().ok())
+            }
+
+            fn doc_type() -> Option<String> {
+                Some(shape_doc_type(&[$(value_doc_type::<$ty>()),+]))
+            }
+        }
+    };
+}
+
+/// A pair converted to and from a PHP list of two elements, e.g.
+/// `[true, 'answer']`.
+///
+/// Tuples of other lengths are converted directly, but `(A, B)` cannot
+/// implement [`IntoZval`] and [`FromZval`], as `Vec<(K, V)>` is converted to
+/// and from an associative array.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ext_php_rs::types::Pair;
+///
+/// fn try_divide(a: i64, b: i64) -> Pair<bool, i64> {
+///     match a.checked_div(b) {
+///         Some(quotient) => Pair(true, quotient),
+///         None => Pair(false, 0),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Pair<A, B>(pub A, pub B);
+
+impl<A, B> Pair<A, B> {
+    /// Returns the elements of the pair as a tuple.
+    pub fn into_inner(self) -> (A, B) {
+        (self.0, self.1)
+    }
+}
+
+impl<A, B> From<(A, B)> for Pair<A, B> {
+    fn from((a, b): (A, B)) -> Self {
+        Self(a, b)
+    }
+}
+
+impl<A, B> From<Pair<A, B>> for (A, B) {
+    fn from(pair: Pair<A, B>) -> Self {
+        pair.into_inner()
+    }
+}
+
+impl<A: IntoZval, B: IntoZval> IntoZval for Pair<A, B> {
+    const TYPE: DataType = DataType::Array;
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        let arr = self.into_inner().try_into()?;
+        zv.set_hashtable(arr);
+        Ok(())
+    }
+
+    fn doc_type() -> Option<String> {
+        Some(shape_doc_type(&[
+            into_value_doc_type::<A>(),
+            into_value_doc_type::<B>(),
+        ]))
+    }
+}
+
+impl<'a, A: FromZval<'a>, B: FromZval<'a>> FromZval<'a> for Pair<A, B> {
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array()
+            .and_then(|arr| <(A, B)>::try_from(arr).ok())
+            .map(Self::from)
+    }
+
+    fn doc_type() -> Option<String> {
+        Some(shape_doc_type(&[
+            value_doc_type::<A>(),
+            value_doc_type::<B>(),
+        ]))
+    }
+}
+
+tuple_conversions!(1 => 0 A);
+tuple_conversions!(2 => 0 A, 1 B);
+tuple_conversions!(3 => 0 A, 1 B, 2 C);
+tuple_conversions!(4 => 0 A, 1 B, 2 C, 3 D);
+tuple_conversions!(5 => 0 A, 1 B, 2 C, 3 D, 4 E);
+tuple_conversions!(6 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+tuple_conversions!(7 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+tuple_conversions!(8 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+tuple_conversions!(9 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I);
+tuple_conversions!(10 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J);
+tuple_conversions!(11 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K);
+tuple_conversions!(12 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K, 11 L);
+
+tuple_zval_conversions!(A);
+tuple_zval_conversions!(A, B, C);
+tuple_zval_conversions!(A, B, C, D);
+tuple_zval_conversions!(A, B, C, D, E);
+tuple_zval_conversions!(A, B, C, D, E, F);
+tuple_zval_conversions!(A, B, C, D, E, F, G);
+tuple_zval_conversions!(A, B, C, D, E, F, G, H);
+tuple_zval_conversions!(A, B, C, D, E, F, G, H, I);
+tuple_zval_conversions!(A, B, C, D, E, F, G, H, I, J);
+tuple_zval_conversions!(A, B, C, D, E, F, G, H, I, J, K);
+tuple_zval_conversions!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::boxed::ZBox;
+    use crate::convert::{FromZval, IntoZval};
+    use crate::embed::Embed;
+    use crate::error::{Error, Result};
+    use crate::types::{Pair, ZendHashTable};
+
+    #[test]
+    fn test_tuple_into_zval() {
+        Embed::run(|| {
+            let zval = (42, "answer", (true, 1.5, ())).into_zval(false).unwrap();
+            let expected = Embed::eval("[42, 'answer', [true, 1.5, null]];").unwrap();
+            assert!(zval.is_identical(&expected));
+        });
+    }
+
+    #[test]
+    fn test_tuple_from_zval() {
+        Embed::run(|| {
+            let zval = Embed::eval("[42, true, 'answer'];").unwrap();
+            let (n, b, s) = <(i64, bool, String)>::from_zval(&zval).unwrap();
+            assert_eq!(n, 42);
+            assert!(b);
+            assert_eq!(s, "answer");
+
+            let mistyped = Embed::eval("['answer', true, 42];").unwrap();
+            assert!(<(i64, bool, String)>::from_zval(&mistyped).is_none());
+        });
+    }
+
+    #[test]
+    fn test_pair_conversions() {
+        Embed::run(|| {
+            let ht: ZBox<ZendHashTable> = (true, "answer").try_into().unwrap();
+            let zval = Embed::eval("[true, 'answer'];").unwrap();
+            assert_eq!(ht.len(), 2);
+            assert!(
+                zval.array()
+                    .unwrap()
+                    .get_index(1)
+                    .unwrap()
+                    .is_identical(ht.get_index(1).unwrap())
+            );
+
+            let (b, s): (bool, String) = zval.array().unwrap().try_into().unwrap();
+            assert!(b);
+            assert_eq!(s, "answer");
+        });
+    }
+
+    #[test]
+    fn test_pair_zval_conversions() {
+        Embed::run(|| {
+            let zval = Pair(true, "answer").into_zval(false).unwrap();
+            let expected = Embed::eval("[true, 'answer'];").unwrap();
+            assert!(zval.is_identical(&expected));
+
+            let Pair(b, s) = <Pair<bool, String>>::from_zval(&expected).unwrap();
+            assert!(b);
+            assert_eq!(s, "answer");
+            assert_eq!(
+                <Pair<bool, String> as IntoZval>::doc_type().as_deref(),
+                Some("array{0: bool, 1: string}")
+            );
+        });
+    }
+
+    #[test]
+    fn test_tuple_requires_exact_list() {
+        Embed::run(|| {
+            let too_short = Embed::eval("[42];").unwrap();
+            let too_long = Embed::eval("[42, 'answer', null];").unwrap();
+            let not_a_list = Embed::eval("[1 => 'answer', 0 => 42];").unwrap();
+            for zval in [too_short, too_long, not_a_list] {
+                let result: Result<(i64, String)> = zval.array().unwrap().try_into();
+                assert!(matches!(result, Err(Error::NotAList(2))));
+            }
+        });
+    }
+
+    #[test]
+    fn test_tuple_doc_type() {
+        assert_eq!(
+            <(i64, bool, String) as FromZval>::doc_type().as_deref(),
+            Some("array{0: int, 1: bool, 2: string}")
+        );
+        assert_eq!(
+            <(bool, Option<f64>, (i64,)) as IntoZval>::doc_type().as_deref(),
+            Some("array{0: bool, 1: float|null, 2: array{0: int}}")
+        );
+    }
+}
@@ -67,9 +67,11 @@ where
             value.len().try_into().map_err(|_| Error::IntegerOverflow)?,
         );
 
-        for (k, v) in value {
-            ht.insert(k, v)?;
-        }
+        ht.try_extend_entries(
+            value
+                .into_iter()
+                .map(|(k, v)| v.into_zval(false).map(|v| (k, v))),
+        )?;
 
         Ok(ht)
     }
@@ -141,13 +143,10 @@ where
     type Error = Error;
 
     fn try_from(value: Vec<T>) -> Result<Self> {
-        let mut ht = ZendHashTable::with_capacity(
-            value.len().try_into().map_err(|_| Error::IntegerOverflow)?,
-        );
+        let mut ht =
+            ZendHashTable::new_packed(value.len().try_into().map_err(|_| Error::IntegerOverflow)?);
 
-        for val in value {
-            ht.push(val)?;
-        }
+        ht.fill_packed(value)?;
 
         Ok(ht)
     }
@@ -1,6 +1,6 @@
 use cfg_if::cfg_if;
 use std::{
-    convert::TryInto,
+    convert::{Infallible, TryInto},
     iter::{DoubleEndedIterator, ExactSizeIterator, Iterator},
     ptr,
 };
@@ -10,8 +10,9 @@ use crate::boxed::ZBox;
 use crate::{
     convert::FromZval,
     ffi::{
-        HashPosition, zend_hash_get_current_data_ex, zend_hash_get_current_key_type_ex,
-        zend_hash_get_current_key_zval_ex, zend_hash_move_backwards_ex, zend_hash_move_forward_ex,
+        HT_MIN_SIZE, HashPosition, zend_hash_get_current_data_ex,
+        zend_hash_get_current_key_type_ex, zend_hash_get_current_key_zval_ex,
+        zend_hash_move_backwards_ex, zend_hash_move_forward_ex,
     },
     types::Zval,
 };
@@ -268,12 +269,10 @@ impl DoubleEndedIterator for Values<'_> {
 
 impl FromIterator<Zval> for ZBox<ZendHashTable> {
     fn from_iter<T: IntoIterator<Item = Zval>>(iter: T) -> Self {
-        let mut ht = ZendHashTable::new();
-        for item in iter {
-            // Inserting a zval cannot fail, as `push` only returns `Err` if converting
-            // `val` to a zval fails.
-            let _ = ht.push(item);
-        }
+        let iter = iter.into_iter();
+        let (size, _) = iter.size_hint();
+        let mut ht = ZendHashTable::new_packed(size.try_into().unwrap_or(HT_MIN_SIZE));
+        ht.extend(iter);
         ht
     }
 }
@@ -281,11 +280,7 @@ impl FromIterator<Zval> for ZBox<ZendHashTable> {
 impl FromIterator<(i64, Zval)> for ZBox<ZendHashTable> {
     fn from_iter<T: IntoIterator<Item = (i64, Zval)>>(iter: T) -> Self {
         let mut ht = ZendHashTable::new();
-        for (key, val) in iter {
-            // Inserting a zval cannot fail, as `push` only returns `Err` if converting
-            // `val` to a zval fails.
-            let _ = ht.insert_at_index(key, val);
-        }
+        ht.extend(iter);
         ht
     }
 }
@@ -293,11 +288,34 @@ impl FromIterator<(i64, Zval)> for ZBox<ZendHashTable> {
 impl<'a> FromIterator<(&'a str, Zval)> for ZBox<ZendHashTable> {
     fn from_iter<T: IntoIterator<Item = (&'a str, Zval)>>(iter: T) -> Self {
         let mut ht = ZendHashTable::new();
-        for (key, val) in iter {
-            // Inserting a zval cannot fail, as `push` only returns `Err` if converting
-            // `val` to a zval fails.
-            let _ = ht.insert(key, val);
-        }
+        ht.extend(iter);
         ht
     }
 }
+
+impl Extend<Zval> for ZendHashTable {
+    fn extend<T: IntoIterator<Item = Zval>>(&mut self, iter: T) {
+        match self.try_fill_packed(iter.into_iter().map(Ok::<_, Infallible>)) {
+            Ok(()) => {}
+            Err(e) => match e {},
+        }
+    }
+}
+
+impl Extend<(i64, Zval)> for ZendHashTable {
+    fn extend<T: IntoIterator<Item = (i64, Zval)>>(&mut self, iter: T) {
+        match self.try_extend_entries(iter.into_iter().map(Ok::<_, Infallible>)) {
+            Ok(()) => {}
+            Err(e) => match e {},
+        }
+    }
+}
+
+impl<'a> Extend<(&'a str, Zval)> for ZendHashTable {
+    fn extend<T: IntoIterator<Item = (&'a str, Zval)>>(&mut self, iter: T) {
+        match self.try_extend_entries(iter.into_iter().map(Ok::<_, Infallible>)) {
+            Ok(()) => {}
+            Err(e) => match e {},
+        }
+    }
+}
@@ -9,10 +9,11 @@ use crate::{
     error::Result,
     ffi::zend_ulong,
     ffi::{
-        _zend_new_array, GC_FLAGS_MASK, GC_FLAGS_SHIFT, HT_MIN_SIZE, zend_array_count,
-        zend_array_destroy, zend_array_dup, zend_empty_array, zend_hash_clean, zend_hash_del,
-        zend_hash_find, zend_hash_index_del, zend_hash_index_find, zend_hash_index_update,
-        zend_hash_next_index_insert, zend_hash_str_del, zend_hash_str_find, zend_hash_str_update,
+        _zend_new_array, GC_FLAGS_MASK, GC_FLAGS_SHIFT, HASH_FLAG_PACKED, HASH_FLAG_UNINITIALIZED,
+        HT_MIN_SIZE, zend_array_count, zend_array_destroy, zend_array_dup, zend_empty_array,
+        zend_hash_clean, zend_hash_del, zend_hash_extend, zend_hash_find, zend_hash_index_del,
+        zend_hash_index_find, zend_hash_index_update, zend_hash_next_index_insert,
+        zend_hash_real_init_packed, zend_hash_str_del, zend_hash_str_find, zend_hash_str_update,
         zend_hash_update,
     },
     flags::{DataType, ZvalTypeFlags},
@@ -23,10 +24,14 @@ mod array_key;
 mod conversions;
 mod entry;
 mod iterators;
+mod sort;
+mod view;
 
 pub use array_key::ArrayKey;
+pub use conversions::Pair;
 pub use entry::{Entry, OccupiedEntry, VacantEntry};
 pub use iterators::{Iter, Values};
+pub use view::{ArrayView, ArrayViewIter, MapView, MapViewIter, MapViewKey};
 
 /// A PHP hashtable.
 ///
@@ -108,6 +113,36 @@ impl ZendHashTable {
         }
     }
 
+    /// Creates a new, empty, PHP hashtable with an initial size, whose values
+    /// are stored in packed storage, returned inside a [`ZBox`].
+    ///
+    /// Packed hashtables are the lists of PHP: their keys are the positions of
+    /// their values, which are appended without being hashed. Use
+    /// [`ZendHashTable::fill_packed`] to append values to them.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - The number of values to allocate storage for.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new_packed(3);
+    /// ht.fill_packed([1, 2, 3]).unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if memory for the hashtable could not be allocated.
+    #[must_use]
+    pub fn new_packed(capacity: u32) -> ZBox<Self> {
+        let mut ht = Self::with_capacity(capacity);
+        unsafe { zend_hash_real_init_packed(&raw mut *ht) };
+        ht
+    }
+
     /// Returns the current number of elements in the array.
     ///
     /// # Example
@@ -432,41 +467,7 @@ impl ZendHashTable {
         K: Into<ArrayKey<'a>>,
         V: IntoZval,
     {
-        let mut val = val.into_zval(false)?;
-        match key.into() {
-            ArrayKey::Long(index) => {
-                unsafe {
-                    #[allow(clippy::cast_sign_loss)]
-                    zend_hash_index_update(self, index as zend_ulong, &raw mut val)
-                };
-            }
-            ArrayKey::String(key) => {
-                unsafe {
-                    // Use raw bytes directly since zend_hash_str_update takes a length.
-                    // This allows keys with embedded null bytes (e.g. PHP property mangling).
-                    zend_hash_str_update(
-                        self,
-                        key.as_str().as_ptr().cast(),
-                        key.len(),
-                        &raw mut val,
-                    )
-                };
-            }
-            ArrayKey::Str(key) => {
-                unsafe {
-                    // Use raw bytes directly since zend_hash_str_update takes a length.
-                    // This allows keys with embedded null bytes (e.g. PHP property mangling).
-                    zend_hash_str_update(self, key.as_ptr().cast(), key.len(), &raw mut val)
-                };
-            }
-            ArrayKey::ZendString(key) => {
-                unsafe {
-                    // zend_hash_update does the addref itself for non-interned strings.
-                    zend_hash_update(self, key.as_ptr().cast_mut(), &raw mut val)
-                };
-            }
-        }
-        val.release();
+        self.insert_zval(key.into(), val.into_zval(false)?);
         Ok(())
     }
 
@@ -542,13 +543,187 @@ impl ZendHashTable {
     where
         V: IntoZval,
     {
-        let mut val = val.into_zval(false)?;
+        self.push_zval(val.into_zval(false)?);
+        Ok(())
+    }
+
+    /// Appends values onto the end of the hash table. Returns a result
+    /// containing nothing if the values were successfully inserted.
+    ///
+    /// Values are written directly into the storage of packed hash tables,
+    /// such as the ones created with [`ZendHashTable::new_packed`], as the
+    /// `ZEND_HASH_FILL_PACKED` macro does. They are pushed one at a time onto
+    /// other hash tables.
+    ///
+    /// # Parameters
+    ///
+    /// * `values` - The values to insert into the hash table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if converting a value into a [`Zval`] failed, in which
+    /// case the values before it have been inserted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::ZendHashTable;
+    ///
+    /// let mut ht = ZendHashTable::new_packed(1000);
+    ///
+    /// ht.fill_packed((0..1000).map(|i| i * 2)).unwrap();
+    /// assert_eq!(ht.len(), 1000);
+    /// ```
+    pub fn fill_packed<I>(&mut self, values: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: IntoZval,
+    {
+        self.try_fill_packed(values.into_iter().map(|val| val.into_zval(false)))
+    }
+
+    /// Inserts a value into the hash table, or updates it if the key already
+    /// exists.
+    fn insert_zval(&mut self, key: ArrayKey<'_>, mut val: Zval) {
+        match key {
+            ArrayKey::Long(index) => {
+                unsafe {
+                    #[allow(clippy::cast_sign_loss)]
+                    zend_hash_index_update(self, index as zend_ulong, &raw mut val)
+                };
+            }
+            ArrayKey::String(key) => {
+                unsafe {
+                    // Use raw bytes directly since zend_hash_str_update takes a length.
+                    // This allows keys with embedded null bytes (e.g. PHP property mangling).
+                    zend_hash_str_update(
+                        self,
+                        key.as_str().as_ptr().cast(),
+                        key.len(),
+                        &raw mut val,
+                    )
+                };
+            }
+            ArrayKey::Str(key) => {
+                unsafe {
+                    // Use raw bytes directly since zend_hash_str_update takes a length.
+                    // This allows keys with embedded null bytes (e.g. PHP property mangling).
+                    zend_hash_str_update(self, key.as_ptr().cast(), key.len(), &raw mut val)
+                };
+            }
+            ArrayKey::ZendString(key) => {
+                unsafe {
+                    // zend_hash_update does the addref itself for non-interned strings.
+                    zend_hash_update(self, key.as_ptr().cast_mut(), &raw mut val)
+                };
+            }
+        }
+        val.release();
+    }
+
+    /// Pushes a value onto the end of the hash table.
+    fn push_zval(&mut self, mut val: Zval) {
         unsafe { zend_hash_next_index_insert(self, &raw mut val) };
         val.release();
+    }
+
+    /// Appends values onto the end of the hash table, writing them directly
+    /// into its packed storage when possible.
+    pub(crate) fn try_fill_packed<E>(
+        &mut self,
+        values: impl Iterator<Item = std::result::Result<Zval, E>>,
+    ) -> std::result::Result<(), E> {
+        if !self.prepare_packed_fill() {
+            for val in values {
+                self.push_zval(val?);
+            }
+            return Ok(());
+        }
+
+        let (additional, _) = values.size_hint();
+        if let Ok(additional) = u32::try_from(additional)
+            && let Some(size) = self.nNumUsed.checked_add(additional)
+            && size > self.nTableSize
+        {
+            unsafe { zend_hash_extend(self, size, true) };
+        }
+        for val in values {
+            self.append_packed(val?);
+        }
+        Ok(())
+    }
 
+    /// Inserts entries into the hash table. Entries with integer keys
+    /// following the last value of a packed hash table are written directly
+    /// into its storage, until an entry with another key is inserted.
+    pub(crate) fn try_extend_entries<'a, K, E>(
+        &mut self,
+        entries: impl Iterator<Item = std::result::Result<(K, Zval), E>>,
+    ) -> std::result::Result<(), E>
+    where
+        K: Into<ArrayKey<'a>>,
+    {
+        // Hash tables are only initialized as packed ones if the first key is an
+        // integer, as string keys would convert them back.
+        let mut packed = None;
+        for entry in entries {
+            let (key, val) = entry?;
+            match key.into() {
+                ArrayKey::Long(index)
+                    if *packed.get_or_insert_with(|| self.prepare_packed_fill())
+                        && index == i64::from(self.nNumUsed) =>
+                {
+                    self.append_packed(val);
+                }
+                key => {
+                    packed = Some(false);
+                    self.insert_zval(key, val);
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Prepares the hash table for values to be appended directly into its
+    /// packed storage. Returns false if the values must be pushed one at a
+    /// time instead, as the hash table is not packed or has holes at its end.
+    fn prepare_packed_fill(&mut self) -> bool {
+        let flags = unsafe { self.u.flags };
+        if flags & HASH_FLAG_UNINITIALIZED != 0 {
+            unsafe { zend_hash_real_init_packed(self) };
+            return true;
+        }
+        flags & HASH_FLAG_PACKED != 0 && self.nNextFreeElement <= i64::from(self.nNumUsed)
+    }
+
+    /// Appends a value onto the end of a hash table prepared with
+    /// [`ZendHashTable::prepare_packed_fill`], growing its storage if it is
+    /// full.
+    fn append_packed(&mut self, val: Zval) {
+        if self.nNumUsed >= self.nTableSize {
+            // The size is rounded up to the next power of two.
+            unsafe { zend_hash_extend(self, self.nNumUsed + 1, true) };
+        }
+
+        let index = self.nNumUsed;
+        // SAFETY: The storage has room for the value, which is moved into it.
+        unsafe {
+            cfg_if::cfg_if! {
+                if #[cfg(php82)] {
+                    ptr::write(self.__bindgen_anon_1.arPacked.add(index as usize), val);
+                } else {
+                    let bucket = self.__bindgen_anon_1.arData.add(index as usize);
+                    ptr::write(&raw mut (*bucket).val, val);
+                    (*bucket).h = zend_ulong::from(index);
+                    (*bucket).key = ptr::null_mut();
+                }
+            }
+        }
+        self.nNumUsed += 1;
+        self.nNumOfElements += 1;
+        self.nNextFreeElement = i64::from(self.nNumUsed);
+    }
+
     /// Checks if the hashtable only contains numerical keys.
     ///
     /// # Returns
@@ -981,4 +1156,54 @@ mod tests {
             assert!(ht.has_key(&ArrayKey::from(&numeric_key)));
         });
     }
+
+    #[test]
+    fn test_fill_packed() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new_packed(2);
+            ht.fill_packed(0..100i64).unwrap();
+            ht.push(100i64).unwrap();
+            ht.fill_packed(["a", "b"]).unwrap();
+
+            assert_eq!(ht.len(), 103);
+            assert!(ht.has_sequential_keys());
+            assert_eq!(ht.get_index(42).and_then(Zval::long), Some(42));
+            assert_eq!(ht.get_index(102).and_then(Zval::str), Some("b"));
+
+            ht.push("c").unwrap();
+            assert_eq!(ht.get_index(103).and_then(Zval::str), Some("c"));
+        });
+    }
+
+    #[test]
+    fn test_fill_packed_after_holes() {
+        Embed::run(|| {
+            let mut ht = ZendHashTable::new();
+            ht.fill_packed([1, 2, 3]).unwrap();
+            ht.remove_index(2).unwrap();
+            ht.fill_packed([4]).unwrap();
+            assert_eq!(ht.get_index(3).and_then(Zval::long), Some(4));
+
+            ht.insert("key", 5).unwrap();
+            ht.fill_packed([6]).unwrap();
+            assert_eq!(ht.get_index(4).and_then(Zval::long), Some(6));
+            assert_eq!(ht.len(), 5);
+        });
+    }
+
+    #[test]
+    fn test_extend() {
+        Embed::run(|| {
+            let mut ht: ZBox<ZendHashTable> =
+                (0..10).map(|i| i.into_zval(false).unwrap()).collect();
+            assert_eq!(ht.len(), 10);
+            assert!(ht.has_sequential_keys());
+
+            ht.extend([(10i64, Zval::null()), (20, Zval::null())]);
+            ht.extend([("a", Zval::null())]);
+            assert_eq!(ht.len(), 13);
+            assert!(ht.get_index(20).is_some());
+            assert!(ht.get("a").is_some());
+        });
+    }
 }
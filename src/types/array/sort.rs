@@ -0,0 +1,285 @@
+//! Sorting of hash tables in place, with the semantics of PHP's sort
+//! functions.
+
+use std::{
+    any::Any,
+    cell::Cell,
+    cmp::Ordering,
+    ffi::{c_int, c_void},
+    mem::ManuallyDrop,
+    panic::{AssertUnwindSafe, catch_unwind, resume_unwind},
+    ptr,
+};
+
+use super::ZendHashTable;
+use crate::{
+    ffi::{
+        Bucket, ext_php_rs_zval_natural_compare, numeric_compare_function,
+        string_case_compare_function, string_compare_function, zend_compare, zend_hash_sort_ex,
+        zend_long, zend_sort, zval,
+    },
+    flags::{SortFlags, ZvalTypeFlags},
+    types::Zval,
+};
+
+/// A comparison of the buckets of a hash table.
+type BucketComparison<'a> = dyn FnMut(&Bucket, &Bucket) -> Ordering + 'a;
+
+/// The state of a sort in progress.
+struct Sort<'a, 'b> {
+    compare: &'a mut BucketComparison<'b>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+thread_local! {
+    /// The sort in progress on this thread, as the comparison function given to
+    /// the engine cannot carry any state.
+    static SORT: Cell<*mut c_void> = const { Cell::new(ptr::null_mut()) };
+}
+
+impl ZendHashTable {
+    /// Sorts the hash table in place with a comparison function, as `usort()`
+    /// and `uasort()` do.
+    ///
+    /// The sort is stable: equal values keep their order.
+    ///
+    /// # Parameters
+    ///
+    /// * `compare` - The function comparing two values.
+    /// * `renumber` - Whether the keys are replaced by the positions of the
+    ///   values, like `usort()`, or kept, like `uasort()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `compare` panics, once the hash table has been sorted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::types::{ZendHashTable, Zval};
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.push(3);
+    /// ht.push(1);
+    /// ht.push(2);
+    ///
+    /// ht.sort_by(|a, b| b.long().cmp(&a.long()), true);
+    /// assert_eq!(ht.get_index(0).and_then(Zval::long), Some(3));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F, renumber: bool)
+    where
+        F: FnMut(&Zval, &Zval) -> Ordering,
+    {
+        self.sort_buckets(&mut |a, b| compare(&a.val, &b.val), renumber);
+    }
+
+    /// Sorts the hash table in place by a key extracted from each value.
+    ///
+    /// The sort is stable: values with equal keys keep their order.
+    ///
+    /// # Parameters
+    ///
+    /// * `f` - The function extracting the key of a value.
+    /// * `renumber` - Whether the keys of the hash table are replaced by the
+    ///   positions of the values, like `usort()`, or kept, like `uasort()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` panics, once the hash table has been sorted.
+    pub fn sort_by_key<K, F>(&mut self, mut f: F, renumber: bool)
+    where
+        K: Ord,
+        F: FnMut(&Zval) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)), renumber);
+    }
+
+    /// Sorts the values of the hash table in place, comparing them as
+    /// `sort()` and `asort()` do with the given flags.
+    ///
+    /// # Parameters
+    ///
+    /// * `flags` - How the values are compared.
+    /// * `renumber` - Whether the keys are replaced by the positions of the
+    ///   values, like `sort()`, or kept, like `asort()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{flags::SortFlags, types::{ZendHashTable, Zval}};
+    ///
+    /// let mut ht = ZendHashTable::new();
+    /// ht.push("img12");
+    /// ht.push("img10");
+    /// ht.push("img2");
+    ///
+    /// ht.sort_with_flags(SortFlags::Natural, true);
+    /// assert_eq!(ht.get_index(0).and_then(Zval::str), Some("img2"));
+    /// ```
+    pub fn sort_with_flags(&mut self, flags: SortFlags, renumber: bool) {
+        self.sort_by(comparison(flags), renumber);
+    }
+
+    /// Sorts the hash table in place by its keys, comparing them as `ksort()`
+    /// does with the given flags. The keys are kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `flags` - How the keys are compared.
+    pub fn ksort(&mut self, flags: SortFlags) {
+        let compare = comparison(flags);
+        self.sort_buckets(&mut |a, b| compare(&bucket_key(a), &bucket_key(b)), false);
+    }
+
+    /// Sorts the buckets of the hash table with `zend_hash_sort()`, which
+    /// renumbers the keys if requested.
+    fn sort_buckets(&mut self, compare: &mut BucketComparison<'_>, renumber: bool) {
+        let mut sort = Sort {
+            compare,
+            panic: None,
+        };
+        // Sorts may be nested in comparison functions.
+        let previous = SORT.replace((&raw mut sort).cast());
+        unsafe { zend_hash_sort_ex(self, Some(zend_sort), Some(compare_buckets), renumber) };
+        SORT.set(previous);
+
+        if let Some(panic) = sort.panic {
+            resume_unwind(panic);
+        }
+    }
+}
+
+/// Compares two buckets with the comparison of the sort in progress. Buckets
+/// which are equal are ordered by their original position, which the engine
+/// stores in the extra space of their values.
+extern "C" fn compare_buckets(a: *mut Bucket, b: *mut Bucket) -> c_int {
+    // SAFETY: The sort is in progress, and the engine passes valid buckets.
+    let (sort, a, b) = unsafe { (&mut *SORT.get().cast::<Sort<'_, '_>>(), &*a, &*b) };
+    if sort.panic.is_some() {
+        return 0;
+    }
+
+    let ordering = match catch_unwind(AssertUnwindSafe(|| (sort.compare)(a, b))) {
+        Ok(ordering) => ordering,
+        // Panics must not unwind through the engine. They are resumed once the
+        // sort is over.
+        Err(panic) => {
+            sort.panic = Some(panic);
+            return 0;
+        }
+    };
+    ordering.then_with(|| unsafe { a.val.u2.extra.cmp(&b.val.u2.extra) }) as c_int
+}
+
+/// Returns the key of a bucket as a zval, which does not own the key and
+/// must not be dropped.
+fn bucket_key(bucket: &Bucket) -> ManuallyDrop<Zval> {
+    let mut key = Zval::new();
+    if bucket.key.is_null() {
+        #[allow(clippy::cast_possible_wrap)]
+        key.set_long(bucket.h as zend_long);
+    } else {
+        key.u1.type_info = ZvalTypeFlags::String.bits();
+        key.value.str_ = bucket.key;
+    }
+    ManuallyDrop::new(key)
+}
+
+/// Returns the function comparing values with the given flags.
+fn comparison(flags: SortFlags) -> fn(&Zval, &Zval) -> Ordering {
+    match flags {
+        SortFlags::Regular => |a, b| engine_compare(a, b, |a, b| unsafe { zend_compare(a, b) }),
+        SortFlags::Numeric => {
+            |a, b| engine_compare(a, b, |a, b| unsafe { numeric_compare_function(a, b) })
+        }
+        SortFlags::String => {
+            |a, b| engine_compare(a, b, |a, b| unsafe { string_compare_function(a, b) })
+        }
+        SortFlags::StringCaseInsensitive => {
+            |a, b| engine_compare(a, b, |a, b| unsafe { string_case_compare_function(a, b) })
+        }
+        SortFlags::Natural => |a, b| {
+            engine_compare(a, b, |a, b| unsafe {
+                ext_php_rs_zval_natural_compare(a, b, false)
+            })
+        },
+        SortFlags::NaturalCaseInsensitive => |a, b| {
+            engine_compare(a, b, |a, b| unsafe {
+                ext_php_rs_zval_natural_compare(a, b, true)
+            })
+        },
+    }
+}
+
+/// Compares two values with a comparison function of the engine.
+///
+/// The comparison functions are `ZEND_FASTCALL`, so they are wrapped in a
+/// closure rather than passed as function pointers.
+fn engine_compare(
+    a: &Zval,
+    b: &Zval,
+    compare: impl FnOnce(*mut zval, *mut zval) -> c_int,
+) -> Ordering {
+    compare(ptr::from_ref(a).cast_mut(), ptr::from_ref(b).cast_mut()).cmp(&0)
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{boxed::ZBox, embed::Embed};
+
+    fn values(ht: &ZendHashTable) -> Vec<String> {
+        ht.iter()
+            .map(|(key, value)| format!("{key}={}", value.coerce_to_string().unwrap()))
+            .collect()
+    }
+
+    fn eval_array(code: &str) -> ZBox<ZendHashTable> {
+        Embed::eval(code).unwrap().array().unwrap().to_owned()
+    }
+
+    #[test]
+    fn test_sort_with_flags() {
+        Embed::run(|| {
+            let mut ht = eval_array("['b' => 'img12', 'a' => 'img10', 'c' => 'IMG2', 'd' => 10];");
+            ht.sort_with_flags(SortFlags::Regular, false);
+            assert_eq!(values(&ht), ["d=10", "c=IMG2", "a=img10", "b=img12"]);
+
+            ht.sort_with_flags(SortFlags::NaturalCaseInsensitive, true);
+            assert_eq!(values(&ht), ["0=10", "1=IMG2", "2=img10", "3=img12"]);
+
+            let mut ht = eval_array("['10', '9', '1e1', 'a'];");
+            ht.sort_with_flags(SortFlags::Numeric, true);
+            assert_eq!(values(&ht), ["0=a", "1=9", "2=10", "3=1e1"]);
+
+            ht.sort_with_flags(SortFlags::String, false);
+            assert_eq!(values(&ht), ["2=10", "3=1e1", "1=9", "0=a"]);
+        });
+    }
+
+    #[test]
+    fn test_ksort() {
+        Embed::run(|| {
+            let mut ht = eval_array("['b' => 1, 10 => 2, 'a' => 3, 9 => 4];");
+            ht.ksort(SortFlags::String);
+            assert_eq!(values(&ht), ["10=2", "9=4", "a=3", "b=1"]);
+
+            ht.ksort(SortFlags::Numeric);
+            assert_eq!(values(&ht), ["a=3", "b=1", "9=4", "10=2"]);
+        });
+    }
+
+    #[test]
+    fn test_sort_by_is_stable() {
+        Embed::run(|| {
+            let mut ht = eval_array("['x' => 'bb', 'y' => 'a', 'z' => 'cc', 'w' => 'd'];");
+            ht.sort_by_key(|value| value.str().map(str::len), false);
+            assert_eq!(values(&ht), ["y=a", "w=d", "x=bb", "z=cc"]);
+
+            ht.sort_by(|a, b| b.compare(a).unwrap(), true);
+            assert_eq!(values(&ht), ["0=d", "1=cc", "2=bb", "3=a"]);
+        });
+    }
+}
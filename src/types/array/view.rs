@@ -0,0 +1,454 @@
+//! Typed views over PHP arrays, converting their elements lazily.
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use super::{
+    ArrayKey, Iter, Values, ZendHashTable,
+    conversions::{convert, value_doc_type},
+};
+use crate::{convert::FromZval, error::Result, flags::DataType, types::Zval};
+
+/// Types which the keys of a [`MapView`] can be converted to.
+pub trait MapViewKey<'a>: Sized {
+    /// The `PHPDoc` type of the keys, e.g. `string`.
+    const DOC_TYPE: &'static str;
+
+    /// Attempts to convert an array key.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key cannot be converted.
+    fn from_key(key: ArrayKey<'a>) -> Result<Self>;
+}
+
+impl<'a> MapViewKey<'a> for ArrayKey<'a> {
+    const DOC_TYPE: &'static str = "array-key";
+
+    fn from_key(key: ArrayKey<'a>) -> Result<Self> {
+        Ok(key)
+    }
+}
+
+impl<'a> MapViewKey<'a> for String {
+    const DOC_TYPE: &'static str = "string";
+
+    fn from_key(key: ArrayKey<'a>) -> Result<Self> {
+        key.try_into()
+    }
+}
+
+impl<'a> MapViewKey<'a> for i64 {
+    const DOC_TYPE: &'static str = "int";
+
+    fn from_key(key: ArrayKey<'a>) -> Result<Self> {
+        key.try_into()
+    }
+}
+
+/// A borrowed view over the values of a PHP array, converted to `T` when they
+/// are accessed.
+///
+/// Unlike `Vec<T>`, accepting an `ArrayView<T>` as a parameter does not copy
+/// the array, nor check the types of its values: an invalid value is only
+/// reported when it is accessed. The keys are ignored when iterating, as they
+/// are when converting to a `Vec<T>`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, types::ArrayView};
+///
+/// /// Returns the first positive number, without converting the numbers after it.
+/// #[php_function]
+/// pub fn first_positive(numbers: ArrayView<i64>) -> PhpResult<Option<i64>> {
+///     for number in numbers.iter() {
+///         let number = number?;
+///         if number > 0 {
+///             return Ok(Some(number));
+///         }
+///     }
+///     Ok(None)
+/// }
+/// ```
+pub struct ArrayView<'a, T> {
+    ht: &'a ZendHashTable,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: FromZval<'a>> ArrayView<'a, T> {
+    /// Creates a view over the values of the given hashtable.
+    ///
+    /// # Parameters
+    ///
+    /// * `ht` - The hashtable to view.
+    #[must_use]
+    pub fn new(ht: &'a ZendHashTable) -> Self {
+        Self {
+            ht,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the value at the given index, or [`None`] if there is none.
+    ///
+    /// # Parameters
+    ///
+    /// * `index` - The integer key of the value.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ZvalConversion`] - If the value is not a valid `T`.
+    ///
+    /// [`Error::ZvalConversion`]: crate::error::Error::ZvalConversion
+    #[must_use]
+    pub fn get(&self, index: i64) -> Option<Result<T>> {
+        self.ht.get_index(index).map(convert)
+    }
+
+    /// Returns an iterator over the values of the array, converted one at a
+    /// time.
+    #[must_use]
+    pub fn iter(&self) -> ArrayViewIter<'a, T> {
+        ArrayViewIter {
+            values: self.ht.values(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of values in the array.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ht.len()
+    }
+
+    /// Returns true if the array is empty, false otherwise.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ht.is_empty()
+    }
+
+    /// Returns the viewed hashtable.
+    #[must_use]
+    pub fn as_hashtable(&self) -> &'a ZendHashTable {
+        self.ht
+    }
+}
+
+impl<T> Clone for ArrayView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArrayView<'_, T> {}
+
+impl<T> Debug for ArrayView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ArrayView").field(&self.ht).finish()
+    }
+}
+
+impl<'a, T: FromZval<'a>> IntoIterator for ArrayView<'a, T> {
+    type Item = Result<T>;
+    type IntoIter = ArrayViewIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: FromZval<'a>> IntoIterator for &ArrayView<'a, T> {
+    type Item = Result<T>;
+    type IntoIter = ArrayViewIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: FromZval<'a>> FromZval<'a> for ArrayView<'a, T> {
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array().map(Self::new)
+    }
+
+    fn doc_type() -> Option<String> {
+        Some(format!("array<int, {}>", value_doc_type::<T>()))
+    }
+}
+
+/// Iterator over the values of an [`ArrayView`].
+pub struct ArrayViewIter<'a, T> {
+    values: Values<'a>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: FromZval<'a>> Iterator for ArrayViewIter<'a, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.next().map(convert)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<'a, T: FromZval<'a>> ExactSizeIterator for ArrayViewIter<'a, T> {
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<'a, T: FromZval<'a>> DoubleEndedIterator for ArrayViewIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.values.next_back().map(convert)
+    }
+}
+
+/// A borrowed view over the keys and values of a PHP array, converted to `K`
+/// and `V` when they are accessed.
+///
+/// Unlike `HashMap<K, V>`, accepting a `MapView<K, V>` as a parameter does not
+/// copy the array, nor check the types of its keys and values: an invalid
+/// entry is only reported when it is accessed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{prelude::*, types::MapView};
+///
+/// #[php_function]
+/// pub fn header_value(headers: MapView<String, String>, name: &str) -> PhpResult<Option<String>> {
+///     Ok(headers.get(name).transpose()?)
+/// }
+/// ```
+pub struct MapView<'a, K, V> {
+    ht: &'a ZendHashTable,
+    phantom: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> MapView<'a, K, V> {
+    /// Creates a view over the entries of the given hashtable.
+    ///
+    /// # Parameters
+    ///
+    /// * `ht` - The hashtable to view.
+    #[must_use]
+    pub fn new(ht: &'a ZendHashTable) -> Self {
+        Self {
+            ht,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the value stored under the given key, or [`None`] if there is
+    /// none.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key of the value.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ZvalConversion`] - If the value is not a valid `V`.
+    ///
+    /// [`Error::ZvalConversion`]: crate::error::Error::ZvalConversion
+    #[must_use]
+    pub fn get<'k>(&self, key: impl Into<ArrayKey<'k>>) -> Option<Result<V>> {
+        self.ht.get(key).map(convert)
+    }
+
+    /// Returns true if a value is stored under the given key, false
+    /// otherwise.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key to look for.
+    #[must_use]
+    pub fn contains_key<'k>(&self, key: impl Into<ArrayKey<'k>>) -> bool {
+        self.ht.get(key).is_some()
+    }
+
+    /// Returns an iterator over the entries of the array, converted one at a
+    /// time.
+    #[must_use]
+    pub fn iter(&self) -> MapViewIter<'a, K, V> {
+        MapViewIter {
+            iter: self.ht.iter(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries in the array.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ht.len()
+    }
+
+    /// Returns true if the array is empty, false otherwise.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ht.is_empty()
+    }
+
+    /// Returns the viewed hashtable.
+    #[must_use]
+    pub fn as_hashtable(&self) -> &'a ZendHashTable {
+        self.ht
+    }
+}
+
+impl<K, V> Clone for MapView<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for MapView<'_, K, V> {}
+
+impl<K, V> Debug for MapView<'_, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("MapView").field(&self.ht).finish()
+    }
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> IntoIterator for MapView<'a, K, V> {
+    type Item = Result<(K, V)>;
+    type IntoIter = MapViewIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> IntoIterator for &MapView<'a, K, V> {
+    type Item = Result<(K, V)>;
+    type IntoIter = MapViewIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> FromZval<'a> for MapView<'a, K, V> {
+    const TYPE: DataType = DataType::Array;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.array().map(Self::new)
+    }
+
+    fn doc_type() -> Option<String> {
+        Some(format!("array<{}, {}>", K::DOC_TYPE, value_doc_type::<V>()))
+    }
+}
+
+/// Iterator over the entries of a [`MapView`].
+pub struct MapViewIter<'a, K, V> {
+    iter: Iter<'a>,
+    phantom: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> MapViewIter<'a, K, V> {
+    /// Converts an entry of the array.
+    fn convert((key, value): (ArrayKey<'a>, &'a Zval)) -> Result<(K, V)> {
+        Ok((K::from_key(key)?, convert(value)?))
+    }
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> Iterator for MapViewIter<'a, K, V> {
+    type Item = Result<(K, V)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(Self::convert)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> ExactSizeIterator for MapViewIter<'a, K, V> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, K: MapViewKey<'a>, V: FromZval<'a>> DoubleEndedIterator for MapViewIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(Self::convert)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{embed::Embed, error::Error};
+
+    #[test]
+    fn test_array_view() {
+        Embed::run(|| {
+            let zv = Embed::eval("[1, 2, 'three'];").unwrap();
+            let view = ArrayView::<i64>::from_zval(&zv).unwrap();
+            assert_eq!(view.len(), 3);
+            assert_eq!(view.get(1).unwrap().unwrap(), 2);
+            assert!(view.get(3).is_none());
+            assert!(matches!(
+                view.get(2),
+                Some(Err(Error::ZvalConversion(DataType::String)))
+            ));
+
+            let mut iter = view.iter();
+            assert_eq!(iter.next().unwrap().unwrap(), 1);
+            assert_eq!(iter.next().unwrap().unwrap(), 2);
+            assert!(iter.next().unwrap().is_err());
+            assert!(iter.next().is_none());
+        });
+    }
+
+    #[test]
+    fn test_map_view() {
+        Embed::run(|| {
+            let zv = Embed::eval("['a' => 'x', 'b' => 2, 5 => 'y'];").unwrap();
+            let view = MapView::<String, String>::from_zval(&zv).unwrap();
+            assert_eq!(view.get("a").unwrap().unwrap(), "x");
+            assert!(view.get("b").unwrap().is_err());
+            assert!(view.contains_key(5));
+            assert!(!view.contains_key("c"));
+
+            let entries = view.iter().filter_map(Result::ok).collect::<Vec<_>>();
+            assert_eq!(
+                entries,
+                vec![
+                    ("a".to_string(), "x".to_string()),
+                    ("5".to_string(), "y".to_string())
+                ]
+            );
+
+            let view = MapView::<i64, &Zval>::from_zval(&zv).unwrap();
+            assert!(view.iter().next().unwrap().is_err());
+        });
+    }
+
+    #[test]
+    fn test_view_doc_types() {
+        assert_eq!(
+            <ArrayView<'_, String>>::doc_type().as_deref(),
+            Some("array<int, string>")
+        );
+        assert_eq!(
+            <MapView<'_, ArrayKey<'_>, ArrayView<'_, f64>>>::doc_type().as_deref(),
+            Some("array<array-key, array<int, float>>")
+        );
+    }
+}
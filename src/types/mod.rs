@@ -10,25 +10,49 @@ mod iterable;
 mod iterator;
 mod long;
 mod object;
+mod operators;
+mod persistent_zval;
 mod php_ref;
+mod resource;
 mod separated;
 mod string;
+mod type_decl;
+#[cfg(php81)]
+mod weak;
 mod zval;
+mod zval_view;
 
-pub use array::{ArrayKey, Entry, OccupiedEntry, VacantEntry, ZendEmptyArray, ZendHashTable};
+pub use array::{
+    ArrayKey, ArrayView, ArrayViewIter, Entry, MapView, MapViewIter, MapViewKey, OccupiedEntry,
+    Pair, VacantEntry, ZendEmptyArray, ZendHashTable,
+};
 pub use callable::ZendCallable;
 pub use class_object::ZendClassObject;
 pub use iterable::Iterable;
 pub use iterator::ZendIterator;
 pub use long::ZendLong;
 pub use object::{PropertyQuery, ZendObject};
+pub use persistent_zval::PersistentZval;
 pub use php_ref::PhpRef;
+pub use resource::ZendResource;
 pub use separated::Separated;
 pub use string::ZendStr;
+pub use type_decl::TypeDecl;
+#[cfg(php81)]
+pub use weak::{ZendWeakMap, ZendWeakRef};
 pub use zval::Zval;
+pub use zval_view::{ZvalView, ZvalViewMut};
 
 use crate::{convert::FromZval, flags::DataType};
 
+/// Releases the request-bound state of the types, once a request has been
+/// shut down.
+pub(crate) fn end_request() {
+    persistent_zval::end_request();
+    #[cfg(php81)]
+    weak::end_request();
+}
+
 into_zval!(f32, set_double, Double);
 into_zval!(f64, set_double, Double);
 into_zval!(bool, set_bool, Bool);
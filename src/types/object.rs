@@ -267,27 +267,54 @@ impl ZendObject {
     /// # Parameters
     ///
     /// * `name` - The name of the property.
-    /// * `query` - The type of query to use when attempting to get a property.
     ///
     /// # Errors
     ///
     /// * `Error::InvalidScope` - If the object handlers or the properties
     ///   cannot be retrieved.
+    /// * `Error::InvalidProperty` - If the property does not exist.
+    /// * `Error::ZvalConversion` - If the value of the property could not be
+    ///   converted.
     pub fn get_property<'a, T>(&'a self, name: &str) -> Result<T>
+    where
+        T: FromZval<'a>,
+    {
+        match self.try_get_property(name) {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(Error::InvalidProperty),
+            Err(Error::InvalidPropertyValue(_, ty)) => Err(Error::ZvalConversion(ty)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Attempts to read a property from the Object, returning [`None`] if it
+    /// does not exist. Errors name the property which could not be read.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the property.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::InvalidScope` - If the object handlers or the properties
+    ///   cannot be retrieved.
+    /// * `Error::InvalidPropertyValue` - If the value of the property could
+    ///   not be converted.
+    pub fn try_get_property<'a, T>(&'a self, name: &str) -> Result<Option<T>>
     where
         T: FromZval<'a>,
     {
         if !self.has_property(name, PropertyQuery::Exists)? {
-            return Err(Error::InvalidProperty);
+            return Ok(None);
         }
 
-        let mut name = ZendStr::new(name, false);
+        let mut zend_name = ZendStr::new(name, false);
         let mut rv = Zval::new();
 
         let zv = unsafe {
             self.handlers()?.read_property.ok_or(Error::InvalidScope)?(
                 self.mut_ptr(),
-                &raw mut *name,
+                &raw mut *zend_name,
                 1,
                 ptr::null_mut(),
                 &raw mut rv,
@@ -296,7 +323,9 @@ impl ZendObject {
         }
         .ok_or(Error::InvalidScope)?;
 
-        T::from_zval(zv).ok_or_else(|| Error::ZvalConversion(zv.get_type()))
+        T::from_zval(zv)
+            .map(Some)
+            .ok_or_else(|| Error::InvalidPropertyValue(name.to_string(), zv.get_type()))
     }
 
     /// Attempts to set a property on the object.
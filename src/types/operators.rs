@@ -0,0 +1,270 @@
+//! Comparison and arithmetic operators on [`Zval`]s, with the semantics of the
+//! PHP operators.
+
+use std::{cmp::Ordering, ops, ptr};
+
+use crate::{
+    error::{Error, Result},
+    ffi::{
+        add_function, bitwise_and_function, bitwise_not_function, bitwise_or_function,
+        bitwise_xor_function, concat_function, div_function, mod_function, mul_function,
+        pow_function, shift_left_function, shift_right_function, sub_function, zend_compare,
+        zend_result, zval,
+    },
+    types::Zval,
+    zend::ExecutorGlobals,
+};
+
+/// Calls a binary operator of the engine, returning the result or the
+/// exception which was thrown.
+///
+/// The operator functions are `ZEND_FASTCALL`, so they are wrapped in a
+/// closure rather than passed as function pointers.
+fn binary_op(
+    op1: &Zval,
+    op2: &Zval,
+    op: impl FnOnce(*mut zval, *mut zval, *mut zval) -> zend_result,
+) -> Result<Zval> {
+    let mut result = Zval::new();
+    let status = op(
+        &raw mut result,
+        ptr::from_ref(op1).cast_mut(),
+        ptr::from_ref(op2).cast_mut(),
+    );
+    check_result(status, result)
+}
+
+fn check_result(status: zend_result, result: Zval) -> Result<Zval> {
+    if let Some(e) = ExecutorGlobals::take_exception() {
+        Err(Error::Exception(e))
+    } else if status < 0 {
+        Err(Error::UnsupportedOperands)
+    } else {
+        Ok(result)
+    }
+}
+
+macro_rules! binary_ops {
+    ($($(#[$attr:meta])* $name:ident => $function:ident, $trait:ident;)*) => {
+        impl Zval {
+            $(
+                $(#[$attr])*
+                ///
+                /// # Parameters
+                ///
+                /// * `other` - The right-hand operand.
+                ///
+                /// # Errors
+                ///
+                /// * [`Error::Exception`] - If the engine threw an exception, e.g.
+                ///   a `TypeError` for unsupported operand types or a
+                ///   `DivisionByZeroError`.
+                /// * [`Error::UnsupportedOperands`] - If the operation failed
+                ///   without throwing an exception.
+                pub fn $name(&self, other: &Zval) -> Result<Zval> {
+                    binary_op(self, other, |r, a, b| unsafe { $function(r, a, b) })
+                }
+            )*
+        }
+
+        $(
+            impl ops::$trait for &Zval {
+                type Output = Result<Zval>;
+
+                fn $name(self, rhs: Self) -> Self::Output {
+                    Zval::$name(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+binary_ops! {
+    /// Adds two values, as `$a + $b` does in PHP.
+    ///
+    /// Arrays are merged as with the array union operator.
+    add => add_function, Add;
+    /// Subtracts a value from another, as `$a - $b` does in PHP.
+    sub => sub_function, Sub;
+    /// Multiplies two values, as `$a * $b` does in PHP.
+    mul => mul_function, Mul;
+    /// Divides a value by another, as `$a / $b` does in PHP.
+    div => div_function, Div;
+    /// Computes the remainder of the integer division of a value by another,
+    /// as `$a % $b` does in PHP.
+    rem => mod_function, Rem;
+    /// Computes the bitwise and of two values, as `$a & $b` does in PHP.
+    bitand => bitwise_and_function, BitAnd;
+    /// Computes the bitwise or of two values, as `$a | $b` does in PHP.
+    bitor => bitwise_or_function, BitOr;
+    /// Computes the bitwise exclusive or of two values, as `$a ^ $b` does in
+    /// PHP.
+    bitxor => bitwise_xor_function, BitXor;
+    /// Shifts the bits of a value to the left, as `$a << $b` does in PHP.
+    shl => shift_left_function, Shl;
+    /// Shifts the bits of a value to the right, as `$a >> $b` does in PHP.
+    shr => shift_right_function, Shr;
+}
+
+impl Zval {
+    /// Checks if the zval is equal to another one after type juggling.
+    /// This works like `==` in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The zval to compare with.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the engine threw an exception while
+    ///   comparing, e.g. from the `__toString` method of an object.
+    pub fn loose_eq(&self, other: &Zval) -> Result<bool> {
+        Ok(self.compare(other)? == Ordering::Equal)
+    }
+
+    /// Compares the zval with another one after type juggling.
+    /// This works like `<=>` in PHP.
+    ///
+    /// Values which cannot be compared, such as `NAN`, are never equal to
+    /// another value, and are ordered as [`Ordering::Greater`].
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The zval to compare with.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the engine threw an exception while
+    ///   comparing, e.g. from the `__toString` method of an object.
+    pub fn compare(&self, other: &Zval) -> Result<Ordering> {
+        let result = unsafe {
+            zend_compare(
+                ptr::from_ref(self).cast_mut(),
+                ptr::from_ref(other).cast_mut(),
+            )
+        };
+        match ExecutorGlobals::take_exception() {
+            Some(e) => Err(Error::Exception(e)),
+            None => Ok(result.cmp(&0)),
+        }
+    }
+
+    /// Raises a value to the power of another, as `$a ** $b` does in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The exponent.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the engine threw an exception, e.g. a
+    ///   `TypeError` for unsupported operand types.
+    /// * [`Error::UnsupportedOperands`] - If the operation failed without
+    ///   throwing an exception.
+    pub fn pow(&self, other: &Zval) -> Result<Zval> {
+        binary_op(self, other, |r, a, b| unsafe { pow_function(r, a, b) })
+    }
+
+    /// Concatenates two values as strings, as `$a . $b` does in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The value to append.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the engine threw an exception, e.g. an
+    ///   `Error` for an object which cannot be converted to a string.
+    /// * [`Error::UnsupportedOperands`] - If the operation failed without
+    ///   throwing an exception.
+    pub fn concat(&self, other: &Zval) -> Result<Zval> {
+        binary_op(self, other, |r, a, b| unsafe { concat_function(r, a, b) })
+    }
+
+    /// Computes the bitwise negation of the value, as `~$a` does in PHP.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the engine threw an exception, e.g. a
+    ///   `TypeError` for unsupported operand types.
+    /// * [`Error::UnsupportedOperands`] - If the operation failed without
+    ///   throwing an exception.
+    pub fn bitnot(&self) -> Result<Zval> {
+        let mut result = Zval::new();
+        let status =
+            unsafe { bitwise_not_function(&raw mut result, ptr::from_ref(self).cast_mut()) };
+        check_result(status, result)
+    }
+}
+
+impl ops::Not for &Zval {
+    type Output = Result<Zval>;
+
+    fn not(self) -> Self::Output {
+        self.bitnot()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::embed::Embed;
+    use crate::types::Zval;
+
+    fn zv(value: impl crate::convert::IntoZval) -> Zval {
+        value.into_zval(false).unwrap()
+    }
+
+    #[test]
+    fn test_compare() {
+        Embed::run(|| {
+            assert!(zv(1).loose_eq(&zv("1")).unwrap());
+            assert!(zv(1).loose_eq(&zv(1.0)).unwrap());
+            assert!(!zv(0).loose_eq(&zv("a")).unwrap());
+            assert!(zv(()).loose_eq(&zv(false)).unwrap());
+            assert!(!zv(f64::NAN).loose_eq(&zv(f64::NAN)).unwrap());
+
+            assert_eq!(zv(1).compare(&zv(2)).unwrap(), Ordering::Less);
+            assert_eq!(zv("abc").compare(&zv("abd")).unwrap(), Ordering::Less);
+            assert_eq!(zv("10").compare(&zv("9")).unwrap(), Ordering::Greater);
+            assert_eq!(
+                zv(vec![1, 2]).compare(&zv(vec![1, 2])).unwrap(),
+                Ordering::Equal
+            );
+        });
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        Embed::run(|| {
+            assert_eq!((&zv(1) + &zv(2)).unwrap().long(), Some(3));
+            assert_eq!((&zv(1) + &zv("1.5")).unwrap().double(), Some(2.5));
+            assert_eq!(
+                (&zv(i64::MAX) + &zv(1)).unwrap().double(),
+                Some(9.223_372_036_854_776e18)
+            );
+            assert_eq!((&zv(5) - &zv(7)).unwrap().long(), Some(-2));
+            assert_eq!((&zv(3) * &zv(4)).unwrap().long(), Some(12));
+            assert_eq!((&zv(7) / &zv(2)).unwrap().double(), Some(3.5));
+            assert_eq!((&zv(6) / &zv(2)).unwrap().long(), Some(3));
+            assert_eq!((&zv(-7) % &zv(3)).unwrap().long(), Some(-1));
+            assert_eq!(zv(2).pow(&zv(10)).unwrap().long(), Some(1024));
+            assert_eq!(zv("a").concat(&zv(1)).unwrap().str(), Some("a1"));
+        });
+    }
+
+    #[test]
+    fn test_bitwise() {
+        Embed::run(|| {
+            assert_eq!((&zv(0b1100) & &zv(0b1010)).unwrap().long(), Some(0b1000));
+            assert_eq!((&zv(0b1100) | &zv(0b1010)).unwrap().long(), Some(0b1110));
+            assert_eq!((&zv(0b1100) ^ &zv(0b1010)).unwrap().long(), Some(0b0110));
+            assert_eq!((&zv(1) << &zv(4)).unwrap().long(), Some(16));
+            assert_eq!((&zv(-16) >> &zv(2)).unwrap().long(), Some(-4));
+            assert_eq!((!&zv(0)).unwrap().long(), Some(-1));
+        });
+    }
+}
@@ -0,0 +1,331 @@
+//! Deep copies of PHP values stored in persistent memory, which outlive the
+//! request.
+
+use std::{cell::RefCell, fmt::Debug, mem, ptr, sync::Arc};
+
+use crate::{
+    boxed::ZBox,
+    convert::IntoZval,
+    error::{Error, Result},
+    ffi::{
+        _zend_hash_init, GC_IMMUTABLE, GC_NOT_COLLECTABLE, GC_PERSISTENT, IS_ARRAY,
+        zend_hash_add_new, zend_hash_destroy, zend_hash_index_update, zend_string_hash_func,
+    },
+    flags::{DataType, ZvalTypeFlags},
+    types::{ZendHashTable, ZendStr, Zval},
+};
+
+thread_local! {
+    /// Persistent values materialized during the current request. The zvals
+    /// point directly at the persistent values, which must be kept alive
+    /// until the end of the request.
+    static MATERIALIZED: RefCell<Vec<Arc<Inner>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Releases the persistent values materialized during the request.
+pub(crate) fn end_request() {
+    let materialized = MATERIALIZED.take();
+    drop(materialized);
+}
+
+/// A PHP value deep-copied into persistent memory, which can be kept across
+/// requests, for example in [`ModuleGlobals`] or in a `static` cache.
+///
+/// Strings and arrays are copied with `pemalloc`, and are flagged as
+/// immutable: they are never modified nor reference counted, so persistent
+/// values can be shared between threads. Objects and resources are bound to
+/// the request, and cannot be stored.
+///
+/// Persistent values are turned back into request values with
+/// [`PersistentZval::to_zval`], which does not copy them: the request value
+/// points at the immutable arrays and strings, as it does for arrays and
+/// strings cached by opcache, and PHP copies them when they are modified.
+/// Persistent values are kept alive until the end of the requests they have
+/// been materialized in.
+///
+/// Requests are tracked by the module generated by the [`macro@php_module`]
+/// macro. Materialized values are only released on thread exit if the module
+/// is built by hand.
+///
+/// [`ModuleGlobals`]: crate::zend::ModuleGlobals
+/// [`macro@php_module`]: crate::php_module
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use std::{collections::HashMap, sync::Mutex};
+///
+/// use ext_php_rs::{prelude::*, types::{PersistentZval, Zval}};
+///
+/// static CACHE: Mutex<Option<HashMap<String, PersistentZval>>> = Mutex::new(None);
+///
+/// #[php_function]
+/// pub fn cache_store(key: String, value: &Zval) -> PhpResult<()> {
+///     let value = PersistentZval::new(value)?;
+///     let mut cache = CACHE.lock().map_err(|_| "Cache is poisoned")?;
+///     cache.get_or_insert_with(HashMap::new).insert(key, value);
+///     Ok(())
+/// }
+///
+/// #[php_function]
+/// pub fn cache_fetch(key: &str) -> PhpResult<Option<PersistentZval>> {
+///     let cache = CACHE.lock().map_err(|_| "Cache is poisoned")?;
+///     Ok(cache.as_ref().and_then(|cache| cache.get(key)).cloned())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct PersistentZval {
+    inner: Arc<Inner>,
+}
+
+/// The persistent copy of a value, along with all the persistent strings and
+/// arrays it is made of.
+struct Inner {
+    zval: Zval,
+    strings: Vec<*mut ZendStr>,
+    arrays: Vec<*mut ZendHashTable>,
+}
+
+// SAFETY: The strings and arrays are allocated in persistent memory, and are
+// never modified once the value has been copied.
+unsafe impl Send for Inner {}
+unsafe impl Sync for Inner {}
+
+impl PersistentZval {
+    /// Deep-copies a value into persistent memory. References are replaced by
+    /// the value they point at.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value to copy.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NotPersistable`] - If the value is or contains an object, a
+    ///   resource, or an array which contains itself.
+    pub fn new(value: &Zval) -> Result<Self> {
+        let mut inner = Inner {
+            zval: Zval::new(),
+            strings: Vec::new(),
+            arrays: Vec::new(),
+        };
+        // On error, the copied strings and arrays are freed with `inner`.
+        inner.zval = inner.persist(value, &mut Vec::new())?;
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Returns the type of the value.
+    #[must_use]
+    pub fn get_type(&self) -> DataType {
+        self.inner.zval.get_type()
+    }
+
+    /// Returns a request value pointing at the persistent value, without
+    /// copying it. The persistent value is kept alive until the end of the
+    /// request.
+    #[must_use]
+    pub fn to_zval(&self) -> Zval {
+        if !self.inner.arrays.is_empty() || !self.inner.strings.is_empty() {
+            MATERIALIZED.with_borrow_mut(|values| values.push(Arc::clone(&self.inner)));
+        }
+        // The value is not reference counted, so this does not modify it.
+        self.inner.zval.shallow_clone()
+    }
+}
+
+impl Inner {
+    /// Copies a value into persistent memory, recording the strings and
+    /// arrays which have been allocated.
+    fn persist(&mut self, value: &Zval, parents: &mut Vec<*const ZendHashTable>) -> Result<Zval> {
+        let value = value.dereference();
+        let mut zv = Zval::new();
+        match value.get_type() {
+            DataType::Null => zv.set_null(),
+            DataType::False | DataType::True | DataType::Bool => {
+                zv.set_bool(value.bool().unwrap_or_default());
+            }
+            DataType::Long => zv.set_long(value.long().unwrap_or_default()),
+            DataType::Double => zv.set_double(value.double().unwrap_or_default()),
+            DataType::String => {
+                let s = value.zend_str().ok_or(Error::InvalidPointer)?;
+                zv.u1.type_info = ZvalTypeFlags::String.bits();
+                zv.value.str_ = self.persist_str(s.as_bytes());
+            }
+            DataType::Array => {
+                let ht = value.array().ok_or(Error::InvalidPointer)?;
+                zv.u1.type_info = ZvalTypeFlags::Array.bits();
+                zv.value.arr = self.persist_array(ht, parents)?;
+            }
+            ty => return Err(Error::NotPersistable(ty)),
+        }
+        Ok(zv)
+    }
+
+    /// Copies a string into persistent memory, flagging it as interned so that
+    /// it is never reference counted.
+    fn persist_str(&mut self, s: &[u8]) -> *mut ZendStr {
+        let s: *mut ZendStr = ZendStr::new(s, true).into_raw();
+        self.strings.push(s);
+        unsafe {
+            // The hash is computed lazily, which would modify the string.
+            zend_string_hash_func(s);
+            (*s).gc.u.type_info |= GC_IMMUTABLE;
+        }
+        s
+    }
+
+    /// Copies an array into persistent memory, flagging it as immutable.
+    fn persist_array(
+        &mut self,
+        ht: &ZendHashTable,
+        parents: &mut Vec<*const ZendHashTable>,
+    ) -> Result<*mut ZendHashTable> {
+        if parents.contains(&ptr::from_ref(ht)) {
+            return Err(Error::NotPersistable(DataType::Reference));
+        }
+
+        let copy = Box::into_raw(Box::new(unsafe { mem::zeroed::<ZendHashTable>() }));
+        unsafe { _zend_hash_init(copy, ht.len().try_into()?, None, true) };
+        self.arrays.push(copy);
+
+        parents.push(ht);
+        let mut iter = ht.iter();
+        while let Some((key, value)) = iter.next_zval() {
+            let mut value = self.persist(value, parents)?;
+            if let Some(key) = key.zend_str() {
+                let key = self.persist_str(key.as_bytes());
+                unsafe { zend_hash_add_new(copy, key, &raw mut value) };
+            } else {
+                #[allow(clippy::cast_sign_loss)]
+                let index = key.long().unwrap_or_default() as u64;
+                unsafe { zend_hash_index_update(copy, index, &raw mut value) };
+            }
+        }
+        parents.pop();
+
+        // Immutable arrays are separated before being modified. Their reference
+        // count is never modified, and is kept above one for this purpose.
+        unsafe {
+            (*copy).gc.refcount = 2;
+            (*copy).gc.u.type_info = IS_ARRAY | GC_IMMUTABLE | GC_NOT_COLLECTABLE | GC_PERSISTENT;
+        }
+        Ok(copy)
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // The zval does not own the value.
+        self.zval.u1.type_info = ZvalTypeFlags::Null.bits();
+        for ht in self.arrays.drain(..) {
+            // The values and keys of the array are freed separately.
+            unsafe {
+                (*ht).gc.refcount = 1;
+                (*ht).gc.u.type_info &= !(GC_IMMUTABLE | GC_NOT_COLLECTABLE);
+                zend_hash_destroy(ht);
+                drop(Box::from_raw(ht));
+            }
+        }
+        for s in self.strings.drain(..) {
+            unsafe {
+                (*s).gc.u.type_info &= !GC_IMMUTABLE;
+                drop(ZBox::from_raw(s));
+            }
+        }
+    }
+}
+
+impl Debug for PersistentZval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PersistentZval")
+            .field(&self.inner.zval)
+            .finish()
+    }
+}
+
+impl IntoZval for PersistentZval {
+    const TYPE: DataType = DataType::Mixed;
+    const NULLABLE: bool = true;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        (&self).set_zval(zv, persistent)
+    }
+}
+
+impl IntoZval for &PersistentZval {
+    const TYPE: DataType = DataType::Mixed;
+    const NULLABLE: bool = true;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        *zv = self.to_zval();
+        Ok(())
+    }
+}
+
+impl TryFrom<&Zval> for PersistentZval {
+    type Error = Error;
+
+    fn try_from(value: &Zval) -> Result<Self> {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{embed::Embed, types::ZendObject};
+
+    #[test]
+    fn test_persist_scalars() {
+        Embed::run(|| {
+            for value in [
+                Zval::null(),
+                true.into_zval(false).unwrap(),
+                42.into_zval(false).unwrap(),
+                1.5.into_zval(false).unwrap(),
+                "hello".into_zval(false).unwrap(),
+            ] {
+                let persistent = PersistentZval::new(&value).unwrap();
+                assert!(persistent.to_zval().is_identical(&value));
+            }
+        });
+    }
+
+    #[test]
+    fn test_persist_array() {
+        Embed::run(|| {
+            let value = Embed::eval("['a' => [1, 2, 'b'], 5 => 'c', 'd' => null];").unwrap();
+            let persistent = PersistentZval::new(&value).unwrap();
+            drop(value);
+
+            let zv = persistent.to_zval();
+            let expected = Embed::eval("['a' => [1, 2, 'b'], 5 => 'c', 'd' => null];").unwrap();
+            assert!(zv.is_identical(&expected));
+            assert!(zv.array().unwrap().is_immutable());
+            assert_eq!(persistent.get_type(), DataType::Array);
+        });
+    }
+
+    #[test]
+    fn test_persist_rejects_objects() {
+        Embed::run(|| {
+            let mut object = Zval::new();
+            object.set_object(&mut ZendObject::new_stdclass());
+            assert!(matches!(
+                PersistentZval::new(&object),
+                Err(Error::NotPersistable(DataType::Object(_)))
+            ));
+
+            let mut array = vec![Zval::new()].into_zval(false).unwrap();
+            let mut inner = Zval::new();
+            inner.set_object(&mut ZendObject::new_stdclass());
+            array.array_mut().unwrap().push(inner).unwrap();
+            assert!(PersistentZval::new(&array).is_err());
+        });
+    }
+}
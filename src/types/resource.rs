@@ -0,0 +1,298 @@
+//! Represents a resource in PHP, allowing Rust values to be exposed to PHP as
+//! opaque handles.
+
+use std::{convert::Infallible, fmt::Debug, marker::PhantomData, ptr};
+
+use crate::{
+    boxed::{ZBox, ZBoxable},
+    convert::{FromZval, IntoZval},
+    error::Result,
+    ffi::{
+        zend_list_close, zend_list_delete, zend_register_persistent_resource,
+        zend_register_resource, zend_resource,
+    },
+    flags::DataType,
+    resource::RegisteredResource,
+    types::{ZendLong, Zval},
+    zend::ExecutorGlobals,
+};
+
+/// A PHP resource holding a value of type `T`, where `T` is a
+/// [`RegisteredResource`].
+///
+/// Owned resources are held inside a [`ZBox`], which releases the resource
+/// when dropped. The value is dropped when the last reference to a
+/// request-scoped resource is released, or when the resource is closed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use ext_php_rs::{boxed::ZBox, prelude::*, types::ZendResource};
+///
+/// #[php_resource]
+/// pub struct Counter {
+///     count: i64,
+/// }
+///
+/// #[php_function]
+/// pub fn counter_new() -> ZBox<ZendResource<Counter>> {
+///     ZendResource::new(Counter { count: 0 })
+/// }
+///
+/// #[php_function]
+/// pub fn counter_increment(mut counter: ZBox<ZendResource<Counter>>) -> PhpResult<i64> {
+///     let counter = counter.get_mut().ok_or("Counter has been closed")?;
+///     counter.count += 1;
+///     Ok(counter.count)
+/// }
+/// ```
+#[repr(transparent)]
+pub struct ZendResource<T> {
+    res: zend_resource,
+    phantom: PhantomData<T>,
+}
+
+impl<T: RegisteredResource> ZendResource<T> {
+    /// Creates a new request-scoped resource owning the given value.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value to store inside the resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource type has not been registered with
+    /// [`ModuleBuilder::resource`].
+    ///
+    /// [`ModuleBuilder::resource`]: crate::builders::ModuleBuilder::resource
+    #[must_use]
+    pub fn new(value: T) -> ZBox<Self> {
+        let id = T::get_metadata().id();
+        let value = Box::into_raw(Box::new(value));
+        // SAFETY: The engine returns a valid resource with a reference count of
+        // one, which is owned by the box.
+        unsafe { ZBox::from_raw(zend_register_resource(value.cast(), id).cast()) }
+    }
+
+    /// Returns a resource pointing at the persistent value stored under the
+    /// given key, creating the value if it does not exist yet.
+    ///
+    /// Persistent values outlive the request and are shared by all requests
+    /// handled by the same process (or thread, for ZTS builds), which is
+    /// useful for pooling connections. They are dropped on module shutdown.
+    ///
+    /// Keys are shared with other extensions, and should be prefixed with the
+    /// name of the extension. A persistent value of another type stored under
+    /// the same key is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key identifying the persistent value.
+    /// * `init` - Function creating the value if it does not exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource type has not been registered with
+    /// [`ModuleBuilder::resource`].
+    ///
+    /// [`ModuleBuilder::resource`]: crate::builders::ModuleBuilder::resource
+    pub fn persistent(key: &str, init: impl FnOnce() -> T) -> ZBox<Self> {
+        match Self::try_persistent(key, || Ok::<_, Infallible>(init())) {
+            Ok(res) => res,
+            Err(e) => match e {},
+        }
+    }
+
+    /// Returns a resource pointing at the persistent value stored under the
+    /// given key, attempting to create the value if it does not exist yet.
+    ///
+    /// See [`ZendResource::persistent`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key identifying the persistent value.
+    /// * `init` - Function attempting to create the value if it does not
+    ///   exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error returned by `init`, in which case nothing is stored
+    /// under the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource type has not been registered with
+    /// [`ModuleBuilder::resource`].
+    ///
+    /// [`ModuleBuilder::resource`]: crate::builders::ModuleBuilder::resource
+    pub fn try_persistent<E>(
+        key: &str,
+        init: impl FnOnce() -> Result<T, E>,
+    ) -> Result<ZBox<Self>, E> {
+        let persistent_id = T::get_metadata().persistent_id();
+
+        // The guard must be released before calling `init`, which may access
+        // the executor globals.
+        let existing = ExecutorGlobals::get()
+            .persistent_list()
+            .get(key)
+            .and_then(Zval::resource)
+            .and_then(|res| unsafe { res.as_ref() })
+            .filter(|res| res.type_ == persistent_id)
+            .map(|res| res.ptr);
+
+        let value = if let Some(value) = existing {
+            value
+        } else {
+            let value = Box::into_raw(Box::new(init()?)).cast();
+            unsafe {
+                zend_register_persistent_resource(
+                    key.as_ptr().cast(),
+                    key.len(),
+                    value,
+                    persistent_id,
+                );
+            }
+            value
+        };
+
+        // The persistent list owns the value, the request-scoped resource only
+        // points at it.
+        // SAFETY: See `ZendResource::new`.
+        Ok(unsafe { ZBox::from_raw(zend_register_resource(value, persistent_id).cast()) })
+    }
+
+    /// Attempts to interpret a resource as a resource of type `T`, returning
+    /// [`None`] if it is of another type or has been closed.
+    fn from_resource(res: &zend_resource) -> Option<&Self> {
+        let meta = T::get_metadata();
+        if !meta.is_registered() || (res.type_ != meta.id() && res.type_ != meta.persistent_id()) {
+            return None;
+        }
+        // SAFETY: `ZendResource` is a transparent wrapper around `zend_resource`.
+        Some(unsafe { &*ptr::from_ref(res).cast::<Self>() })
+    }
+
+    /// Returns a reference to the value held by the resource, or [`None`] if
+    /// the resource has been closed.
+    #[must_use]
+    pub fn get(&self) -> Option<&T> {
+        if self.is_closed() {
+            return None;
+        }
+        // SAFETY: Values of open resources of type `T` are valid `T`s.
+        unsafe { self.res.ptr.cast::<T>().as_ref() }
+    }
+
+    /// Returns a mutable reference to the value held by the resource, or
+    /// [`None`] if the resource has been closed.
+    #[must_use]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if self.is_closed() {
+            return None;
+        }
+        // SAFETY: See `ZendResource::get`.
+        unsafe { self.res.ptr.cast::<T>().as_mut() }
+    }
+
+    /// Returns true if the resource points at a persistent value, false
+    /// otherwise.
+    #[must_use]
+    pub fn is_persistent(&self) -> bool {
+        self.res.type_ == T::get_metadata().persistent_id()
+    }
+
+    /// Closes the resource, as `fclose()` does for streams.
+    ///
+    /// The value of a request-scoped resource is dropped, even if there are
+    /// other references to the resource. Persistent values are kept.
+    pub fn close(&mut self) {
+        unsafe { zend_list_close(&raw mut self.res) };
+    }
+}
+
+impl<T> ZendResource<T> {
+    /// Returns the handle of the resource, which is displayed when dumping
+    /// it, e.g. `resource(5) of type (stream)`.
+    #[must_use]
+    pub fn handle(&self) -> ZendLong {
+        self.res.handle
+    }
+
+    /// Returns true if the resource has been closed, false otherwise.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.res.type_ < 0 || self.res.ptr.is_null()
+    }
+
+    /// Returns a new owned reference to the resource.
+    fn new_ref(&self) -> ZBox<Self> {
+        let res = ptr::from_ref(self).cast_mut();
+        // SAFETY: The resource is valid, and is kept alive by the additional
+        // reference held by the box.
+        unsafe {
+            (*res).res.gc.refcount += 1;
+            ZBox::from_raw(res)
+        }
+    }
+}
+
+unsafe impl<T> ZBoxable for ZendResource<T> {
+    fn free(&mut self) {
+        unsafe { zend_list_delete(&raw mut self.res) }
+    }
+}
+
+impl<T> Clone for ZBox<ZendResource<T>> {
+    fn clone(&self) -> Self {
+        self.new_ref()
+    }
+}
+
+impl<T> Debug for ZendResource<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZendResource")
+            .field("handle", &self.handle())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl<'a, T: RegisteredResource> FromZval<'a> for &'a ZendResource<T> {
+    const TYPE: DataType = DataType::Resource;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        ZendResource::from_resource(unsafe { zval.resource()?.as_ref()? })
+    }
+}
+
+impl<T: RegisteredResource> FromZval<'_> for ZBox<ZendResource<T>> {
+    const TYPE: DataType = DataType::Resource;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        <&ZendResource<T>>::from_zval(zval).map(ZendResource::new_ref)
+    }
+}
+
+impl<T: RegisteredResource> IntoZval for ZBox<ZendResource<T>> {
+    const TYPE: DataType = DataType::Resource;
+    const NULLABLE: bool = false;
+
+    #[inline]
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        // The reference held by the box is moved into the zval.
+        zv.set_resource(ptr::from_mut(self.into_raw()).cast());
+        Ok(())
+    }
+}
+
+impl<T: RegisteredResource> IntoZval for &ZendResource<T> {
+    const TYPE: DataType = DataType::Resource;
+    const NULLABLE: bool = false;
+
+    #[inline]
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        self.new_ref().set_zval(zv, persistent)
+    }
+}
@@ -0,0 +1,303 @@
+//! Type declarations of parameters and return values, which can describe
+//! union, intersection and DNF types.
+
+use std::fmt::{self, Display};
+
+use crate::flags::DataType;
+
+/// A type declaration of a parameter or a return value.
+///
+/// Unlike [`DataType`], which describes a single type, a declaration can be a
+/// union (`int|string`), an intersection of classes
+/// (`Countable&Traversable`), or a union of intersections, known as a DNF type
+/// (`(Countable&Traversable)|null`).
+///
+/// Intersections require PHP 8.1, and DNF types PHP 8.2. Internal functions
+/// can only declare them from PHP 8.3, and are registered with the first class
+/// of each intersection on earlier versions.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::{flags::DataType, types::TypeDecl};
+///
+/// let decl = TypeDecl::union([DataType::Long.into(), DataType::String.into()]);
+/// assert_eq!(decl.to_string(), "string|int");
+///
+/// let decl = TypeDecl::intersection(["Countable", "Traversable"]).nullable();
+/// assert_eq!(decl.to_string(), "(Countable&Traversable)|null");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDecl {
+    /// A single type, e.g. `int` or `Countable`.
+    Single(DataType),
+    /// A union of single types and intersections, e.g. `int|string|null`.
+    /// Built with [`TypeDecl::union`], which flattens nested unions.
+    Union(Vec<TypeDecl>),
+    /// An intersection of classes and interfaces, e.g.
+    /// `Countable&Traversable`.
+    Intersection(Vec<&'static str>),
+}
+
+impl TypeDecl {
+    /// Creates a union of types.
+    ///
+    /// Nested unions are flattened and duplicate types removed. The types are
+    /// sorted in the order the engine displays them, e.g. `string|int|null`,
+    /// with classes first in the given order, and a union containing `mixed`
+    /// is `mixed`.
+    ///
+    /// # Parameters
+    ///
+    /// * `types` - The types of the union.
+    pub fn union<I>(types: I) -> Self
+    where
+        I: IntoIterator<Item = TypeDecl>,
+    {
+        let mut members = Vec::new();
+        for ty in types {
+            let flattened = match ty {
+                Self::Union(inner) => inner,
+                ty => vec![ty],
+            };
+            for ty in flattened {
+                if !members.contains(&ty) {
+                    members.push(ty);
+                }
+            }
+        }
+
+        if members.is_empty() || members.contains(&Self::Single(DataType::Mixed)) {
+            return Self::Single(DataType::Mixed);
+        }
+        members.sort_by_key(Self::rank);
+        if members.len() == 1 {
+            return members.remove(0);
+        }
+        Self::Union(members)
+    }
+
+    /// Creates an intersection of classes and interfaces.
+    ///
+    /// # Parameters
+    ///
+    /// * `classes` - The names of the classes and interfaces.
+    pub fn intersection<I>(classes: I) -> Self
+    where
+        I: IntoIterator<Item = &'static str>,
+    {
+        let mut classes: Vec<_> = classes.into_iter().collect();
+        classes.dedup();
+        match classes[..] {
+            [] => Self::Single(DataType::Object(None)),
+            [class] => Self::Single(DataType::Object(Some(class))),
+            _ => Self::Intersection(classes),
+        }
+    }
+
+    /// Returns the declaration allowing `null` as well.
+    #[must_use]
+    pub fn nullable(self) -> Self {
+        match self {
+            Self::Single(DataType::Void) => self,
+            ty => Self::union([ty, Self::Single(DataType::Null)]),
+        }
+    }
+
+    /// Returns whether the declaration allows `null`.
+    #[must_use]
+    pub fn allows_null(&self) -> bool {
+        match self {
+            Self::Single(ty) => matches!(ty, DataType::Null | DataType::Mixed),
+            Self::Union(types) => types.iter().any(Self::allows_null),
+            Self::Intersection(_) => false,
+        }
+    }
+
+    /// Returns the type of the declaration if it is a single type, which may be
+    /// nullable.
+    ///
+    /// # Returns
+    ///
+    /// The type, and whether `null` is allowed.
+    #[must_use]
+    pub fn as_single(&self) -> Option<(DataType, bool)> {
+        match self {
+            Self::Single(ty) => Some((*ty, false)),
+            Self::Union(types) => match &types[..] {
+                [Self::Single(ty), Self::Single(DataType::Null)] => Some((*ty, true)),
+                _ => None,
+            },
+            Self::Intersection(_) => None,
+        }
+    }
+
+    /// Returns the position of a member of a union in the canonical order of
+    /// `zend_type_to_string()`.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Intersection(_) | Self::Single(DataType::Object(Some(_))) => 0,
+            Self::Single(ty) => match ty {
+                DataType::Callable => 1,
+                DataType::Object(None) => 2,
+                DataType::Iterable => 3,
+                DataType::Array => 4,
+                DataType::String => 5,
+                DataType::Long => 6,
+                DataType::Double => 7,
+                DataType::Bool | DataType::False | DataType::True => 8,
+                DataType::Void => 9,
+                DataType::Null => 11,
+                _ => 10,
+            },
+            Self::Union(_) => 10,
+        }
+    }
+
+    /// Formats the declaration, formatting the names of classes with `class`.
+    pub(crate) fn fmt_with(
+        &self,
+        f: &mut dyn fmt::Write,
+        class: &dyn Fn(&mut dyn fmt::Write, &str) -> fmt::Result,
+    ) -> fmt::Result {
+        match self {
+            Self::Single(DataType::Object(Some(name))) => class(f, name),
+            Self::Single(ty) => f.write_str(type_name(*ty)),
+            Self::Union(types) => {
+                if let Some((ty, true)) = self.as_single() {
+                    f.write_char('?')?;
+                    return Self::Single(ty).fmt_with(f, class);
+                }
+                for (i, ty) in types.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char('|')?;
+                    }
+                    let parenthesized = matches!(ty, Self::Intersection(_));
+                    if parenthesized {
+                        f.write_char('(')?;
+                    }
+                    ty.fmt_with(f, class)?;
+                    if parenthesized {
+                        f.write_char(')')?;
+                    }
+                }
+                Ok(())
+            }
+            Self::Intersection(classes) => {
+                for (i, name) in classes.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char('&')?;
+                    }
+                    class(f, name)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the declaration as it is written in stubs, with fully qualified
+    /// class names.
+    pub(crate) fn to_stub(&self) -> String {
+        let mut stub = String::new();
+        // Writing to a string cannot fail.
+        let _ = self.fmt_with(&mut stub, &|f, name| write!(f, "\\{name}"));
+        stub
+    }
+}
+
+impl From<DataType> for TypeDecl {
+    fn from(ty: DataType) -> Self {
+        Self::Single(ty)
+    }
+}
+
+impl Display for TypeDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, &|f, name| f.write_str(name))
+    }
+}
+
+/// Returns the name of a type which is not a class, as it is declared in PHP.
+fn type_name(ty: DataType) -> &'static str {
+    match ty {
+        DataType::Bool | DataType::True | DataType::False => "bool",
+        DataType::Long => "int",
+        DataType::Double => "float",
+        DataType::String => "string",
+        DataType::Array => "array",
+        DataType::Object(_) => "object",
+        DataType::Resource => "resource",
+        DataType::Callable => "callable",
+        DataType::Iterable => "iterable",
+        DataType::Void => "void",
+        DataType::Null => "null",
+        _ => "mixed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union() {
+        let decl = TypeDecl::union([
+            DataType::Long.into(),
+            DataType::Null.into(),
+            TypeDecl::union([DataType::String.into(), DataType::Long.into()]),
+        ]);
+        assert_eq!(
+            decl,
+            TypeDecl::Union(vec![
+                DataType::String.into(),
+                DataType::Long.into(),
+                DataType::Null.into(),
+            ])
+        );
+        assert_eq!(decl.to_string(), "string|int|null");
+        assert!(decl.allows_null());
+
+        let decl = TypeDecl::union([
+            DataType::Bool.into(),
+            DataType::Double.into(),
+            DataType::Array.into(),
+            DataType::Object(Some("Countable")).into(),
+            DataType::Object(None).into(),
+        ]);
+        assert_eq!(decl.to_string(), "Countable|object|array|float|bool");
+
+        let decl = TypeDecl::union([DataType::Bool.into(), DataType::Mixed.into()]);
+        assert_eq!(decl, TypeDecl::Single(DataType::Mixed));
+    }
+
+    #[test]
+    fn test_nullable() {
+        let decl = TypeDecl::from(DataType::Long).nullable();
+        assert_eq!(decl.to_string(), "?int");
+        assert_eq!(decl.as_single(), Some((DataType::Long, true)));
+        assert_eq!(
+            TypeDecl::from(DataType::Void).nullable().to_string(),
+            "void"
+        );
+        assert_eq!(
+            TypeDecl::from(DataType::Mixed).nullable().to_string(),
+            "mixed"
+        );
+    }
+
+    #[test]
+    fn test_intersection() {
+        let decl = TypeDecl::intersection(["Countable", "Traversable"]);
+        assert_eq!(decl.to_string(), "Countable&Traversable");
+        assert!(!decl.allows_null());
+
+        let decl = TypeDecl::union([decl, DataType::Object(Some("Stringable")).into()]);
+        assert_eq!(decl.to_string(), "(Countable&Traversable)|Stringable");
+        assert_eq!(decl.as_single(), None);
+
+        assert_eq!(
+            TypeDecl::intersection(["Countable"]),
+            TypeDecl::Single(DataType::Object(Some("Countable")))
+        );
+    }
+}
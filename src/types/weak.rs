@@ -0,0 +1,434 @@
+//! Weak references to PHP objects, which do not keep the objects alive.
+//!
+//! These are built on the same engine machinery as the `WeakReference` and
+//! `WeakMap` PHP classes: the engine notifies the weak references when the
+//! object they point at is released. Weak references require PHP 8.1 or
+//! later.
+
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    marker::PhantomData,
+    mem,
+    ptr::{self, NonNull},
+};
+
+use crate::{
+    boxed::ZBox,
+    ffi::{
+        _zend_hash_init, ext_php_rs_zend_object_to_weakref_key, zend_hash_clean, zend_hash_destroy,
+        zend_hash_index_find, zend_object, zend_weakrefs_hash_add, zend_weakrefs_hash_del, zval,
+    },
+    rc::PhpRc,
+    types::{ZendHashTable, ZendObject, Zval},
+};
+
+thread_local! {
+    /// Incremented at the end of each request. The engine does not notify weak
+    /// references of the objects it releases on request shutdown, so weak
+    /// references created during a previous request are considered dead.
+    static REQUEST_EPOCH: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Marks the end of a request, invalidating the weak references created
+/// during the request.
+pub(crate) fn end_request() {
+    REQUEST_EPOCH.with(|epoch| epoch.set(epoch.get().wrapping_add(1)));
+}
+
+fn current_epoch() -> u64 {
+    REQUEST_EPOCH.with(Cell::get)
+}
+
+/// A map from PHP objects to Rust values, which does not keep the objects
+/// alive. This is the Rust equivalent of the `WeakMap` PHP class.
+///
+/// Objects are compared by identity, i.e. two objects are the same key if
+/// they have the same [`ZendObject::get_id`]. The value associated with an
+/// object is dropped when the object is released.
+///
+/// The map may outlive the request, for example when stored in a
+/// `thread_local!` cache. The entries of objects created during previous
+/// requests are dropped the next time the map is modified.
+///
+/// Request boundaries are tracked by the module generated by the
+/// [`macro@php_module`] macro. Weak maps must not outlive the request if the
+/// module is built by hand.
+///
+/// [`macro@php_module`]: crate::php_module
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![cfg_attr(windows, feature(abi_vectorcall))]
+/// use std::cell::RefCell;
+///
+/// use ext_php_rs::{prelude::*, types::{ZendObject, ZendWeakMap}};
+///
+/// thread_local! {
+///     static HITS: RefCell<ZendWeakMap<u64>> = RefCell::new(ZendWeakMap::new());
+/// }
+///
+/// #[php_function]
+/// pub fn count_hits(object: &ZendObject) -> u64 {
+///     HITS.with_borrow_mut(|hits| {
+///         let count = hits.get(object).copied().unwrap_or_default() + 1;
+///         hits.insert(object, count);
+///         count
+///     })
+/// }
+/// ```
+pub struct ZendWeakMap<T> {
+    /// Registered with the engine, so must not move.
+    table: Box<ZendHashTable>,
+    epoch: u64,
+    // Values are boxed, and weak maps must stay on the thread of their
+    // objects.
+    phantom: PhantomData<(Box<T>, *mut ZendObject)>,
+}
+
+/// A value of a weak map, stored as a pointer in the hash table.
+struct Entry<T> {
+    object: NonNull<ZendObject>,
+    value: T,
+}
+
+impl<T> ZendWeakMap<T> {
+    /// Creates a new, empty weak map.
+    #[must_use]
+    pub fn new() -> Self {
+        // SAFETY: The table is initialized by `_zend_hash_init`. It is
+        // persistent, as it may outlive the request.
+        let mut table = Box::new(unsafe { mem::zeroed::<ZendHashTable>() });
+        unsafe { _zend_hash_init(&raw mut *table, 8, Some(drop_entry::<T>), true) };
+        Self {
+            table,
+            epoch: current_epoch(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        if self.is_stale() { 0 } else { self.table.len() }
+    }
+
+    /// Returns true if the map is empty, false otherwise.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the map contains a value for the given object, false
+    /// otherwise.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The object to look up.
+    #[must_use]
+    pub fn contains_key(&self, object: &ZendObject) -> bool {
+        self.find(object).is_some()
+    }
+
+    /// Returns a reference to the value associated with the given object.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The object to look up.
+    #[must_use]
+    pub fn get(&self, object: &ZendObject) -> Option<&T> {
+        let entry = self.find(object)?;
+        // SAFETY: Entries are valid until removed from the table.
+        Some(unsafe { &(*entry.as_ptr()).value })
+    }
+
+    /// Returns a mutable reference to the value associated with the given
+    /// object.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The object to look up.
+    #[must_use]
+    pub fn get_mut(&mut self, object: &ZendObject) -> Option<&mut T> {
+        let entry = self.find(object)?;
+        // SAFETY: See `ZendWeakMap::get`.
+        Some(unsafe { &mut (*entry.as_ptr()).value })
+    }
+
+    /// Associates a value with the given object, returning the value which was
+    /// previously associated with it, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The object to use as a key.
+    /// * `value` - The value to associate with the object.
+    pub fn insert(&mut self, object: &ZendObject, value: T) -> Option<T> {
+        self.forget_stale();
+        if let Some(entry) = self.find(object) {
+            // SAFETY: See `ZendWeakMap::get`.
+            return Some(mem::replace(unsafe { &mut (*entry.as_ptr()).value }, value));
+        }
+
+        let entry = Box::into_raw(Box::new(Entry {
+            object: NonNull::from(object),
+            value,
+        }));
+        let mut zv = Zval::new();
+        zv.set_ptr(entry);
+        // The object is not modified, but the engine flags it as weakly
+        // referenced.
+        unsafe {
+            zend_weakrefs_hash_add(
+                &raw mut *self.table,
+                ptr::from_ref(object).cast_mut(),
+                &raw mut zv,
+            );
+        }
+        None
+    }
+
+    /// Removes the value associated with the given object from the map,
+    /// returning it.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The object to remove.
+    pub fn remove(&mut self, object: &ZendObject) -> Option<T> {
+        let zv = self.find_zval(object)?;
+        // SAFETY: The entry is detached from the table before being removed,
+        // so it is not dropped by the table destructor.
+        let entry = unsafe {
+            let entry = (*zv).ptr::<Entry<T>>()?;
+            (*zv).set_ptr(ptr::null_mut::<Entry<T>>());
+            zend_weakrefs_hash_del(&raw mut *self.table, ptr::from_ref(object).cast_mut());
+            Box::from_raw(entry)
+        };
+        Some(entry.value)
+    }
+
+    /// Removes all the entries of the map.
+    pub fn clear(&mut self) {
+        if self.is_stale() {
+            self.forget_stale();
+            return;
+        }
+
+        let objects: Vec<_> = self
+            .table
+            .values()
+            // SAFETY: All values of the table are entries.
+            .filter_map(|zv| unsafe { zv.ptr::<Entry<T>>() })
+            .map(|entry| unsafe { (*entry).object })
+            .collect();
+        for object in objects {
+            // SAFETY: Objects are still alive while they have an entry, and
+            // removing an entry drops its value.
+            unsafe { zend_weakrefs_hash_del(&raw mut *self.table, object.as_ptr()) };
+        }
+    }
+
+    /// Returns true if the entries were created during a previous request.
+    fn is_stale(&self) -> bool {
+        self.epoch != current_epoch()
+    }
+
+    /// Drops the entries created during a previous request. Their objects
+    /// have been released, and the engine is not aware of the entries anymore.
+    fn forget_stale(&mut self) {
+        if self.is_stale() {
+            unsafe { zend_hash_clean(&raw mut *self.table) };
+            self.epoch = current_epoch();
+        }
+    }
+
+    /// Looks up the zval holding the entry of the given object.
+    fn find_zval(&self, object: *const ZendObject) -> Option<*mut zval> {
+        if self.is_stale() {
+            return None;
+        }
+        // The key only depends on the address of the object, which may have
+        // been released.
+        let key = unsafe { ext_php_rs_zend_object_to_weakref_key(object.cast::<zend_object>()) };
+        let zv = unsafe { zend_hash_index_find(&raw const *self.table, key) };
+        (!zv.is_null()).then_some(zv)
+    }
+
+    /// Looks up the entry of the given object.
+    fn find(&self, object: *const ZendObject) -> Option<NonNull<Entry<T>>> {
+        let zv = self.find_zval(object)?;
+        // SAFETY: All values of the table are entries.
+        NonNull::new(unsafe { (*zv).ptr::<Entry<T>>()? })
+    }
+}
+
+impl<T> Default for ZendWeakMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ZendWeakMap<T> {
+    fn drop(&mut self) {
+        // Entries must be unregistered from the engine before the table is
+        // freed.
+        self.clear();
+        unsafe { zend_hash_destroy(&raw mut *self.table) };
+    }
+}
+
+impl<T: Debug> Debug for ZendWeakMap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        if !self.is_stale() {
+            for zv in self.table.values() {
+                // SAFETY: All values of the table are entries, and their
+                // objects are still alive.
+                if let Some(entry) = unsafe { zv.ptr::<Entry<T>>().and_then(|e| e.as_ref()) } {
+                    map.entry(&unsafe { entry.object.as_ref() }.get_id(), &entry.value);
+                }
+            }
+        }
+        map.finish()
+    }
+}
+
+/// Drops an entry removed from the table of a weak map, which happens when
+/// its object is released.
+unsafe extern "C" fn drop_entry<T>(zv: *mut zval) {
+    // SAFETY: All values of the table are entries, or null once detached.
+    if let Some(entry) = unsafe { zv.as_ref().and_then(|zv| zv.ptr::<Entry<T>>()) }
+        && !entry.is_null()
+    {
+        drop(unsafe { Box::from_raw(entry) });
+    }
+}
+
+/// A reference to a PHP object which does not keep it alive. This is the Rust
+/// equivalent of the `WeakReference` PHP class.
+///
+/// Weak references are created with [`ZendObject::downgrade`], and upgraded
+/// back to a strong reference with [`ZendWeakRef::upgrade`] while the object
+/// is alive. See [`ZendWeakMap`] for details on request boundaries.
+pub struct ZendWeakRef {
+    refs: ZendWeakMap<()>,
+    object: NonNull<ZendObject>,
+    id: u32,
+}
+
+impl ZendWeakRef {
+    /// Creates a new weak reference to the given object.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The object to reference.
+    #[must_use]
+    pub fn new(object: &ZendObject) -> Self {
+        let mut refs = ZendWeakMap::new();
+        refs.insert(object, ());
+        Self {
+            refs,
+            object: NonNull::from(object),
+            id: object.get_id(),
+        }
+    }
+
+    /// Returns a strong reference to the object, or [`None`] if it has been
+    /// released.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<ZBox<ZendObject>> {
+        if !self.is_alive() {
+            return None;
+        }
+        let object = self.object.as_ptr();
+        // SAFETY: The object is alive, and the new reference is owned by the
+        // box.
+        unsafe {
+            (*object).inc_count();
+            Some(ZBox::from_raw(object))
+        }
+    }
+
+    /// Returns true if the object is still alive, false otherwise.
+    #[must_use]
+    pub fn is_alive(&self) -> bool {
+        self.refs.find(self.object.as_ptr()).is_some()
+    }
+
+    /// Returns the identifier of the object, as returned by
+    /// [`ZendObject::get_id`].
+    ///
+    /// Identifiers are reused once objects are released, so this may be the
+    /// identifier of another object if this one is not alive anymore.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Debug for ZendWeakRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZendWeakRef")
+            .field("id", &self.id)
+            .field("alive", &self.is_alive())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ZendObject {
+    /// Creates a weak reference to the object, which does not keep it alive.
+    #[must_use]
+    pub fn downgrade(&self) -> ZendWeakRef {
+        ZendWeakRef::new(self)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_weak_ref() {
+        Embed::run(|| {
+            let object = ZendObject::new_stdclass();
+            let weak = object.downgrade();
+            assert!(weak.is_alive());
+            assert_eq!(weak.id(), object.get_id());
+
+            let strong = weak.upgrade().unwrap();
+            assert_eq!(strong.get_id(), object.get_id());
+            drop(object);
+            assert!(weak.is_alive());
+            drop(strong);
+            assert!(!weak.is_alive());
+            assert!(weak.upgrade().is_none());
+        });
+    }
+
+    #[test]
+    fn test_weak_map() {
+        Embed::run(|| {
+            let a = ZendObject::new_stdclass();
+            let b = ZendObject::new_stdclass();
+            let mut map = ZendWeakMap::new();
+            assert!(map.insert(&a, String::from("a")).is_none());
+            assert!(map.insert(&b, String::from("b")).is_none());
+            assert_eq!(map.insert(&b, String::from("c")).as_deref(), Some("b"));
+            assert_eq!(map.len(), 2);
+            assert_eq!(map.get(&a).map(String::as_str), Some("a"));
+
+            drop(a);
+            assert_eq!(map.len(), 1);
+            assert_eq!(map.remove(&b).as_deref(), Some("c"));
+            assert!(map.is_empty());
+            assert!(!map.contains_key(&b));
+
+            map.insert(&b, String::from("d"));
+            map.clear();
+            assert!(map.is_empty());
+        });
+    }
+}
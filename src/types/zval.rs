@@ -65,6 +65,15 @@ impl Zval {
         zval
     }
 
+    /// Creates an undefined zval, e.g. the default value of an uninitialized
+    /// typed property.
+    #[must_use]
+    pub fn undef() -> Zval {
+        let mut zval = Zval::new();
+        zval.u1.type_info = ZvalTypeFlags::Undef.bits();
+        zval
+    }
+
     /// Creates a zval containing an empty array.
     #[must_use]
     pub fn new_array() -> Zval {
@@ -301,22 +310,32 @@ impl Zval {
             self.dereference_mut()
         };
         if zval.get_type() == DataType::Array {
-            unsafe {
-                let arr = zval.value.arr;
-                let ht = &*arr;
-                if ht.is_immutable() {
-                    zval.value.arr = zend_array_dup(arr);
-                } else if (*arr).gc.refcount > 1 {
-                    (*arr).gc.refcount -= 1;
-                    zval.value.arr = zend_array_dup(arr);
-                }
-                zval.value.arr.as_mut()
-            }
+            unsafe { zval.separate_array().as_mut() }
         } else {
             None
         }
     }
 
+    /// Separates the array contained in the zval if it is shared, returning
+    /// the array owned by the zval.
+    ///
+    /// # Safety
+    ///
+    /// The zval must contain an array.
+    pub(crate) unsafe fn separate_array(&mut self) -> *mut ZendHashTable {
+        unsafe {
+            let arr = self.value.arr;
+            let ht = &*arr;
+            if ht.is_immutable() {
+                self.value.arr = zend_array_dup(arr);
+            } else if (*arr).gc.refcount > 1 {
+                (*arr).gc.refcount -= 1;
+                self.value.arr = zend_array_dup(arr);
+            }
+            self.value.arr
+        }
+    }
+
     /// Returns the value of the zval if it is an object.
     ///
     /// References are dereferenced transparently.
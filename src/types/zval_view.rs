@@ -0,0 +1,266 @@
+//! Borrowed views of the value held by a [`Zval`], which can be matched on
+//! instead of querying the type of the zval.
+
+use std::fmt::{self, Debug};
+
+use crate::{
+    convert::FromZval,
+    ffi::zend_resource,
+    flags::DataType,
+    types::{ZendHashTable, ZendLong, ZendObject, ZendStr, Zval},
+};
+
+/// An immutable view of the value held by a [`Zval`], returned by
+/// [`Zval::view`].
+///
+/// References and indirect zvals are resolved, so the view is always of the
+/// value they point at.
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::types::{Zval, ZvalView};
+///
+/// fn describe(zv: &Zval) -> String {
+///     match zv.view() {
+///         ZvalView::Null => "null".into(),
+///         ZvalView::Bool(b) => format!("bool({b})"),
+///         ZvalView::Long(l) => format!("int({l})"),
+///         ZvalView::Double(d) => format!("float({d})"),
+///         ZvalView::String(s) => format!("string({})", s.len()),
+///         ZvalView::Array(ht) => format!("array({})", ht.len()),
+///         ZvalView::Object(obj) => format!("object({})", obj.get_class_name().unwrap_or_default()),
+///         _ => "other".into(),
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub enum ZvalView<'a> {
+    /// An undefined value, e.g. an uninitialized typed property.
+    Undef,
+    /// `null`.
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Long(ZendLong),
+    /// A floating point number.
+    Double(f64),
+    /// A string.
+    String(&'a ZendStr),
+    /// An array.
+    Array(&'a ZendHashTable),
+    /// An object.
+    Object(&'a ZendObject),
+    /// A resource.
+    Resource(&'a zend_resource),
+    /// A value internal to the engine, such as a constant expression or a
+    /// pointer, which is never passed to PHP code.
+    Internal(&'a Zval),
+}
+
+/// A mutable view of the value held by a [`Zval`], returned by
+/// [`Zval::view_mut`].
+///
+/// References and indirect zvals are resolved, so the view is always of the
+/// value they point at. Shared arrays are separated before being borrowed.
+///
+/// Booleans are stored in the type of the zval, and are viewed by value.
+/// Strings may be shared or interned, and cannot be modified.
+pub enum ZvalViewMut<'a> {
+    /// An undefined value, e.g. an uninitialized typed property.
+    Undef,
+    /// `null`.
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Long(&'a mut ZendLong),
+    /// A floating point number.
+    Double(&'a mut f64),
+    /// A string.
+    String(&'a ZendStr),
+    /// An array.
+    Array(&'a mut ZendHashTable),
+    /// An object.
+    Object(&'a mut ZendObject),
+    /// A resource.
+    Resource(&'a mut zend_resource),
+    /// A value internal to the engine, such as a constant expression or a
+    /// pointer, which is never passed to PHP code.
+    Internal(&'a mut Zval),
+}
+
+impl Zval {
+    /// Returns a view of the value held by the zval, which can be matched on.
+    ///
+    /// References and indirect zvals are resolved transparently.
+    #[must_use]
+    pub fn view(&self) -> ZvalView<'_> {
+        // Indirect zvals may point at references.
+        let zv = self.dereference().dereference();
+        // SAFETY: The type of the zval guarantees which member of the value is
+        // valid, and that pointers are not null.
+        unsafe {
+            match zv.get_type() {
+                DataType::Undef => ZvalView::Undef,
+                DataType::Null => ZvalView::Null,
+                DataType::False => ZvalView::Bool(false),
+                DataType::True => ZvalView::Bool(true),
+                DataType::Long => ZvalView::Long(zv.value.lval),
+                DataType::Double => ZvalView::Double(zv.value.dval),
+                DataType::String => ZvalView::String(&*zv.value.str_),
+                DataType::Array => ZvalView::Array(&*zv.value.arr),
+                DataType::Object(_) => ZvalView::Object(&*zv.value.obj),
+                DataType::Resource => ZvalView::Resource(&*zv.value.res),
+                _ => ZvalView::Internal(zv),
+            }
+        }
+    }
+
+    /// Returns a mutable view of the value held by the zval, which can be
+    /// matched on.
+    ///
+    /// References and indirect zvals are resolved transparently, and shared
+    /// arrays are separated, as [`Zval::array_mut`] does.
+    pub fn view_mut(&mut self) -> ZvalViewMut<'_> {
+        let zv = self.dereference_mut().dereference_mut();
+        // SAFETY: See `Zval::view`.
+        unsafe {
+            match zv.get_type() {
+                DataType::Undef => ZvalViewMut::Undef,
+                DataType::Null => ZvalViewMut::Null,
+                DataType::False => ZvalViewMut::Bool(false),
+                DataType::True => ZvalViewMut::Bool(true),
+                DataType::Long => ZvalViewMut::Long(&mut zv.value.lval),
+                DataType::Double => ZvalViewMut::Double(&mut zv.value.dval),
+                DataType::String => ZvalViewMut::String(&*zv.value.str_),
+                DataType::Array => ZvalViewMut::Array(&mut *zv.separate_array()),
+                DataType::Object(_) => ZvalViewMut::Object(&mut *zv.value.obj),
+                DataType::Resource => ZvalViewMut::Resource(&mut *zv.value.res),
+                _ => ZvalViewMut::Internal(zv),
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a Zval> for ZvalView<'a> {
+    fn from(zv: &'a Zval) -> Self {
+        zv.view()
+    }
+}
+
+impl<'a> From<&'a mut Zval> for ZvalViewMut<'a> {
+    fn from(zv: &'a mut Zval) -> Self {
+        zv.view_mut()
+    }
+}
+
+impl Debug for ZvalView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undef => f.write_str("Undef"),
+            Self::Null => f.write_str("Null"),
+            Self::Bool(b) => f.debug_tuple("Bool").field(b).finish(),
+            Self::Long(l) => f.debug_tuple("Long").field(l).finish(),
+            Self::Double(d) => f.debug_tuple("Double").field(d).finish(),
+            Self::String(s) => f.debug_tuple("String").field(s).finish(),
+            Self::Array(ht) => f.debug_tuple("Array").field(ht).finish(),
+            Self::Object(obj) => f.debug_tuple("Object").field(obj).finish(),
+            Self::Resource(res) => f.debug_tuple("Resource").field(&res.handle).finish(),
+            Self::Internal(zv) => f.debug_tuple("Internal").field(zv).finish(),
+        }
+    }
+}
+
+impl Debug for ZvalViewMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undef => f.write_str("Undef"),
+            Self::Null => f.write_str("Null"),
+            Self::Bool(b) => f.debug_tuple("Bool").field(b).finish(),
+            Self::Long(l) => f.debug_tuple("Long").field(l).finish(),
+            Self::Double(d) => f.debug_tuple("Double").field(d).finish(),
+            Self::String(s) => f.debug_tuple("String").field(s).finish(),
+            Self::Array(ht) => f.debug_tuple("Array").field(ht).finish(),
+            Self::Object(obj) => f.debug_tuple("Object").field(obj).finish(),
+            Self::Resource(res) => f.debug_tuple("Resource").field(&res.handle).finish(),
+            Self::Internal(zv) => f.debug_tuple("Internal").field(zv).finish(),
+        }
+    }
+}
+
+impl<'a> FromZval<'a> for ZvalView<'a> {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        Some(zval.view())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_view() {
+        Embed::run(|| {
+            let values =
+                Embed::eval("[null, true, 42, 1.5, 'hello', [1, 2], new stdClass];").unwrap();
+            let values: Vec<_> = values.array().unwrap().values().map(Zval::view).collect();
+            assert!(matches!(
+                values[..],
+                [
+                    ZvalView::Null,
+                    ZvalView::Bool(true),
+                    ZvalView::Long(42),
+                    ZvalView::Double(1.5),
+                    ZvalView::String(_),
+                    ZvalView::Array(_),
+                    ZvalView::Object(_),
+                ]
+            ));
+            assert!(matches!(values[4], ZvalView::String(s) if s.as_bytes() == b"hello"));
+            assert!(matches!(values[5], ZvalView::Array(ht) if ht.len() == 2));
+        });
+    }
+
+    #[test]
+    fn test_view_resolves_references() {
+        Embed::run(|| {
+            Embed::eval("$a = 42;").unwrap();
+            let values = Embed::eval("[&$a];").unwrap();
+            let value = values.array().unwrap().get_index(0).unwrap();
+            assert!(value.is_reference());
+            assert!(matches!(value.view(), ZvalView::Long(42)));
+        });
+    }
+
+    #[test]
+    fn test_view_mut() {
+        Embed::run(|| {
+            Embed::eval("$a = [1, 2];").unwrap();
+            let mut values = Embed::eval("[41, 1.5, $a];").unwrap();
+            let ht = values.array_mut().unwrap();
+
+            if let ZvalViewMut::Long(l) = ht.get_index_mut(0).unwrap().view_mut() {
+                *l += 1;
+            }
+            if let ZvalViewMut::Double(d) = ht.get_index_mut(1).unwrap().view_mut() {
+                *d *= 2.0;
+            }
+            if let ZvalViewMut::Array(inner) = ht.get_index_mut(2).unwrap().view_mut() {
+                inner.push(3).unwrap();
+            }
+
+            let expected = Embed::eval("[42, 3.0, [1, 2, 3]];").unwrap();
+            assert!(values.is_identical(&expected));
+            // The array shared with `$a` has been separated.
+            let shared = Embed::eval("$a;").unwrap();
+            assert_eq!(shared.array().unwrap().len(), 2);
+        });
+    }
+}
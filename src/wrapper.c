@@ -153,3 +153,267 @@ void ext_php_rs_zend_execute(zend_op_array *op_array) {
   destroy_op_array(op_array);
   efree(op_array);
 }
+
+zend_string *ext_php_rs_var_serialize(zval *value) {
+  php_serialize_data_t var_hash;
+  smart_str buf = {0};
+
+  PHP_VAR_SERIALIZE_INIT(var_hash);
+  php_var_serialize(&buf, value, &var_hash);
+  PHP_VAR_SERIALIZE_DESTROY(var_hash);
+
+  if (EG(exception)) {
+    smart_str_free(&buf);
+    return NULL;
+  }
+
+  return smart_str_extract(&buf);
+}
+
+bool ext_php_rs_var_unserialize(zval *retval, const char *buf, size_t len, size_t *offset) {
+  php_unserialize_data_t var_hash;
+  const unsigned char *p = (const unsigned char *) buf;
+  bool result;
+
+  ZVAL_UNDEF(retval);
+
+  PHP_VAR_UNSERIALIZE_INIT(var_hash);
+  result = php_var_unserialize(retval, &p, (const unsigned char *) buf + len, &var_hash);
+  *offset = (size_t) ((const char *) p - buf);
+  if (!result && BG(unserialize).level <= 1) {
+    zval_ptr_dtor(retval);
+    ZVAL_UNDEF(retval);
+  }
+  PHP_VAR_UNSERIALIZE_DESTROY(var_hash);
+
+  // Values must not be returned as references, and `__unserialize()` calls
+  // performed when destroying the unserialize data may affect the value.
+  if (result && Z_ISREF_P(retval)) {
+    zend_unwrap_reference(retval);
+  }
+
+  return result;
+}
+
+zend_ulong ext_php_rs_zend_object_to_weakref_key(const zend_object *object) {
+#if PHP_VERSION_ID >= 80300
+  return zend_object_to_weakref_key(object);
+#else
+  return (zend_ulong) object;
+#endif
+}
+
+int ext_php_rs_zval_natural_compare(zval *op1, zval *op2, bool fold_case) {
+  zend_string *tmp_str1, *tmp_str2;
+  zend_string *str1 = zval_get_tmp_string(op1, &tmp_str1);
+  zend_string *str2 = zval_get_tmp_string(op2, &tmp_str2);
+  int result = strnatcmp_ex(ZSTR_VAL(str1), ZSTR_LEN(str1), ZSTR_VAL(str2), ZSTR_LEN(str2), fold_case);
+
+  zend_tmp_string_release(tmp_str1);
+  zend_tmp_string_release(tmp_str2);
+  return result;
+}
+
+bool ext_php_rs_parse_arg_weak(zval *arg, uint32_t type, uint32_t arg_num) {
+#if PHP_VERSION_ID >= 80100
+#define EXT_PHP_RS_ARG_NUM , arg_num
+#else
+#define EXT_PHP_RS_ARG_NUM
+#endif
+  switch (type) {
+    case IS_LONG: {
+      zend_long dest;
+      if (!zend_parse_arg_long_weak(arg, &dest EXT_PHP_RS_ARG_NUM)) {
+        return false;
+      }
+      zval_ptr_dtor(arg);
+      ZVAL_LONG(arg, dest);
+      return true;
+    }
+    case IS_DOUBLE: {
+      double dest;
+      if (!zend_parse_arg_double_weak(arg, &dest EXT_PHP_RS_ARG_NUM)) {
+        return false;
+      }
+      zval_ptr_dtor(arg);
+      ZVAL_DOUBLE(arg, dest);
+      return true;
+    }
+    case _IS_BOOL: {
+      bool dest;
+      if (!zend_parse_arg_bool_weak(arg, &dest EXT_PHP_RS_ARG_NUM)) {
+        return false;
+      }
+      zval_ptr_dtor(arg);
+      ZVAL_BOOL(arg, dest);
+      return true;
+    }
+    case IS_STRING: {
+      // The argument is converted in place.
+      zend_string *dest;
+      return zend_parse_arg_str_weak(arg, &dest EXT_PHP_RS_ARG_NUM);
+    }
+    default:
+      return false;
+  }
+#undef EXT_PHP_RS_ARG_NUM
+}
+
+void ext_php_rs_argument_error(zend_class_entry *error_ce, uint32_t arg_num,
+                               const char *message) {
+  zend_argument_error(error_ce, arg_num, "%s", message);
+}
+
+const char *ext_php_rs_zval_value_name(const zval *arg) {
+#if PHP_VERSION_ID >= 80300
+  return zend_zval_value_name(arg);
+#else
+  return zend_zval_type_name(arg);
+#endif
+}
+
+zend_type_list *ext_php_rs_type_list_alloc(uint32_t num_types) {
+  zend_type_list *list = pemalloc(ZEND_TYPE_LIST_SIZE(num_types), 1);
+  list->num_types = num_types;
+  return list;
+}
+
+/* Adds a `#[\Deprecated]` attribute, from which the engine reads the message
+ * of deprecations since PHP 8.4. */
+static void ext_php_rs_add_deprecated_attribute(HashTable **attributes,
+                                                const char *message,
+                                                const char *since) {
+#if PHP_VERSION_ID >= 80400
+  uint32_t argc = (message != NULL) + (since != NULL);
+  zend_string *name =
+      zend_string_init_interned("Deprecated", sizeof("Deprecated") - 1, 1);
+  zend_attribute *attr =
+      zend_add_attribute(attributes, name, argc, ZEND_ATTRIBUTE_PERSISTENT, 0, 0);
+  zend_string_release(name);
+
+  uint32_t i = 0;
+  if (message != NULL) {
+    ZVAL_STR(&attr->args[i].value, zend_string_init(message, strlen(message), 1));
+    attr->args[i++].name =
+        zend_string_init_interned("message", sizeof("message") - 1, 1);
+  }
+  if (since != NULL) {
+    ZVAL_STR(&attr->args[i].value, zend_string_init(since, strlen(since), 1));
+    attr->args[i++].name =
+        zend_string_init_interned("since", sizeof("since") - 1, 1);
+  }
+#else
+  (void)attributes;
+  (void)message;
+  (void)since;
+#endif
+}
+
+bool ext_php_rs_deprecate_function(HashTable *function_table, const char *name,
+                                   size_t len, const char *message,
+                                   const char *since) {
+  zend_function *func = zend_hash_str_find_ptr_lc(
+      function_table != NULL ? function_table : CG(function_table), name, len);
+  if (func == NULL) {
+    return false;
+  }
+  func->common.fn_flags |= ZEND_ACC_DEPRECATED;
+  ext_php_rs_add_deprecated_attribute(&func->common.attributes, message, since);
+  return true;
+}
+
+bool ext_php_rs_deprecate_class_constant(zend_class_entry *ce, const char *name,
+                                         size_t len, const char *message,
+                                         const char *since) {
+  zend_class_constant *c =
+      zend_hash_str_find_ptr(CE_CONSTANTS_TABLE(ce), name, len);
+  if (c == NULL) {
+    return false;
+  }
+#if PHP_VERSION_ID >= 80400
+  ZEND_CLASS_CONST_FLAGS(c) |= ZEND_ACC_DEPRECATED;
+#endif
+  ext_php_rs_add_deprecated_attribute(&c->attributes, message, since);
+  return true;
+}
+
+bool ext_php_rs_deprecate_constant(const char *name, size_t len,
+                                   const char *message, const char *since) {
+  zend_constant *c = zend_hash_str_find_ptr(EG(zend_constants), name, len);
+  if (c == NULL) {
+    return false;
+  }
+  ZEND_CONSTANT_SET_FLAGS(c, ZEND_CONSTANT_FLAGS(c) | CONST_DEPRECATED,
+                          ZEND_CONSTANT_MODULE_NUMBER(c));
+#if PHP_VERSION_ID >= 80500
+  ext_php_rs_add_deprecated_attribute(&c->attributes, message, since);
+#else
+  (void)message;
+  (void)since;
+#endif
+  return true;
+}
+
+/* Adds an attribute with `argc` arguments, to be set with
+ * `ext_php_rs_attribute_set_arg`. The offset is 0 for the item itself, or the
+ * position of the parameter plus one for parameters of functions. */
+zend_attribute *ext_php_rs_add_attribute(HashTable **attributes,
+                                         const char *name, size_t len,
+                                         uint32_t argc, uint32_t offset) {
+  zend_string *str = zend_string_init_interned(name, len, 1);
+#if PHP_VERSION_ID >= 80100
+  zend_attribute *attr = zend_add_attribute(attributes, str, argc,
+                                            ZEND_ATTRIBUTE_PERSISTENT, offset, 0);
+#else
+  zend_attribute *attr = zend_add_attribute(attributes, 1, offset, str, argc);
+#endif
+  zend_string_release(str);
+  return attr;
+}
+
+/* Sets an argument of an attribute, taking ownership of the value. Positional
+ * arguments have a null name. */
+void ext_php_rs_attribute_set_arg(zend_attribute *attr, uint32_t index,
+                                  const char *name, size_t len, zval *value) {
+  ZVAL_COPY_VALUE(&attr->args[index].value, value);
+  attr->args[index].name =
+      name != NULL ? zend_string_init_interned(name, len, 1) : NULL;
+}
+
+HashTable **ext_php_rs_function_attributes(HashTable *function_table,
+                                           const char *name, size_t len) {
+  zend_function *func = zend_hash_str_find_ptr_lc(
+      function_table != NULL ? function_table : CG(function_table), name, len);
+  return func != NULL ? &func->common.attributes : NULL;
+}
+
+HashTable **ext_php_rs_property_attributes(zend_class_entry *ce,
+                                           const char *name, size_t len) {
+  zend_property_info *info =
+      zend_hash_str_find_ptr(&ce->properties_info, name, len);
+  return info != NULL ? &info->attributes : NULL;
+}
+
+HashTable **ext_php_rs_class_constant_attributes(zend_class_entry *ce,
+                                                 const char *name, size_t len) {
+  zend_class_constant *c =
+      zend_hash_str_find_ptr(CE_CONSTANTS_TABLE(ce), name, len);
+  return c != NULL ? &c->attributes : NULL;
+}
+
+/* Declares a class as an attribute class, which can be applied to the targets
+ * given by the `ZEND_ATTRIBUTE_*` flags. */
+void ext_php_rs_declare_attribute_class(zend_class_entry *ce, uint32_t flags) {
+#if PHP_VERSION_ID >= 80200
+  zend_attribute *attr =
+      zend_add_class_attribute(ce, zend_ce_attribute->name, 1);
+  ZVAL_LONG(&attr->args[0].value, flags);
+  zend_mark_internal_attribute(ce);
+#else
+  zend_internal_attribute_register(ce, flags);
+#endif
+}
+
+/* Turns a zval into a reference to its value, unless it already is one, so
+ * that the engine modifies the value in place. */
+void ext_php_rs_zval_make_ref(zval *zv) { ZVAL_MAKE_REF(zv); }
@@ -29,15 +29,18 @@
 
 #include "ext/standard/info.h"
 #include "ext/standard/php_var.h"
+#include "ext/standard/php_string.h"
 #include "ext/standard/file.h"
 #ifdef EXT_PHP_RS_PHP_81
 #include "zend_enum.h"
 #endif
+#include "zend_attributes.h"
 #include "zend_exceptions.h"
 #include "zend_inheritance.h"
 #include "zend_interfaces.h"
 #include "php_variables.h"
 #include "zend_ini.h"
+#include "zend_weakrefs.h"
 #ifdef EXT_PHP_RS_OBSERVER
 #include "zend_observer.h"
 #include "zend_extensions.h"
@@ -66,3 +69,33 @@ bool ext_php_rs_zend_first_try_catch(void* (*callback)(void *), void *ctx, void
 void ext_php_rs_zend_bailout();
 zend_op_array *ext_php_rs_zend_compile_string(zend_string *source, const char *filename);
 void ext_php_rs_zend_execute(zend_op_array *op_array);
+zend_string *ext_php_rs_var_serialize(zval *value);
+bool ext_php_rs_var_unserialize(zval *retval, const char *buf, size_t len, size_t *offset);
+zend_ulong ext_php_rs_zend_object_to_weakref_key(const zend_object *object);
+int ext_php_rs_zval_natural_compare(zval *op1, zval *op2, bool fold_case);
+bool ext_php_rs_parse_arg_weak(zval *arg, uint32_t type, uint32_t arg_num);
+void ext_php_rs_argument_error(zend_class_entry *error_ce, uint32_t arg_num,
+                               const char *message);
+const char *ext_php_rs_zval_value_name(const zval *arg);
+zend_type_list *ext_php_rs_type_list_alloc(uint32_t num_types);
+bool ext_php_rs_deprecate_function(HashTable *function_table, const char *name,
+                                   size_t len, const char *message,
+                                   const char *since);
+bool ext_php_rs_deprecate_class_constant(zend_class_entry *ce, const char *name,
+                                         size_t len, const char *message,
+                                         const char *since);
+bool ext_php_rs_deprecate_constant(const char *name, size_t len,
+                                   const char *message, const char *since);
+zend_attribute *ext_php_rs_add_attribute(HashTable **attributes,
+                                         const char *name, size_t len,
+                                         uint32_t argc, uint32_t offset);
+void ext_php_rs_attribute_set_arg(zend_attribute *attr, uint32_t index,
+                                  const char *name, size_t len, zval *value);
+HashTable **ext_php_rs_function_attributes(HashTable *function_table,
+                                           const char *name, size_t len);
+HashTable **ext_php_rs_property_attributes(zend_class_entry *ce,
+                                           const char *name, size_t len);
+HashTable **ext_php_rs_class_constant_attributes(zend_class_entry *ce,
+                                                 const char *name, size_t len);
+void ext_php_rs_declare_attribute_class(zend_class_entry *ce, uint32_t flags);
+void ext_php_rs_zval_make_ref(zval *zv);
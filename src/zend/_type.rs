@@ -2,10 +2,11 @@ use std::{ffi::c_void, ptr};
 
 use crate::{
     ffi::{
-        _IS_BOOL, _ZEND_IS_VARIADIC_BIT, _ZEND_SEND_MODE_SHIFT, _ZEND_TYPE_NULLABLE_BIT, IS_MIXED,
-        MAY_BE_ANY, MAY_BE_BOOL, zend_type,
+        _IS_BOOL, _ZEND_IS_VARIADIC_BIT, _ZEND_SEND_MODE_SHIFT, _ZEND_TYPE_NAME_BIT,
+        _ZEND_TYPE_NULLABLE_BIT, IS_MIXED, MAY_BE_ANY, MAY_BE_BOOL, zend_type,
     },
     flags::DataType,
+    types::{TypeDecl, ZendStr},
 };
 
 /// Internal Zend type.
@@ -51,6 +52,9 @@ impl ZendType {
             DataType::Object(Some(class)) => {
                 Self::empty_from_class_type(class, pass_by_ref, is_variadic, allow_null)
             }
+            // `resource` cannot be used in type declarations, resources are
+            // only documented as such.
+            DataType::Resource => Some(Self::empty(pass_by_ref, is_variadic)),
             type_ => Some(Self::empty_from_primitive_type(
                 type_,
                 pass_by_ref,
@@ -60,6 +64,189 @@ impl ZendType {
         }
     }
 
+    /// Creates a zend type for the declaration of a property.
+    ///
+    /// Classes are referenced by interned strings, as in the property
+    /// declarations of internal classes. Types which cannot be declared on
+    /// properties, e.g. `callable` or `resource`, give an empty type.
+    ///
+    /// # Parameters
+    ///
+    /// * `type_` - Data type of the property.
+    /// * `allow_null` - Whether the property accepts null.
+    #[must_use]
+    pub fn property_type(type_: DataType, allow_null: bool) -> Self {
+        match type_ {
+            DataType::Object(Some(class)) => Self {
+                ptr: ptr::from_mut(ZendStr::new_interned(class, true).into_raw()).cast::<c_void>(),
+                type_mask: _ZEND_TYPE_NAME_BIT
+                    | if allow_null {
+                        _ZEND_TYPE_NULLABLE_BIT
+                    } else {
+                        0
+                    },
+            },
+            DataType::Null
+            | DataType::False
+            | DataType::True
+            | DataType::Long
+            | DataType::Double
+            | DataType::String
+            | DataType::Array
+            | DataType::Object(None)
+            | DataType::Mixed
+            | DataType::Bool => Self::empty_from_primitive_type(type_, false, false, allow_null),
+            _ => Self::empty(false, false),
+        }
+    }
+
+    /// Attempts to create a zend type for a type declaration, which may be a
+    /// union, an intersection or a DNF type. Returns an option containing the
+    /// type.
+    ///
+    /// Intersections are only supported by internal functions from PHP 8.3.
+    /// On earlier versions, the first class of each intersection is used.
+    ///
+    /// Returns [`None`] if a class name could not be converted into a C
+    /// string (i.e. contained NUL-bytes).
+    ///
+    /// # Parameters
+    ///
+    /// * `decl` - Type declaration to create zend type for.
+    /// * `pass_by_ref` - Whether the type should be passed by reference.
+    /// * `is_variadic` - Whether the type is for a variadic argument.
+    #[must_use]
+    pub fn empty_from_decl(decl: &TypeDecl, pass_by_ref: bool, is_variadic: bool) -> Option<Self> {
+        if let Some((type_, allow_null)) = decl.as_single() {
+            return Self::empty_from_type(type_, pass_by_ref, is_variadic, allow_null);
+        }
+
+        let mut type_mask = Self::arg_info_flags(pass_by_ref, is_variadic);
+        let mut classes = Vec::new();
+        Self::collect_decl(decl, &mut type_mask, &mut classes);
+        if classes.is_empty() {
+            return Some(Self {
+                ptr: ptr::null_mut::<c_void>(),
+                type_mask,
+            });
+        }
+
+        #[cfg(php83)]
+        if classes
+            .iter()
+            .any(|class| matches!(class, TypeDecl::Intersection(_)))
+        {
+            return Some(match decl {
+                TypeDecl::Intersection(names) => Self::intersection_list(names, type_mask),
+                _ => Self::union_list(&classes, type_mask),
+            });
+        }
+
+        // The engine splits literal class names on `|` when registering the
+        // function.
+        let names = classes
+            .iter()
+            .filter_map(|class| match class {
+                TypeDecl::Single(DataType::Object(Some(name))) => Some(*name),
+                TypeDecl::Intersection(names) => names.first().copied(),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+        cfg_if::cfg_if! {
+            if #[cfg(php83)] {
+                type_mask |= crate::ffi::_ZEND_TYPE_LITERAL_NAME_BIT
+            } else {
+                type_mask |= crate::ffi::_ZEND_TYPE_NAME_BIT
+            }
+        }
+
+        Some(Self {
+            ptr: std::ffi::CString::new(names)
+                .ok()?
+                .into_raw()
+                .cast::<c_void>(),
+            type_mask,
+        })
+    }
+
+    /// Adds the types of a declaration to a type mask, collecting the classes
+    /// and intersections which cannot be part of the mask.
+    fn collect_decl<'a>(decl: &'a TypeDecl, type_mask: &mut u32, classes: &mut Vec<&'a TypeDecl>) {
+        match decl {
+            TypeDecl::Union(types) => {
+                for decl in types {
+                    Self::collect_decl(decl, type_mask, classes);
+                }
+            }
+            TypeDecl::Single(DataType::Object(Some(_))) | TypeDecl::Intersection(_) => {
+                classes.push(decl);
+            }
+            // `resource` cannot be used in type declarations.
+            TypeDecl::Single(DataType::Resource) => {}
+            TypeDecl::Single(type_) => {
+                *type_mask |= Self::type_init_code(*type_, false, false, false);
+            }
+        }
+    }
+
+    /// Creates a zend type for a union of classes and intersections, which
+    /// owns a list of the types. The list is freed by the engine.
+    #[cfg(php83)]
+    fn union_list(classes: &[&TypeDecl], type_mask: u32) -> Self {
+        let types = classes.iter().map(|class| match class {
+            TypeDecl::Intersection(names) => Self::intersection_list(names, 0),
+            TypeDecl::Single(DataType::Object(Some(name))) => Self::persistent_name(name),
+            _ => Self::empty(false, false),
+        });
+        Self::list(
+            types,
+            classes.len(),
+            type_mask | crate::ffi::_ZEND_TYPE_UNION_BIT,
+        )
+    }
+
+    /// Creates a zend type for an intersection of classes, which owns a list
+    /// of the classes. The list is freed by the engine.
+    #[cfg(php83)]
+    fn intersection_list(names: &[&str], type_mask: u32) -> Self {
+        Self::list(
+            names.iter().map(|name| Self::persistent_name(name)),
+            names.len(),
+            type_mask | crate::ffi::_ZEND_TYPE_INTERSECTION_BIT,
+        )
+    }
+
+    /// Creates a zend type owning a persistent list of types.
+    #[cfg(php83)]
+    fn list(types: impl Iterator<Item = Self>, len: usize, type_mask: u32) -> Self {
+        let len = u32::try_from(len).expect("too many types in a type declaration");
+        // SAFETY: The list is allocated with room for `len` types, which are
+        // all initialized.
+        let list = unsafe { crate::ffi::ext_php_rs_type_list_alloc(len) };
+        for (i, type_) in types.enumerate() {
+            unsafe { (&raw mut (*list).types).cast::<Self>().add(i).write(type_) };
+        }
+
+        Self {
+            ptr: list.cast::<c_void>(),
+            type_mask: type_mask | crate::ffi::_ZEND_TYPE_LIST_BIT,
+        }
+    }
+
+    /// Creates a zend type for a class, which owns a persistent string of the
+    /// name of the class, as the types of lists must.
+    #[cfg(php83)]
+    fn persistent_name(name: &str) -> Self {
+        Self {
+            ptr: unsafe {
+                crate::ffi::ext_php_rs_zend_string_init(name.as_ptr().cast(), name.len(), true)
+            }
+            .cast::<c_void>(),
+            type_mask: crate::ffi::_ZEND_TYPE_NAME_BIT,
+        }
+    }
+
     /// Attempts to create a zend type for a class object type. Returns an
     /// option containing the type if successful.
     ///
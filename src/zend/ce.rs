@@ -2,6 +2,8 @@
 
 #![allow(clippy::unwrap_used)]
 
+#[cfg(php81)]
+use crate::ffi::zend_ce_fiber;
 use crate::ffi::{
     zend_ce_aggregate, zend_ce_argument_count_error, zend_ce_arithmetic_error, zend_ce_arrayaccess,
     zend_ce_compile_error, zend_ce_countable, zend_ce_division_by_zero_error,
@@ -184,6 +186,16 @@ pub fn stringable() -> &'static ClassEntry {
     unsafe { zend_ce_stringable.as_ref() }.unwrap()
 }
 
+/// Returns the [`Fiber`](https://www.php.net/manual/en/class.fiber.php) class.
+///
+/// # Panics
+///
+/// If fiber [`ClassEntry`] is not available
+#[cfg(php81)]
+pub fn fiber() -> &'static ClassEntry {
+    unsafe { zend_ce_fiber.as_ref() }.unwrap()
+}
+
 #[cfg(test)]
 #[cfg(feature = "embed")]
 mod tests {
@@ -341,4 +353,13 @@ mod tests {
             assert_eq!(stringable.name(), Some("Stringable"));
         });
     }
+
+    #[test]
+    #[cfg(php81)]
+    fn test_fiber() {
+        Embed::run(|| {
+            let fiber = fiber();
+            assert_eq!(fiber.name(), Some("Fiber"));
+        });
+    }
 }
@@ -61,6 +61,12 @@ impl ClassEntry {
         self.flags().contains(ClassFlags::Interface)
     }
 
+    /// Returns `true` if the class entry is a trait, and `false` otherwise.
+    #[must_use]
+    pub fn is_trait(&self) -> bool {
+        self.flags().contains(ClassFlags::Trait)
+    }
+
     /// Returns `true` if instances of this class can be made lazy.
     ///
     /// Only user-defined classes and `stdClass` can be made lazy.
@@ -3,6 +3,7 @@ use crate::ffi::{ZEND_MM_ALIGNMENT, ZEND_MM_ALIGNMENT_MASK, zend_execute_data};
 use crate::{
     args::ArgParser,
     class::RegisteredClass,
+    flags::MethodFlags,
     types::{ZendClassObject, ZendObject, Zval},
 };
 
@@ -223,6 +224,30 @@ impl ExecuteData {
         unsafe { self.prev_execute_data.as_ref() }
     }
 
+    /// Returns whether the arguments of the call were passed from code declaring
+    /// `strict_types=1`, in which case they must not be coerced. Translation of
+    /// macro `ZEND_ARG_USES_STRICT_TYPES()`.
+    #[must_use]
+    pub fn arg_uses_strict_types(&self) -> bool {
+        self.previous()
+            .and_then(Self::function)
+            .is_some_and(|func| {
+                MethodFlags::from_bits_truncate(unsafe { func.common.fn_flags })
+                    .contains(MethodFlags::StrictTypes)
+            })
+    }
+
+    /// Returns whether the function being called is declared in code declaring
+    /// `strict_types=1`, e.g. to check the values it assigns to typed
+    /// properties. Translation of macro `ZEND_CALL_USES_STRICT_TYPES()`.
+    #[must_use]
+    pub fn uses_strict_types(&self) -> bool {
+        self.function().is_some_and(|func| {
+            MethodFlags::from_bits_truncate(unsafe { func.common.fn_flags })
+                .contains(MethodFlags::StrictTypes)
+        })
+    }
+
     /// Translation of macro `ZEND_CALL_ARG(call, n)`
     /// zend_compile.h:578
     ///
@@ -0,0 +1,322 @@
+//! PHP fibers, which run a callable on their own stack and can be suspended
+//! and resumed. Fibers require PHP 8.1.
+
+use std::{fmt::Debug, ptr};
+
+use crate::{
+    boxed::{ZBox, ZBoxable},
+    convert::{FromZval, FromZvalMut, IntoZval, IntoZvalDyn},
+    error::{Error, Result},
+    ffi::{
+        ext_php_rs_zend_object_release, zend_call_known_function, zend_fiber,
+        zend_fiber_status_ZEND_FIBER_STATUS_DEAD, zend_fiber_status_ZEND_FIBER_STATUS_INIT,
+        zend_fiber_status_ZEND_FIBER_STATUS_RUNNING, zend_fiber_status_ZEND_FIBER_STATUS_SUSPENDED,
+        zend_fiber_switch_blocked, zend_function, zend_hash_str_find_ptr_lc,
+    },
+    flags::DataType,
+    types::{ZendObject, Zval},
+    zend::{ExecutorGlobals, ce},
+};
+
+/// A PHP [`Fiber`](https://www.php.net/manual/en/class.fiber.php).
+///
+/// A fiber runs a callable, which can suspend the fiber from anywhere in its
+/// call stack with [`ZendFiber::suspend`]. The code which started the fiber
+/// then continues, and resumes the fiber later with [`ZendFiber::resume`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ext_php_rs::zend::ZendFiber;
+///
+/// // The fiber suspends itself with the value it is started with.
+/// let mut fiber = ZendFiber::new("Fiber::suspend").unwrap();
+/// let suspended = fiber.start(vec![&"hello"]).unwrap();
+/// assert_eq!(suspended.str(), Some("hello"));
+/// assert!(fiber.is_suspended());
+///
+/// // The value of `Fiber::suspend()` is then returned by the fiber.
+/// fiber.resume(&"world").unwrap();
+/// assert!(fiber.is_terminated());
+/// assert_eq!(fiber.get_return().unwrap().str(), Some("world"));
+/// ```
+pub type ZendFiber = zend_fiber;
+
+impl ZendFiber {
+    /// Creates a fiber which runs the given callable once started.
+    ///
+    /// # Parameters
+    ///
+    /// * `callable` - The callable run by the fiber, e.g. a closure or the
+    ///   name of a function.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Callable`] - If the value is not callable.
+    /// * If the callable cannot be converted into a zval.
+    pub fn new(callable: impl IntoZval) -> Result<ZBox<Self>> {
+        let callable = callable.into_zval(false)?;
+        if !callable.is_callable() {
+            return Err(Error::Callable);
+        }
+
+        let object = ce::fiber().new();
+        // SAFETY: The object is an instance of `Fiber`, which starts with the
+        // `zend_object`.
+        let fiber = unsafe { ZBox::from_raw(ptr::from_mut(object.into_raw()).cast::<Self>()) };
+        fiber.call_method("__construct", vec![&callable])?;
+        Ok(fiber)
+    }
+
+    /// Returns the fiber running the current code, if any.
+    #[must_use]
+    pub fn current() -> Option<&'static Self> {
+        let fiber = ExecutorGlobals::get().active_fiber;
+        // SAFETY: The active fiber is kept alive while it is running.
+        unsafe { fiber.as_ref() }
+    }
+
+    /// Returns whether the current code runs in a fiber which can be
+    /// suspended.
+    ///
+    /// Fibers cannot be suspended while switching is blocked by the engine,
+    /// e.g. while running destructors during garbage collection.
+    #[must_use]
+    pub fn can_suspend() -> bool {
+        Self::current().is_some() && !unsafe { zend_fiber_switch_blocked() }
+    }
+
+    /// Suspends the current fiber, returning `value` to the code which started
+    /// or resumed it.
+    ///
+    /// Returns the value the fiber is resumed with.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value returned by `Fiber::start()` or
+    ///   `Fiber::resume()`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FiberInterrupted`] - If the current code does not run in a
+    ///   fiber which can be suspended, or if the fiber is resumed with an
+    ///   exception, e.g. with `Fiber::throw()` or when it is destroyed. The
+    ///   exception is left pending, and is thrown once the Rust function
+    ///   returns to PHP.
+    /// * If the value cannot be converted into a zval.
+    pub fn suspend(value: &dyn IntoZvalDyn) -> Result<Zval> {
+        let retval = call(None, "suspend", vec![value])?;
+        if ExecutorGlobals::has_exception() {
+            return Err(Error::FiberInterrupted);
+        }
+        Ok(retval)
+    }
+
+    /// Starts the fiber, which runs until it suspends itself or returns.
+    ///
+    /// Returns the value the fiber is suspended with, or `null` if the fiber
+    /// has returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `args` - The arguments passed to the callable of the fiber.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the fiber throws an exception, or has
+    ///   already been started.
+    /// * If an argument cannot be converted into a zval.
+    pub fn start(&mut self, args: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+        self.call_method("start", args)
+    }
+
+    /// Resumes the fiber, which runs until it suspends itself again or
+    /// returns.
+    ///
+    /// Returns the value the fiber is suspended with, or `null` if the fiber
+    /// has returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The value returned by `Fiber::suspend()` in the fiber.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the fiber throws an exception, or is not
+    ///   suspended.
+    /// * If the value cannot be converted into a zval.
+    pub fn resume(&mut self, value: &dyn IntoZvalDyn) -> Result<Zval> {
+        self.call_method("resume", vec![value])
+    }
+
+    /// Returns the value returned by the callable of the fiber.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Exception`] - If the fiber has not returned.
+    pub fn get_return(&self) -> Result<Zval> {
+        self.call_method("getReturn", vec![])
+    }
+
+    /// Returns whether the fiber has been started.
+    #[must_use]
+    pub fn is_started(&self) -> bool {
+        self.context.status != zend_fiber_status_ZEND_FIBER_STATUS_INIT
+    }
+
+    /// Returns whether the fiber is suspended.
+    #[must_use]
+    pub fn is_suspended(&self) -> bool {
+        self.context.status == zend_fiber_status_ZEND_FIBER_STATUS_SUSPENDED
+            && self.caller.is_null()
+    }
+
+    /// Returns whether the fiber is running, including when it has started or
+    /// resumed another fiber.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.context.status == zend_fiber_status_ZEND_FIBER_STATUS_RUNNING || !self.caller.is_null()
+    }
+
+    /// Returns whether the fiber has returned or thrown an exception.
+    #[must_use]
+    pub fn is_terminated(&self) -> bool {
+        self.context.status == zend_fiber_status_ZEND_FIBER_STATUS_DEAD
+    }
+
+    /// Calls a method of the fiber, returning the exception it throws as an
+    /// error.
+    fn call_method(&self, name: &str, params: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+        let retval = call(Some(&self.std), name, params)?;
+        match ExecutorGlobals::take_exception() {
+            Some(e) => Err(Error::Exception(e)),
+            None => Ok(retval),
+        }
+    }
+}
+
+/// Calls a method of the `Fiber` class, on the given fiber or statically.
+/// Exceptions are left pending.
+fn call(object: Option<&ZendObject>, name: &str, params: Vec<&dyn IntoZvalDyn>) -> Result<Zval> {
+    let ce = ce::fiber();
+    let params = params
+        .into_iter()
+        .map(|param| param.as_zval(false))
+        .collect::<Result<Vec<_>>>()?;
+    let mut retval = Zval::new();
+
+    unsafe {
+        let function = zend_hash_str_find_ptr_lc(
+            &raw const ce.function_table,
+            name.as_ptr().cast(),
+            name.len(),
+        )
+        .cast::<zend_function>();
+        if function.is_null() {
+            return Err(Error::Callable);
+        }
+
+        zend_call_known_function(
+            function,
+            object.map_or(ptr::null_mut(), |object| ptr::from_ref(object).cast_mut()),
+            ptr::from_ref(ce).cast_mut(),
+            &raw mut retval,
+            params.len().try_into()?,
+            params.as_ptr().cast_mut(),
+            ptr::null_mut(),
+        );
+    }
+
+    Ok(retval)
+}
+
+unsafe impl ZBoxable for ZendFiber {
+    fn free(&mut self) {
+        unsafe { ext_php_rs_zend_object_release(&raw mut self.std) }
+    }
+}
+
+impl Debug for ZendFiber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZendFiber")
+            .field("id", &self.std.get_id())
+            .field("status", &self.context.status)
+            .finish()
+    }
+}
+
+impl<'a> FromZval<'a> for &'a ZendFiber {
+    const TYPE: DataType = DataType::Object(Some("Fiber"));
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        let object = zval.object()?;
+        if !object.instance_of(ce::fiber()) {
+            return None;
+        }
+        // SAFETY: Instances of `Fiber` start with the `zend_object`.
+        unsafe { ptr::from_ref(object).cast::<ZendFiber>().as_ref() }
+    }
+}
+
+impl<'a> FromZvalMut<'a> for &'a mut ZendFiber {
+    const TYPE: DataType = DataType::Object(Some("Fiber"));
+
+    fn from_zval_mut(zval: &'a mut Zval) -> Option<Self> {
+        let object = zval.object_mut()?;
+        if !object.instance_of(ce::fiber()) {
+            return None;
+        }
+        // SAFETY: Instances of `Fiber` start with the `zend_object`.
+        unsafe { ptr::from_mut(object).cast::<ZendFiber>().as_mut() }
+    }
+}
+
+impl IntoZval for ZBox<ZendFiber> {
+    const TYPE: DataType = DataType::Object(Some("Fiber"));
+    const NULLABLE: bool = false;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        // SAFETY: The fiber starts with the `zend_object`, which takes over the
+        // reference held by the box.
+        let object = unsafe { ZBox::from_raw(&raw mut self.into_raw().std) };
+        object.set_zval(zv, persistent)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "embed")]
+mod tests {
+    use super::*;
+    use crate::embed::Embed;
+
+    #[test]
+    fn test_start_and_resume() {
+        Embed::run(|| {
+            let mut fiber = ZendFiber::new("Fiber::suspend").expect("failed to create fiber");
+            assert!(!fiber.is_started());
+
+            let suspended = fiber.start(vec![&"hello"]).expect("failed to start fiber");
+            assert_eq!(suspended.str(), Some("hello"));
+            assert!(fiber.is_suspended());
+            assert!(fiber.get_return().is_err());
+
+            let returned = fiber.resume(&"world").expect("failed to resume fiber");
+            assert!(returned.is_null());
+            assert!(fiber.is_terminated());
+            assert_eq!(fiber.get_return().expect("no return").str(), Some("world"));
+        });
+    }
+
+    #[test]
+    fn test_suspend_outside_fiber() {
+        Embed::run(|| {
+            assert!(ZendFiber::current().is_none());
+            assert!(!ZendFiber::can_suspend());
+            assert!(matches!(
+                ZendFiber::suspend(&()),
+                Err(Error::FiberInterrupted)
+            ));
+            assert!(ExecutorGlobals::take_exception().is_some());
+        });
+    }
+}
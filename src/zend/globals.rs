@@ -142,6 +142,12 @@ impl ExecutorGlobals {
         ini_hash_map
     }
 
+    /// Retrieves the list of persistent resources, which outlive requests.
+    #[must_use]
+    pub fn persistent_list(&self) -> &ZendHashTable {
+        &self.persistent_list
+    }
+
     /// Attempts to retrieve the global constants table.
     #[must_use]
     pub fn constants(&self) -> Option<&ZendHashTable> {
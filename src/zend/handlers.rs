@@ -1,17 +1,21 @@
 use std::{ffi::CString, ffi::c_void, mem::MaybeUninit, os::raw::c_int, ptr};
 
+#[cfg(php81)]
+use crate::ffi::zend_readonly_property_modification_error;
 use crate::{
     class::RegisteredClass,
     exception::PhpResult,
     ffi::{
         ext_php_rs_executor_globals, instanceof_function_slow, std_object_handlers,
         zend_class_entry, zend_is_true, zend_object_handlers, zend_object_std_dtor,
-        zend_objects_clone_members, zend_std_get_properties, zend_std_has_property,
-        zend_std_read_property, zend_std_write_property, zend_throw_error,
+        zend_objects_clone_members, zend_property_info, zend_std_get_properties,
+        zend_std_has_property, zend_std_read_property, zend_std_write_property, zend_throw_error,
+        zend_verify_property_type,
     },
     flags::{PropertyFlags, ZvalTypeFlags},
     internal::property::PropertyDescriptor,
     types::{ZendClassObject, ZendHashTable, ZendObject, ZendStr, Zval},
+    zend::ExecuteData,
 };
 
 /// A set of functions associated with a PHP class.
@@ -57,6 +61,19 @@ impl ZendObjectHandlers {
         unsafe { (*ptr).write_property = Some(Self::write_property::<T>) };
         unsafe { (*ptr).get_properties = Some(Self::get_properties::<T>) };
         unsafe { (*ptr).has_property = Some(Self::has_property::<T>) };
+
+        if let Some(handlers) = T::array_access() {
+            unsafe { (*ptr).read_dimension = Some(handlers.read_dimension) };
+            unsafe { (*ptr).write_dimension = Some(handlers.write_dimension) };
+            unsafe { (*ptr).has_dimension = Some(handlers.has_dimension) };
+            unsafe { (*ptr).unset_dimension = Some(handlers.unset_dimension) };
+        }
+        if let Some(handler) = T::do_operation() {
+            unsafe { (*ptr).do_operation = Some(handler) };
+        }
+        if let Some(handler) = T::cast_object() {
+            unsafe { (*ptr).cast_object = Some(handler) };
+        }
     }
 
     unsafe extern "C" fn free_obj<T: RegisteredClass>(object: *mut ZendObject) {
@@ -210,7 +227,7 @@ impl ZendObjectHandlers {
         ) -> PhpResult<*mut Zval> {
             let self_ = &mut *obj;
             let prop = unsafe { resolve_property::<T>(member, cache_slot)? };
-            let value_mut = unsafe { value.as_mut().ok_or("Invalid return zval given")? };
+            let value_ref = unsafe { value.as_ref().ok_or("Invalid return zval given")? };
 
             Ok(match prop {
                 Some(prop_info) => {
@@ -231,10 +248,40 @@ impl ZendObjectHandlers {
                         }
                         return Ok(value);
                     }
+                    #[cfg(php84)]
+                    if !unsafe { check_property_set_access(prop_info.flags, object_ce) } {
+                        let prop_name = unsafe {
+                            member
+                                .as_ref()
+                                .ok_or("Invalid property name pointer given")?
+                        };
+                        unsafe {
+                            throw_property_set_access_error(
+                                T::CLASS_NAME,
+                                prop_name.as_str()?,
+                                prop_info.flags,
+                            );
+                        }
+                        return Ok(value);
+                    }
+                    #[cfg(php81)]
+                    if prop_info.flags.contains(PropertyFlags::Readonly) {
+                        if let Some(info) = declared_property_info::<T>(prop_info.name) {
+                            unsafe { zend_readonly_property_modification_error(info) };
+                        }
+                        return Ok(value);
+                    }
+                    // The value is the operand of the assignment, e.g. a
+                    // variable or a literal of the op array, so it is coerced
+                    // in a copy, as by `zend_std_write_property`.
+                    let mut tmp = value_ref.shallow_clone();
+                    if !unsafe { verify_property_type::<T>(prop_info.name, &mut tmp) } {
+                        return Ok(value);
+                    }
                     let setter = prop_info
                         .set
                         .ok_or("No setter available for this property.")?;
-                    setter(self_, value_mut)?;
+                    setter(self_, &tmp)?;
                     value
                 }
                 None => unsafe { zend_std_write_property(object, member, value, cache_slot) },
@@ -439,7 +486,7 @@ unsafe fn resolve_property<T: RegisteredClass>(
             .as_ref()
             .ok_or("Invalid property name pointer given")?
     };
-    let Some(descriptor) = meta.find_property(prop_name.as_str()?) else {
+    let Some(descriptor) = meta.find_handled_property(prop_name.as_str()?) else {
         return Ok(None);
     };
 
@@ -479,6 +526,44 @@ unsafe fn get_calling_scope() -> *const zend_class_entry {
     unsafe { (*func).common.scope }
 }
 
+/// Returns whether the code being executed declares `strict_types=1`.
+///
+/// # Safety
+///
+/// Must only be called during PHP execution when executor globals are valid.
+#[inline]
+unsafe fn uses_strict_types() -> bool {
+    unsafe { ext_php_rs_executor_globals().as_ref() }
+        .and_then(|eg| unsafe { eg.current_execute_data.as_ref() })
+        .is_some_and(ExecuteData::uses_strict_types)
+}
+
+/// Returns the declared property of a Rust class with the given name.
+fn declared_property_info<T: RegisteredClass>(name: &str) -> Option<&'static zend_property_info> {
+    let info = T::get_metadata().ce().properties_info.get(name)?;
+    unsafe { info.ptr::<zend_property_info>()?.as_ref() }
+}
+
+/// Checks a value assigned to a property of a Rust class against the declared
+/// type of the property, as the engine does for typed properties. Unless the
+/// assigning code declares `strict_types=1`, scalar values are coerced to the
+/// type of the property.
+///
+/// Returns `false` and throws a `TypeError` if the value is not valid.
+///
+/// # Safety
+///
+/// Must only be called during PHP execution when executor globals are valid.
+unsafe fn verify_property_type<T: RegisteredClass>(name: &str, value: &mut Zval) -> bool {
+    match declared_property_info::<T>(name) {
+        // Properties declared without a type accept any value.
+        Some(info) if info.type_.type_mask != 0 => unsafe {
+            zend_verify_property_type(info, value, uses_strict_types())
+        },
+        _ => true,
+    }
+}
+
 /// Checks if the calling scope has access to a property with the given flags.
 ///
 /// Returns `true` if access is allowed, `false` otherwise.
@@ -523,6 +608,59 @@ unsafe fn check_property_access(flags: PropertyFlags, object_ce: *const zend_cla
     }
 }
 
+/// Checks if the calling scope may modify a property with the given flags,
+/// whose set visibility may be restricted (PHP 8.4+).
+///
+/// Returns `true` if modification is allowed, `false` otherwise.
+///
+/// # Safety
+///
+/// Must only be called during PHP execution when executor globals are valid.
+/// The `object_ce` pointer must be valid.
+#[cfg(php84)]
+#[inline]
+unsafe fn check_property_set_access(
+    flags: PropertyFlags,
+    object_ce: *const zend_class_entry,
+) -> bool {
+    if flags.contains(PropertyFlags::PrivateSet) {
+        unsafe { check_property_access(PropertyFlags::Private, object_ce) }
+    } else if flags.contains(PropertyFlags::ProtectedSet) {
+        unsafe { check_property_access(PropertyFlags::Protected, object_ce) }
+    } else {
+        true
+    }
+}
+
+/// Throws an error for modifying a property outside of its set visibility.
+///
+/// # Safety
+///
+/// Must only be called during PHP execution.
+///
+/// # Panics
+///
+/// Panics if the error message cannot be converted to a `CString`.
+#[cfg(php84)]
+unsafe fn throw_property_set_access_error(class_name: &str, prop_name: &str, flags: PropertyFlags) {
+    let visibility = if flags.contains(PropertyFlags::PrivateSet) {
+        "private"
+    } else {
+        "protected"
+    };
+    let scope = unsafe { get_calling_scope().as_ref() }
+        .and_then(crate::zend::ClassEntry::name)
+        .map_or_else(|| "global scope".into(), |name| format!("scope {name}"));
+    let message = CString::new(format!(
+        "Cannot modify {visibility}(set) property {class_name}::${prop_name} from {scope}"
+    ))
+    .expect("Failed to create error message");
+
+    unsafe {
+        zend_throw_error(ptr::null_mut(), message.as_ptr());
+    }
+}
+
 /// Throws an error for invalid property access.
 ///
 /// # Safety
@@ -9,6 +9,8 @@ pub(crate) mod error_observer;
 mod ex;
 #[cfg(feature = "observer")]
 pub(crate) mod exception_observer;
+#[cfg(php81)]
+mod fiber;
 mod function;
 mod globals;
 mod handlers;
@@ -39,6 +41,8 @@ pub use error_observer::{BacktraceFrame, ErrorInfo, ErrorObserver, ErrorType};
 pub use ex::ExecuteData;
 #[cfg(feature = "observer")]
 pub use exception_observer::{ExceptionInfo, ExceptionObserver};
+#[cfg(php81)]
+pub use fiber::ZendFiber;
 pub use function::Function;
 pub use function::FunctionEntry;
 pub use globals::ExecutorGlobals;
@@ -140,3 +140,63 @@ $empty_for_mut = [];
 assert(test_array_mut_empty($empty_for_mut) === 1, '&mut ZendHashTable should work on empty array without segfault');
 assert(array_key_exists('added', $empty_for_mut), 'Rust should have added a key to the empty array');
 assert($empty_for_mut['added'] === 'value', 'Added value should be correct');
+
+// Test lazily converted array views
+assert(test_array_view_sum([1, 2, 3]) === 6, 'ArrayView should convert every value');
+assert(test_array_view_sum([1, 2, -1, 'not a number']) === 3, 'ArrayView should not convert values which are not accessed');
+try {
+    test_array_view_sum([1, 'not a number']);
+    assert(false, 'ArrayView should report invalid values when they are accessed');
+} catch (Exception $e) {
+}
+assert(test_map_view_get(['a' => 'x', 'b' => 42], 'a') === 'x', 'MapView should convert the accessed value');
+assert(test_map_view_get(['a' => 'x'], 'b') === null, 'MapView should return null for missing keys');
+try {
+    test_map_view_get(['a' => 'x', 'b' => 42], 'b');
+    assert(false, 'MapView should report invalid values when they are accessed');
+} catch (Exception $e) {
+}
+
+// Test sorting in place
+$files = ['b' => 'img12.png', 'a' => 'IMG10.png', 'c' => 'img2.png'];
+$sorted = $files;
+test_array_natcasesort($sorted, false);
+$expected = $files;
+natcasesort($expected);
+assert($sorted === $expected, 'Sorting with keys should match natcasesort()');
+test_array_natcasesort($files, true);
+assert($files === ['img2.png', 'IMG10.png', 'img12.png'], 'Sorting should renumber the keys');
+
+$words = ['x' => 'ccc', 'y' => 'a', 'z' => 'bb', 'w' => 'd'];
+test_array_sort_by_length($words);
+assert($words === ['y' => 'a', 'w' => 'd', 'z' => 'bb', 'x' => 'ccc'], 'Sorting by key should be stable and keep the keys');
+
+$map = ['b' => 1, 10 => 2, 'a' => 3, 9 => 4];
+$expected = $map;
+ksort($expected);
+test_array_ksort($map);
+assert($map === $expected, 'Sorting by keys should match ksort()');
+
+// Test tuples, fixed-size arrays and smart pointers
+assert(test_array_tuple([1, 'a', true]) === [true, 'a', 1], 'Tuples should convert to and from lists');
+assert(test_array_pair(true, 42) === [true, 42], 'Pairs should convert into lists');
+assert(test_array_pair_swap([true, 'a']) === ['a', true], 'Pair should convert to and from lists');
+try {
+    test_array_pair_swap(['ok' => true, 'value' => 'a']);
+    assert(false, 'Pair should only accept lists');
+} catch (ValueError $e) {
+}
+foreach ([[1, 'a'], [1, 'a', true, null], [1 => 'a', 0 => 1, 2 => true]] as $invalid) {
+    try {
+        test_array_tuple($invalid);
+        assert(false, 'Tuples should only accept lists of their exact length');
+    } catch (ValueError $e) {
+    }
+}
+assert(test_array_fixed([1.0, 2.0, 3.0]) === [3.0, 2.0, 1.0], 'Fixed-size arrays should convert to and from lists');
+try {
+    test_array_fixed([1.0, 2.0]);
+    assert(false, 'Fixed-size arrays should only accept lists of their exact length');
+} catch (ValueError $e) {
+}
+assert(test_array_smart_pointers([1, 2, 3], 'sum', 2) === ['sum', 12, 3], 'Smart pointers should convert like the values they hold');
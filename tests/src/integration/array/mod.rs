@@ -1,11 +1,18 @@
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+    sync::Arc,
+};
 
 use ext_php_rs::{
+    boxed::ZBox,
     convert::IntoZval,
     ffi::HashTable,
+    flags::SortFlags,
     php_function,
-    prelude::ModuleBuilder,
-    types::{ArrayKey, ZendEmptyArray, ZendHashTable, Zval},
+    prelude::{ModuleBuilder, PhpResult},
+    types::{ArrayKey, ArrayView, MapView, Pair, ZendEmptyArray, ZendHashTable, Zval},
     wrap_function,
 };
 
@@ -98,6 +105,75 @@ pub fn test_array_mut_empty(arr: &mut ZendHashTable) -> i64 {
     i64::try_from(arr.len()).unwrap_or(i64::MAX)
 }
 
+/// Sums the numbers until the first negative one, without converting the rest
+#[php_function]
+pub fn test_array_view_sum(numbers: ArrayView<i64>) -> PhpResult<i64> {
+    let mut sum = 0;
+    for number in numbers {
+        let number = number?;
+        if number < 0 {
+            break;
+        }
+        sum += number;
+    }
+    Ok(sum)
+}
+
+#[php_function]
+pub fn test_map_view_get(map: MapView<String, String>, key: &str) -> PhpResult<Option<String>> {
+    Ok(map.get(key).transpose()?)
+}
+
+#[php_function]
+pub fn test_array_natcasesort(arr: &mut ZendHashTable, renumber: bool) {
+    arr.sort_with_flags(SortFlags::NaturalCaseInsensitive, renumber);
+}
+
+#[php_function]
+pub fn test_array_sort_by_length(arr: &mut ZendHashTable) {
+    arr.sort_by_key(|value| value.str().map(str::len), false);
+}
+
+#[php_function]
+pub fn test_array_ksort(arr: &mut ZendHashTable) {
+    arr.ksort(SortFlags::Regular);
+}
+
+#[php_function]
+pub fn test_array_tuple(tuple: (i64, String, bool)) -> (bool, String, i64) {
+    (tuple.2, tuple.1, tuple.0)
+}
+
+#[php_function]
+pub fn test_array_pair(ok: bool, value: i64) -> PhpResult<ZBox<ZendHashTable>> {
+    Ok((ok, value).try_into()?)
+}
+
+#[php_function]
+pub fn test_array_pair_swap(pair: Pair<bool, String>) -> Pair<String, bool> {
+    Pair(pair.1, pair.0)
+}
+
+#[php_function]
+pub fn test_array_fixed(mut values: [f64; 3]) -> [f64; 3] {
+    values.reverse();
+    values
+}
+
+#[php_function]
+#[allow(clippy::boxed_local)]
+pub fn test_array_smart_pointers(
+    values: Arc<Vec<i64>>,
+    name: Cow<str>,
+    scale: Box<i64>,
+) -> Rc<(String, i64, usize)> {
+    Rc::new((
+        name.into_owned(),
+        values.iter().sum::<i64>() * *scale,
+        values.len(),
+    ))
+}
+
 pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
     builder
         .function(wrap_function!(test_array))
@@ -113,6 +189,16 @@ pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
         .function(wrap_function!(test_empty_vec))
         .function(wrap_function!(test_empty_hashmap))
         .function(wrap_function!(test_array_mut_empty))
+        .function(wrap_function!(test_array_view_sum))
+        .function(wrap_function!(test_map_view_get))
+        .function(wrap_function!(test_array_natcasesort))
+        .function(wrap_function!(test_array_sort_by_length))
+        .function(wrap_function!(test_array_ksort))
+        .function(wrap_function!(test_array_tuple))
+        .function(wrap_function!(test_array_pair))
+        .function(wrap_function!(test_array_pair_swap))
+        .function(wrap_function!(test_array_fixed))
+        .function(wrap_function!(test_array_smart_pointers))
 }
 
 #[cfg(test)]
@@ -0,0 +1,95 @@
+<?php
+
+$bag = new TestArrayAccess();
+assert($bag instanceof ArrayAccess);
+
+$bag['name'] = 'ext-php-rs';
+$bag[1] = 'one';
+$bag[] = 'two';
+assert($bag['name'] === 'ext-php-rs');
+assert($bag[1] === 'one');
+assert($bag[2] === 'two');
+assert($bag['missing'] === null);
+assert($bag->size() === 3);
+
+$bag['zero'] = 0;
+assert(isset($bag['name']));
+assert(!isset($bag['missing']));
+assert(isset($bag['zero']));
+assert(empty($bag['zero']));
+assert(!empty($bag['name']));
+assert(($bag['missing'] ?? 'default') === 'default');
+
+unset($bag['zero']);
+assert(!isset($bag['zero']));
+assert($bag->size() === 3);
+
+// Nested writes modify the stored values in place.
+$bag['list'] = [];
+$bag['list'][] = 1;
+$bag['list'][] = 2;
+$bag['map']['key'] = 'value';
+$bag['map']['count'] = 1;
+$bag['map']['count']++;
+assert($bag['list'] === [1, 2]);
+assert($bag['map'] === ['key' => 'value', 'count' => 2]);
+
+// Nested unsets of missing offsets do not create them.
+unset($bag['missing']['x']);
+assert(!isset($bag['missing']));
+assert($bag->size() === 5);
+unset($bag['map']['key']);
+assert($bag['map'] === ['count' => 2]);
+
+// Nested reads and writes of missing offsets warn as they do on arrays.
+$warnings = [];
+set_error_handler(function (int $errno, string $errstr) use (&$warnings) {
+    $warnings[] = $errstr;
+    return true;
+});
+$bag['text']['x'] .= 'a';
+restore_error_handler();
+assert(count($warnings) === 2);
+assert(str_ends_with($warnings[0], 'Undefined array key "text"'));
+assert(str_ends_with($warnings[1], 'Undefined array key "x"'));
+assert($bag['text'] === ['x' => 'a']);
+assert($bag->size() === 6);
+
+// The `ArrayAccess` methods call the same implementation.
+assert($bag->offsetExists('name'));
+assert(!$bag->offsetExists('missing'));
+assert($bag->offsetGet('list') === [1, 2]);
+$bag->offsetSet('method', true);
+assert($bag['method'] === true);
+$bag->offsetUnset('method');
+assert(!isset($bag['method']));
+
+$method = new ReflectionMethod(TestArrayAccess::class, 'offsetSet');
+assert((string) $method->getReturnType() === 'void');
+assert((string) $method->getParameters()[1]->getType() === 'mixed');
+
+try {
+    $bag[[]] = 'invalid';
+    assert(false, 'Expected an exception');
+} catch (Exception $e) {
+    assert($e->getMessage() === 'Offset must be an integer or a string.');
+}
+
+try {
+    isset($bag[1.5]);
+    assert(false, 'Expected an exception');
+} catch (Exception $e) {
+    assert($e->getMessage() === 'Offset must be an integer or a string.');
+}
+
+// Userland subclasses can override the methods.
+class UppercaseBag extends TestArrayAccess {
+    public function offsetGet(mixed $offset): mixed {
+        return strtoupper(parent::offsetGet($offset));
+    }
+}
+
+$upper = new UppercaseBag();
+$upper['greeting'] = 'hello';
+assert($upper['greeting'] === 'HELLO');
+assert(isset($upper['greeting']));
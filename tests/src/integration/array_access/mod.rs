@@ -0,0 +1,80 @@
+use ext_php_rs::{
+    boxed::ZBox,
+    convert::FromZval,
+    prelude::*,
+    types::{ArrayKey, ZendHashTable, Zval},
+};
+
+#[php_class]
+#[php(array_access)]
+pub struct TestArrayAccess {
+    data: ZBox<ZendHashTable>,
+}
+
+#[php_impl]
+impl TestArrayAccess {
+    pub fn __construct() -> Self {
+        Self {
+            data: ZendHashTable::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+}
+
+fn key(offset: &Zval) -> PhpResult<ArrayKey<'static>> {
+    ArrayKey::from_zval(offset).ok_or_else(|| "Offset must be an integer or a string.".into())
+}
+
+impl PhpArrayAccess for TestArrayAccess {
+    fn offset_exists(&self, offset: &Zval) -> PhpResult<bool> {
+        Ok(self.data.get(key(offset)?).is_some())
+    }
+
+    fn offset_get(&self, offset: &Zval) -> PhpResult<Zval> {
+        Ok(self
+            .data
+            .get(key(offset)?)
+            .map_or_else(Zval::new, Zval::shallow_clone))
+    }
+
+    fn offset_set(&mut self, offset: Option<&Zval>, value: &Zval) -> PhpResult {
+        match offset {
+            Some(offset) => self.data.insert(key(offset)?, value.shallow_clone())?,
+            None => self.data.push(value.shallow_clone())?,
+        }
+        Ok(())
+    }
+
+    fn offset_unset(&mut self, offset: &Zval) -> PhpResult {
+        self.data.remove(key(offset)?);
+        Ok(())
+    }
+
+    fn offset_get_mut(
+        &mut self,
+        offset: Option<&Zval>,
+        _fetch: DimensionFetch,
+    ) -> PhpResult<Option<&mut Zval>> {
+        let Some(offset) = offset else {
+            return Ok(None);
+        };
+        Ok(Some(self.data.entry(key(offset)?).or_default()?))
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder.class::<TestArrayAccess>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn array_access_works() {
+        assert!(crate::integration::test::run_php(
+            "array_access/array_access.php"
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+<?php
+
+require(__DIR__ . '/../_utils.php');
+
+// Outside of a fiber, futures are run with the executor
+assert(test_async_add(1, 2, 3) === 3);
+assert(test_async_concat('foo', 'bar') === 'foobar');
+assert_exception_thrown(fn () => test_async_fail());
+
+$counter = new TestAsyncCounter('first');
+assert($counter->increment(2) === 2);
+assert(TestAsyncCounter::double(21) === 42);
+assert(test_async_executor_runs() === 5);
+
+if (PHP_VERSION_ID < 80100) {
+    return;
+}
+
+// In a fiber, the fiber is suspended with a waker each time the future is
+// pending
+$fiber = new Fiber(fn () => test_async_add(1, 2, 3));
+$waker = $fiber->start();
+$resumes = 0;
+while (!$fiber->isTerminated()) {
+    assert($fiber->isSuspended());
+    assert($waker instanceof RustWaker);
+    assert($waker->isWoken());
+    $waker = $fiber->resume();
+    $resumes++;
+}
+assert($resumes === 3);
+assert($fiber->getReturn() === 3);
+
+// The waker tells whether the future was woken since it was last polled
+$fiber = new Fiber(fn () => test_async_wait());
+$waker = $fiber->start();
+assert(!$waker->isWoken());
+assert($fiber->resume() === $waker);
+assert(!$waker->isWoken());
+test_async_release();
+assert($waker->isWoken());
+$fiber->resume();
+assert($fiber->isTerminated());
+assert($fiber->getReturn() === 'released');
+
+// Calls on the same object in several fibers run on clones of the object,
+// while the object can still be modified
+$fibers = [
+    new Fiber(fn () => $counter->increment(1)),
+    new Fiber(fn () => $counter->nameLater()),
+    new Fiber(fn () => $counter->increment(2)),
+];
+foreach ($fibers as $fiber) {
+    $fiber->start();
+    assert($fiber->isSuspended());
+}
+$counter->rename('second');
+foreach ($fibers as $fiber) {
+    $fiber->resume();
+    assert($fiber->isTerminated());
+}
+assert($fibers[0]->getReturn() === 3);
+assert($fibers[1]->getReturn() === 'first');
+assert($fibers[2]->getReturn() === 5);
+
+// Calls in several fibers are interleaved
+$fibers = [];
+foreach ([3, 1, 2] as $yields) {
+    $fiber = new Fiber(fn () => test_async_add($yields, 0, $yields));
+    $fiber->start();
+    $fibers[] = $fiber;
+}
+$completed = [];
+while (count($completed) < 3) {
+    foreach ($fibers as $fiber) {
+        if ($fiber->isSuspended()) {
+            $fiber->resume();
+            if ($fiber->isTerminated()) {
+                $completed[] = $fiber->getReturn();
+            }
+        }
+    }
+}
+assert($completed === [1, 2, 3]);
+
+// Errors are thrown in the fiber
+$fiber = new Fiber(function () {
+    try {
+        test_async_fail();
+    } catch (Exception $e) {
+        return $e->getMessage();
+    }
+});
+$fiber->start();
+$fiber->resume();
+assert($fiber->getReturn() === 'Async failure');
+
+// Throwing into the fiber cancels the call
+$fiber = new Fiber(fn () => test_async_add(1, 2, 5));
+$fiber->start();
+try {
+    $fiber->throw(new RuntimeException('Cancelled'));
+    assert(false, 'Exception was not thrown');
+} catch (RuntimeException $e) {
+    assert($e->getMessage() === 'Cancelled');
+}
+assert($fiber->isTerminated());
+
+// Destroying the suspended fiber cancels the call
+$fiber = new Fiber(fn () => test_async_add(1, 2, 5));
+$fiber->start();
+unset($fiber);
+
+// The executor was not used by the fibers
+assert(test_async_executor_runs() === 5);
@@ -0,0 +1,158 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Waker},
+};
+
+use ext_php_rs::{
+    executor::{self, Executor, LocalExecutor},
+    prelude::*,
+};
+
+/// Number of futures run by the executor, i.e. outside of fibers.
+static EXECUTOR_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingExecutor;
+
+impl Executor for CountingExecutor {
+    fn block_on(&self, future: Pin<&mut dyn Future<Output = ()>>) {
+        EXECUTOR_RUNS.fetch_add(1, Ordering::SeqCst);
+        LocalExecutor.block_on(future);
+    }
+}
+
+/// A future which is pending the given number of times before completing.
+struct Yield(u32);
+
+impl Future for Yield {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 == 0 {
+            return Poll::Ready(());
+        }
+        self.0 -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Whether the future of `test_async_wait` has been released.
+static RELEASED: AtomicBool = AtomicBool::new(false);
+
+/// The waker of the future of `test_async_wait`, woken once released.
+static RELEASE_WAKER: Mutex<Option<Waker>> = Mutex::new(None);
+
+/// A future which is pending until released by `test_async_release`.
+struct Release;
+
+impl Future for Release {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if RELEASED.swap(false, Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        *RELEASE_WAKER.lock().unwrap() = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[php_function]
+pub async fn test_async_add(a: i64, b: i64, yields: u32) -> i64 {
+    Yield(yields).await;
+    a + b
+}
+
+#[php_function]
+pub async fn test_async_concat(a: &str, b: &str) -> String {
+    Yield(1).await;
+    format!("{a}{b}")
+}
+
+#[php_function]
+pub async fn test_async_fail() -> PhpResult<i64> {
+    Yield(1).await;
+    Err("Async failure".into())
+}
+
+#[php_function]
+pub async fn test_async_wait() -> String {
+    Release.await;
+    "released".to_string()
+}
+
+#[php_function]
+pub fn test_async_release() {
+    RELEASED.store(true, Ordering::SeqCst);
+    if let Some(waker) = RELEASE_WAKER.lock().unwrap().take() {
+        waker.wake();
+    }
+}
+
+#[php_function]
+pub fn test_async_executor_runs() -> usize {
+    EXECUTOR_RUNS.load(Ordering::SeqCst)
+}
+
+/// A counter whose `async` methods run on clones, sharing the count.
+#[php_class]
+#[derive(Clone)]
+pub struct TestAsyncCounter {
+    count: Arc<AtomicI64>,
+    name: String,
+}
+
+#[php_impl]
+impl TestAsyncCounter {
+    pub fn __construct(name: String) -> Self {
+        Self {
+            count: Arc::default(),
+            name,
+        }
+    }
+
+    pub fn rename(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub async fn increment(self, by: i64) -> i64 {
+        Yield(1).await;
+        self.count.fetch_add(by, Ordering::SeqCst) + by
+    }
+
+    pub async fn name_later(self) -> String {
+        Yield(1).await;
+        self.name
+    }
+
+    pub async fn double(value: i64) -> i64 {
+        Yield(2).await;
+        value * 2
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    executor::set_executor(CountingExecutor);
+
+    builder
+        .function(wrap_function!(test_async_add))
+        .function(wrap_function!(test_async_concat))
+        .function(wrap_function!(test_async_fail))
+        .function(wrap_function!(test_async_wait))
+        .function(wrap_function!(test_async_release))
+        .function(wrap_function!(test_async_executor_runs))
+        .class::<TestAsyncCounter>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn async_fn_works() {
+        assert!(crate::integration::test::run_php("async_fn/async_fn.php"));
+    }
+}
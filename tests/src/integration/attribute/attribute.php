@@ -0,0 +1,61 @@
+<?php
+
+require __DIR__ . '/../_utils.php';
+
+use Test\Route;
+
+// Attributes attached to functions and their parameters.
+$function = new ReflectionFunction('test_attribute_login');
+$routes = $function->getAttributes(Route::class);
+assert(count($routes) === 2);
+assert($routes[0]->getArguments() === ['/login', 'method' => 'POST']);
+$route = $routes[0]->newInstance();
+assert($route->path === '/login');
+assert($route->method === 'POST');
+assert($routes[1]->newInstance()->method === 'GET');
+
+$password = $function->getParameters()[1];
+$attributes = $password->getAttributes();
+assert(count($attributes) === 1);
+assert($attributes[0]->getName() === 'SensitiveParameter');
+assert(count($function->getParameters()[0]->getAttributes()) === 0);
+
+// Attribute classes declared from Rust can be used by userland code.
+#[Route('/user', method: 'PUT')]
+function test_attribute_user() {}
+
+$route = (new ReflectionFunction('test_attribute_user'))->getAttributes()[0]->newInstance();
+assert($route instanceof Route);
+assert($route->path === '/user');
+assert($route->method === 'PUT');
+
+$attribute = (new ReflectionClass(Route::class))->getAttributes(Attribute::class)[0];
+assert($attribute->newInstance()->flags === (Attribute::TARGET_FUNCTION | Attribute::TARGET_METHOD | Attribute::IS_REPEATABLE));
+
+#[Route('/class')]
+class TestAttributeTarget {}
+
+try {
+    (new ReflectionClass(TestAttributeTarget::class))->getAttributes()[0]->newInstance();
+    assert(false, 'The attribute should not target classes');
+} catch (Error $e) {
+    assert(str_contains($e->getMessage(), 'cannot target class'), $e->getMessage());
+}
+
+// Attributes attached to classes and their members.
+$attributes = (new ReflectionClass(TestAttributeClass::class))->getAttributes();
+assert(count($attributes) === 1);
+assert($attributes[0]->getName() === 'Test\Marker');
+assert($attributes[0]->getArguments() === ['class']);
+
+$attributes = (new ReflectionMethod(TestAttributeClass::class, 'count'))->getAttributes();
+assert(count($attributes) === 1);
+assert($attributes[0]->getName() === 'ReturnTypeWillChange');
+
+$attributes = (new ReflectionProperty(TestAttributeClass::class, 'count'))->getAttributes();
+assert(count($attributes) === 1);
+assert($attributes[0]->getArguments() === [true]);
+
+$attributes = (new ReflectionClassConstant(TestAttributeClass::class, 'LIMIT'))->getAttributes();
+assert(count($attributes) === 1);
+assert($attributes[0]->getArguments() === ['max' => 10]);
@@ -0,0 +1,80 @@
+use ext_php_rs::{attribute::Attribute, builders::ClassBuilder, flags::AttributeFlags, prelude::*};
+
+#[php_attribute]
+#[php(
+    name = "Test\\Route",
+    target = AttributeFlags::Function | AttributeFlags::Method | AttributeFlags::Repeatable
+)]
+pub struct TestRoute {
+    #[php(prop)]
+    path: String,
+    #[php(prop)]
+    method: String,
+}
+
+#[php_impl]
+impl TestRoute {
+    pub fn __construct(path: String, method: Option<String>) -> Self {
+        Self {
+            path,
+            method: method.unwrap_or_else(|| "GET".into()),
+        }
+    }
+}
+
+#[php_function]
+pub fn test_attribute_login(user: &str, password: &str) -> bool {
+    user == "admin" && password == "secret"
+}
+
+#[php_class]
+#[php(modifier = test_attribute_class_modifier)]
+pub struct TestAttributeClass {
+    #[php(prop)]
+    count: i64,
+}
+
+#[php_impl]
+impl TestAttributeClass {
+    pub const LIMIT: i64 = 10;
+
+    pub fn __construct() -> Self {
+        Self { count: 0 }
+    }
+
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+}
+
+fn test_attribute_class_modifier(builder: ClassBuilder) -> ClassBuilder {
+    builder
+        .attribute(Attribute::new("Test\\Marker").arg("class"))
+        .method_attribute("count", Attribute::new("\\ReturnTypeWillChange"))
+        .property_attribute("count", Attribute::new("Test\\Marker").arg(true))
+        .constant_attribute("LIMIT", Attribute::new("Test\\Marker").named_arg("max", 10))
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .class::<TestRoute>()
+        .class::<TestAttributeClass>()
+        .function(
+            wrap_function!(test_attribute_login)
+                .attribute(
+                    Attribute::new("Test\\Route")
+                        .arg("/login")
+                        .named_arg("method", "POST"),
+                )
+                .attribute(Attribute::new("Test\\Route").arg("/signin"))
+                .arg_attribute("password", Attribute::new("SensitiveParameter")),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn attribute_works() {
+        assert!(crate::integration::test::run_php("attribute/attribute.php"));
+    }
+}
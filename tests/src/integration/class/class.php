@@ -428,3 +428,82 @@ $vis = new TestPropertyVisibility(1, 'secret', 'guarded');
 assert($vis->publicNum === 1, 'Public read should work before cache warms');
 assert($vis->publicNum === 1, 'Public read should work after cache warms');
 assert_exception_thrown(fn() => $vis->privateStr, 'Private access should throw even if cache_slot is warm');
+
+// Test property hooks and asymmetric visibility (PHP 8.4+)
+if (PHP_VERSION_ID >= 80_400) {
+    $hooks = new TestPropertyHooks('ferris');
+
+    // Getters and setters are declared as virtual properties with hooks
+    assert($hooks->name === 'ferris', 'Get hook should call the getter');
+    $hooks->name = 'crab';
+    assert($hooks->name === 'crab', 'Set hook should call the setter');
+    assert($hooks->shout === 'CRAB', 'Get-only hook should call the getter');
+    assert_exception_thrown(fn() => $hooks->shout = 'x', 'Writing a get-only hooked property should throw');
+
+    $nameProp = new ReflectionProperty(TestPropertyHooks::class, 'name');
+    assert($nameProp->isVirtual(), 'Hooked property should be virtual');
+    assert($nameProp->hasHook(PropertyHookType::Get), 'Hooked property should have a get hook');
+    assert($nameProp->hasHook(PropertyHookType::Set), 'Hooked property should have a set hook');
+    assert($nameProp->getHook(PropertyHookType::Get)->invoke($hooks) === 'crab', 'Get hook should be invokable');
+    $shoutProp = new ReflectionProperty(TestPropertyHooks::class, 'shout');
+    assert(!$shoutProp->hasHook(PropertyHookType::Set), 'Get-only property should not have a set hook');
+
+    // Asymmetric visibility: public read, private write
+    assert($hooks->counter === 0, 'private(set) property should be readable');
+    assert_exception_thrown(fn() => $hooks->counter = 1, 'private(set) property should not be writable from outside');
+    (fn() => $this->counter = 5)->call($hooks);
+    assert($hooks->counter === 5, 'private(set) property should be writable from the class scope');
+    $counterProp = new ReflectionProperty(TestPropertyHooks::class, 'counter');
+    assert($counterProp->isPrivateSet(), 'Property should be private(set)');
+    assert($counterProp->isPublic(), 'Property should be publicly readable');
+}
+
+// Test typed properties declared for fields
+$typed = new TestTypedProperties();
+$countProp = new ReflectionProperty(TestTypedProperties::class, 'count');
+assert((string) $countProp->getType() === 'int', 'Field property should be declared with its type');
+assert(!$countProp->hasDefaultValue(), 'Typed property without default should be uninitialized by default');
+$answerProp = new ReflectionProperty(TestTypedProperties::class, 'answer');
+assert($answerProp->getDefaultValue() === 42, 'Declared default value should be visible to reflection');
+$labelProp = new ReflectionProperty(TestTypedProperties::class, 'label');
+assert((string) $labelProp->getType() === '?string', 'Option field should be declared nullable');
+assert($labelProp->getDefaultValue() === null, 'Nullable property should default to null');
+assert(property_exists($typed, 'count'), 'Declared property should exist');
+assert(get_object_vars($typed) == ['count' => 0, 'answer' => 42, 'label' => null]);
+
+// Assignments are checked against the declared type
+$typed->count = '5';
+assert($typed->count === 5, 'Numeric string should be coerced to int');
+$count = '6';
+$typed->count = $count;
+assert($typed->count === 6, 'Numeric string variable should be coerced to int');
+assert($count === '6', 'Coercion should not modify the assigned variable');
+for ($i = 0; $i < 2; $i++) {
+    $typed->count = '7';
+    assert($typed->count === 7, 'Coercion should not modify the assigned literal');
+}
+$typed->label = 'label';
+assert($typed->label === 'label');
+$typed->label = null;
+assert($typed->label === null, 'Nullable property should accept null');
+try {
+    $typed->count = [];
+    throw new Exception('Assigning an array to an int property should throw');
+} catch (TypeError $e) {
+    assert(str_contains($e->getMessage(), 'TestTypedProperties::$count of type int'), $e->getMessage());
+}
+
+// Readonly properties cannot be modified (PHP 8.1+)
+if (PHP_VERSION_ID >= 80_100) {
+    $readonly = new TestReadonlyProperty('abc');
+    $idProp = new ReflectionProperty(TestReadonlyProperty::class, 'id');
+    assert($idProp->isReadOnly(), 'Property should be readonly');
+    assert($readonly->id === 'abc');
+    try {
+        $readonly->id = 'def';
+        throw new Exception('Modifying a readonly property should throw');
+    } catch (Error $e) {
+        assert($e->getMessage() === 'Cannot modify readonly property TestReadonlyProperty::$id', $e->getMessage());
+    }
+    assert($readonly->id === 'abc');
+}
@@ -568,6 +568,77 @@ impl TestPropertyVisibility {
     }
 }
 
+/// Test class for typed properties with default values
+#[php_class]
+pub struct TestTypedProperties {
+    #[php(prop)]
+    pub count: i64,
+    #[php(prop, default = 42)]
+    pub answer: i64,
+    #[php(prop)]
+    pub label: Option<String>,
+}
+
+#[php_impl]
+impl TestTypedProperties {
+    pub fn __construct() -> Self {
+        Self {
+            count: 0,
+            answer: 42,
+            label: None,
+        }
+    }
+}
+
+/// Test class for readonly properties (PHP 8.1+)
+#[cfg(php81)]
+#[php_class]
+pub struct TestReadonlyProperty {
+    #[php(prop, readonly)]
+    pub id: String,
+}
+
+#[cfg(php81)]
+#[php_impl]
+impl TestReadonlyProperty {
+    pub fn __construct(id: String) -> Self {
+        Self { id }
+    }
+}
+
+/// Test class for property hooks and asymmetric visibility (PHP 8.4+)
+#[cfg(php84)]
+#[php_class]
+pub struct TestPropertyHooks {
+    /// Readable from anywhere, but only modifiable from within the class
+    #[php(prop, set_vis = "private")]
+    pub counter: i64,
+    name: String,
+}
+
+#[cfg(php84)]
+#[php_impl]
+impl TestPropertyHooks {
+    pub fn __construct(name: String) -> Self {
+        Self { counter: 0, name }
+    }
+
+    #[php(getter)]
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[php(setter)]
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    #[php(getter)]
+    pub fn get_shout(&self) -> String {
+        self.name.to_uppercase()
+    }
+}
+
 /// Test class for issue #325 - returning &'static str from getter
 #[php_class]
 pub struct TestClassStaticStrGetter;
@@ -687,6 +758,7 @@ pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
         .class::<TestStaticProps>()
         .class::<FluentBuilder>()
         .class::<TestPropertyVisibility>()
+        .class::<TestTypedProperties>()
         .class::<TestReservedKeywordMethods>()
         .class::<TestLazyClass>()
         .class::<TestFinalMethods>()
@@ -704,11 +776,15 @@ pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
 
     #[cfg(php84)]
     let builder = builder
+        .class::<TestPropertyHooks>()
         .function(wrap_function!(test_is_lazy))
         .function(wrap_function!(test_is_lazy_ghost))
         .function(wrap_function!(test_is_lazy_proxy))
         .function(wrap_function!(test_is_lazy_initialized));
 
+    #[cfg(php81)]
+    let builder = builder.class::<TestReadonlyProperty>();
+
     #[cfg(php82)]
     let builder = builder.class::<TestReadonlyClass>();
 
@@ -0,0 +1,75 @@
+<?php
+
+require __DIR__ . '/../_utils.php';
+
+// Scalar arguments are coerced as they are for built-in functions.
+assert(test_coercion_int('5') === 5);
+assert(test_coercion_int(5.0) === 5);
+assert(test_coercion_int(true) === 1);
+assert(test_coercion_float(2) === 2.0);
+assert(test_coercion_float('1.5') === 1.5);
+assert(test_coercion_string(42) === '42');
+assert(test_coercion_string(1.5) === '1.5');
+assert(test_coercion_bool(1) === true);
+assert(test_coercion_bool('') === false);
+assert(test_coercion_nullable_int('7') === 7);
+assert(test_coercion_nullable_int(null) === null);
+assert(test_coercion_variadic('5', 'a', 'b') === 7);
+
+assert_exception_thrown(fn () => test_coercion_int('abc'));
+assert_exception_thrown(fn () => test_coercion_int([]));
+assert_exception_thrown(fn () => test_coercion_variadic('abc'));
+
+// Null given to a non-nullable scalar parameter is coerced, with a
+// deprecation since PHP 8.1.
+$deprecations = [];
+set_error_handler(function (int $errno, string $errstr) use (&$deprecations) {
+    $deprecations[] = $errstr;
+    return true;
+}, E_DEPRECATED);
+assert(test_coercion_int(null) === 0);
+assert(test_coercion_string(null) === '');
+restore_error_handler();
+
+if (PHP_VERSION_ID >= 80100) {
+    assert(count($deprecations) === 2);
+    assert(str_contains($deprecations[0], 'Passing null to parameter #1 ($a) of type int is deprecated'));
+}
+
+// Deprecations turned into exceptions abort the call.
+set_error_handler(function (int $errno, string $errstr) {
+    throw new ErrorException($errstr, 0, $errno);
+}, E_DEPRECATED);
+if (PHP_VERSION_ID >= 80100) {
+    assert_exception_thrown(fn () => test_coercion_bool(null));
+}
+restore_error_handler();
+
+// Invalid arguments throw the errors the engine throws for built-in functions.
+function assert_error(callable $callback, string $class, string $message): void
+{
+    try {
+        $callback();
+    } catch (Throwable $e) {
+        assert($e instanceof $class, 'Expected ' . $class . ', got ' . get_class($e));
+        assert($e->getMessage() === $message, $e->getMessage());
+        return;
+    }
+    assert(false, 'Expected ' . $class . ' to be thrown');
+}
+
+assert_error(fn () => test_coercion_int('abc'), TypeError::class, 'test_coercion_int(): Argument #1 ($a) must be of type int, string given');
+assert_error(fn () => test_coercion_nullable_int([]), TypeError::class, 'test_coercion_nullable_int(): Argument #1 ($a) must be of type ?int, array given');
+assert_error(fn () => test_coercion_variadic('abc'), TypeError::class, 'test_coercion_variadic(): Argument #1 ($a) must be of type int, string given');
+assert_error(fn () => test_coercion_unsigned(-1), ValueError::class, 'test_coercion_unsigned(): Argument #1 ($a) must be a valid int');
+assert_error(fn () => test_coercion_optional(1, 'x'), TypeError::class, 'test_coercion_optional(): Argument #2 ($b) must be of type array, string given');
+assert_error(fn () => test_coercion_optional(1, [1, 'x']), ValueError::class, 'test_coercion_optional(): Argument #2 ($b) must be a valid array');
+assert_error(fn () => test_coercion_optional(1), ArgumentCountError::class, 'test_coercion_optional(): Argument #2 ($b) not passed');
+assert_error(fn () => test_coercion_int(), ArgumentCountError::class, 'test_coercion_int() expects exactly 1 argument, 0 given');
+assert(test_coercion_optional(1, [2, 3]) === 6);
+
+// Errors thrown by methods are qualified with the name of the class.
+$counter = new TestCoercionCounter('1');
+assert($counter->add('2') === 3);
+assert_error(fn () => new TestCoercionCounter('abc'), TypeError::class, 'TestCoercionCounter::__construct(): Argument #1 ($count) must be of type int, string given');
+assert_error(fn () => $counter->add([]), TypeError::class, 'TestCoercionCounter::add(): Argument #1 ($step) must be of type int, array given');
@@ -0,0 +1,25 @@
+<?php
+
+declare(strict_types=1);
+
+require __DIR__ . '/../_utils.php';
+
+// Arguments are not coerced when the caller declares strict types, except
+// integers given for floats.
+assert(test_coercion_int(5) === 5);
+assert(test_coercion_float(2) === 2.0);
+assert(test_coercion_nullable_int(null) === null);
+
+assert_exception_thrown(fn () => test_coercion_int('5'));
+assert_exception_thrown(fn () => test_coercion_int(5.0));
+assert_exception_thrown(fn () => test_coercion_string(42));
+assert_exception_thrown(fn () => test_coercion_bool(1));
+assert_exception_thrown(fn () => test_coercion_int(null));
+assert_exception_thrown(fn () => test_coercion_variadic('5'));
+
+try {
+    test_coercion_int('5');
+    assert(false, 'Expected a TypeError');
+} catch (TypeError $e) {
+    assert($e->getMessage() === 'test_coercion_int(): Argument #1 ($a) must be of type int, string given');
+}
@@ -0,0 +1,87 @@
+use ext_php_rs::{prelude::*, types::Zval};
+
+#[php_function]
+pub fn test_coercion_int(a: i64) -> i64 {
+    a
+}
+
+#[php_function]
+pub fn test_coercion_float(a: f64) -> f64 {
+    a
+}
+
+#[php_function]
+pub fn test_coercion_string(a: String) -> String {
+    a
+}
+
+#[php_function]
+pub fn test_coercion_bool(a: bool) -> bool {
+    a
+}
+
+#[php_function]
+pub fn test_coercion_nullable_int(a: Option<i64>) -> Option<i64> {
+    a
+}
+
+#[php_function]
+pub fn test_coercion_variadic(a: i64, rest: &[&Zval]) -> i64 {
+    a + i64::try_from(rest.len()).unwrap_or_default()
+}
+
+#[php_function]
+pub fn test_coercion_unsigned(a: u8) -> u8 {
+    a
+}
+
+#[php_function]
+#[php(optional = "b")]
+pub fn test_coercion_optional(a: i64, b: Vec<i64>) -> i64 {
+    a + b.iter().sum::<i64>()
+}
+
+#[php_class]
+pub struct TestCoercionCounter {
+    count: i64,
+}
+
+#[php_impl]
+impl TestCoercionCounter {
+    pub fn __construct(count: i64) -> Self {
+        Self { count }
+    }
+
+    pub fn add(&mut self, step: i64) -> i64 {
+        self.count += step;
+        self.count
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .class::<TestCoercionCounter>()
+        .function(wrap_function!(test_coercion_int))
+        .function(wrap_function!(test_coercion_float))
+        .function(wrap_function!(test_coercion_string))
+        .function(wrap_function!(test_coercion_bool))
+        .function(wrap_function!(test_coercion_nullable_int))
+        .function(wrap_function!(test_coercion_variadic))
+        .function(wrap_function!(test_coercion_unsigned))
+        .function(wrap_function!(test_coercion_optional))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn coercion_works() {
+        assert!(crate::integration::test::run_php("coercion/coercion.php"));
+    }
+
+    #[test]
+    fn coercion_strict_types_works() {
+        assert!(crate::integration::test::run_php(
+            "coercion/coercion_strict.php"
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 <?php
 
+declare(strict_types=1);
+
 assert(test_defaults_integer() === 42);
 assert(test_defaults_integer(12) === 12);
 assert(test_defaults_nullable_string() === null);
@@ -9,7 +11,7 @@ assert(test_defaults_multiple_option_arguments(a: 'a') === 'a');
 assert(test_defaults_multiple_option_arguments(b: 'b') === 'b');
 
 // Test that passing null to a non-nullable parameter with a default value throws TypeError
-// (fixes: https://github.com/extphprs/ext-php-rs/issues/538)
+// in strict mode (fixes: https://github.com/extphprs/ext-php-rs/issues/538)
 $threw = false;
 try {
     test_defaults_integer(null);
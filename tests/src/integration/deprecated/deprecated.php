@@ -0,0 +1,60 @@
+<?php
+
+require __DIR__ . '/../_utils.php';
+
+$deprecations = [];
+set_error_handler(function (int $errno, string $errstr) use (&$deprecations) {
+    $deprecations[] = $errstr;
+    return true;
+}, E_DEPRECATED);
+
+function take_deprecations(): array
+{
+    global $deprecations;
+    $taken = $deprecations;
+    $deprecations = [];
+    return $taken;
+}
+
+// Deprecated functions and methods still run, and emit a deprecation.
+assert(test_deprecation_old() === 1);
+assert(test_deprecation_bare() === 2);
+assert(test_deprecation_new() === 3);
+$messages = take_deprecations();
+assert(count($messages) === 2);
+if (PHP_VERSION_ID >= 80400) {
+    assert($messages[0] === 'Function test_deprecation_old() is deprecated since 2.0, use test_deprecation_new() instead', $messages[0]);
+} else {
+    assert($messages[0] === 'Function test_deprecation_old() is deprecated', $messages[0]);
+}
+assert($messages[1] === 'Function test_deprecation_bare() is deprecated', $messages[1]);
+
+$obj = new TestDeprecation();
+assert($obj->runOld() === 4);
+assert(TestDeprecation::create() === 5);
+$messages = take_deprecations();
+assert(count($messages) === 2);
+assert(str_starts_with($messages[0], 'Method TestDeprecation::runOld() is deprecated'), $messages[0]);
+assert($messages[1] === 'Method TestDeprecation::create() is deprecated', $messages[1]);
+
+// Constants emit a deprecation when they are read.
+assert(TEST_DEPRECATED_LIMIT === 10);
+$messages = take_deprecations();
+assert(count($messages) === 1);
+assert(str_starts_with($messages[0], 'Constant TEST_DEPRECATED_LIMIT is deprecated'), $messages[0]);
+
+// Class constants can only be deprecated since PHP 8.4.
+assert(TestDeprecation::OLD_LIMIT === 5);
+$messages = take_deprecations();
+if (PHP_VERSION_ID >= 80400) {
+    assert(count($messages) === 1);
+    assert($messages[0] === 'Constant TestDeprecation::OLD_LIMIT is deprecated since 2.0, use 10 instead', $messages[0]);
+
+    $attributes = (new ReflectionFunction('test_deprecation_old'))->getAttributes(Deprecated::class);
+    assert(count($attributes) === 1);
+    assert($attributes[0]->newInstance()->since === '2.0');
+} else {
+    assert(count($messages) === 0);
+}
+
+restore_error_handler();
@@ -0,0 +1,66 @@
+use ext_php_rs::prelude::*;
+
+#[php_const]
+#[php(deprecated = "use TEST_LIMIT instead", since = "2.0")]
+const TEST_DEPRECATED_LIMIT: i64 = 10;
+
+#[php_function]
+#[php(deprecated = "use test_deprecation_new() instead", since = "2.0")]
+pub fn test_deprecation_old() -> i64 {
+    1
+}
+
+#[php_function]
+#[php(deprecated)]
+pub fn test_deprecation_bare() -> i64 {
+    2
+}
+
+#[php_function]
+pub fn test_deprecation_new() -> i64 {
+    3
+}
+
+#[php_class]
+#[php(deprecated = "use TestDeprecationNew instead")]
+pub struct TestDeprecation;
+
+#[php_impl]
+impl TestDeprecation {
+    #[php(deprecated = "use 10 instead", since = "2.0")]
+    pub const OLD_LIMIT: i64 = 5;
+
+    pub fn __construct() -> Self {
+        Self
+    }
+
+    #[php(deprecated = "use run() instead")]
+    #[allow(clippy::unused_self)]
+    pub fn run_old(&self) -> i64 {
+        4
+    }
+
+    #[php(deprecated)]
+    pub fn create() -> i64 {
+        5
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .constant(wrap_constant!(TEST_DEPRECATED_LIMIT))
+        .class::<TestDeprecation>()
+        .function(wrap_function!(test_deprecation_old))
+        .function(wrap_function!(test_deprecation_bare))
+        .function(wrap_function!(test_deprecation_new))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn deprecation_works() {
+        assert!(crate::integration::test::run_php(
+            "deprecated/deprecated.php"
+        ));
+    }
+}
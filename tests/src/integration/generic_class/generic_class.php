@@ -0,0 +1,25 @@
+<?php
+
+require(__DIR__ . '/../_utils.php');
+
+$ints = new TestIntCache();
+$ints->set('one', 1);
+$ints->set('two', 2);
+assert($ints->get('one') === 1);
+assert($ints->get('three') === null);
+assert($ints->values() === [1, 2]);
+assert($ints->last === 2);
+
+$strings = TestStringCache::with('greeting', 'hello');
+assert($strings instanceof TestStringCache);
+assert($strings->get('greeting') === 'hello');
+assert($strings->last === 'hello');
+
+// Each instantiation is a separate class, with its own types.
+assert(!($ints instanceof TestStringCache));
+assert(get_class($ints) === 'TestIntCache');
+assert_exception_thrown(fn () => $ints->set('three', 'three'));
+assert_exception_thrown(fn () => $strings->last = []);
+
+$strings->last = 'world';
+assert($strings->last === 'world');
@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+
+use ext_php_rs::{
+    convert::{FromZvalMut, IntoZval},
+    prelude::*,
+};
+
+/// A cache of values by key.
+#[php_class]
+#[php(instantiate(
+    TestCache<i64> = "TestIntCache",
+    TestCache<String> = "TestStringCache"
+))]
+pub struct TestCache<T: 'static> {
+    entries: BTreeMap<String, T>,
+    #[php(prop)]
+    last: Option<T>,
+}
+
+#[php_impl]
+impl<T> TestCache<T>
+where
+    T: Clone + IntoZval + for<'a> FromZvalMut<'a> + 'static,
+{
+    pub fn __construct() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            last: None,
+        }
+    }
+
+    pub fn with(key: String, value: T) -> Self {
+        let mut cache = Self::__construct();
+        cache.set(key, value);
+        cache
+    }
+
+    pub fn set(&mut self, key: String, value: T) {
+        self.last = Some(value.clone());
+        self.entries.insert(key, value);
+    }
+
+    pub fn get(&self, key: &str) -> Option<T> {
+        self.entries.get(key).cloned()
+    }
+
+    pub fn values(&self) -> Vec<T> {
+        self.entries.values().cloned().collect()
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .class::<TestCache<i64>>()
+        .class::<TestCache<String>>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn generic_class_works() {
+        assert!(crate::integration::test::run_php(
+            "generic_class/generic_class.php"
+        ));
+    }
+}
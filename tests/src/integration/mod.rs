@@ -1,14 +1,21 @@
 pub mod array;
+pub mod array_access;
+#[cfg(feature = "async")]
+pub mod async_fn;
+pub mod attribute;
 pub mod bailout;
 pub mod binary;
 pub mod bool;
 pub mod callable;
 pub mod class;
 pub mod closure;
+pub mod coercion;
 pub mod defaults;
+pub mod deprecated;
 #[cfg(feature = "enum")]
 pub mod enum_;
 pub mod exception;
+pub mod generic_class;
 pub mod globals;
 pub mod interface;
 pub mod iterator;
@@ -19,12 +26,20 @@ pub mod number;
 pub mod object;
 #[cfg(feature = "observer")]
 pub mod observer;
+pub mod operators;
 pub mod persistent_string;
+pub mod persistent_zval;
+pub mod php_serialize;
 pub mod reference;
+pub mod resource;
+pub mod rust_iterator;
 pub mod separated;
 pub mod string;
+pub mod trait_;
 pub mod types;
 pub mod variadic_args;
+pub mod weak;
+pub mod zval_convert;
 
 #[cfg(test)]
 mod test {
@@ -62,6 +77,8 @@ mod test {
                 features.push("static");
                 #[cfg(feature = "observer")]
                 features.push("observer");
+                #[cfg(feature = "async")]
+                features.push("async");
 
                 if !features.is_empty() {
                     command.arg("--no-default-features");
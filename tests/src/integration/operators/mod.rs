@@ -0,0 +1,176 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+use ext_php_rs::{error::Error, operator::Pow, prelude::*, types::Zval};
+
+#[php_function]
+pub fn test_operators_compare(a: &Zval, b: &Zval) -> PhpResult<i64> {
+    a.compare(b)
+        .map(|ordering| ordering as i64)
+        .map_err(exception_class)
+}
+
+#[php_function]
+pub fn test_operators_loose_eq(a: &Zval, b: &Zval) -> PhpResult<bool> {
+    a.loose_eq(b).map_err(exception_class)
+}
+
+/// Converts an error into an exception with the class name of the exception
+/// thrown by the engine, if any.
+fn exception_class(err: Error) -> PhpException {
+    match err {
+        Error::Exception(e) => e.get_class_name().unwrap_or_default().into(),
+        err => err.into(),
+    }
+}
+
+/// Applies an operator, throwing an exception with the class name of the
+/// exception thrown by the engine, if any.
+#[php_function]
+pub fn test_operators_apply(op: &str, a: &Zval, b: &Zval) -> PhpResult<Zval> {
+    let result = match op {
+        "+" => a + b,
+        "-" => a - b,
+        "*" => a * b,
+        "/" => a / b,
+        "%" => a % b,
+        "**" => a.pow(b),
+        "." => a.concat(b),
+        "&" => a & b,
+        "|" => a | b,
+        "^" => a ^ b,
+        "<<" => a << b,
+        ">>" => a >> b,
+        "~" => !a,
+        _ => return Err(format!("Unknown operator {op}").into()),
+    };
+    result.map_err(exception_class)
+}
+
+/// An amount of money in cents, with overloaded operators.
+#[php_class]
+#[php(operators(
+    Add,
+    Sub,
+    Mul(i64, f64),
+    RMul(i64),
+    Div(i64),
+    Mod(i64),
+    Pow(u32),
+    Neg,
+    Concat
+))]
+#[derive(Clone, Copy)]
+pub struct TestMoney {
+    cents: i64,
+}
+
+#[php_impl]
+impl TestMoney {
+    pub fn __construct(cents: i64) -> Self {
+        Self { cents }
+    }
+
+    #[php(getter)]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn cents(&self) -> i64 {
+        self.cents
+    }
+}
+
+impl Add for TestMoney {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::__construct(self.cents + rhs.cents)
+    }
+}
+
+impl Sub for TestMoney {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::__construct(self.cents - rhs.cents)
+    }
+}
+
+impl Mul<i64> for TestMoney {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self::__construct(self.cents * rhs)
+    }
+}
+
+impl Mul<f64> for TestMoney {
+    type Output = Self;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn mul(self, rhs: f64) -> Self {
+        Self::__construct((self.cents as f64 * rhs).round() as i64)
+    }
+}
+
+impl Mul<TestMoney> for i64 {
+    type Output = TestMoney;
+
+    fn mul(self, rhs: TestMoney) -> TestMoney {
+        rhs * self
+    }
+}
+
+impl Div<i64> for TestMoney {
+    type Output = Option<Self>;
+
+    fn div(self, rhs: i64) -> Option<Self> {
+        self.cents.checked_div(rhs).map(Self::__construct)
+    }
+}
+
+impl Rem<i64> for TestMoney {
+    type Output = Option<Self>;
+
+    fn rem(self, rhs: i64) -> Option<Self> {
+        self.cents.checked_rem(rhs).map(Self::__construct)
+    }
+}
+
+impl Pow<u32> for TestMoney {
+    type Output = Self;
+
+    fn pow(self, rhs: u32) -> Self {
+        Self::__construct(self.cents.pow(rhs))
+    }
+}
+
+impl Neg for TestMoney {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::__construct(-self.cents)
+    }
+}
+
+impl fmt::Display for TestMoney {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${}.{:02}", self.cents / 100, self.cents % 100)
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_operators_compare))
+        .function(wrap_function!(test_operators_loose_eq))
+        .function(wrap_function!(test_operators_apply))
+        .class::<TestMoney>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn operators_works() {
+        assert!(crate::integration::test::run_php("operators/operators.php"));
+    }
+}
@@ -0,0 +1,115 @@
+<?php
+
+require __DIR__ . '/../_utils.php';
+
+function assert_throws(callable $callback, string $class): void
+{
+    try {
+        call_user_func($callback);
+    } catch (\Exception $e) {
+        assert($e->getMessage() === $class, $e->getMessage());
+        return;
+    }
+    throw new Exception('Exception was not thrown', 255);
+}
+
+// Comparison
+assert(test_operators_compare(1, 2) === -1);
+assert(test_operators_compare('10', '9') === 1);
+assert(test_operators_compare([1, 2], [1, 2]) === 0);
+assert(test_operators_loose_eq(1, '1'));
+assert(test_operators_loose_eq(null, false));
+assert(!test_operators_loose_eq(0, 'a'));
+assert(!test_operators_loose_eq(NAN, NAN));
+
+class ThrowingToString
+{
+    public function __toString(): string
+    {
+        throw new Exception('__toString failed');
+    }
+}
+
+assert_throws(fn () => test_operators_compare(new ThrowingToString(), 'a'), 'Exception');
+assert_throws(fn () => test_operators_loose_eq('a', new ThrowingToString()), 'Exception');
+
+// Arithmetic
+assert(test_operators_apply('+', 1, 2) === 3);
+assert(test_operators_apply('+', 1, '1.5') === 2.5);
+assert(test_operators_apply('+', PHP_INT_MAX, 1) === PHP_INT_MAX + 1);
+assert(test_operators_apply('+', ['a' => 1], ['a' => 2, 'b' => 3]) === ['a' => 1, 'b' => 3]);
+assert(test_operators_apply('-', 5, 7) === -2);
+assert(test_operators_apply('*', 3, 4) === 12);
+assert(test_operators_apply('/', 7, 2) === 3.5);
+assert(test_operators_apply('%', -7, 3) === -1);
+assert(test_operators_apply('**', 2, 10) === 1024);
+assert(test_operators_apply('.', 'a', 1) === 'a1');
+
+// Bitwise
+assert(test_operators_apply('&', 0b1100, 0b1010) === 0b1000);
+assert(test_operators_apply('|', 0b1100, 0b1010) === 0b1110);
+assert(test_operators_apply('^', 0b1100, 0b1010) === 0b0110);
+assert(test_operators_apply('^', 'ab', '  ') === 'AB');
+assert(test_operators_apply('<<', 1, 4) === 16);
+assert(test_operators_apply('>>', -16, 2) === -4);
+assert(test_operators_apply('~', 0, null) === -1);
+
+// Errors
+assert_throws(fn () => test_operators_apply('/', 1, 0), 'DivisionByZeroError');
+assert_throws(fn () => test_operators_apply('%', 1, 0), 'DivisionByZeroError');
+assert_throws(fn () => test_operators_apply('+', [], 1), 'TypeError');
+assert_throws(fn () => test_operators_apply('-', new stdClass(), 1), 'TypeError');
+assert_throws(fn () => test_operators_apply('<<', 1, -1), 'ArithmeticError');
+assert_throws(fn () => test_operators_apply('~', [], null), 'TypeError');
+
+// Operators overloaded by Rust classes
+$a = new TestMoney(150);
+$b = new TestMoney(250);
+assert(($a + $b)->cents === 400);
+assert(($b - $a)->cents === 100);
+assert(($a * 2)->cents === 300);
+assert((2 * $a)->cents === 300);
+assert(($a * 1.5)->cents === 225);
+assert(($b / 2)->cents === 125);
+assert($b / 0 === null);
+assert(($b % 100)->cents === 50);
+assert((new TestMoney(3) ** 2)->cents === 9);
+assert((-$a)->cents === -150);
+assert($b . ' in total' === '$2.50 in total');
+assert('In total: ' . $b === 'In total: $2.50');
+assert((string) $b === '$2.50');
+assert("In total: $b" === 'In total: $2.50');
+ob_start();
+echo $b;
+assert(ob_get_clean() === '$2.50');
+assert(@($b . []) === '$2.50Array');
+assert((bool) $a === true);
+
+$c = $a;
+$c += $b;
+assert($c->cents === 400);
+assert($a->cents === 150);
+$c *= 2;
+assert($c->cents === 800);
+$c -= $a;
+assert($c->cents === 650);
+
+function assert_type_error(callable $callback): void
+{
+    try {
+        $callback();
+    } catch (\TypeError $e) {
+        return;
+    }
+    throw new Exception('TypeError was not thrown', 255);
+}
+
+assert_type_error(fn () => $a + 1);
+assert_type_error(fn () => $a - 'one');
+assert_type_error(fn () => 1 - $a);
+assert_type_error(fn () => 2.5 * $a);
+assert_type_error(fn () => 1 + $a);
+assert_type_error(fn () => $a * $b);
+assert_type_error(function () use ($a) {
+    $a += 1;
+});
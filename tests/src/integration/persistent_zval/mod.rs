@@ -0,0 +1,38 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use ext_php_rs::{
+    prelude::*,
+    types::{PersistentZval, Zval},
+};
+
+static CACHE: Mutex<Option<HashMap<String, PersistentZval>>> = Mutex::new(None);
+
+#[php_function]
+pub fn test_persistent_zval_store(key: String, value: &Zval) -> PhpResult<()> {
+    let value = PersistentZval::new(value)?;
+    let mut cache = CACHE.lock().map_err(|_| "Cache is poisoned")?;
+    cache.get_or_insert_with(HashMap::new).insert(key, value);
+    Ok(())
+}
+
+#[php_function]
+pub fn test_persistent_zval_fetch(key: &str) -> PhpResult<Option<PersistentZval>> {
+    let cache = CACHE.lock().map_err(|_| "Cache is poisoned")?;
+    Ok(cache.as_ref().and_then(|cache| cache.get(key)).cloned())
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_persistent_zval_store))
+        .function(wrap_function!(test_persistent_zval_fetch))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn persistent_zval_works() {
+        assert!(crate::integration::test::run_php(
+            "persistent_zval/persistent_zval.php"
+        ));
+    }
+}
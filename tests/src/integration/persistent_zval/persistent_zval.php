@@ -0,0 +1,56 @@
+<?php
+
+require __DIR__ . '/../_utils.php';
+
+$values = [
+    'null' => null,
+    'bool' => true,
+    'int' => 42,
+    'float' => 1.5,
+    'string' => 'Hello, world!',
+    'binary' => "\x00\xff",
+    'list' => [1, 2, 3],
+    'map' => ['a' => 1, 'b' => ['c' => 'd', 5 => [true, null]], 10 => 'e'],
+    'empty' => [],
+];
+
+foreach ($values as $key => $value) {
+    test_persistent_zval_store($key, $value);
+}
+foreach ($values as $key => $value) {
+    assert(test_persistent_zval_fetch($key) === $value, $key);
+}
+assert(test_persistent_zval_fetch('missing') === null);
+
+// Values are copied when they are modified.
+$map = test_persistent_zval_fetch('map');
+$map['b']['c'] = 'changed';
+$map[] = 'appended';
+assert($map['b']['c'] === 'changed');
+assert(test_persistent_zval_fetch('map') === $values['map']);
+
+$string = test_persistent_zval_fetch('string');
+$string .= '!';
+assert($string === 'Hello, world!!');
+assert(test_persistent_zval_fetch('string') === 'Hello, world!');
+
+// Values are independent from the original.
+$list = [1, 2];
+test_persistent_zval_store('copy', $list);
+$list[] = 3;
+assert(test_persistent_zval_fetch('copy') === [1, 2]);
+
+// References are replaced by their value.
+$value = 'referenced';
+test_persistent_zval_store('reference', ['ref' => &$value]);
+$value = 'changed';
+assert(test_persistent_zval_fetch('reference') === ['ref' => 'referenced']);
+
+// Objects, resources and recursive arrays cannot be stored.
+assert_exception_thrown(fn () => test_persistent_zval_store('object', new stdClass()));
+assert_exception_thrown(fn () => test_persistent_zval_store('nested', ['a' => [new stdClass()]]));
+assert_exception_thrown(fn () => test_persistent_zval_store('resource', fopen('php://memory', 'r')));
+$recursive = [];
+$recursive['self'] = &$recursive;
+assert_exception_thrown(fn () => test_persistent_zval_store('recursive', $recursive));
+assert(test_persistent_zval_fetch('object') === null);
@@ -0,0 +1,71 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ext_php_rs::{
+    binary::Binary,
+    php_serialize::{self, Value},
+    prelude::*,
+    types::Zval,
+};
+
+#[php_class]
+#[derive(Default)]
+pub struct SerializablePoint {
+    x: i64,
+    y: i64,
+}
+
+#[php_impl]
+impl SerializablePoint {
+    pub fn __construct(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+
+    pub fn y(&self) -> i64 {
+        self.y
+    }
+
+    pub fn __serialize(&self) -> BTreeMap<String, i64> {
+        BTreeMap::from([("x".to_string(), self.x), ("y".to_string(), self.y)])
+    }
+
+    pub fn __unserialize(&mut self, data: HashMap<String, i64>) {
+        self.x = data.get("x").copied().unwrap_or_default();
+        self.y = data.get("y").copied().unwrap_or_default();
+    }
+}
+
+/// Serializes the value through the engine, then encodes its decoded value.
+#[php_function]
+pub fn php_serialize_encode(value: &Zval) -> PhpResult<Binary<u8>> {
+    let value = Value::from_zval(value)?;
+    Ok(php_serialize::encode(&value).into())
+}
+
+/// Decodes serialized data into a value, then unserializes it through the
+/// engine.
+#[php_function]
+pub fn php_serialize_decode(data: Binary<u8>) -> PhpResult<Zval> {
+    let value = php_serialize::decode(&Vec::from(data))?;
+    Ok(value.to_zval()?)
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .class::<SerializablePoint>()
+        .function(wrap_function!(php_serialize_encode))
+        .function(wrap_function!(php_serialize_decode))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn php_serialize_works() {
+        assert!(crate::integration::test::run_php(
+            "php_serialize/php_serialize.php"
+        ));
+    }
+}
@@ -0,0 +1,58 @@
+<?php
+
+require __DIR__ . '/../_utils.php';
+
+enum Suit
+{
+    case Hearts;
+}
+
+// Scalars and arrays encode exactly like `serialize()`.
+$values = [
+    null,
+    true,
+    -42,
+    0.1,
+    1.5e-7,
+    1e25,
+    -0.0,
+    "binary\0string",
+    ['a' => 1, 'b' => [1.5, null, true], 5 => 'c'],
+    Suit::Hearts,
+];
+foreach ($values as $value) {
+    assert(php_serialize_encode($value) === serialize($value));
+    assert(php_serialize_decode(serialize($value)) === $value);
+}
+
+// Objects and back-references.
+$obj = new stdClass();
+$obj->a = 1;
+$data = serialize([$obj, $obj]);
+assert(php_serialize_encode([$obj, $obj]) === $data);
+$copy = php_serialize_decode($data);
+assert($copy[0] == $obj);
+assert($copy[0] === $copy[1]);
+
+$value = [1, 2];
+$value[2] = &$value[0];
+assert(php_serialize_encode($value) === serialize($value));
+
+// `#[php_class]` objects implementing `__serialize()` and `__unserialize()`.
+$point = new SerializablePoint(1, 2);
+$data = serialize($point);
+assert(php_serialize_encode($point) === $data);
+
+$copy = unserialize($data);
+assert($copy instanceof SerializablePoint);
+assert($copy->x() === 1);
+assert($copy->y() === 2);
+
+$copy = php_serialize_decode($data);
+assert($copy instanceof SerializablePoint);
+assert($copy->x() === 1);
+assert($copy->y() === 2);
+
+// Invalid data throws.
+assert_exception_thrown(fn () => php_serialize_decode('a:1:{i:0;'));
+assert_exception_thrown(fn () => php_serialize_encode(fn () => 1));
@@ -0,0 +1,64 @@
+use ext_php_rs::{boxed::ZBox, prelude::*, types::ZendResource};
+
+#[php_resource]
+#[php(name = "test counter")]
+pub struct TestCounter {
+    count: i64,
+}
+
+#[php_resource]
+pub struct TestOtherResource;
+
+#[php_function]
+pub fn test_resource_counter_open(start: i64) -> ZBox<ZendResource<TestCounter>> {
+    ZendResource::new(TestCounter { count: start })
+}
+
+#[php_function]
+pub fn test_resource_counter_shared() -> ZBox<ZendResource<TestCounter>> {
+    ZendResource::persistent("ext_php_rs_test.counter", || TestCounter { count: 0 })
+}
+
+#[php_function]
+pub fn test_resource_counter_increment(
+    mut counter: ZBox<ZendResource<TestCounter>>,
+) -> PhpResult<i64> {
+    let counter = counter.get_mut().ok_or("The counter has been closed")?;
+    counter.count += 1;
+    Ok(counter.count)
+}
+
+#[php_function]
+pub fn test_resource_counter_is_persistent(counter: &ZendResource<TestCounter>) -> bool {
+    counter.is_persistent()
+}
+
+#[php_function]
+pub fn test_resource_counter_close(mut counter: ZBox<ZendResource<TestCounter>>) {
+    counter.close();
+}
+
+#[php_function]
+pub fn test_resource_other_open() -> ZBox<ZendResource<TestOtherResource>> {
+    ZendResource::new(TestOtherResource)
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .resource::<TestCounter>()
+        .resource::<TestOtherResource>()
+        .function(wrap_function!(test_resource_counter_open))
+        .function(wrap_function!(test_resource_counter_shared))
+        .function(wrap_function!(test_resource_counter_increment))
+        .function(wrap_function!(test_resource_counter_is_persistent))
+        .function(wrap_function!(test_resource_counter_close))
+        .function(wrap_function!(test_resource_other_open))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn resource_works() {
+        assert!(crate::integration::test::run_php("resource/resource.php"));
+    }
+}
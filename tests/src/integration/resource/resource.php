@@ -0,0 +1,35 @@
+<?php
+
+require __DIR__ . '/../_utils.php';
+
+$counter = test_resource_counter_open(41);
+assert(is_resource($counter));
+assert(get_resource_type($counter) === 'test counter');
+assert(!test_resource_counter_is_persistent($counter));
+assert(test_resource_counter_increment($counter) === 42);
+assert(test_resource_counter_increment($counter) === 43);
+
+// Copies refer to the same resource.
+$copy = $counter;
+assert(test_resource_counter_increment($copy) === 44);
+
+test_resource_counter_close($counter);
+assert(get_resource_type($counter) === 'Unknown');
+assert(get_resource_type($copy) === 'Unknown');
+assert_exception_thrown(fn () => test_resource_counter_increment($counter));
+
+// Persistent values are shared between resources.
+$shared = test_resource_counter_shared();
+assert(test_resource_counter_is_persistent($shared));
+$count = test_resource_counter_increment($shared);
+assert(test_resource_counter_increment(test_resource_counter_shared()) === $count + 1);
+
+// Closing a persistent resource keeps the value.
+test_resource_counter_close($shared);
+assert(test_resource_counter_increment(test_resource_counter_shared()) === $count + 2);
+
+// Resources of other types are rejected.
+$other = test_resource_other_open();
+assert(get_resource_type($other) === 'test_other_resource');
+assert_exception_thrown(fn () => test_resource_counter_increment($other));
+assert_exception_thrown(fn () => test_resource_counter_increment(fopen('php://memory', 'r')));
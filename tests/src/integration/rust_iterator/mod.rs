@@ -0,0 +1,68 @@
+use ext_php_rs::{iterator::RustIterator, prelude::*};
+
+#[php_function]
+pub fn test_rust_iterator_range(end: i64) -> impl Iterator<Item = i64> {
+    (0..end).map(|i| i * 10)
+}
+
+#[php_function]
+pub fn test_rust_iterator_keyed() -> impl Iterator<Item = (String, i64)> {
+    ["one", "two", "three"]
+        .into_iter()
+        .zip(1..)
+        .map(|(name, i)| (name.to_string(), i))
+}
+
+#[php_function]
+pub fn test_rust_iterator_wrapped() -> RustIterator {
+    RustIterator::new(vec![true, false])
+}
+
+#[php_function]
+pub fn test_rust_iterator_failing() -> impl Iterator<Item = Result<i64, String>> {
+    [Ok(1), Err("Item 2 is invalid".to_string())].into_iter()
+}
+
+#[php_class]
+#[php(iterator)]
+pub struct TestCountdown {
+    from: i64,
+}
+
+#[php_impl]
+impl TestCountdown {
+    pub fn __construct(from: i64) -> Self {
+        Self { from }
+    }
+}
+
+impl Iterator for TestCountdown {
+    type Item = (i64, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.from == 0 {
+            return None;
+        }
+        self.from -= 1;
+        Some((self.from, format!("T-{}", self.from)))
+    }
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_rust_iterator_range))
+        .function(wrap_function!(test_rust_iterator_keyed))
+        .function(wrap_function!(test_rust_iterator_wrapped))
+        .function(wrap_function!(test_rust_iterator_failing))
+        .class::<TestCountdown>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn rust_iterator_works() {
+        assert!(crate::integration::test::run_php(
+            "rust_iterator/rust_iterator.php"
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+<?php
+
+$range = test_rust_iterator_range(4);
+assert($range instanceof RustIterator);
+assert($range instanceof IteratorAggregate);
+assert(iterator_to_array($range) === [0, 10, 20, 30]);
+
+// Rust iterators cannot be rewound.
+assert(iterator_to_array($range) === []);
+
+$values = [];
+foreach (test_rust_iterator_range(3) as $key => $value) {
+    $values[] = "$key:$value";
+}
+assert($values === ['0:0', '1:10', '2:20']);
+
+assert(iterator_to_array(test_rust_iterator_keyed()) === ['one' => 1, 'two' => 2, 'three' => 3]);
+assert(iterator_to_array(test_rust_iterator_wrapped()) === [true, false]);
+
+// A traversal resumes after the item at which the previous one stopped.
+$range = test_rust_iterator_range(5);
+foreach ($range as $value) {
+    if ($value === 20) {
+        break;
+    }
+}
+assert(iterator_to_array($range, false) === [30, 40]);
+
+$iterator = test_rust_iterator_range(2)->getIterator();
+assert($iterator instanceof Iterator);
+assert(iterator_to_array($iterator) === [0, 10]);
+
+$values = [];
+try {
+    foreach (test_rust_iterator_failing() as $value) {
+        $values[] = $value;
+    }
+    assert(false, 'Expected an exception');
+} catch (Exception $e) {
+    assert($e->getMessage() === 'Item 2 is invalid');
+}
+assert($values === [1]);
+
+$range = test_rust_iterator_range(1);
+try {
+    foreach ($range as &$value) {
+    }
+    assert(false, 'Expected an exception');
+} catch (Exception $e) {
+    assert($e->getMessage() === 'An iterator cannot be used with foreach by reference');
+}
+
+try {
+    new RustIterator();
+    assert(false, 'Expected an exception');
+} catch (Exception $e) {
+}
+
+$countdown = new TestCountdown(3);
+assert($countdown instanceof IteratorAggregate);
+assert(iterator_to_array($countdown) === [2 => 'T-2', 1 => 'T-1', 0 => 'T-0']);
+assert(iterator_to_array(new TestCountdown(0)) === []);
@@ -0,0 +1,50 @@
+use ext_php_rs::{builders::ClassBuilder, class::RegisteredClass, prelude::*, types::ZendObject};
+
+/// Greets people by the name of the object using the trait.
+pub struct TestGreets;
+
+#[php_trait]
+impl TestGreets {
+    pub fn greet(self_: &mut ZendObject, greeting: Option<String>) -> String {
+        let name: String = self_.get_property("name").unwrap_or_default();
+        format!("{}, {name}!", greeting.as_deref().unwrap_or("Hello"))
+    }
+
+    pub fn shout(text: String) -> String {
+        text.to_uppercase()
+    }
+}
+
+#[php_class]
+#[php(modifier = greeter_modifier)]
+pub struct TestGreeter {
+    #[php(prop)]
+    name: String,
+}
+
+#[php_impl]
+impl TestGreeter {
+    pub fn __construct(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn shout(text: String) -> String {
+        format!("{text}!")
+    }
+}
+
+fn greeter_modifier(builder: ClassBuilder) -> ClassBuilder {
+    builder.use_trait((|| TestGreets::get_metadata().ce(), TestGreets::CLASS_NAME))
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder.php_trait::<TestGreets>().class::<TestGreeter>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn trait_works() {
+        assert!(crate::integration::test::run_php("trait_/trait.php"));
+    }
+}
@@ -0,0 +1,26 @@
+<?php
+
+declare(strict_types = 1);
+
+require(__DIR__ . '/../_utils.php');
+
+assert(trait_exists('TestGreets'));
+assert(!class_exists('TestGreets'));
+assert_exception_thrown(fn () => new TestGreets());
+
+class Person {
+    use TestGreets;
+
+    public function __construct(public string $name) {}
+}
+
+$person = new Person('David');
+assert($person->greet() === 'Hello, David!');
+assert($person->greet('Hi') === 'Hi, David!');
+assert(Person::shout('hello') === 'HELLO');
+assert(in_array('TestGreets', class_uses($person)));
+
+// Rust classes use the methods of the trait, unless they declare their own.
+$greeter = new TestGreeter('Ferris');
+assert($greeter->greet() === 'Hello, Ferris!');
+assert(TestGreeter::shout('hello') === 'hello!');
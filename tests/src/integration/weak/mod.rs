@@ -0,0 +1,63 @@
+use std::cell::RefCell;
+
+use ext_php_rs::{
+    boxed::ZBox,
+    prelude::*,
+    types::{ZendObject, ZendWeakMap, ZendWeakRef},
+};
+
+thread_local! {
+    static LABELS: RefCell<ZendWeakMap<String>> = RefCell::new(ZendWeakMap::new());
+    static WEAK_REF: RefCell<Option<ZendWeakRef>> = const { RefCell::new(None) };
+}
+
+#[php_function]
+pub fn test_weak_map_insert(object: &ZendObject, label: String) -> Option<String> {
+    LABELS.with_borrow_mut(|labels| labels.insert(object, label))
+}
+
+#[php_function]
+pub fn test_weak_map_get(object: &ZendObject) -> Option<String> {
+    LABELS.with_borrow(|labels| labels.get(object).cloned())
+}
+
+#[php_function]
+pub fn test_weak_map_remove(object: &ZendObject) -> Option<String> {
+    LABELS.with_borrow_mut(|labels| labels.remove(object))
+}
+
+#[php_function]
+pub fn test_weak_map_len() -> usize {
+    LABELS.with_borrow(ZendWeakMap::len)
+}
+
+#[php_function]
+pub fn test_weak_ref_set(object: &ZendObject) -> u32 {
+    let weak = object.downgrade();
+    let id = weak.id();
+    WEAK_REF.set(Some(weak));
+    id
+}
+
+#[php_function]
+pub fn test_weak_ref_get() -> Option<ZBox<ZendObject>> {
+    WEAK_REF.with_borrow(|weak| weak.as_ref()?.upgrade())
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_weak_map_insert))
+        .function(wrap_function!(test_weak_map_get))
+        .function(wrap_function!(test_weak_map_remove))
+        .function(wrap_function!(test_weak_map_len))
+        .function(wrap_function!(test_weak_ref_set))
+        .function(wrap_function!(test_weak_ref_get))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn weak_works() {
+        assert!(crate::integration::test::run_php("weak/weak.php"));
+    }
+}
@@ -0,0 +1,37 @@
+<?php
+
+$a = new stdClass();
+$b = new stdClass();
+
+// Weak maps
+assert(test_weak_map_len() === 0);
+assert(test_weak_map_insert($a, 'a') === null);
+assert(test_weak_map_insert($b, 'b') === null);
+assert(test_weak_map_insert($b, 'c') === 'b');
+assert(test_weak_map_len() === 2);
+assert(test_weak_map_get($a) === 'a');
+assert(test_weak_map_get($b) === 'c');
+assert(test_weak_map_get(new stdClass()) === null);
+
+// Entries are removed when their object is released.
+unset($a);
+assert(test_weak_map_len() === 1);
+assert(test_weak_map_remove($b) === 'c');
+assert(test_weak_map_remove($b) === null);
+assert(test_weak_map_len() === 0);
+
+// Weak maps do not keep objects alive.
+$c = new stdClass();
+test_weak_map_insert($c, 'c');
+$c = null;
+assert(test_weak_map_len() === 0);
+
+// Weak references
+$d = new stdClass();
+assert(test_weak_ref_set($d) === spl_object_id($d));
+assert(test_weak_ref_get() === $d);
+$copy = $d;
+unset($d);
+assert(test_weak_ref_get() === $copy);
+unset($copy);
+assert(test_weak_ref_get() === null);
@@ -0,0 +1,158 @@
+use ext_php_rs::{
+    ZvalConvert,
+    convert::{FromZendObject, Properties},
+    php_function,
+    prelude::ModuleBuilder,
+    types::{ZendHashTable, ZendObject},
+    wrap_function,
+};
+
+#[derive(Debug, ZvalConvert)]
+pub struct Origin {
+    host: String,
+}
+
+#[derive(Debug, ZvalConvert)]
+#[php(change_field_case = "camelCase")]
+pub struct Settings {
+    user_name: String,
+    #[php(name = "max_items")]
+    limit: i64,
+    #[php(default)]
+    verbose: bool,
+    #[php(default = 8080)]
+    port: i64,
+    #[php(skip)]
+    cache: Vec<String>,
+    #[php(flatten)]
+    origin: Origin,
+}
+
+#[derive(Debug, ZvalConvert)]
+#[php(tag = "type")]
+pub enum Shape {
+    Circle {
+        radius: f64,
+    },
+    #[php(name = "rect")]
+    Rectangle {
+        width: f64,
+        height: f64,
+    },
+    Point,
+}
+
+#[derive(Debug, ZvalConvert)]
+#[php(tag = "kind", content = "value", change_variant_case = "snake_case")]
+pub enum Message {
+    Text(String),
+    Ping,
+    MoveTo { x: i64, y: i64 },
+}
+
+#[derive(Debug, ZvalConvert)]
+pub enum IntOrText {
+    Int(i64),
+    Text(String),
+}
+
+/// Returns the settings with every value changed, to check both conversions.
+#[php_function]
+pub fn test_zval_convert_settings(settings: Settings) -> Settings {
+    assert!(settings.cache.is_empty());
+    Settings {
+        user_name: settings.user_name.to_uppercase(),
+        limit: settings.limit * 2,
+        verbose: !settings.verbose,
+        port: settings.port + 1,
+        cache: vec!["ignored".into()],
+        origin: Origin {
+            host: format!("www.{}", settings.origin.host),
+        },
+    }
+}
+
+#[php_function]
+pub fn test_zval_convert_settings_error(obj: &ZendObject) -> Option<String> {
+    Settings::from_zend_object(obj).err().map(|e| e.to_string())
+}
+
+#[php_function]
+pub fn test_zval_convert_settings_array_error(array: &ZendHashTable) -> Option<String> {
+    Settings::from_properties(Properties::Array(array))
+        .err()
+        .map(|e| e.to_string())
+}
+
+#[php_function]
+pub fn test_zval_convert_shape_area(shape: Shape) -> f64 {
+    match shape {
+        Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+        Shape::Rectangle { width, height } => width * height,
+        Shape::Point => 0.0,
+    }
+}
+
+#[php_function]
+pub fn test_zval_convert_shape_error(obj: &ZendObject) -> Option<String> {
+    Shape::from_zend_object(obj).err().map(|e| e.to_string())
+}
+
+#[php_function]
+pub fn test_zval_convert_scale_shape(shape: Shape) -> Shape {
+    match shape {
+        Shape::Circle { radius } => Shape::Circle {
+            radius: radius * 2.0,
+        },
+        Shape::Rectangle { width, height } => Shape::Rectangle {
+            width: width * 2.0,
+            height: height * 2.0,
+        },
+        Shape::Point => Shape::Point,
+    }
+}
+
+#[php_function]
+pub fn test_zval_convert_echo_message(message: Message) -> Message {
+    match message {
+        Message::Text(text) => Message::Text(format!("echo: {text}")),
+        Message::Ping => Message::Ping,
+        Message::MoveTo { x, y } => Message::MoveTo { x: -x, y: -y },
+    }
+}
+
+#[php_function]
+pub fn test_zval_convert_union(value: IntOrText) -> IntOrText {
+    match value {
+        IntOrText::Int(i) => IntOrText::Int(i * 2),
+        IntOrText::Text(text) => IntOrText::Text(text.repeat(2)),
+    }
+}
+
+#[php_function]
+pub fn test_zval_convert_nullable_union(value: Option<IntOrText>) -> Option<IntOrText> {
+    value
+}
+
+pub fn build_module(builder: ModuleBuilder) -> ModuleBuilder {
+    builder
+        .function(wrap_function!(test_zval_convert_settings))
+        .function(wrap_function!(test_zval_convert_settings_error))
+        .function(wrap_function!(test_zval_convert_settings_array_error))
+        .function(wrap_function!(test_zval_convert_shape_area))
+        .function(wrap_function!(test_zval_convert_shape_error))
+        .function(wrap_function!(test_zval_convert_scale_shape))
+        .function(wrap_function!(test_zval_convert_echo_message))
+        .function(wrap_function!(test_zval_convert_union))
+        .function(wrap_function!(test_zval_convert_nullable_union))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn zval_convert_works() {
+        assert!(crate::integration::test::run_php(
+            "zval_convert/zval_convert.php"
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+<?php
+
+// Renamed, defaulted, skipped and flattened fields
+$settings = test_zval_convert_settings((object) [
+    'userName' => 'alice',
+    'max_items' => 10,
+    'host' => 'example.com',
+]);
+assert($settings->userName === 'ALICE');
+assert($settings->max_items === 20);
+assert($settings->verbose === true, 'Missing fields with a default should use it');
+assert($settings->port === 8081, 'Missing fields should use the given default value');
+assert($settings->host === 'www.example.com', 'Flattened fields should be stored on the object');
+assert(!property_exists($settings, 'cache'), 'Skipped fields should not be converted');
+assert(!property_exists($settings, 'origin'), 'Flattened fields should not be nested');
+
+// Errors name the missing or mistyped field
+assert(test_zval_convert_settings_error((object) ['max_items' => 1, 'host' => 'a']) === 'Missing property `userName`.');
+assert(test_zval_convert_settings_error((object) ['userName' => 'a', 'max_items' => [], 'host' => 'a']) === 'Invalid value of type Array for property `max_items`.');
+assert(test_zval_convert_settings_error((object) ['userName' => 'a', 'max_items' => 1]) === 'Missing property `host`.');
+assert(test_zval_convert_settings_error((object) ['userName' => 'a', 'max_items' => 1, 'host' => 'a']) === null);
+
+// Associative arrays are converted like objects
+$settings = test_zval_convert_settings(['userName' => 'bob', 'max_items' => 1, 'verbose' => true, 'host' => 'example.org']);
+assert($settings->userName === 'BOB');
+assert($settings->max_items === 2);
+assert($settings->verbose === false);
+assert($settings->host === 'www.example.org');
+assert(test_zval_convert_settings_array_error(['max_items' => 1, 'host' => 'a']) === 'Missing property `userName`.');
+assert(test_zval_convert_settings_array_error(['userName' => 'a', 'max_items' => 'x', 'host' => 'a']) === 'Invalid value of type String for property `max_items`.');
+assert(test_zval_convert_settings_array_error(['userName' => 'a', 'max_items' => 1, 'host' => 'a']) === null);
+$settingsType = (string) (new ReflectionFunction('test_zval_convert_settings'))->getParameters()[0]->getType();
+assert($settingsType === 'object|array', $settingsType);
+
+// Internally tagged enums
+assert(test_zval_convert_shape_area((object) ['type' => 'rect', 'width' => 2.0, 'height' => 3.0]) === 6.0);
+assert(test_zval_convert_shape_area((object) ['type' => 'Point']) === 0.0);
+$circle = test_zval_convert_scale_shape((object) ['type' => 'Circle', 'radius' => 1.5]);
+assert($circle == (object) ['type' => 'Circle', 'radius' => 3.0]);
+assert(test_zval_convert_shape_error((object) ['type' => 'Triangle']) === 'Unknown variant `Triangle`.');
+assert(test_zval_convert_shape_error((object) ['radius' => 1.0]) === 'Missing property `type`.');
+assert(test_zval_convert_shape_error((object) ['type' => 'Circle']) === 'Missing property `radius`.');
+assert(test_zval_convert_shape_area(['type' => 'rect', 'width' => 4.0, 'height' => 0.5]) === 2.0);
+assert(test_zval_convert_shape_area(['type' => 'Point']) === 0.0);
+assert(test_zval_convert_scale_shape(['type' => 'Circle', 'radius' => 1.0]) == (object) ['type' => 'Circle', 'radius' => 2.0]);
+
+// Adjacently tagged enums
+$text = test_zval_convert_echo_message((object) ['kind' => 'text', 'value' => 'hello']);
+assert($text == (object) ['kind' => 'text', 'value' => 'echo: hello']);
+$ping = test_zval_convert_echo_message((object) ['kind' => 'ping']);
+assert($ping == (object) ['kind' => 'ping']);
+$move = test_zval_convert_echo_message((object) ['kind' => 'move_to', 'value' => (object) ['x' => 1, 'y' => 2]]);
+assert($move == (object) ['kind' => 'move_to', 'value' => (object) ['x' => -1, 'y' => -2]]);
+$move = test_zval_convert_echo_message(['kind' => 'move_to', 'value' => ['x' => 3, 'y' => 4]]);
+assert($move == (object) ['kind' => 'move_to', 'value' => (object) ['x' => -3, 'y' => -4]]);
+assert(test_zval_convert_echo_message(['kind' => 'text', 'value' => 'hi'])->value === 'echo: hi');
+
+// Untagged enums declare the union of the types of their variants
+assert(test_zval_convert_union(21) === 42);
+assert(test_zval_convert_union('ab') === 'abab');
+$union = new ReflectionFunction('test_zval_convert_union');
+assert((string) $union->getParameters()[0]->getType() === 'string|int');
+assert((string) $union->getReturnType() === 'string|int');
+$nullable = new ReflectionFunction('test_zval_convert_nullable_union');
+assert((string) $nullable->getParameters()[0]->getType() === 'string|int|null');
+assert((string) $nullable->getReturnType() === 'string|int|null');
+assert(test_zval_convert_nullable_union(null) === null);
+try {
+    test_zval_convert_union([]);
+    assert(false, 'TypeError was not thrown');
+} catch (TypeError $e) {
+    assert($e->getMessage() === 'test_zval_convert_union(): Argument #1 ($value) must be of type string|int, array given', $e->getMessage());
+}
@@ -12,18 +12,27 @@ mod integration;
 #[php_module]
 pub fn build_module(module: ModuleBuilder) -> ModuleBuilder {
     let mut module = integration::array::build_module(module);
+    module = integration::array_access::build_module(module);
+    #[cfg(feature = "async")]
+    {
+        module = integration::async_fn::build_module(module);
+    }
+    module = integration::attribute::build_module(module);
     module = integration::bailout::build_module(module);
     module = integration::binary::build_module(module);
     module = integration::bool::build_module(module);
     module = integration::callable::build_module(module);
     module = integration::class::build_module(module);
     module = integration::closure::build_module(module);
+    module = integration::coercion::build_module(module);
     module = integration::defaults::build_module(module);
+    module = integration::deprecated::build_module(module);
     #[cfg(feature = "enum")]
     {
         module = integration::enum_::build_module(module);
     }
     module = integration::exception::build_module(module);
+    module = integration::generic_class::build_module(module);
     module = integration::globals::build_module(module);
     module = integration::module_globals::build_module(module);
     module = integration::iterator::build_module(module);
@@ -35,11 +44,19 @@ pub fn build_module(module: ModuleBuilder) -> ModuleBuilder {
     {
         module = integration::observer::build_module(module);
     }
+    module = integration::operators::build_module(module);
     module = integration::persistent_string::build_module(module);
+    module = integration::persistent_zval::build_module(module);
+    module = integration::php_serialize::build_module(module);
     module = integration::reference::build_module(module);
+    module = integration::resource::build_module(module);
+    module = integration::rust_iterator::build_module(module);
     module = integration::separated::build_module(module);
     module = integration::string::build_module(module);
+    module = integration::trait_::build_module(module);
     module = integration::variadic_args::build_module(module);
+    module = integration::weak::build_module(module);
+    module = integration::zval_convert::build_module(module);
     module = integration::interface::build_module(module);
 
     module
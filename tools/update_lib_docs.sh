@@ -43,6 +43,7 @@ function update_docs() {
     sed -i -e "/$lead/,/$tail/{ /$lead/{p; r /dev/stdin" -e "}; /$tail/p; d }" "crates/macros/src/lib.rs"
 }
 
+update_docs "attribute"
 update_docs "classes"
 update_docs "constant"
 update_docs "extern"
@@ -52,6 +53,8 @@ update_docs "module"
 update_docs "zval_convert"
 update_docs "enum"
 update_docs "interface"
+update_docs "resource"
+update_docs "trait"
 
 # Format to remove trailing whitespace
 rustup run nightly rustfmt --edition 2024 crates/macros/src/lib.rs